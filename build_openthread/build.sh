@@ -6,11 +6,27 @@ rm -rf build
 
 OT_CMAKE_NINJA_TARGET=${OT_CMAKE_NINJA_TARGET:-}
 
+# chip whose ESP-IDF toolchain file is used, the ESP32-C6 libraries also run on the ESP32-H2
+OT_TARGET=${OT_TARGET:-esp32c6}
+
+# optional MLE attach backoff tuning (in milliseconds), empty keeps the OpenThread defaults
+OT_ATTACH_BACKOFF_MIN_INTERVAL=${OT_ATTACH_BACKOFF_MIN_INTERVAL:-}
+OT_ATTACH_BACKOFF_MAX_INTERVAL=${OT_ATTACH_BACKOFF_MAX_INTERVAL:-}
+OT_ATTACH_BACKOFF_JITTER_INTERVAL=${OT_ATTACH_BACKOFF_JITTER_INTERVAL:-}
+
+# optional MPL seed set tuning, empty keeps the OpenThread defaults
+OT_MPL_SEED_SET_ENTRIES=${OT_MPL_SEED_SET_ENTRIES:-}
+OT_MPL_SEED_SET_ENTRY_LIFETIME=${OT_MPL_SEED_SET_ENTRY_LIFETIME:-}
+
+# optional CSMA/CA tuning, empty keeps the OpenThread defaults
+OT_MAX_CSMA_BACKOFFS_DIRECT=${OT_MAX_CSMA_BACKOFFS_DIRECT:-}
+OT_MAX_CSMA_BACKOFFS_INDIRECT=${OT_MAX_CSMA_BACKOFFS_INDIRECT:-}
+
 OT_SRCDIR="$(pwd)"
 readonly OT_SRCDIR
 
 OT_OPTIONS=(
-    "-DCMAKE_TOOLCHAIN_FILE=$IDF_PATH/tools/cmake/toolchain-esp32c6.cmake"
+    "-DCMAKE_TOOLCHAIN_FILE=$IDF_PATH/tools/cmake/toolchain-${OT_TARGET}.cmake"
     "-DCMAKE_BUILD_TYPE=MinSizeRel"
     "-DBUILD_TESTING=off"
     "-DOT_PLATFORM=external"
@@ -35,6 +51,13 @@ build()
         -DOT_PLATFORM=external \
         -DOT_SLAAC=ON \
         -DOT_SETTINGS_RAM=ON \
+        -DOT_ATTACH_BACKOFF_MIN_INTERVAL="${OT_ATTACH_BACKOFF_MIN_INTERVAL}" \
+        -DOT_ATTACH_BACKOFF_MAX_INTERVAL="${OT_ATTACH_BACKOFF_MAX_INTERVAL}" \
+        -DOT_ATTACH_BACKOFF_JITTER_INTERVAL="${OT_ATTACH_BACKOFF_JITTER_INTERVAL}" \
+        -DOT_MPL_SEED_SET_ENTRIES="${OT_MPL_SEED_SET_ENTRIES}" \
+        -DOT_MPL_SEED_SET_ENTRY_LIFETIME="${OT_MPL_SEED_SET_ENTRY_LIFETIME}" \
+        -DOT_MAX_CSMA_BACKOFFS_DIRECT="${OT_MAX_CSMA_BACKOFFS_DIRECT}" \
+        -DOT_MAX_CSMA_BACKOFFS_INDIRECT="${OT_MAX_CSMA_BACKOFFS_INDIRECT}" \
         -DOT_COMPILE_WARNING_AS_ERROR=ON "$@" "${OT_SRCDIR}"
 
     if [[ -n ${OT_CMAKE_NINJA_TARGET[*]} ]]; then
@@ -1,11 +1,30 @@
+#include "openthread/coap.h"
+#include "openthread/coap_secure.h"
 #include "openthread/instance.h"
 #include "openthread/udp.h"
+#include "openthread/border_routing.h"
+#include "openthread/dns_client.h"
+#include "openthread/dnssd_server.h"
+#include "openthread/srp_client.h"
+#include "openthread/srp_server.h"
 #include "openthread/thread.h"
+#include "openthread/thread_ftd.h"
 #include "openthread/tasklet.h"
+#include "openthread/link.h"
+#include "openthread/tcp.h"
+#include "openthread/link_raw.h"
+#include "openthread/netdata.h"
+#include "openthread/icmp6.h"
+#include "openthread/diag.h"
 
 #include "openthread/platform/alarm-milli.h"
+#include "openthread/platform/border_routing.h"
+#include "openthread/platform/dns.h"
+#include "openthread/platform/dnssd.h"
 #include "openthread/platform/radio.h"
 #include "openthread/platform/misc.h"
 #include "openthread/platform/entropy.h"
 #include "openthread/platform/settings.h"
 #include "openthread/platform/logging.h"
+#include "openthread/platform/crypto.h"
+#include "openthread/platform/diag.h"
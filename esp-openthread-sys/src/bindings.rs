@@ -4952,6 +4952,489 @@ extern "C" {
     #[doc = ""]
     pub fn otUdpIsPortInUse(aInstance: *mut otInstance, port: u16) -> bool;
 }
+pub const OT_DEFAULT_COAP_PORT: u32 = 5683;
+pub const OT_DEFAULT_COAP_SECURE_PORT: u32 = 5684;
+pub const OT_COAP_DEFAULT_TOKEN_LENGTH: u32 = 2;
+pub const OT_COAP_MAX_TOKEN_LENGTH: u32 = 8;
+#[doc = "< Confirmable"]
+pub const otCoapType_OT_COAP_TYPE_CONFIRMABLE: otCoapType = 0;
+#[doc = "< Non-confirmable"]
+pub const otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE: otCoapType = 1;
+#[doc = "< Acknowledgment"]
+pub const otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT: otCoapType = 2;
+#[doc = "< Reset"]
+pub const otCoapType_OT_COAP_TYPE_RESET: otCoapType = 3;
+#[doc = " CoAP Type values (2 bit unsigned integer)."]
+#[doc = ""]
+pub type otCoapType = crate::c_types::c_uint;
+#[doc = "< Empty message code"]
+pub const otCoapCode_OT_COAP_CODE_EMPTY: otCoapCode = 0;
+#[doc = "< Get"]
+pub const otCoapCode_OT_COAP_CODE_GET: otCoapCode = 1;
+#[doc = "< Post"]
+pub const otCoapCode_OT_COAP_CODE_POST: otCoapCode = 2;
+#[doc = "< Put"]
+pub const otCoapCode_OT_COAP_CODE_PUT: otCoapCode = 3;
+#[doc = "< Delete"]
+pub const otCoapCode_OT_COAP_CODE_DELETE: otCoapCode = 4;
+#[doc = "< 2.00"]
+pub const otCoapCode_OT_COAP_CODE_RESPONSE_MIN: otCoapCode = 64;
+#[doc = "< Created"]
+pub const otCoapCode_OT_COAP_CODE_CREATED: otCoapCode = 65;
+#[doc = "< Deleted"]
+pub const otCoapCode_OT_COAP_CODE_DELETED: otCoapCode = 66;
+#[doc = "< Valid"]
+pub const otCoapCode_OT_COAP_CODE_VALID: otCoapCode = 67;
+#[doc = "< Changed"]
+pub const otCoapCode_OT_COAP_CODE_CHANGED: otCoapCode = 68;
+#[doc = "< Content"]
+pub const otCoapCode_OT_COAP_CODE_CONTENT: otCoapCode = 69;
+#[doc = "< RFC7959 Continue"]
+pub const otCoapCode_OT_COAP_CODE_CONTINUE: otCoapCode = 95;
+#[doc = "< Bad Request"]
+pub const otCoapCode_OT_COAP_CODE_BAD_REQUEST: otCoapCode = 128;
+#[doc = "< Unauthorized"]
+pub const otCoapCode_OT_COAP_CODE_UNAUTHORIZED: otCoapCode = 129;
+#[doc = "< Bad Option"]
+pub const otCoapCode_OT_COAP_CODE_BAD_OPTION: otCoapCode = 130;
+#[doc = "< Forbidden"]
+pub const otCoapCode_OT_COAP_CODE_FORBIDDEN: otCoapCode = 131;
+#[doc = "< Not Found"]
+pub const otCoapCode_OT_COAP_CODE_NOT_FOUND: otCoapCode = 132;
+#[doc = "< Method Not Allowed"]
+pub const otCoapCode_OT_COAP_CODE_METHOD_NOT_ALLOWED: otCoapCode = 133;
+#[doc = "< Not Acceptable"]
+pub const otCoapCode_OT_COAP_CODE_NOT_ACCEPTABLE: otCoapCode = 134;
+#[doc = "< RFC7959 Request Entity Incomplete"]
+pub const otCoapCode_OT_COAP_CODE_REQUEST_INCOMPLETE: otCoapCode = 136;
+#[doc = "< Precondition Failed"]
+pub const otCoapCode_OT_COAP_CODE_PRECONDITION_FAILED: otCoapCode = 140;
+#[doc = "< Request Entity Too Large"]
+pub const otCoapCode_OT_COAP_CODE_REQUEST_TOO_LARGE: otCoapCode = 141;
+#[doc = "< Unsupported Content-Format"]
+pub const otCoapCode_OT_COAP_CODE_UNSUPPORTED_FORMAT: otCoapCode = 143;
+#[doc = "< Internal Server Error"]
+pub const otCoapCode_OT_COAP_CODE_INTERNAL_ERROR: otCoapCode = 160;
+#[doc = "< Not Implemented"]
+pub const otCoapCode_OT_COAP_CODE_NOT_IMPLEMENTED: otCoapCode = 161;
+#[doc = "< Bad Gateway"]
+pub const otCoapCode_OT_COAP_CODE_BAD_GATEWAY: otCoapCode = 162;
+#[doc = "< Service Unavailable"]
+pub const otCoapCode_OT_COAP_CODE_SERVICE_UNAVAILABLE: otCoapCode = 163;
+#[doc = "< Gateway Timeout"]
+pub const otCoapCode_OT_COAP_CODE_GATEWAY_TIMEOUT: otCoapCode = 164;
+#[doc = "< Proxying Not Supported"]
+pub const otCoapCode_OT_COAP_CODE_PROXY_NOT_SUPPORTED: otCoapCode = 165;
+#[doc = " CoAP Code values."]
+#[doc = ""]
+pub type otCoapCode = crate::c_types::c_uint;
+#[doc = "< Uri-Path"]
+pub const otCoapOptionType_OT_COAP_OPTION_URI_PATH: otCoapOptionType = 11;
+#[doc = "< Content-Format"]
+pub const otCoapOptionType_OT_COAP_OPTION_CONTENT_FORMAT: otCoapOptionType = 12;
+#[doc = " CoAP Option Numbers"]
+#[doc = ""]
+pub type otCoapOptionType = crate::c_types::c_uint;
+#[doc = " This structure represents a CoAP option."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otCoapOption {
+    #[doc = "< Option Number"]
+    pub mNumber: u16,
+    #[doc = "< Option Length"]
+    pub mLength: u16,
+}
+#[doc = " This structure acts as an iterator for CoAP options"]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otCoapOptionIterator {
+    #[doc = "< CoAP message"]
+    pub mMessage: *const otMessage,
+    #[doc = "< CoAP message option"]
+    pub mOption: otCoapOption,
+    #[doc = "< Byte offset of next option"]
+    pub mNextOptionOffset: u16,
+}
+#[doc = " This function pointer is called when a CoAP response is received or on the request timeout."]
+#[doc = ""]
+#[doc = " @param[in]  aContext      A pointer to application-specific context."]
+#[doc = " @param[in]  aMessage      A pointer to the message buffer containing the response. NULL if no response was received."]
+#[doc = " @param[in]  aMessageInfo  A pointer to the message info for @p aMessage. NULL if no response was received."]
+#[doc = " @param[in]  aResult       A result of the CoAP transaction."]
+#[doc = ""]
+pub type otCoapResponseHandler = ::core::option::Option<
+    unsafe extern "C" fn(
+        aContext: *mut crate::c_types::c_void,
+        aMessage: *mut otMessage,
+        aMessageInfo: *const otMessageInfo,
+        aResult: otError,
+    ),
+>;
+#[doc = " This function pointer is called when a CoAP request with a given Uri-Path is received."]
+#[doc = ""]
+#[doc = " @param[in]  aContext      A pointer to arbitrary context information."]
+#[doc = " @param[in]  aMessage      A pointer to the message."]
+#[doc = " @param[in]  aMessageInfo  A pointer to the message info for @p aMessage."]
+#[doc = ""]
+pub type otCoapRequestHandler = ::core::option::Option<
+    unsafe extern "C" fn(
+        aContext: *mut crate::c_types::c_void,
+        aMessage: *mut otMessage,
+        aMessageInfo: *const otMessageInfo,
+    ),
+>;
+extern "C" {
+    #[doc = " This function initializes the CoAP header."]
+    #[doc = ""]
+    #[doc = " @param[inout] aMessage   A pointer to the CoAP message to initialize."]
+    #[doc = " @param[in]    aType      CoAP message type."]
+    #[doc = " @param[in]    aCode      CoAP message code."]
+    #[doc = ""]
+    pub fn otCoapMessageInit(aMessage: *mut otMessage, aType: otCoapType, aCode: otCoapCode);
+}
+extern "C" {
+    #[doc = " This function initializes a response message."]
+    #[doc = ""]
+    #[doc = " @note Both message ID and token are set according to @p aRequest."]
+    #[doc = ""]
+    #[doc = " @param[inout] aResponse  A pointer to the CoAP response message."]
+    #[doc = " @param[in]    aRequest   A pointer to the CoAP request message."]
+    #[doc = " @param[in]    aType      CoAP message type."]
+    #[doc = " @param[in]    aCode      CoAP message code."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE     Successfully initialized the response message."]
+    #[doc = " @retval OT_ERROR_NO_BUFS  Insufficient message buffers available to initialize the response message."]
+    #[doc = ""]
+    pub fn otCoapMessageInitResponse(
+        aResponse: *mut otMessage,
+        aRequest: *const otMessage,
+        aType: otCoapType,
+        aCode: otCoapCode,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function sets the Token length and randomizes its value."]
+    #[doc = ""]
+    #[doc = " @param[inout]  aMessage      A pointer to the CoAP message."]
+    #[doc = " @param[in]     aTokenLength  The Length of a Token to set."]
+    #[doc = ""]
+    pub fn otCoapMessageGenerateToken(aMessage: *mut otMessage, aTokenLength: u8);
+}
+extern "C" {
+    #[doc = " This function appends an unsigned integer CoAP option as specified in RFC-7252 with generic content."]
+    #[doc = ""]
+    #[doc = " @param[inout]  aMessage  A pointer to the CoAP message."]
+    #[doc = " @param[in]     aNumber   The CoAP Option number."]
+    #[doc = " @param[in]     aValue    The CoAP Option unsigned integer value."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully appended the option."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The option type is not equal or greater than the last option type."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       The option length exceeds the buffer size."]
+    #[doc = ""]
+    pub fn otCoapMessageAppendUintOption(
+        aMessage: *mut otMessage,
+        aNumber: u16,
+        aValue: u32,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function appends a Uri-Path option."]
+    #[doc = ""]
+    #[doc = " @param[inout]  aMessage  A pointer to the CoAP message."]
+    #[doc = " @param[in]     aUriPath  A pointer to a NULL-terminated string."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully appended the option."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The option type is not equal or greater than the last option type."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       The option length exceeds the buffer size."]
+    #[doc = ""]
+    pub fn otCoapMessageAppendUriPathOptions(
+        aMessage: *mut otMessage,
+        aUriPath: *const crate::c_types::c_char,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function adds Payload Marker indicating beginning of the payload to the CoAP header."]
+    #[doc = ""]
+    #[doc = " @param[inout]  aMessage  A pointer to the CoAP message."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE     Payload Marker successfully added."]
+    #[doc = " @retval OT_ERROR_NO_BUFS  Header Payload Marker exceeds the buffer size."]
+    #[doc = ""]
+    pub fn otCoapMessageSetPayloadMarker(aMessage: *mut otMessage) -> otError;
+}
+extern "C" {
+    #[doc = " This function returns the Type value."]
+    #[doc = ""]
+    #[doc = " @param[in]  aMessage  A pointer to the CoAP message."]
+    #[doc = ""]
+    #[doc = " @returns The Type value."]
+    #[doc = ""]
+    pub fn otCoapMessageGetType(aMessage: *const otMessage) -> otCoapType;
+}
+extern "C" {
+    #[doc = " This function returns the Code value."]
+    #[doc = ""]
+    #[doc = " @param[in]  aMessage  A pointer to the CoAP message."]
+    #[doc = ""]
+    #[doc = " @returns The Code value."]
+    #[doc = ""]
+    pub fn otCoapMessageGetCode(aMessage: *const otMessage) -> otCoapCode;
+}
+extern "C" {
+    #[doc = " Initialise a CoAP Option iterator for the given message."]
+    #[doc = ""]
+    #[doc = " @param[in]  aIterator   A pointer to the CoAP message option iterator."]
+    #[doc = " @param[in]  aMessage    A pointer to the CoAP message."]
+    #[doc = ""]
+    #[doc = " @retval  OT_ERROR_NONE   Successfully initialised."]
+    #[doc = " @retval  OT_ERROR_PARSE  Message state is inconsistent."]
+    #[doc = ""]
+    pub fn otCoapOptionIteratorInit(
+        aIterator: *mut otCoapOptionIterator,
+        aMessage: *const otMessage,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function returns a pointer to the first option matching the specified option number."]
+    #[doc = ""]
+    #[doc = " @param[in]  aIterator A pointer to the CoAP message option iterator."]
+    #[doc = " @param[in]  aOption   The option number sought."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the first matching option. If no matching option is present NULL pointer is returned."]
+    #[doc = ""]
+    pub fn otCoapOptionIteratorGetFirstOptionMatching(
+        aIterator: *mut otCoapOptionIterator,
+        aOption: u16,
+    ) -> *const otCoapOption;
+}
+extern "C" {
+    #[doc = " This function returns a pointer to the next option matching the specified option number."]
+    #[doc = ""]
+    #[doc = " @param[in]  aIterator A pointer to the CoAP message option iterator."]
+    #[doc = " @param[in]  aOption   The option number sought."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the next matching option. If no further matching option is present NULL pointer is returned."]
+    #[doc = ""]
+    pub fn otCoapOptionIteratorGetNextOptionMatching(
+        aIterator: *mut otCoapOptionIterator,
+        aOption: u16,
+    ) -> *const otCoapOption;
+}
+extern "C" {
+    #[doc = " This function fills current option value into @p aValue assuming the current value is an unsigned integer encoded"]
+    #[doc = " according to https://tools.ietf.org/html/rfc7252#section-3.2"]
+    #[doc = ""]
+    #[doc = " @param[in]     aIterator A pointer to the CoAP message option iterator."]
+    #[doc = " @param[out]    aValue    A pointer to an unsigned integer to receive the option value."]
+    #[doc = ""]
+    #[doc = " @retval  OT_ERROR_NONE       Successfully filled value."]
+    #[doc = " @retval  OT_ERROR_NOT_FOUND  No current option."]
+    #[doc = " @retval  OT_ERROR_NO_BUFS    Value is too long to fit in a uint64_t."]
+    #[doc = ""]
+    pub fn otCoapOptionIteratorGetOptionUintValue(
+        aIterator: *mut otCoapOptionIterator,
+        aValue: *mut u64,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function fills current option value into @p aValue."]
+    #[doc = ""]
+    #[doc = " @param[in]     aIterator A pointer to the CoAP message option iterator."]
+    #[doc = " @param[out]    aValue    A pointer to a buffer to receive the option value."]
+    #[doc = ""]
+    #[doc = " @retval  OT_ERROR_NONE       Successfully filled value."]
+    #[doc = " @retval  OT_ERROR_NOT_FOUND  No current option, i.e. the iterator reached the end of the options."]
+    #[doc = ""]
+    pub fn otCoapOptionIteratorGetOptionValue(
+        aIterator: *mut otCoapOptionIterator,
+        aValue: *mut crate::c_types::c_void,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function creates a new CoAP message."]
+    #[doc = ""]
+    #[doc = " @note If @p aSettings is 'NULL', the link layer security is enabled and the message priority is set to"]
+    #[doc = " OT_MESSAGE_PRIORITY_NORMAL by default."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aSettings  A pointer to the message settings or NULL to set default settings."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the message buffer or NULL if no message buffers are available or parameters are invalid."]
+    #[doc = ""]
+    pub fn otCoapNewMessage(
+        aInstance: *mut otInstance,
+        aSettings: *const otMessageSettings,
+    ) -> *mut otMessage;
+}
+#[doc = " This function pointer is called when the DTLS connection state changes."]
+#[doc = ""]
+#[doc = " @param[in]  aConnected  true, if a connection was established, false otherwise."]
+#[doc = " @param[in]  aContext    A pointer to arbitrary context information."]
+#[doc = ""]
+pub type otHandleCoapSecureClientConnect = ::core::option::Option<
+    unsafe extern "C" fn(aConnected: bool, aContext: *mut crate::c_types::c_void),
+>;
+extern "C" {
+    #[doc = " This function starts the CoAP Secure service."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aPort      The local UDP port to bind to."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE  Successfully started the CoAP Secure server."]
+    #[doc = ""]
+    pub fn otCoapSecureStart(aInstance: *mut otInstance, aPort: u16) -> otError;
+}
+extern "C" {
+    #[doc = " This function stops the CoAP Secure server."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    pub fn otCoapSecureStop(aInstance: *mut otInstance);
+}
+extern "C" {
+    #[doc = " This method sets the Pre-Shared Key (PSK) and cipher suite"]
+    #[doc = " DTLS_PSK_WITH_AES_128_CCM_8."]
+    #[doc = ""]
+    #[doc = " @note This function requires the build-time feature `MBEDTLS_KEY_EXCHANGE_PSK_ENABLED` to be enabled."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance        A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aPsk             A pointer to the PSK."]
+    #[doc = " @param[in]  aPskLength       The PSK length."]
+    #[doc = " @param[in]  aPskIdentity     The Identity Name for the PSK."]
+    #[doc = " @param[in]  aPskIdLength     The PSK Identity Length."]
+    #[doc = ""]
+    pub fn otCoapSecureSetPsk(
+        aInstance: *mut otInstance,
+        aPsk: *const u8,
+        aPskLength: u16,
+        aPskIdentity: *const u8,
+        aPskIdLength: u16,
+    );
+}
+extern "C" {
+    #[doc = " This method sets the authentication mode for the coap secure connection."]
+    #[doc = ""]
+    #[doc = " Disable or enable the verification of peer certificate."]
+    #[doc = " Must be called before start."]
+    #[doc = ""]
+    #[doc = " @param[in]   aInstance               A pointer to an OpenThread instance."]
+    #[doc = " @param[in]   aVerifyPeerCertificate  true, to verify the peer certificate."]
+    #[doc = ""]
+    pub fn otCoapSecureSetSslAuthMode(aInstance: *mut otInstance, aVerifyPeerCertificate: bool);
+}
+extern "C" {
+    #[doc = " This function initializes DTLS session with a peer."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance               A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aSockAddr               A pointer to the remote socket address."]
+    #[doc = " @param[in]  aHandler                A pointer to a function that will be called when the DTLS connection"]
+    #[doc = "                                     state changes."]
+    #[doc = " @param[in]  aContext                A pointer to arbitrary context information."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE  Successfully started DTLS connection."]
+    #[doc = ""]
+    pub fn otCoapSecureConnect(
+        aInstance: *mut otInstance,
+        aSockAddr: *const otSockAddr,
+        aHandler: otHandleCoapSecureClientConnect,
+        aContext: *mut crate::c_types::c_void,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function stops the DTLS connection."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    pub fn otCoapSecureDisconnect(aInstance: *mut otInstance);
+}
+extern "C" {
+    #[doc = " This function indicates whether or not the DTLS session is connected."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval TRUE   The DTLS session is connected."]
+    #[doc = " @retval FALSE  The DTLS session is not connected."]
+    #[doc = ""]
+    pub fn otCoapSecureIsConnected(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " This function indicates whether or not the DTLS session is active."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval TRUE  If DTLS session is active."]
+    #[doc = " @retval FALSE If DTLS session is not active."]
+    #[doc = ""]
+    pub fn otCoapSecureIsConnectionActive(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " This function sends a CoAP request over secure DTLS connection."]
+    #[doc = ""]
+    #[doc = " If a response for a request is expected, respective function and context information should be provided."]
+    #[doc = " If no response is expected, these arguments should be NULL pointers."]
+    #[doc = " If Message Id was not set in the header (equal to 0), this function will assign unique Message Id to the message."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance     A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aMessage      A reference to the message to send."]
+    #[doc = " @param[in]  aHandler      A function pointer that shall be called on response reception or time-out."]
+    #[doc = " @param[in]  aContext      A pointer to arbitrary context information."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE           Successfully sent CoAP message."]
+    #[doc = " @retval OT_ERROR_NO_BUFS        Failed to allocate retransmission data."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE  DTLS connection was not initialized."]
+    #[doc = ""]
+    pub fn otCoapSecureSendRequest(
+        aInstance: *mut otInstance,
+        aMessage: *mut otMessage,
+        aHandler: otCoapResponseHandler,
+        aContext: *mut crate::c_types::c_void,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function sets the connected callback to indicate, when"]
+    #[doc = " a Client connect to the CoAP Secure server."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance     A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aHandler      A pointer to a function that will be called once DTLS connection is established."]
+    #[doc = " @param[in]  aContext      A pointer to arbitrary context information. May be NULL if not used."]
+    #[doc = ""]
+    pub fn otCoapSecureSetClientConnectedCallback(
+        aInstance: *mut otInstance,
+        aHandler: otHandleCoapSecureClientConnect,
+        aContext: *mut crate::c_types::c_void,
+    );
+}
+extern "C" {
+    #[doc = " This function sets the default handler for unhandled CoAP Secure requests."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aHandler   A function pointer that shall be called when an unhandled request arrives."]
+    #[doc = " @param[in]  aContext   A pointer to arbitrary context information. May be NULL if not used."]
+    #[doc = ""]
+    pub fn otCoapSecureSetDefaultHandler(
+        aInstance: *mut otInstance,
+        aHandler: otCoapRequestHandler,
+        aContext: *mut crate::c_types::c_void,
+    );
+}
+extern "C" {
+    #[doc = " This function sends a CoAP response over secure DTLS connection."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance     A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aMessage      A pointer to the CoAP response to send."]
+    #[doc = " @param[in]  aMessageInfo  A pointer to the message info associated with @p aMessage."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE      Successfully enqueued the CoAP response message."]
+    #[doc = " @retval OT_ERROR_NO_BUFS   Insufficient buffers available to send the CoAP response."]
+    #[doc = ""]
+    pub fn otCoapSecureSendResponse(
+        aInstance: *mut otInstance,
+        aMessage: *mut otMessage,
+        aMessageInfo: *const otMessageInfo,
+    ) -> otError;
+}
 #[doc = " @struct otNetworkKey"]
 #[doc = ""]
 #[doc = " This structure represents a Thread Network Key."]
@@ -9186,4 +9669,1716 @@ extern "C" {
     #[doc = " @param[in] aInstance  The OpenThread instance structure."]
     pub fn otPlatSettingsWipe(aInstance: *mut otInstance);
 }
+pub const OT_DNS_MAX_NAME_SIZE: u32 = 255;
+extern "C" {
+    #[doc = " Enables or disables listening for wake-up frames."]
+    #[doc = ""]
+    #[doc = " Requires `OPENTHREAD_CONFIG_WAKEUP_END_DEVICE_ENABLE`."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in] aEnable    true to enable listening for wake-up frames, false otherwise."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE           Successfully enabled / disabled the listening for wake-up frames."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS   The listen duration is greater than the listen interval."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE  Not in the Thread disabled state when enabling."]
+    #[doc = ""]
+    pub fn otLinkSetWakeUpListenEnabled(aInstance: *mut otInstance, aEnable: bool) -> otError;
+}
+extern "C" {
+    #[doc = " Returns whether listening for wake-up frames is enabled."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    pub fn otLinkIsWakeupListenEnabled(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " Gets the wake-up listen parameters."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance   A pointer to an OpenThread instance."]
+    #[doc = " @param[out] aInterval   A pointer to return the wake-up listen interval in microseconds."]
+    #[doc = " @param[out] aDuration   A pointer to return the wake-up listen duration in microseconds."]
+    #[doc = ""]
+    pub fn otLinkGetWakeupListenParameters(
+        aInstance: *mut otInstance,
+        aInterval: *mut u32,
+        aDuration: *mut u32,
+    );
+}
+extern "C" {
+    #[doc = " Sets the wake-up listen parameters."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance   A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aInterval   The wake-up listen interval in microseconds."]
+    #[doc = " @param[in]  aDuration   The wake-up listen duration in microseconds."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully set the wake-up listen parameters."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  Configured listen interval is not a valid value."]
+    #[doc = ""]
+    pub fn otLinkSetWakeupListenParameters(
+        aInstance: *mut otInstance,
+        aInterval: u32,
+        aDuration: u32,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Gets the wake-up channel."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns The wake-up channel."]
+    #[doc = ""]
+    pub fn otLinkGetWakeupChannel(aInstance: *mut otInstance) -> u8;
+}
+extern "C" {
+    #[doc = " Sets the wake-up channel."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in] aChannel   The wake-up sample channel. Channel value should be `0` (Set Wake-up Channel unspecified,"]
+    #[doc = "                       which means the device will use the PAN channel) or within the range [1, 10] (if 915-MHz"]
+    #[doc = "                       supported) and [11, 26] (if 2.4 GHz supported)."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully set the wake-up channel."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The @p aChannel is not in the supported channel mask."]
+    #[doc = ""]
+    pub fn otLinkSetWakeupChannel(aInstance: *mut otInstance, aChannel: u8) -> otError;
+}
+#[doc = "< Indicates the flag is not specified."]
+pub const otDnsRecursionFlag_OT_DNS_FLAG_UNSPECIFIED: otDnsRecursionFlag = 0;
+#[doc = "< Indicates DNS name server can resolve the query recursively."]
+pub const otDnsRecursionFlag_OT_DNS_FLAG_RECURSION_DESIRED: otDnsRecursionFlag = 1;
+#[doc = "< Indicates DNS name server can not resolve the query recursively."]
+pub const otDnsRecursionFlag_OT_DNS_FLAG_NO_RECURSION: otDnsRecursionFlag = 2;
+#[doc = " Type represents the \"Recursion Desired\" (RD) flag in an `otDnsQueryConfig`."]
+#[doc = ""]
+pub type otDnsRecursionFlag = crate::c_types::c_uint;
+#[doc = "< NAT64 mode is not specified. Use default NAT64 mode."]
+pub const otDnsNat64Mode_OT_DNS_NAT64_UNSPECIFIED: otDnsNat64Mode = 0;
+#[doc = "< Allow NAT64 address translation during DNS client address resolution."]
+pub const otDnsNat64Mode_OT_DNS_NAT64_ALLOW: otDnsNat64Mode = 1;
+#[doc = "< Do not allow NAT64 address translation during DNS client address resolution."]
+pub const otDnsNat64Mode_OT_DNS_NAT64_DISALLOW: otDnsNat64Mode = 2;
+#[doc = " Type represents the NAT64 mode in an `otDnsQueryConfig`."]
+#[doc = ""]
+#[doc = " The NAT64 mode indicates whether to allow or disallow NAT64 address translation during DNS client address resolution."]
+#[doc = " This mode is only used when `OPENTHREAD_CONFIG_DNS_CLIENT_NAT64_ENABLE` is enabled."]
+#[doc = ""]
+pub type otDnsNat64Mode = crate::c_types::c_uint;
+#[doc = "< Mode is not specified. Use default service mode."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_UNSPECIFIED: otDnsServiceMode = 0;
+#[doc = "< Query for SRV record only."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_SRV: otDnsServiceMode = 1;
+#[doc = "< Query for TXT record only."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_TXT: otDnsServiceMode = 2;
+#[doc = "< Query for both SRV and TXT records in same message."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_SRV_TXT: otDnsServiceMode = 3;
+#[doc = "< Query in parallel for SRV and TXT using separate messages."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_SRV_TXT_SEPARATE: otDnsServiceMode = 4;
+#[doc = "< Query for TXT/SRV together first, if fails then query separately."]
+pub const otDnsServiceMode_OT_DNS_SERVICE_MODE_SRV_TXT_OPTIMIZE: otDnsServiceMode = 5;
+#[doc = " Type represents the service resolution mode in an `otDnsQueryConfig`."]
+#[doc = ""]
+pub type otDnsServiceMode = crate::c_types::c_uint;
+#[doc = "< DNS transport is unspecified."]
+pub const otDnsTransportProto_OT_DNS_TRANSPORT_UNSPECIFIED: otDnsTransportProto = 0;
+#[doc = "< DNS query should be sent via UDP."]
+pub const otDnsTransportProto_OT_DNS_TRANSPORT_UDP: otDnsTransportProto = 1;
+#[doc = "< DNS query should be sent via TCP."]
+pub const otDnsTransportProto_OT_DNS_TRANSPORT_TCP: otDnsTransportProto = 2;
+#[doc = " Type represents the DNS transport protocol in an `otDnsQueryConfig`."]
+#[doc = ""]
+pub type otDnsTransportProto = crate::c_types::c_uint;
+#[doc = " Represents a DNS query configuration."]
+#[doc = ""]
+#[doc = " Any of the fields in this structure can be set to zero to indicate that it is not specified. How the unspecified"]
+#[doc = " fields are treated is determined by the function which uses the instance of `otDnsQueryConfig`."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otDnsQueryConfig {
+    #[doc = "< Server address (IPv6 addr/port). All zero or zero port for unspecified."]
+    pub mServerSockAddr: otSockAddr,
+    #[doc = "< Wait time (in msec) to rx response. Zero indicates unspecified value."]
+    pub mResponseTimeout: u32,
+    #[doc = "< Maximum tx attempts before reporting failure. Zero for unspecified value."]
+    pub mMaxTxAttempts: u8,
+    #[doc = "< Indicates whether the server can resolve the query recursively or not."]
+    pub mRecursionFlag: otDnsRecursionFlag,
+    #[doc = "< Allow/Disallow NAT64 address translation during address resolution."]
+    pub mNat64Mode: otDnsNat64Mode,
+    #[doc = "< Determines which records to query during service resolution."]
+    pub mServiceMode: otDnsServiceMode,
+    #[doc = "< Select default transport protocol."]
+    pub mTransportProto: otDnsTransportProto,
+}
+extern "C" {
+    #[doc = " Gets the current default query config used by DNS client."]
+    #[doc = ""]
+    #[doc = " When OpenThread stack starts, the default DNS query config is determined from a set of OT config options such as"]
+    #[doc = " `OPENTHREAD_CONFIG_DNS_CLIENT_DEFAULT_SERVER_IP6_ADDRESS`, `_DEFAULT_SERVER_PORT`, `_DEFAULT_RESPONSE_TIMEOUT`, etc."]
+    #[doc = " (see `config/dns_client.h` for all related config options)."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance        A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the current default config being used by DNS client."]
+    #[doc = ""]
+    pub fn otDnsClientGetDefaultConfig(aInstance: *mut otInstance) -> *const otDnsQueryConfig;
+}
+extern "C" {
+    #[doc = " Sets the default query config on DNS client."]
+    #[doc = ""]
+    #[doc = " @note Any ongoing query will continue to use the config from when it was started. The new default config will be"]
+    #[doc = " used for any future DNS queries."]
+    #[doc = ""]
+    #[doc = " The @p aConfig can be NULL. In this case the default config will be set to the defaults from OT config options"]
+    #[doc = " `OPENTHREAD_CONFIG_DNS_CLIENT_DEFAULT_{}`. This resets the default query config back to to the config when the"]
+    #[doc = " OpenThread stack starts."]
+    #[doc = ""]
+    #[doc = " In a non-NULL @p aConfig, caller can choose to leave some of the fields in `otDnsQueryConfig` instance unspecified"]
+    #[doc = " (value zero). The unspecified fields are replaced by the corresponding OT config option definitions"]
+    #[doc = " `OPENTHREAD_CONFIG_DNS_CLIENT_DEFAULT_{}` to form the default query config."]
+    #[doc = ""]
+    #[doc = " When `OPENTHREAD_CONFIG_DNS_CLIENT_DEFAULT_SERVER_ADDRESS_AUTO_SET_ENABLE` is enabled, the server's IPv6 address in"]
+    #[doc = " the default config is automatically set and updated by DNS client. This is done only when user does not explicitly"]
+    #[doc = " set or specify it."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aConfig    A pointer to the new query config to use as default."]
+    #[doc = ""]
+    pub fn otDnsClientSetDefaultConfig(aInstance: *mut otInstance, aConfig: *const otDnsQueryConfig);
+}
+#[doc = " This opaque type represents a response to an address resolution DNS query."]
+#[doc = ""]
+#[doc = " Pointers to instance of this type are provided from callback `otDnsAddressCallback`."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otDnsAddressResponse {
+    _unused: [u8; 0],
+}
+#[doc = " This function pointer is called when a DNS response is received for an address resolution query."]
+#[doc = ""]
+#[doc = " Within this callback the user can use `otDnsAddressResponseGet{Item}()` functions along with the @p aResponse"]
+#[doc = " pointer to get more info about the response."]
+#[doc = ""]
+#[doc = " The @p aResponse pointer can only be used within this callback and after returning from this function it will not"]
+#[doc = " stay valid, so the user MUST NOT retain the @p aResponse pointer for later use."]
+#[doc = ""]
+#[doc = " @param[in]  aError     The result of the DNS transaction."]
+#[doc = " @param[in]  aResponse  A pointer to the response (it is always non-NULL)."]
+#[doc = " @param[in]  aContext   A pointer to application-specific context."]
+#[doc = ""]
+#[doc = " The @p aError can have the following:"]
+#[doc = ""]
+#[doc = "  - OT_ERROR_NONE              A response was received successfully."]
+#[doc = "  - OT_ERROR_ABORT             A DNS transaction was aborted by stack."]
+#[doc = "  - OT_ERROR_RESPONSE_TIMEOUT  No DNS response has been received within timeout."]
+#[doc = ""]
+#[doc = " If the server rejects the address resolution request the error code from server is mapped as follow:"]
+#[doc = ""]
+#[doc = "  - (0)  NOERROR   Success (no error condition)                    -> OT_ERROR_NONE"]
+#[doc = "  - (1)  FORMERR   Server unable to interpret due to format error  -> OT_ERROR_PARSE"]
+#[doc = "  - (2)  SERVFAIL  Server encountered an internal failure          -> OT_ERROR_FAILED"]
+#[doc = "  - (3)  NXDOMAIN  Name that ought to exist, does not exist         -> OT_ERROR_NOT_FOUND"]
+#[doc = "  - (4)  NOTIMP    Server does not support the query type (OpCode) -> OT_ERROR_NOT_IMPLEMENTED"]
+#[doc = "  - (5)  REFUSED   Server refused for policy/security reasons      -> OT_ERROR_SECURITY"]
+#[doc = "  - (6)  YXDOMAIN  Some name that ought not to exist, does exist    -> OT_ERROR_DUPLICATED"]
+#[doc = "  - (7)  YXRRSET   Some RRset that ought not to exist, does exist  -> OT_ERROR_DUPLICATED"]
+#[doc = "  - (8)  NXRRSET   Some RRset that ought to exist, does not exist  -> OT_ERROR_NOT_FOUND"]
+#[doc = "  - (9)  NOTAUTH   Service is not authoritative for zone           -> OT_ERROR_SECURITY"]
+#[doc = "  - (10) NOTZONE   A name is not in the zone                       -> OT_ERROR_PARSE"]
+#[doc = "  - (20) BADNAME   Bad name                                        -> OT_ERROR_PARSE"]
+#[doc = "  - (21) BADALG    Bad algorithm                                   -> OT_ERROR_SECURITY"]
+#[doc = "  - (22) BADTRUN   Bad truncation                                  -> OT_ERROR_PARSE"]
+#[doc = "  - Other response codes                                              -> OT_ERROR_FAILED"]
+#[doc = ""]
+pub type otDnsAddressCallback = ::core::option::Option<
+    unsafe extern "C" fn(
+        aError: otError,
+        aResponse: *const otDnsAddressResponse,
+        aContext: *mut crate::c_types::c_void,
+    ),
+>;
+extern "C" {
+    #[doc = " This function sends an address resolution DNS query for AAAA (IPv6) record(s) for a given host name."]
+    #[doc = ""]
+    #[doc = " The @p aConfig can be NULL. In this case the default config (from `otDnsClientGetDefaultConfig()`) will be used as"]
+    #[doc = " the config for this query. In a non-NULL @p aConfig, some of the fields can be left unspecified (value zero). The"]
+    #[doc = " unspecified fields are then replaced by the values from the default config."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance        A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aHostName        The host name for which to query the address (MUST NOT be NULL)."]
+    #[doc = " @param[in]  aCallback        A function pointer that shall be called on response reception or time-out."]
+    #[doc = " @param[in]  aContext         A pointer to arbitrary context information."]
+    #[doc = " @param[in]  aConfig          A pointer to the config to use for this query."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Query sent successfully. @p aCallback will be invoked to report the status."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Insufficient buffer to prepare and send query."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The host name is not valid format."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE Cannot send query since Thread interface is not up."]
+    #[doc = ""]
+    pub fn otDnsClientResolveAddress(
+        aInstance: *mut otInstance,
+        aHostName: *const crate::c_types::c_char,
+        aCallback: otDnsAddressCallback,
+        aContext: *mut crate::c_types::c_void,
+        aConfig: *const otDnsQueryConfig,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function gets an IPv6 address associated with an address resolution DNS response."]
+    #[doc = ""]
+    #[doc = " This function MUST only be used from `otDnsAddressCallback`."]
+    #[doc = ""]
+    #[doc = " The response may include multiple IPv6 address records. @p aIndex can be used to iterate through the list of"]
+    #[doc = " addresses. Index zero gets the first address and so on. When we reach end of the list, `OT_ERROR_NOT_FOUND` is"]
+    #[doc = " returned."]
+    #[doc = ""]
+    #[doc = " @param[in]  aResponse     A pointer to the response."]
+    #[doc = " @param[in]  aIndex        The address record index to retrieve."]
+    #[doc = " @param[out] aAddress      A pointer to a IPv6 address to output the address (MUST NOT be NULL)."]
+    #[doc = " @param[out] aTtl          A pointer to an `uint32_t` to output TTL for the address. It can be NULL if caller does not"]
+    #[doc = "                           want to get the TTL."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE           The address was read successfully."]
+    #[doc = " @retval OT_ERROR_NOT_FOUND      No address record in @p aResponse at @p aIndex."]
+    #[doc = " @retval OT_ERROR_PARSE          Could not parse the records in the @p aResponse."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE  No NAT64 prefix (applicable only when NAT64 is allowed)."]
+    #[doc = ""]
+    pub fn otDnsAddressResponseGetAddress(
+        aResponse: *const otDnsAddressResponse,
+        aIndex: u16,
+        aAddress: *mut otIp6Address,
+        aTtl: *mut u32,
+    ) -> otError;
+}
+pub const OT_CRYPTO_SHA256_HASH_SIZE: u32 = 32;
+pub const OT_CRYPTO_ECDSA_MAX_DER_SIZE: u32 = 125;
+pub const OT_CRYPTO_ECDSA_PUBLIC_KEY_SIZE: u32 = 64;
+pub const OT_CRYPTO_ECDSA_SIGNATURE_SIZE: u32 = 64;
+#[doc = " @struct otPlatCryptoSha256Hash"]
+#[doc = ""]
+#[doc = " Represents a SHA-256 hash."]
+#[doc = ""]
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct otPlatCryptoSha256Hash {
+    #[doc = "< Hash bytes."]
+    pub m8: [u8; 32usize],
+}
+#[doc = " @struct otPlatCryptoEcdsaKeyPair"]
+#[doc = ""]
+#[doc = " Represents an ECDSA key pair (public and private keys)."]
+#[doc = ""]
+#[doc = " The key pair is stored using Distinguished Encoding Rules (DER) format (per RFC 5915)."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct otPlatCryptoEcdsaKeyPair {
+    pub mDerBytes: [u8; 125usize],
+    pub mDerLength: u8,
+}
+#[doc = " @struct otPlatCryptoEcdsaPublicKey"]
+#[doc = ""]
+#[doc = " Represents a ECDSA public key."]
+#[doc = ""]
+#[doc = " The public key is stored as a byte sequence representation of an uncompressed curve point (RFC 6605 - sec 4)."]
+#[doc = ""]
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct otPlatCryptoEcdsaPublicKey {
+    pub m8: [u8; 64usize],
+}
+#[doc = " @struct otPlatCryptoEcdsaSignature"]
+#[doc = ""]
+#[doc = " Represents an ECDSA signature."]
+#[doc = ""]
+#[doc = " The signature is encoded as the concatenated binary representation of two MPIs `r` and `s` which are calculated"]
+#[doc = " during signing (RFC 6605 - section 4)."]
+#[doc = ""]
+#[repr(C, packed)]
+#[derive(Debug, Copy, Clone)]
+pub struct otPlatCryptoEcdsaSignature {
+    pub m8: [u8; 64usize],
+}
+extern "C" {
+    #[doc = " Generate and populate the output buffer with a new ECDSA key-pair."]
+    #[doc = ""]
+    #[doc = " @param[out] aKeyPair           A pointer to an ECDSA key-pair structure to store the generated key-pair."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          A new key-pair was generated successfully."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Failed to allocate buffer for key generation."]
+    #[doc = " @retval OT_ERROR_NOT_CAPABLE   Feature not supported."]
+    #[doc = " @retval OT_ERROR_FAILED        Failed to generate key-pair."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaGenerateKey(aKeyPair: *mut otPlatCryptoEcdsaKeyPair) -> otError;
+}
+extern "C" {
+    #[doc = " Get the associated public key from the input context."]
+    #[doc = ""]
+    #[doc = " @param[in]  aKeyPair           A pointer to an ECDSA key-pair structure where the key-pair is stored."]
+    #[doc = " @param[out] aPublicKey         A pointer to an ECDSA public key structure to store the public key."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Public key was retrieved successfully, and @p aBuffer is updated."]
+    #[doc = " @retval OT_ERROR_PARSE         The key-pair DER format could not be parsed (invalid format)."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The @p aContext is NULL."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaGetPublicKey(
+        aKeyPair: *const otPlatCryptoEcdsaKeyPair,
+        aPublicKey: *mut otPlatCryptoEcdsaPublicKey,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Calculate the ECDSA signature for a hashed message using the private key from the input context."]
+    #[doc = ""]
+    #[doc = " Uses the deterministic digital signature generation procedure from RFC 6979."]
+    #[doc = ""]
+    #[doc = " @param[in]  aKeyPair           A pointer to an ECDSA key-pair structure where the key-pair is stored."]
+    #[doc = " @param[in]  aHash              A pointer to a SHA-256 hash structure where the hash value for signature calculation"]
+    #[doc = "                                is stored."]
+    #[doc = " @param[out] aSignature         A pointer to an ECDSA signature structure to output the calculated signature."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          The signature was calculated successfully, @p aSignature was updated."]
+    #[doc = " @retval OT_ERROR_PARSE         The key-pair DER format could not be parsed (invalid format)."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Failed to allocate buffer for signature calculation."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The @p aContext is NULL."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaSign(
+        aKeyPair: *const otPlatCryptoEcdsaKeyPair,
+        aHash: *const otPlatCryptoSha256Hash,
+        aSignature: *mut otPlatCryptoEcdsaSignature,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Use the key from the input context to verify the ECDSA signature of a hashed message."]
+    #[doc = ""]
+    #[doc = " @param[in]  aPublicKey         A pointer to an ECDSA public key structure where the public key for signature"]
+    #[doc = "                                verification is stored."]
+    #[doc = " @param[in]  aHash              A pointer to a SHA-256 hash structure where the hash value for signature verification"]
+    #[doc = "                                is stored."]
+    #[doc = " @param[in]  aSignature         A pointer to an ECDSA signature structure where the signature value to be verified is"]
+    #[doc = "                                stored."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          The signature was verified successfully."]
+    #[doc = " @retval OT_ERROR_SECURITY      The signature is invalid."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The key or hash is invalid."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Failed to allocate buffer for signature verification."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaVerify(
+        aPublicKey: *const otPlatCryptoEcdsaPublicKey,
+        aHash: *const otPlatCryptoSha256Hash,
+        aSignature: *const otPlatCryptoEcdsaSignature,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Calculate the ECDSA signature for a hashed message using the Key reference passed."]
+    #[doc = ""]
+    #[doc = " Uses the deterministic digital signature generation procedure from RFC 6979."]
+    #[doc = ""]
+    #[doc = " @param[in]  aKeyRef            Key Reference to the slot where the key-pair is stored."]
+    #[doc = " @param[in]  aHash              A pointer to a SHA-256 hash structure where the hash value for signature calculation"]
+    #[doc = "                                is stored."]
+    #[doc = " @param[out] aSignature         A pointer to an ECDSA signature structure to output the calculated signature."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          The signature was calculated successfully, @p aSignature was updated."]
+    #[doc = " @retval OT_ERROR_PARSE         The key-pair DER format could not be parsed (invalid format)."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Failed to allocate buffer for signature calculation."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The @p aContext is NULL."]
+    #[doc = ""]
+    #[doc = " @note This API is only used by OT core when `OPENTHREAD_CONFIG_PLATFORM_KEY_REFERENCES_ENABLE` is enabled."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaSignUsingKeyRef(
+        aKeyRef: otCryptoKeyRef,
+        aHash: *const otPlatCryptoSha256Hash,
+        aSignature: *mut otPlatCryptoEcdsaSignature,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Get the associated public key from the key reference passed."]
+    #[doc = ""]
+    #[doc = " The public key is stored differently depending on the crypto backend library being used"]
+    #[doc = " (OPENTHREAD_CONFIG_CRYPTO_LIB)."]
+    #[doc = ""]
+    #[doc = " This API must make sure to return the public key as a byte sequence representation of an"]
+    #[doc = " uncompressed curve point (RFC 6605 - sec 4)"]
+    #[doc = ""]
+    #[doc = " @param[in]  aKeyRef            Key Reference to the slot where the key-pair is stored."]
+    #[doc = " @param[out] aPublicKey         A pointer to an ECDSA public key structure to store the public key."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Public key was retrieved successfully, and @p aBuffer is updated."]
+    #[doc = " @retval OT_ERROR_PARSE         The key-pair DER format could not be parsed (invalid format)."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The @p aContext is NULL."]
+    #[doc = ""]
+    #[doc = " @note This API is only used by OT core when `OPENTHREAD_CONFIG_PLATFORM_KEY_REFERENCES_ENABLE` is enabled."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaExportPublicKey(
+        aKeyRef: otCryptoKeyRef,
+        aPublicKey: *mut otPlatCryptoEcdsaPublicKey,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Generate and import a new ECDSA key-pair at reference passed."]
+    #[doc = ""]
+    #[doc = " @param[in]  aKeyRef            Key Reference to the slot where the key-pair is stored."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          A new key-pair was generated successfully."]
+    #[doc = " @retval OT_ERROR_NO_BUFS       Failed to allocate buffer for key generation."]
+    #[doc = " @retval OT_ERROR_NOT_CAPABLE   Feature not supported."]
+    #[doc = " @retval OT_ERROR_FAILED        Failed to generate key-pair."]
+    #[doc = ""]
+    #[doc = " @note This API is only used by OT core when `OPENTHREAD_CONFIG_PLATFORM_KEY_REFERENCES_ENABLE` is enabled."]
+    #[doc = ""]
+    pub fn otPlatCryptoEcdsaGenerateAndImportKey(aKeyRef: otCryptoKeyRef) -> otError;
+}
+extern "C" {
+    #[doc = " Starts the SRP client operation."]
+    #[doc = ""]
+    #[doc = " SRP client will prepare and send \"SRP Update\" message to the SRP server once all the following conditions are met:"]
+    #[doc = ""]
+    #[doc = "  - The SRP client is started - `otSrpClientStart()` is called."]
+    #[doc = "  - Host name is set - `otSrpClientSetHostName()` is called."]
+    #[doc = "  - At least one host IPv6 address is set - `otSrpClientSetHostAddresses()` is called."]
+    #[doc = "  - At least one service is added - `otSrpClientAddService()` is called."]
+    #[doc = ""]
+    #[doc = " It does not matter in which order these functions are called. When all conditions are met, the SRP client will"]
+    #[doc = " wait for a short delay before preparing an \"SRP Update\" message and sending it to server."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aServerSockAddr  The socket address (IPv6 address and port number) of the SRP server."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE       SRP client operation started successfully or it is already running with same server"]
+    #[doc = "                             socket address and callback."]
+    #[doc = " @retval OT_ERROR_BUSY       SRP client is busy running with a different socket address."]
+    #[doc = " @retval OT_ERROR_FAILED     Failed to open/connect the client's UDP socket."]
+    #[doc = ""]
+    pub fn otSrpClientStart(
+        aInstance: *mut otInstance,
+        aServerSockAddr: *const otSockAddr,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Stops the SRP client operation."]
+    #[doc = ""]
+    #[doc = " Stops any further interactions with the SRP server. Note that it does not remove or clear host info"]
+    #[doc = " and/or list of services. It marks all services to be added/removed again once the client is (re)started."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance       A pointer to the OpenThread instance."]
+    #[doc = ""]
+    pub fn otSrpClientStop(aInstance: *mut otInstance);
+}
+extern "C" {
+    #[doc = " Indicates whether the SRP client is running or not."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance       A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns TRUE if the SRP client is running, FALSE otherwise."]
+    #[doc = ""]
+    pub fn otSrpClientIsRunning(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " Gets the socket address (IPv6 address and port number) of the SRP server which is being used by SRP"]
+    #[doc = " client."]
+    #[doc = ""]
+    #[doc = " If the client is not running, the address is unspecified (all zero) with zero port number."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance       A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the SRP server's socket address (is always non-NULL)."]
+    #[doc = ""]
+    pub fn otSrpClientGetServerAddress(aInstance: *mut otInstance) -> *const otSockAddr;
+}
+#[doc = " Pointer type defines the callback used by SRP client to notify user when it is auto-started or stopped."]
+#[doc = ""]
+#[doc = " This is only used when auto-start feature `OPENTHREAD_CONFIG_SRP_CLIENT_AUTO_START_API_ENABLE` is enabled."]
+#[doc = ""]
+#[doc = " This callback is invoked when auto-start mode is enabled and the SRP client is either automatically started or"]
+#[doc = " stopped."]
+#[doc = ""]
+#[doc = " @param[in] aServerSockAddr   A non-NULL pointer indicates SRP server was started and pointer will give the"]
+#[doc = "                              selected server socket address. A NULL pointer indicates SRP server was stopped."]
+#[doc = " @param[in] aContext          A pointer to an arbitrary context (provided when callback was registered)."]
+#[doc = ""]
+pub type otSrpClientAutoStartCallback = ::core::option::Option<
+    unsafe extern "C" fn(aServerSockAddr: *const otSockAddr, aContext: *mut crate::c_types::c_void),
+>;
+extern "C" {
+    #[doc = " Enables the auto-start mode."]
+    #[doc = ""]
+    #[doc = " This is only available when auto-start feature `OPENTHREAD_CONFIG_SRP_CLIENT_AUTO_START_API_ENABLE` is enabled."]
+    #[doc = ""]
+    #[doc = " When auto-start mode is enabled, the SRP client will monitor the Thread Network Data to discover SRP servers and"]
+    #[doc = " select the preferred server and automatically start and stop the client when an SRP server is detected."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance   A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aCallback   A callback to notify when client is auto-started/stopped. Can be NULL if not needed."]
+    #[doc = " @param[in] aContext    A context to be passed when invoking @p aCallback."]
+    #[doc = ""]
+    pub fn otSrpClientEnableAutoStartMode(
+        aInstance: *mut otInstance,
+        aCallback: otSrpClientAutoStartCallback,
+        aContext: *mut crate::c_types::c_void,
+    );
+}
+extern "C" {
+    #[doc = " Disables the auto-start mode."]
+    #[doc = ""]
+    #[doc = " This is only available when auto-start feature `OPENTHREAD_CONFIG_SRP_CLIENT_AUTO_START_API_ENABLE` is enabled."]
+    #[doc = ""]
+    #[doc = " Disabling the auto-start mode will not stop the client if it is already running but the client stops monitoring"]
+    #[doc = " the Thread Network Data to verify that the selected SRP server is still present in it."]
+    #[doc = ""]
+    #[doc = " Note that a call to `otSrpClientStop()` will also disable the auto-start mode."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance   A pointer to the OpenThread instance."]
+    #[doc = ""]
+    pub fn otSrpClientDisableAutoStartMode(aInstance: *mut otInstance);
+}
+extern "C" {
+    #[doc = " Indicates the current state of auto-start mode (enabled or disabled)."]
+    #[doc = ""]
+    #[doc = " This is only available when auto-start feature `OPENTHREAD_CONFIG_SRP_CLIENT_AUTO_START_API_ENABLE` is enabled."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance   A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns TRUE if the auto-start mode is enabled, FALSE otherwise."]
+    #[doc = ""]
+    pub fn otSrpClientIsAutoStartModeEnabled(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " Starts the remove process of the host info and all services."]
+    #[doc = ""]
+    #[doc = " After returning from this function, `otSrpClientCallback` will be called to report the status of remove request"]
+    #[doc = " with SRP server."]
+    #[doc = ""]
+    #[doc = " If the host info is to be permanently removed from server, @p aRemoveKeyLease should be set to `true` which removes"]
+    #[doc = " the key lease associated with host on server. Otherwise, the key lease record is kept as before, which ensures"]
+    #[doc = " that the server holds the host name in reserve for when the client is once again able to provide and register its"]
+    #[doc = " service(s)."]
+    #[doc = ""]
+    #[doc = " The @p aSendUnregToServer determines the behavior when the host info is not yet registered with the server. If"]
+    #[doc = " @p aSendUnregToServer is set to `false` (which is the default/expected value) then the SRP client will immediately"]
+    #[doc = " remove the host info and services without sending an update message to server (no need to update the server if"]
+    #[doc = " nothing is yet registered with it). If @p aSendUnregToServer is set to `true` then the SRP client will send an"]
+    #[doc = " update message to the server."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance          A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aRemoveKeyLease    A boolean indicating whether or not the host key lease should also be removed."]
+    #[doc = " @param[in] aSendUnregToServer A boolean indicating whether to send update to server when host info is not registered."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE      The removal of host info and services started successfully."]
+    #[doc = " @retval OT_ERROR_ALREADY   The host info is already removed."]
+    #[doc = ""]
+    pub fn otSrpClientRemoveHostAndServices(
+        aInstance: *mut otInstance,
+        aRemoveKeyLease: bool,
+        aSendUnregToServer: bool,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Clears all host info and all the services."]
+    #[doc = ""]
+    #[doc = " Unlike `otSrpClientRemoveHostAndServices()` which sends an update message to the server to remove all the info, this"]
+    #[doc = " function clears all the info immediately without any interaction with the server."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance          A pointer to the OpenThread instance."]
+    #[doc = ""]
+    pub fn otSrpClientClearHostAndServices(aInstance: *mut otInstance);
+}
+#[doc = " This structure represents a TXT record entry representing a key/value pair (RFC 6763 - section 6.3)."]
+#[doc = ""]
+#[doc = " The string buffers pointed to by `mKey` and `mValue` MUST persist and remain unchanged after an instance of such"]
+#[doc = " structure is passed to OpenThread (as part of `otSrpClientService` instance)."]
+#[doc = ""]
+#[doc = " An array of `otDnsTxtEntry` entries are used in `otSrpClientService` to specify the full TXT record (a list of"]
+#[doc = " entries)."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otDnsTxtEntry {
+    #[doc = " The TXT record key string."]
+    #[doc = ""]
+    #[doc = " If `mKey` is not NULL, then it MUST be a null-terminated C string. The entry is treated as key/value pair with"]
+    #[doc = " `mValue` buffer providing the value."]
+    #[doc = "   - The entry is encoded as follows:"]
+    #[doc = "        - A single string length byte followed by \"key=value\" format (without the quotation marks)."]
+    #[doc = "        - In this case, the overall encoded length must be 255 bytes or less."]
+    #[doc = "   - If `mValue` is NULL, then key is treated as a boolean attribute and encoded as \"key\" (with no `=`)."]
+    #[doc = "   - If `mValue` is not NULL but `mValueLength` is zero, then it is treated as empty value and encoded as \"key=\"."]
+    #[doc = ""]
+    #[doc = " If `mKey` is NULL, then `mValue` buffer is treated as an already encoded TXT-DATA and is appended as is in the"]
+    #[doc = " DNS message."]
+    #[doc = ""]
+    pub mKey: *const crate::c_types::c_char,
+    #[doc = "< The TXT record value or already encoded TXT-DATA (depending on `mKey`)."]
+    pub mValue: *const u8,
+    #[doc = "< Number of bytes in `mValue` buffer."]
+    pub mValueLength: u16,
+}
+#[doc = "< Item to be added/registered."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_ADD: otSrpClientItemState = 0;
+#[doc = "< Item is being added/registered."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_ADDING: otSrpClientItemState = 1;
+#[doc = "< Item to be refreshed (re-register to renew lease)."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REFRESH: otSrpClientItemState = 2;
+#[doc = "< Item is being refreshed."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REFRESHING: otSrpClientItemState = 3;
+#[doc = "< Item to be removed."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REMOVE: otSrpClientItemState = 4;
+#[doc = "< Item is being removed."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVING: otSrpClientItemState = 5;
+#[doc = "< Item is registered with server."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REGISTERED: otSrpClientItemState = 6;
+#[doc = "< Item is removed."]
+pub const otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVED: otSrpClientItemState = 7;
+#[doc = " This enumeration specifies an SRP client item (service or host info) state."]
+#[doc = ""]
+pub type otSrpClientItemState = crate::c_types::c_uint;
+#[doc = " This structure represents an SRP client host info."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otSrpClientHostInfo {
+    #[doc = "< Host name (label) string (NULL if not yet set)."]
+    pub mName: *const crate::c_types::c_char,
+    #[doc = "< Array of host IPv6 addresses (NULL if not set or auto address is enabled)."]
+    pub mAddresses: *const otIp6Address,
+    #[doc = "< Number of IPv6 addresses in `mAddresses` array."]
+    pub mNumAddresses: u8,
+    #[doc = "< Indicates whether auto address mode is enabled or not."]
+    pub mAutoAddress: bool,
+    #[doc = "< Host info state."]
+    pub mState: otSrpClientItemState,
+}
+#[doc = " This structure represents an SRP client service."]
+#[doc = ""]
+#[doc = " The values in this structure, including the string buffers for the names and the TXT record entries, MUST persist"]
+#[doc = " and stay constant after an instance of this structure is passed to OpenThread from `otSrpClientAddService()` or"]
+#[doc = " `otSrpClientRemoveService()`."]
+#[doc = ""]
+#[doc = " The `mState`, `mData`, `mNext` fields are used/managed by OT core only. Their value is ignored when an instance of"]
+#[doc = " `otSrpClientService` is passed in `otSrpClientAddService()` or `otSrpClientRemoveService()` or other functions. The"]
+#[doc = " caller does not need to set these fields."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otSrpClientService {
+    #[doc = "< The service labels (e.g., \"_mt._udp\", not the full domain name)."]
+    pub mName: *const crate::c_types::c_char,
+    #[doc = "< The service instance name label (not the full name)."]
+    pub mInstanceName: *const crate::c_types::c_char,
+    #[doc = "< Array of sub-type labels (must end with `NULL` or can be `NULL`)."]
+    pub mSubTypeLabels: *const *const crate::c_types::c_char,
+    #[doc = "< Array of TXT entries (`mNumTxtEntries` gives num of entries)."]
+    pub mTxtEntries: *const otDnsTxtEntry,
+    #[doc = "< The service port number."]
+    pub mPort: u16,
+    #[doc = "< The service priority."]
+    pub mPriority: u16,
+    #[doc = "< The service weight."]
+    pub mWeight: u16,
+    #[doc = "< Number of entries in the `mTxtEntries` array."]
+    pub mNumTxtEntries: u8,
+    #[doc = "< Service state (managed by OT core)."]
+    pub mState: otSrpClientItemState,
+    #[doc = "< Internal data (used by OT core)."]
+    pub mData: u32,
+    #[doc = "< Pointer to next entry in a linked-list (managed by OT core)."]
+    pub mNext: *mut otSrpClientService,
+}
+#[doc = " This function pointer type defines the callback used by SRP client to notify user of changes/events/errors."]
+#[doc = ""]
+#[doc = " This callback is invoked on a successful registration of an update (i.e., add/remove of host-info and/or some"]
+#[doc = " service(s)) with the SRP server, or if there is a failure or error (e.g., server rejects a update request or client"]
+#[doc = " times out waiting for response, etc)."]
+#[doc = ""]
+#[doc = " In case of a successful reregistration of an update, `aError` parameter would be `OT_ERROR_NONE` and the host info"]
+#[doc = " and the full list of services is provided as input parameters to the callback. Note that host info and services each"]
+#[doc = " track its own state in the corresponding `mState` member variable of the related data structure (the state"]
+#[doc = " indicating whether the host-info/service is registered or removed or still being added/removed, etc)."]
+#[doc = ""]
+#[doc = " The list of removed services is passed as its own linked-list `aRemovedServices` in the callback. Note that when the"]
+#[doc = " callback is invoked, the SRP client (OpenThread implementation) is done with the removed service instances listed in"]
+#[doc = " `aRemovedServices` and no longer tracks/stores them (i.e., if from the callback we call `otSrpClientGetServices()`"]
+#[doc = " the removed services will not be present in the returned list). Providing a separate list of removed services in"]
+#[doc = " the callback helps indicate to user which items are now removed and allow user to re-claim/reuse the instances."]
+#[doc = ""]
+#[doc = " @param[in] aError            The error (see above)."]
+#[doc = " @param[in] aHostInfo         A pointer to host info."]
+#[doc = " @param[in] aServices         The head of linked-list containing all services (excluding the ones removed). NULL if"]
+#[doc = "                              the list is empty."]
+#[doc = " @param[in] aRemovedServices  The head of linked-list containing all removed services. NULL if the list is empty."]
+#[doc = " @param[in] aContext          A pointer to an arbitrary context (provided when callback was registered)."]
+#[doc = ""]
+pub type otSrpClientCallback = ::core::option::Option<
+    unsafe extern "C" fn(
+        aError: otError,
+        aHostInfo: *const otSrpClientHostInfo,
+        aServices: *const otSrpClientService,
+        aRemovedServices: *const otSrpClientService,
+        aContext: *mut crate::c_types::c_void,
+    ),
+>;
+extern "C" {
+    #[doc = " This function sets the callback to notify caller of events/changes from SRP client."]
+    #[doc = ""]
+    #[doc = " The SRP client allows a single callback to be registered. So consecutive calls to this function will overwrite any"]
+    #[doc = " previously set callback functions."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aCallback        The callback to notify of events and changes. Can be NULL if not needed."]
+    #[doc = " @param[in] aContext         An arbitrary context used with @p aCallback."]
+    #[doc = ""]
+    pub fn otSrpClientSetCallback(
+        aInstance: *mut otInstance,
+        aCallback: otSrpClientCallback,
+        aContext: *mut crate::c_types::c_void,
+    );
+}
+extern "C" {
+    #[doc = " This function gets the default lease interval used in SRP update requests."]
+    #[doc = ""]
+    #[doc = " Note that this is the lease duration requested by the SRP client. The server may choose to accept a different lease"]
+    #[doc = " interval."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance       A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns The lease interval (in seconds)."]
+    #[doc = ""]
+    pub fn otSrpClientGetLeaseInterval(aInstance: *mut otInstance) -> u32;
+}
+extern "C" {
+    #[doc = " This function sets the default lease interval used in SRP update requests."]
+    #[doc = ""]
+    #[doc = " The change to the lease interval only affects future host and service registrations. It does not change any already"]
+    #[doc = " registered services/host-info."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aInterval  The lease interval (in seconds). If zero, the default value specified by"]
+    #[doc = "                       `OPENTHREAD_CONFIG_SRP_CLIENT_DEFAULT_LEASE` would be used."]
+    #[doc = ""]
+    pub fn otSrpClientSetLeaseInterval(aInstance: *mut otInstance, aInterval: u32);
+}
+extern "C" {
+    #[doc = " This function gets the default key lease interval used in SRP update requests."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance       A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns The key lease interval (in seconds)."]
+    #[doc = ""]
+    pub fn otSrpClientGetKeyLeaseInterval(aInstance: *mut otInstance) -> u32;
+}
+extern "C" {
+    #[doc = " This function sets the default key lease interval used in SRP update requests."]
+    #[doc = ""]
+    #[doc = " The change to the key lease interval only affects future host and service registrations. It does not change any"]
+    #[doc = " already registered services/host-info."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aInterval  The key lease interval (in seconds). If zero, the default value specified by"]
+    #[doc = "                       `OPENTHREAD_CONFIG_SRP_CLIENT_DEFAULT_KEY_LEASE` would be used."]
+    #[doc = ""]
+    pub fn otSrpClientSetKeyLeaseInterval(aInstance: *mut otInstance, aInterval: u32);
+}
+extern "C" {
+    #[doc = " This function gets the host info."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to host info structure."]
+    #[doc = ""]
+    pub fn otSrpClientGetHostInfo(aInstance: *mut otInstance) -> *const otSrpClientHostInfo;
+}
+extern "C" {
+    #[doc = " This function sets the host name label."]
+    #[doc = ""]
+    #[doc = " After a successful call to this function, `otSrpClientCallback` will be called to report the status of host info"]
+    #[doc = "  registration with SRP server."]
+    #[doc = ""]
+    #[doc = " The name string buffer pointed to by @p aName MUST persist and stay unchanged after returning from this function."]
+    #[doc = " OpenThread will keep the pointer to the string."]
+    #[doc = ""]
+    #[doc = " The host name can be set before client is started or after start but before host info is registered with server"]
+    #[doc = " (host info should be in either `STATE_TO_ADD` or `STATE_REMOVED`)."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance   A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aName       A pointer to host name label string (MUST NOT be NULL). Pointer to the string buffer MUST"]
+    #[doc = "                        persist and remain valid and constant after return from this function."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE           The host name label was set successfully."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS   The @p aName is NULL."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE  The host name is already set and registered with the server."]
+    #[doc = ""]
+    pub fn otSrpClientSetHostName(
+        aInstance: *mut otInstance,
+        aName: *const crate::c_types::c_char,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function enables auto host address mode."]
+    #[doc = ""]
+    #[doc = " When enabled host IPv6 addresses are automatically set by SRP client using all the unicast addresses on Thread netif"]
+    #[doc = " excluding all link-local and mesh-local addresses. If there is no valid address, then Mesh Local EID address is"]
+    #[doc = " added. The SRP client will automatically re-register when/if addresses on Thread netif are updated (new addresses"]
+    #[doc = " are added or existing addresses are removed)."]
+    #[doc = ""]
+    #[doc = " The auto host address mode can be enabled before start or during operation of SRP client except when the host info"]
+    #[doc = " is being removed (client is busy handling a remove request from an call to `otSrpClientRemoveHostAndServices()` and"]
+    #[doc = " host info still being in  either `STATE_TO_REMOVE` or `STATE_REMOVING` states)."]
+    #[doc = ""]
+    #[doc = " After auto host address mode is enabled, it can be disabled by a call to `otSrpClientSetHostAddresses()` which"]
+    #[doc = " then explicitly sets the host addresses."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE            Successfully enabled auto host address mode."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE   Host is being removed and therefore cannot enable auto host address mode."]
+    #[doc = ""]
+    pub fn otSrpClientEnableAutoHostAddress(aInstance: *mut otInstance) -> otError;
+}
+extern "C" {
+    #[doc = " This function sets/updates the list of host IPv6 address."]
+    #[doc = ""]
+    #[doc = " Host IPv6 addresses can be set/changed before start or during operation of SRP client (e.g. to add/remove or change"]
+    #[doc = " a previously registered host address), except when the host info is being removed (client is busy handling a remove"]
+    #[doc = " request from an earlier call to `otSrpClientRemoveHostAndServices()` and host info still being in  either"]
+    #[doc = " `STATE_TO_REMOVE` or `STATE_REMOVING` states)."]
+    #[doc = ""]
+    #[doc = " The host IPv6 address array pointed to by @p aIp6Addresses MUST persist and remain unchanged after returning from"]
+    #[doc = " this function (with `OT_ERROR_NONE`). OpenThread will save the pointer to the array."]
+    #[doc = ""]
+    #[doc = " After a successful call to this function, `otSrpClientCallback` will be called to report the status of the address"]
+    #[doc = " registration with SRP server."]
+    #[doc = ""]
+    #[doc = " Calling this function disables auto host address mode if it was previously enabled from a successful call to"]
+    #[doc = " `otSrpClientEnableAutoHostAddress()`."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance           A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aIp6Addresses       A pointer to the an array containing the host IPv6 addresses."]
+    #[doc = " @param[in] aNumAddresses       The number of addresses in the @p aIp6Addresses array."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE            The host IPv6 address list change started successfully. The `otSrpClientCallback`"]
+    #[doc = "                                  will be called to report the status of registering addresses with server."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS    The address list is invalid (e.g., must contain at least one address)."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE   Host is being removed and therefore cannot change host address."]
+    #[doc = ""]
+    pub fn otSrpClientSetHostAddresses(
+        aInstance: *mut otInstance,
+        aIp6Addresses: *const otIp6Address,
+        aNumAddresses: u8,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function adds a service to be registered with server."]
+    #[doc = ""]
+    #[doc = " After a successful call to this function, `otSrpClientCallback` will be called to report the status of the service"]
+    #[doc = " addition/registration with SRP server."]
+    #[doc = ""]
+    #[doc = " The `otSrpClientService` instance being pointed to by @p aService MUST persist and remain unchanged after returning"]
+    #[doc = " from this function (with `OT_ERROR_NONE`). OpenThread will save the pointer to the service instance."]
+    #[doc = ""]
+    #[doc = " The `otSrpClientService` instance is not longer tracked by OpenThread and can be reclaimed only when"]
+    #[doc = ""]
+    #[doc = " -  It is removed explicitly by a call to `otSrpClientRemoveService()` or removed along with other services by a"]
+    #[doc = "    call to `otSrpClientRemoveHostAndServices() and only after the `otSrpClientCallback` is called indicating the"]
+    #[doc = "    service was removed. Or,"]
+    #[doc = " -  A call to `otSrpClientClearHostAndServices()` which removes the host and all related services immediately."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aService         A pointer to a `otSrpClientService` instance to add."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          The addition of service started successfully. The `otSrpClientCallback` will be"]
+    #[doc = "                                called to report the status."]
+    #[doc = " @retval OT_ERROR_ALREADY       A service with the same service and instance names is already in the list."]
+    #[doc = " @retval OT_ERROR_INVALID_ARGS  The service structure is invalid (e.g., bad service name or `otDnsTxtEntry`)."]
+    #[doc = ""]
+    pub fn otSrpClientAddService(
+        aInstance: *mut otInstance,
+        aService: *mut otSrpClientService,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function requests a service to be unregistered with server."]
+    #[doc = ""]
+    #[doc = " After a successful call to this function, `otSrpClientCallback` will be called to report the status of remove"]
+    #[doc = " request with SRP server."]
+    #[doc = ""]
+    #[doc = " The `otSrpClientService` instance being pointed to by @p aService MUST persist and remain unchanged after returning"]
+    #[doc = " from this function (with `OT_ERROR_NONE`). OpenThread will keep the service instance during the remove process."]
+    #[doc = " Only after the `otSrpClientCallback` is called indicating the service instance is removed from SRP client"]
+    #[doc = " service list and can be be freed/reused."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aService         A pointer to a `otSrpClientService` instance to remove."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE       The removal of service started successfully. The `otSrpClientCallback` will be called to"]
+    #[doc = "                             report the status."]
+    #[doc = " @retval OT_ERROR_NOT_FOUND  The service could not be found in the list."]
+    #[doc = ""]
+    pub fn otSrpClientRemoveService(
+        aInstance: *mut otInstance,
+        aService: *mut otSrpClientService,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function clears a service, immediately removing it from the client service list."]
+    #[doc = ""]
+    #[doc = " Unlike `otSrpClientRemoveService()` which sends an update message to the server to remove the service, this function"]
+    #[doc = " clears the service from the client's service list without any interaction with the server. On a successful call to"]
+    #[doc = " this function, the `otSrpClientCallback` will NOT be called and the @p aService entry can be reclaimed and re-used"]
+    #[doc = " by the caller immediately."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance        A pointer to the OpenThread instance."]
+    #[doc = " @param[in] aService         A pointer to a `otSrpClientService` instance to delete."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE       The service was cleared successfully."]
+    #[doc = " @retval OT_ERROR_NOT_FOUND  The service could not be found in the list."]
+    #[doc = ""]
+    pub fn otSrpClientClearService(
+        aInstance: *mut otInstance,
+        aService: *mut otSrpClientService,
+    ) -> otError;
+}
+#[doc = " A linked buffer structure for use with TCP."]
+#[doc = ""]
+#[doc = " A single otLinkedBuffer structure references an array of bytes in memory, via mData and mLength. The mNext field is"]
+#[doc = " used to form a chain of otLinkedBuffer structures."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct otLinkedBuffer {
+    #[doc = "< Pointer to the next linked buffer in the chain, or NULL if it is the end."]
+    pub mNext: *mut otLinkedBuffer,
+    #[doc = "< Pointer to data referenced by this linked buffer."]
+    pub mData: *const u8,
+    #[doc = "< Length of this linked buffer (number of bytes)."]
+    pub mLength: size_t,
+}
+#[doc = " This callback informs the application that the TCP 3-way handshake is complete and that the connection is now"]
+#[doc = " established."]
+#[doc = ""]
+#[doc = " @param[in]  aEndpoint  The TCP endpoint whose connection is now established."]
+#[doc = ""]
+pub type otTcpEstablished =
+    ::core::option::Option<unsafe extern "C" fn(aEndpoint: *mut otTcpEndpoint)>;
+#[doc = " This callback informs the application that data in the provided @p aData have been acknowledged by the connection"]
+#[doc = " peer and that @p aData and the data it contains can be reclaimed by the application."]
+#[doc = ""]
+#[doc = " @param[in]  aEndpoint  The TCP endpoint for the connection."]
+#[doc = " @param[in]  aData      A pointer to the otLinkedBuffer that can be reclaimed."]
+#[doc = ""]
+pub type otTcpSendDone = ::core::option::Option<
+    unsafe extern "C" fn(aEndpoint: *mut otTcpEndpoint, aData: *mut otLinkedBuffer),
+>;
+#[doc = " This callback informs the application if forward progress has been made in transferring data from the send buffer"]
+#[doc = " to the recipient."]
+#[doc = ""]
+#[doc = " @param[in]  aEndpoint     The TCP endpoint for the connection."]
+#[doc = " @param[in]  aInSendBuffer The number of bytes in the send buffer (sum of \"in-flight\" and \"backlog\" regions)."]
+#[doc = " @param[in]  aBacklog      The number of bytes that are queued for sending but have not yet been sent or are"]
+#[doc = "                           \"in-flight\" (the \"backlog\" region)."]
+#[doc = ""]
+pub type otTcpForwardProgress = ::core::option::Option<
+    unsafe extern "C" fn(aEndpoint: *mut otTcpEndpoint, aInSendBuffer: size_t, aBacklog: size_t),
+>;
+#[doc = " This callback indicates the number of bytes available for consumption from the receive buffer."]
+#[doc = ""]
+#[doc = " @param[in]  aEndpoint        The TCP endpoint for the connection."]
+#[doc = " @param[in]  aBytesAvailable  The number of bytes in the connection's receive buffer."]
+#[doc = " @param[in]  aEndOfStream     Indicates if additional data, beyond what is already in the connection's receive"]
+#[doc = "                              buffer, can be received."]
+#[doc = " @param[in]  aBytesRemaining  The number of additional bytes that can be received before the receive buffer becomes"]
+#[doc = "                              full."]
+#[doc = ""]
+pub type otTcpReceiveAvailable = ::core::option::Option<
+    unsafe extern "C" fn(
+        aEndpoint: *mut otTcpEndpoint,
+        aBytesAvailable: size_t,
+        aEndOfStream: bool,
+        aBytesRemaining: size_t,
+    ),
+>;
+pub const otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_NORMAL: otTcpDisconnectedReason = 0;
+pub const otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_REFUSED: otTcpDisconnectedReason = 1;
+pub const otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_RESET: otTcpDisconnectedReason = 2;
+pub const otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_TIME_WAIT: otTcpDisconnectedReason = 3;
+pub const otTcpDisconnectedReason_OT_TCP_DISCONNECTED_REASON_TIMED_OUT: otTcpDisconnectedReason = 4;
+pub type otTcpDisconnectedReason = crate::c_types::c_uint;
+#[doc = " This callback indicates that the connection was broken and should no longer be used, or that a connection has"]
+#[doc = " entered the TIME-WAIT state."]
+#[doc = ""]
+#[doc = " @param[in]  aEndpoint  The TCP endpoint whose connection has been lost."]
+#[doc = " @param[in]  aReason    The reason why the connection was lost."]
+#[doc = ""]
+pub type otTcpDisconnected = ::core::option::Option<
+    unsafe extern "C" fn(aEndpoint: *mut otTcpEndpoint, aReason: otTcpDisconnectedReason),
+>;
+pub const OT_TCP_ENDPOINT_TCB_SIZE_BASE: u32 = 392;
+pub const OT_TCP_ENDPOINT_TCB_NUM_PTR: u32 = 36;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union otTcpEndpoint__bindgen_ty_1 {
+    pub mSize: [u8; 536usize],
+    pub mAlign: u64,
+}
+#[doc = " Represents a TCP endpoint."]
+#[doc = ""]
+#[doc = " A TCP endpoint acts an endpoint of TCP connection. It can be used to initiate TCP connections, and, once a TCP"]
+#[doc = " connection is established, send data to and receive data from the connection peer."]
+#[doc = ""]
+#[doc = " The application should not inspect the fields of this structure directly; it should only interact with it via the"]
+#[doc = " TCP API functions whose signatures are provided in this file."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otTcpEndpoint {
+    pub mTcb: otTcpEndpoint__bindgen_ty_1,
+    #[doc = "< A pointer to the next TCP endpoint (internal use only)"]
+    pub mNext: *mut otTcpEndpoint,
+    #[doc = "< A pointer to application-specific context"]
+    pub mContext: *mut crate::c_types::c_void,
+    #[doc = "< \"Established\" callback function"]
+    pub mEstablishedCallback: otTcpEstablished,
+    #[doc = "< \"Send done\" callback function"]
+    pub mSendDoneCallback: otTcpSendDone,
+    #[doc = "< \"Forward progress\" callback function"]
+    pub mForwardProgressCallback: otTcpForwardProgress,
+    #[doc = "< \"Receive available\" callback function"]
+    pub mReceiveAvailableCallback: otTcpReceiveAvailable,
+    #[doc = "< \"Disconnected\" callback function"]
+    pub mDisconnectedCallback: otTcpDisconnected,
+    pub mTimers: [u32; 4usize],
+    pub mReceiveLinks: [otLinkedBuffer; 2usize],
+    pub mSockName: otSockAddr,
+    pub mPendingCallbacks: u8,
+}
+#[doc = " Contains arguments to the otTcpEndpointInitialize() function."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct otTcpEndpointInitializeArgs {
+    #[doc = "< Pointer to application-specific context"]
+    pub mContext: *mut crate::c_types::c_void,
+    #[doc = "< \"Established\" callback function"]
+    pub mEstablishedCallback: otTcpEstablished,
+    #[doc = "< \"Send done\" callback function"]
+    pub mSendDoneCallback: otTcpSendDone,
+    #[doc = "< \"Forward progress\" callback function"]
+    pub mForwardProgressCallback: otTcpForwardProgress,
+    #[doc = "< \"Receive available\" callback function"]
+    pub mReceiveAvailableCallback: otTcpReceiveAvailable,
+    #[doc = "< \"Disconnected\" callback function"]
+    pub mDisconnectedCallback: otTcpDisconnected,
+    #[doc = "< Pointer to memory provided to the system for the TCP receive buffer"]
+    pub mReceiveBuffer: *mut crate::c_types::c_void,
+    #[doc = "< Size of memory provided to the system for the TCP receive buffer"]
+    pub mReceiveBufferSize: size_t,
+}
+#[doc = " Recommended buffer size for TCP connections that traverse about 3 wireless hops or fewer."]
+pub const OT_TCP_RECEIVE_BUFFER_SIZE_FEW_HOPS: u32 = 2598;
+#[doc = " Recommended buffer size for TCP connections that traverse many wireless hops."]
+pub const OT_TCP_RECEIVE_BUFFER_SIZE_MANY_HOPS: u32 = 4157;
+extern "C" {
+    #[doc = " Initializes a TCP endpoint."]
+    #[doc = ""]
+    #[doc = " Calling this function causes OpenThread to keep track of the TCP endpoint and store and retrieve TCP data inside"]
+    #[doc = " the @p aEndpoint. The application should refrain from directly accessing or modifying the fields in"]
+    #[doc = " @p aEndpoint. If the application needs to reclaim the memory backing @p aEndpoint, it should call"]
+    #[doc = " otTcpEndpointDeinitialize()."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aEndpoint  A pointer to a TCP endpoint structure."]
+    #[doc = " @param[in]  aArgs      A pointer to a structure of arguments."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully opened the TCP endpoint."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to open the TCP endpoint."]
+    #[doc = ""]
+    pub fn otTcpEndpointInitialize(
+        aInstance: *mut otInstance,
+        aEndpoint: *mut otTcpEndpoint,
+        aArgs: *const otTcpEndpointInitializeArgs,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Obtains the context pointer that was associated with @p aEndpoint upon initialization."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  The TCP endpoint whose context to obtain."]
+    #[doc = ""]
+    #[doc = " @returns  The context pointer associated with @p aEndpoint."]
+    #[doc = ""]
+    pub fn otTcpEndpointGetContext(aEndpoint: *mut otTcpEndpoint) -> *mut crate::c_types::c_void;
+}
+extern "C" {
+    #[doc = " Obtains a pointer to a TCP endpoint's peer's host and port."]
+    #[doc = ""]
+    #[doc = " The contents of the host and port may be stale if this socket is not in a connected state."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  The TCP endpoint whose peer's host and port to obtain."]
+    #[doc = ""]
+    #[doc = " @returns  The host and port of the connection peer of @p aEndpoint."]
+    #[doc = ""]
+    pub fn otTcpGetPeerAddress(aEndpoint: *const otTcpEndpoint) -> *const otSockAddr;
+}
+extern "C" {
+    #[doc = " Binds the TCP endpoint to an IP address and port."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint   A pointer to the TCP endpoint structure to bind."]
+    #[doc = " @param[in]  aSockName   The address and port to which to bind this TCP endpoint."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully bound the TCP endpoint."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to bind the TCP endpoint."]
+    #[doc = ""]
+    pub fn otTcpBind(aEndpoint: *mut otTcpEndpoint, aSockName: *const otSockAddr) -> otError;
+}
+pub const OT_TCP_CONNECT_NO_FAST_OPEN: crate::c_types::c_uint = 1;
+extern "C" {
+    #[doc = " Records the remote host and port for this connection."]
+    #[doc = ""]
+    #[doc = " TCP Fast Open must be enabled or disabled using @p aFlags. If it is disabled, then the TCP connection establishment"]
+    #[doc = " handshake is initiated immediately. If it is enabled, then this function merely records the the remote host and"]
+    #[doc = " port, and the TCP connection establishment handshake only happens on the first call to `otTcpSendByReference()`."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure to connect."]
+    #[doc = " @param[in]  aSockName  The IP address and port of the host to which to connect."]
+    #[doc = " @param[in]  aFlags     Flags specifying options for this operation (see enumeration above)."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully completed the operation."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to complete the operation."]
+    #[doc = ""]
+    pub fn otTcpConnect(
+        aEndpoint: *mut otTcpEndpoint,
+        aSockName: *const otSockAddr,
+        aFlags: u32,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Adds data referenced by the linked buffer pointed to by @p aBuffer to the send buffer."]
+    #[doc = ""]
+    #[doc = " Upon a successful call to this function, the linked buffer and data it references are owned by the TCP stack; they"]
+    #[doc = " should not be modified by the application until a \"send done\" callback returns ownership of those objects to the"]
+    #[doc = " application."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure representing the TCP endpoint on which to send data."]
+    #[doc = " @param[in]  aBuffer    A pointer to the linked buffer chain referencing data to add to the send buffer."]
+    #[doc = " @param[in]  aFlags     Flags specifying options for this operation (see enumeration above)."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully added data to the send buffer."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to add data to the send buffer."]
+    #[doc = ""]
+    pub fn otTcpSendByReference(
+        aEndpoint: *mut otTcpEndpoint,
+        aBuffer: *mut otLinkedBuffer,
+        aFlags: u32,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Provides the application with a linked buffer chain referencing data currently in the TCP receive buffer."]
+    #[doc = ""]
+    #[doc = " The linked buffer chain is valid until the \"receive ready\" callback is next invoked, or until the next call to"]
+    #[doc = " otTcpReceiveContiguify() or otTcpCommitReceive()."]
+    #[doc = ""]
+    #[doc = " @param[in]   aEndpoint  A pointer to the TCP endpoint structure representing the TCP endpoint on which to receive"]
+    #[doc = "                         data."]
+    #[doc = " @param[out]  aBuffer    A pointer to the linked buffer chain referencing data currently in the receive buffer."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully completed the operation."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to complete the operation."]
+    #[doc = ""]
+    pub fn otTcpReceiveByReference(
+        aEndpoint: *mut otTcpEndpoint,
+        aBuffer: *mut *const otLinkedBuffer,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Informs the TCP stack that the application has finished processing @p aNumBytes bytes of data at the start of the"]
+    #[doc = " receive buffer and that the TCP stack need not continue maintaining those bytes in the receive buffer."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure representing the TCP endpoint on which to receive"]
+    #[doc = "                        data."]
+    #[doc = " @param[in]  aNumBytes  The number of bytes consumed."]
+    #[doc = " @param[in]  aFlags     Flags specifying options for this operation (none yet)."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully completed the receive operation."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to complete the receive operation."]
+    #[doc = ""]
+    pub fn otTcpCommitReceive(
+        aEndpoint: *mut otTcpEndpoint,
+        aNumBytes: size_t,
+        aFlags: u32,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Informs the connection peer that this TCP endpoint will not send more data."]
+    #[doc = ""]
+    #[doc = " This should be used when the application has no more data to send to the connection peer. For this connection,"]
+    #[doc = " future reads on the connection peer will result in the \"end of stream\" condition, and future writes on this"]
+    #[doc = " connection endpoint will fail."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure representing the TCP endpoint to shut down."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully queued the \"end of stream\" condition for transmission."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to queue the \"end of stream\" condition for transmission."]
+    #[doc = ""]
+    pub fn otTcpSendEndOfStream(aEndpoint: *mut otTcpEndpoint) -> otError;
+}
+extern "C" {
+    #[doc = " Forcibly ends the TCP connection associated with this TCP endpoint."]
+    #[doc = ""]
+    #[doc = " This immediately makes the TCP endpoint free for use for another connection and empties the send and receive"]
+    #[doc = " buffers, transferring ownership of any data provided by the application in otTcpSendByReference() and"]
+    #[doc = " otTcpSendByExtension() calls back to the application."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure representing the TCP endpoint to abort."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully aborted the TCP endpoint's connection."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to abort the TCP endpoint's connection."]
+    #[doc = ""]
+    pub fn otTcpAbort(aEndpoint: *mut otTcpEndpoint) -> otError;
+}
+extern "C" {
+    #[doc = " Deinitializes this TCP endpoint."]
+    #[doc = ""]
+    #[doc = " This means that OpenThread no longer keeps track of this TCP endpoint and deallocates all resources it has"]
+    #[doc = " internally allocated for this TCP endpoint. The application can reuse the memory backing the TCP endpoint as it"]
+    #[doc = " sees fit."]
+    #[doc = ""]
+    #[doc = " If it corresponds to a live TCP connection, the connection is terminated unceremoniously (as in otTcpAbort())."]
+    #[doc = ""]
+    #[doc = " @param[in]  aEndpoint  A pointer to the TCP endpoint structure to deinitialize."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully deinitialized the TCP endpoint."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to deinitialize the TCP endpoint."]
+    #[doc = ""]
+    pub fn otTcpEndpointDeinitialize(aEndpoint: *mut otTcpEndpoint) -> otError;
+}
+#[doc = "< Accept the incoming connection."]
+pub const otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT:
+    otTcpIncomingConnectionAction = 0;
+#[doc = "< Defer (silently ignore) the incoming connection."]
+pub const otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_DEFER:
+    otTcpIncomingConnectionAction = 1;
+#[doc = "< Refuse the incoming connection."]
+pub const otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_REFUSE:
+    otTcpIncomingConnectionAction = 2;
+#[doc = " Defines incoming connection actions."]
+#[doc = ""]
+#[doc = " This is used in otTcpAcceptReady() callback."]
+#[doc = ""]
+pub type otTcpIncomingConnectionAction = crate::c_types::c_uint;
+#[doc = " This callback indicates that an incoming connection that matches this TCP listener has arrived."]
+#[doc = ""]
+#[doc = " The typical response is for the application to accept the incoming connection. It does so by populating"]
+#[doc = " @p aAcceptInto with a pointer to the otTcpEndpoint into which to accept the incoming connection. This otTcpEndpoint"]
+#[doc = " must already be initialized using otTcpEndpointInitialize(). Then, the application returns"]
+#[doc = " OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT."]
+#[doc = ""]
+#[doc = " Alternatively, the application can decline to accept the incoming connection. There are two ways for the"]
+#[doc = " application to do this. First, if the application returns OT_TCP_INCOMING_CONNECTION_ACTION_DEFER, then OpenThread"]
+#[doc = " silently ignores the connection establishment request; the connection peer will likely retransmit the request, at"]
+#[doc = " which point the callback will be called again. Second, if the application returns"]
+#[doc = " OT_TCP_INCOMING_CONNECTION_ACTION_REFUSE, then OpenThread sends a \"connection refused\" message to the host that"]
+#[doc = " attempted to establish a connection."]
+#[doc = ""]
+#[doc = " @param[in]   aListener    The TCP listener that matches the incoming connection."]
+#[doc = " @param[in]   aPeer        The host and port from which the incoming connection originates."]
+#[doc = " @param[out]  aAcceptInto  The TCP endpoint into which to accept the incoming connection."]
+#[doc = ""]
+#[doc = " @returns  Description of how to handle the incoming connection."]
+#[doc = ""]
+pub type otTcpAcceptReady = ::core::option::Option<
+    unsafe extern "C" fn(
+        aListener: *mut otTcpListener,
+        aPeer: *const otSockAddr,
+        aAcceptInto: *mut *mut otTcpEndpoint,
+    ) -> otTcpIncomingConnectionAction,
+>;
+#[doc = " This callback indicates that the TCP connection is now ready for two-way communication."]
+#[doc = ""]
+#[doc = " @param[in]  aListener  The TCP listener that matches the incoming connection."]
+#[doc = " @param[in]  aEndpoint  The TCP endpoint into which the incoming connection was accepted."]
+#[doc = " @param[in]  aPeer      the host and port from which the incoming connection originated."]
+#[doc = ""]
+pub type otTcpAcceptDone = ::core::option::Option<
+    unsafe extern "C" fn(
+        aListener: *mut otTcpListener,
+        aEndpoint: *mut otTcpEndpoint,
+        aPeer: *const otSockAddr,
+    ),
+>;
+pub const OT_TCP_LISTENER_TCB_SIZE_BASE: u32 = 16;
+pub const OT_TCP_LISTENER_TCB_NUM_PTR: u32 = 3;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union otTcpListener__bindgen_ty_1 {
+    pub mSize: [u8; 28usize],
+    pub mAlign: *mut crate::c_types::c_void,
+}
+#[doc = " Represents a TCP listener."]
+#[doc = ""]
+#[doc = " A TCP listener is used to listen for and accept incoming TCP connections."]
+#[doc = ""]
+#[doc = " The application should not inspect the fields of this structure directly; it should only interact with it via the"]
+#[doc = " TCP API functions whose signatures are provided in this file."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct otTcpListener {
+    pub mTcbListen: otTcpListener__bindgen_ty_1,
+    #[doc = "< A pointer to the next TCP listener (internal use only)"]
+    pub mNext: *mut otTcpListener,
+    #[doc = "< A pointer to application-specific context"]
+    pub mContext: *mut crate::c_types::c_void,
+    #[doc = "< \"Accept ready\" callback function"]
+    pub mAcceptReadyCallback: otTcpAcceptReady,
+    #[doc = "< \"Accept done\" callback function"]
+    pub mAcceptDoneCallback: otTcpAcceptDone,
+}
+#[doc = " Contains arguments to the otTcpListenerInitialize() function."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct otTcpListenerInitializeArgs {
+    #[doc = "< Pointer to application-specific context"]
+    pub mContext: *mut crate::c_types::c_void,
+    #[doc = "< \"Accept ready\" callback function"]
+    pub mAcceptReadyCallback: otTcpAcceptReady,
+    #[doc = "< \"Accept done\" callback function"]
+    pub mAcceptDoneCallback: otTcpAcceptDone,
+}
+extern "C" {
+    #[doc = " Initializes a TCP listener."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aListener  A pointer to a TCP listener structure."]
+    #[doc = " @param[in]  aArgs      A pointer to a structure of arguments."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully opened the TCP listener."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to open the TCP listener."]
+    #[doc = ""]
+    pub fn otTcpListenerInitialize(
+        aInstance: *mut otInstance,
+        aListener: *mut otTcpListener,
+        aArgs: *const otTcpListenerInitializeArgs,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Obtains the context pointer that was associated with @p aListener upon initialization."]
+    #[doc = ""]
+    #[doc = " @param[in]  aListener  The TCP listener whose context to obtain."]
+    #[doc = ""]
+    #[doc = " @returns  The context pointer associated with @p aListener."]
+    #[doc = ""]
+    pub fn otTcpListenerGetContext(aListener: *mut otTcpListener) -> *mut crate::c_types::c_void;
+}
+extern "C" {
+    #[doc = " Causes incoming TCP connections that match the specified IP address and port to trigger this TCP listener's"]
+    #[doc = " callbacks."]
+    #[doc = ""]
+    #[doc = " @param[in]  aListener  A pointer to the TCP listener structure that should begin listening."]
+    #[doc = " @param[in]  aSockName  The address and port on which to listen for incoming connections."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully initiated listening on the TCP listener."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to initiate listening on the TCP listener."]
+    #[doc = ""]
+    pub fn otTcpListen(aListener: *mut otTcpListener, aSockName: *const otSockAddr) -> otError;
+}
+extern "C" {
+    #[doc = " Causes this TCP listener to stop listening for incoming connections."]
+    #[doc = ""]
+    #[doc = " @param[in]  aListener  A pointer to the TCP listener structure that should stop listening."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully stopped listening on the TCP listener."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to stop listening on the TCP listener."]
+    #[doc = ""]
+    pub fn otTcpStopListening(aListener: *mut otTcpListener) -> otError;
+}
+extern "C" {
+    #[doc = " Deinitializes this TCP listener."]
+    #[doc = ""]
+    #[doc = " This means that OpenThread no longer keeps track of this TCP listener and deallocates all resources it has"]
+    #[doc = " internally allocated for this TCP listener. The application can reuse the memory backing the TCP listener as it"]
+    #[doc = " sees fit."]
+    #[doc = ""]
+    #[doc = " If the TCP listener is currently listening, it stops listening."]
+    #[doc = ""]
+    #[doc = " @param[in]  aListener  A pointer to the TCP listener structure to deinitialize."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE    Successfully deinitialized the TCP listener."]
+    #[doc = " @retval OT_ERROR_FAILED  Failed to deinitialize the TCP listener."]
+    #[doc = ""]
+    pub fn otTcpListenerDeinitialize(aListener: *mut otTcpListener) -> otError;
+}
+#[doc = " Pointer on receipt of a IEEE 802.15.4 frame."]
+#[doc = ""]
+#[doc = " @param[in]  aInstance    The OpenThread instance structure."]
+#[doc = " @param[in]  aFrame       A pointer to the received frame or NULL if the receive operation was aborted."]
+#[doc = " @param[in]  aError       OT_ERROR_NONE when successfully received a frame."]
+#[doc = "                          OT_ERROR_ABORT when reception was aborted and a frame was not received."]
+#[doc = ""]
+pub type otLinkRawReceiveDone = ::core::option::Option<
+    unsafe extern "C" fn(aInstance: *mut otInstance, aFrame: *mut otRadioFrame, aError: otError),
+>;
+extern "C" {
+    #[doc = " Enables/disables the raw link-layer."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance     A pointer to an OpenThread instance."]
+    #[doc = " @param[in] aCallback     A pointer to a function called on receipt of a IEEE 802.15.4 frame. NULL to disable the"]
+    #[doc = "                          raw-link layer."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_FAILED            The radio could not be enabled/disabled."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE     If the OpenThread IPv6 interface is already enabled."]
+    #[doc = " @retval OT_ERROR_NONE              If the enable state was successfully set."]
+    #[doc = ""]
+    pub fn otLinkRawSetReceiveDone(
+        aInstance: *mut otInstance,
+        aCallback: otLinkRawReceiveDone,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Indicates whether or not the raw link-layer is enabled."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance     A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval true     The raw link-layer is enabled."]
+    #[doc = " @retval false    The raw link-layer is disabled."]
+    #[doc = ""]
+    pub fn otLinkRawIsEnabled(aInstance: *mut otInstance) -> bool;
+}
+extern "C" {
+    #[doc = " Transition the radio from Receive to Sleep (turn off the radio)."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully transitioned to Sleep."]
+    #[doc = " @retval OT_ERROR_BUSY          The radio was transmitting"]
+    #[doc = " @retval OT_ERROR_INVALID_STATE The radio was disabled"]
+    #[doc = ""]
+    pub fn otLinkRawSleep(aInstance: *mut otInstance) -> otError;
+}
+extern "C" {
+    #[doc = " Transition the radio from Sleep to Receive (turn on the radio)."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance    A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully transitioned to Receive."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE The radio was disabled or transmitting."]
+    #[doc = ""]
+    pub fn otLinkRawReceive(aInstance: *mut otInstance) -> otError;
+}
+extern "C" {
+    #[doc = " The radio transmit frame buffer."]
+    #[doc = ""]
+    #[doc = " OpenThread forms the IEEE 802.15.4 frame in this buffer then calls otLinkRawTransmit() to request transmission."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance    A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns A pointer to the transmit buffer or NULL if the raw link-layer isn't enabled."]
+    #[doc = ""]
+    pub fn otLinkRawGetTransmitBuffer(aInstance: *mut otInstance) -> *mut otRadioFrame;
+}
+#[doc = " Pointer on receipt of a IEEE 802.15.4 frame."]
+#[doc = ""]
+#[doc = " @param[in]  aInstance    The OpenThread instance structure."]
+#[doc = " @param[in]  aFrame       A pointer to the frame that was transmitted."]
+#[doc = " @param[in]  aAckFrame    A pointer to the ACK frame."]
+#[doc = " @param[in]  aError       OT_ERROR_NONE when the frame was transmitted."]
+#[doc = "                          OT_ERROR_NO_ACK when the frame was transmitted but no ACK was received"]
+#[doc = "                          OT_ERROR_CHANNEL_ACCESS_FAILURE tx failed due to activity on the channel."]
+#[doc = "                          OT_ERROR_ABORT when transmission was aborted for other reasons."]
+#[doc = ""]
+pub type otLinkRawTransmitDone = ::core::option::Option<
+    unsafe extern "C" fn(
+        aInstance: *mut otInstance,
+        aFrame: *mut otRadioFrame,
+        aAckFrame: *mut otRadioFrame,
+        aError: otError,
+    ),
+>;
+extern "C" {
+    #[doc = " Begins the transmit sequence on the radio."]
+    #[doc = ""]
+    #[doc = " The caller must form the IEEE 802.15.4 frame in the buffer provided by otLinkRawGetTransmitBuffer() before"]
+    #[doc = " requesting transmission.  The channel and transmit power are also included in the otRadioFrame structure."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance    A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aCallback    A pointer to a function called on completion of the transmission."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          Successfully transitioned to Transmit."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE The radio was not in the Receive state."]
+    #[doc = ""]
+    pub fn otLinkRawTransmit(aInstance: *mut otInstance, aCallback: otLinkRawTransmitDone)
+        -> otError;
+}
+extern "C" {
+    #[doc = " Set the short address for address filtering."]
+    #[doc = ""]
+    #[doc = " @param[in] aInstance         A pointer to an OpenThread instance."]
+    #[doc = " @param[in] aShortAddress     The IEEE 802.15.4 Short Address."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE             If successful."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE    If the raw link-layer isn't enabled."]
+    #[doc = ""]
+    pub fn otLinkRawSetShortAddress(aInstance: *mut otInstance, aShortAddress: u16) -> otError;
+}
+extern "C" {
+    #[doc = " Get the Network Data Version."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns The Network Data Version."]
+    #[doc = ""]
+    pub fn otNetDataGetVersion(aInstance: *mut otInstance) -> u8;
+}
+extern "C" {
+    #[doc = " Get the Stable Network Data Version."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @returns The Stable Network Data Version."]
+    #[doc = ""]
+    pub fn otNetDataGetStableVersion(aInstance: *mut otInstance) -> u8;
+}
+extern "C" {
+    #[doc = " Check if the steering data includes a Joiner."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance          A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aEui64             A pointer to the Joiner's IEEE EUI-64."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          @p aEui64 is included in the steering data."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE No steering data present."]
+    #[doc = " @retval OT_ERROR_NOT_FOUND     @p aEui64 is not included in the steering data."]
+    #[doc = ""]
+    pub fn otNetDataSteeringDataCheckJoiner(
+        aInstance: *mut otInstance,
+        aEui64: *const otExtAddress,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " Check if the steering data includes a Joiner with a given discerner value."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance          A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aDiscerner         A pointer to the Joiner Discerner."]
+    #[doc = ""]
+    #[doc = " @retval OT_ERROR_NONE          @p aDiscerner is included in the steering data."]
+    #[doc = " @retval OT_ERROR_INVALID_STATE No steering data present."]
+    #[doc = " @retval OT_ERROR_NOT_FOUND     @p aDiscerner is not included in the steering data."]
+    #[doc = ""]
+    pub fn otNetDataSteeringDataCheckJoinerWithDiscerner(
+        aInstance: *mut otInstance,
+        aDiscerner: *const otJoinerDiscerner,
+    ) -> otError;
+}
+#[doc = "< Echo Reply"]
+pub const OT_ICMP6_TYPE_ECHO_REPLY: crate::c_types::c_uint = 129;
+#[doc = " This structure represents an ICMPv6 header."]
+#[doc = ""]
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub struct otIcmp6Header {
+    #[doc = "< Type"]
+    pub mType: u8,
+    #[doc = "< Code"]
+    pub mCode: u8,
+    #[doc = "< Checksum"]
+    pub mChecksum: u16,
+    #[doc = "< Message-specific data"]
+    pub mData: otIcmp6Header__bindgen_ty_1,
+}
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub union otIcmp6Header__bindgen_ty_1 {
+    pub m8: [u8; 4usize],
+    pub m16: [u16; 2usize],
+    pub m32: [u32; 1usize],
+}
+#[doc = " This callback allows OpenThread to inform the application of a received ICMPv6 message."]
+#[doc = ""]
+#[doc = " @param[in]  aContext      A pointer to arbitrary context information."]
+#[doc = " @param[in]  aMessage      A pointer to the received message."]
+#[doc = " @param[in]  aMessageInfo  A pointer to message information associated with @p aMessage."]
+#[doc = " @param[in]  aIcmpHeader   A pointer to the received ICMPv6 header."]
+#[doc = ""]
+pub type otIcmp6ReceiveCallback = ::core::option::Option<
+    unsafe extern "C" fn(
+        aContext: *mut crate::c_types::c_void,
+        aMessage: *mut otMessage,
+        aMessageInfo: *const otMessageInfo,
+        aIcmpHeader: *const otIcmp6Header,
+    ),
+>;
+#[doc = " This structure implements ICMPv6 message handler."]
+#[doc = ""]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct otIcmp6Handler {
+    #[doc = "< The ICMPv6 received callback"]
+    pub mReceiveCallback: otIcmp6ReceiveCallback,
+    #[doc = "< A pointer to arbitrary context information."]
+    pub mContext: *mut crate::c_types::c_void,
+    #[doc = "< A pointer to the next handler in the list."]
+    pub mNext: *mut otIcmp6Handler,
+}
+extern "C" {
+    #[doc = " This function registers a handler to provide received ICMPv6 messages."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aHandler   A handler containing callback that is called when"]
+    #[doc = "                        an ICMPv6 message is received."]
+    #[doc = ""]
+    pub fn otIcmp6RegisterHandler(
+        aInstance: *mut otInstance,
+        aHandler: *mut otIcmp6Handler,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function sends an ICMPv6 Echo Request via the Thread interface."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance     A pointer to an OpenThread instance."]
+    #[doc = " @param[in]  aMessage      A pointer to the message buffer containing the ICMPv6 payload."]
+    #[doc = " @param[in]  aMessageInfo  A reference to message information associated with @p aMessage."]
+    #[doc = " @param[in]  aIdentifier   An identifier to aid in matching Echo Replies to this Echo Request."]
+    #[doc = "                           May be zero."]
+    #[doc = ""]
+    pub fn otIcmp6SendEchoRequest(
+        aInstance: *mut otInstance,
+        aMessage: *mut otMessage,
+        aMessageInfo: *const otMessageInfo,
+        aIdentifier: u16,
+    ) -> otError;
+}
+#[doc = "< Input mode without pull resistor."]
+pub const otGpioMode_OT_GPIO_MODE_INPUT: otGpioMode = 0;
+#[doc = "< Output mode."]
+pub const otGpioMode_OT_GPIO_MODE_OUTPUT: otGpioMode = 1;
+#[doc = " This enumeration defines the gpio modes."]
+pub type otGpioMode = crate::c_types::c_uint;
+extern "C" {
+    #[doc = " This function processes a factory diagnostics command line."]
+    #[doc = ""]
+    #[doc = " The output of this function (the content written to @p aOutput) MUST terminate with `\\0` and the `\\0` is"]
+    #[doc = " within the output buffer."]
+    #[doc = ""]
+    #[doc = " @param[in]   aInstance       A pointer to an OpenThread instance."]
+    #[doc = " @param[in]   aString         A NULL-terminated input string."]
+    #[doc = " @param[out]  aOutput         The diagnostics execution result."]
+    #[doc = " @param[in]   aOutputMaxLen   The output buffer size."]
+    #[doc = ""]
+    #[doc = " @retval  OT_ERROR_NONE               The command is successfully process."]
+    #[doc = " @retval  OT_ERROR_INVALID_ARGS       The command is supported but invalid arguments provided."]
+    #[doc = " @retval  OT_ERROR_NOT_IMPLEMENTED    The command is not supported."]
+    #[doc = " @retval  OT_ERROR_NO_BUFS            The command string is too long."]
+    #[doc = ""]
+    pub fn otDiagProcessCmdLine(
+        aInstance: *mut otInstance,
+        aString: *const crate::c_types::c_char,
+        aOutput: *mut crate::c_types::c_char,
+        aOutputMaxLen: usize,
+    ) -> otError;
+}
+extern "C" {
+    #[doc = " This function indicates whether or not the factory diagnostics mode is enabled."]
+    #[doc = ""]
+    #[doc = " @param[in]  aInstance  A pointer to an OpenThread instance."]
+    #[doc = ""]
+    #[doc = " @retval TRUE if factory diagnostics mode is enabled"]
+    #[doc = " @retval FALSE if factory diagnostics mode is disabled."]
+    #[doc = ""]
+    pub fn otDiagIsEnabled(aInstance: *mut otInstance) -> bool;
+}
 pub type __builtin_va_list = *mut crate::c_types::c_void;
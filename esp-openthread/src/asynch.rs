@@ -0,0 +1,78 @@
+use core::{
+    cell::RefCell,
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Poll, Waker},
+};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::otInstance;
+
+use crate::OpenThread;
+
+/// Longest time [OpenThread::run] sleeps without any event, so the helpers polled by
+/// [OpenThread::process] (e.g. the parent search) still run
+pub const ASYNC_HOUSEKEEPING_INTERVAL: embassy_time::Duration =
+    embassy_time::Duration::from_millis(1000);
+
+/// Set from the alarm and radio interrupts and when OpenThread schedules tasklets
+static WORK_PENDING: AtomicBool = AtomicBool::new(false);
+
+static WAKER: Mutex<RefCell<Option<Waker>>> = Mutex::new(RefCell::new(None));
+
+impl<'a> OpenThread<'a> {
+    /// Drive OpenThread from an async task, e.g. an embassy executor, instead of a busy loop
+    ///
+    /// Calls [OpenThread::process] and [OpenThread::run_tasklets] whenever the alarm or radio
+    /// interrupt fired or OpenThread scheduled tasklets, otherwise the task sleeps for up to
+    /// [ASYNC_HOUSEKEEPING_INTERVAL]. Never returns.
+    pub async fn run(&self) -> ! {
+        loop {
+            self.process();
+            self.run_tasklets();
+            wait_for_work().await;
+        }
+    }
+}
+
+/// Wait until there is work for [OpenThread::process] or [OpenThread::run_tasklets], or
+/// [ASYNC_HOUSEKEEPING_INTERVAL] passed
+///
+/// For loops not covered by [OpenThread::run], e.g. ones reaching the instance via a mutex.
+pub async fn wait_for_work() {
+    let mut timeout = pin!(embassy_time::Timer::after(ASYNC_HOUSEKEEPING_INTERVAL));
+
+    poll_fn(|cx| {
+        // register first, a signal in between is seen by the check below
+        critical_section::with(|cs| {
+            let mut waker = WAKER.borrow_ref_mut(cs);
+            match waker.as_ref() {
+                Some(waker) if waker.will_wake(cx.waker()) => (),
+                _ => *waker = Some(cx.waker().clone()),
+            }
+        });
+
+        if WORK_PENDING.swap(false, Ordering::AcqRel) || timeout.as_mut().poll(cx).is_ready() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// Note that there is work and wake the task waiting for it, called from interrupts
+pub(crate) fn signal() {
+    WORK_PENDING.store(true, Ordering::Release);
+
+    let waker = critical_section::with(|cs| WAKER.borrow_ref_mut(cs).take());
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn otTaskletsSignalPending(_instance: *mut otInstance) {
+    signal();
+}
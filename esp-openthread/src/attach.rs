@@ -0,0 +1,136 @@
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole, otDeviceRole_OT_DEVICE_ROLE_CHILD, otDeviceRole_OT_DEVICE_ROLE_DETACHED,
+    otDeviceRole_OT_DEVICE_ROLE_LEADER, otDeviceRole_OT_DEVICE_ROLE_ROUTER, otInstance,
+    otThreadGetDeviceRole, otThreadGetMleCounters,
+};
+
+use crate::{OpenThread, ThreadDeviceRole};
+
+pub(crate) static ATTACH_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(AttachEvent) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static ATTACH_TRACKER: Mutex<Cell<AttachTracker>> =
+    Mutex::new(Cell::new(AttachTracker::DEFAULT));
+
+/// Progress of attaching to a Thread network
+#[derive(Debug)]
+pub enum AttachEvent {
+    /// The device got detached (e.g. it lost its parent or Thread was enabled) and starts
+    /// looking for a parent
+    Started,
+    /// An attach attempt ended without success, OpenThread retries on its own
+    Failed {
+        /// Failed attempts since the device got detached
+        attempts: u16,
+    },
+    /// The device attached to a network
+    Succeeded {
+        role: ThreadDeviceRole,
+        /// Attempts it took since the device got detached
+        attempts: u16,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct AttachTracker {
+    role: otDeviceRole,
+    /// OpenThread's attach attempt counter when last checked
+    attach_attempts: u16,
+    /// Attempts since the device got detached
+    attempts_since_detach: u16,
+}
+
+impl AttachTracker {
+    pub(crate) const DEFAULT: Self = Self {
+        role: otDeviceRole_OT_DEVICE_ROLE_DETACHED,
+        attach_attempts: 0,
+        attempts_since_detach: 0,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set a callback reporting attach progress, e.g. to show the connection state or to apply
+    /// a custom retry policy
+    ///
+    /// The events are derived from the device role and OpenThread's attach attempt counter as
+    /// part of [OpenThread::process] and [OpenThread::run_tasklets].
+    pub fn set_attach_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(AttachEvent) + Send)>,
+    ) {
+        let tracker = AttachTracker {
+            role: unsafe { otThreadGetDeviceRole(self.instance) },
+            attach_attempts: attach_attempts(self.instance),
+            attempts_since_detach: 0,
+        };
+
+        critical_section::with(|cs| {
+            ATTACH_TRACKER.borrow(cs).set(tracker);
+            let mut attach_callback = ATTACH_CALLBACK.borrow_ref_mut(cs);
+            *attach_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+}
+
+fn attach_attempts(instance: *mut otInstance) -> u16 {
+    unsafe { (*otThreadGetMleCounters(instance)).mAttachAttempts }
+}
+
+#[allow(non_upper_case_globals)]
+fn attached_role(role: otDeviceRole) -> Option<ThreadDeviceRole> {
+    match role {
+        otDeviceRole_OT_DEVICE_ROLE_CHILD => Some(ThreadDeviceRole::Child),
+        otDeviceRole_OT_DEVICE_ROLE_ROUTER => Some(ThreadDeviceRole::Router),
+        otDeviceRole_OT_DEVICE_ROLE_LEADER => Some(ThreadDeviceRole::Leader),
+        _ => None,
+    }
+}
+
+/// Report attach progress since the last check, called from [OpenThread::process] and
+/// [OpenThread::run_tasklets]
+pub(crate) fn process(instance: *mut otInstance) {
+    critical_section::with(|cs| {
+        let mut callback = ATTACH_CALLBACK.borrow_ref_mut(cs);
+        let Some(callback) = callback.as_mut() else {
+            return;
+        };
+
+        let cell = ATTACH_TRACKER.borrow(cs);
+        let mut tracker = cell.get();
+        let role = unsafe { otThreadGetDeviceRole(instance) };
+        let attach_attempts = attach_attempts(instance);
+        let new_attempts = attach_attempts.wrapping_sub(tracker.attach_attempts);
+
+        if role == otDeviceRole_OT_DEVICE_ROLE_DETACHED {
+            if tracker.role != otDeviceRole_OT_DEVICE_ROLE_DETACHED {
+                tracker.attempts_since_detach = 0;
+                callback(AttachEvent::Started);
+            }
+
+            // every further attempt means the previous one failed
+            for _ in 0..new_attempts {
+                if tracker.attempts_since_detach > 0 {
+                    callback(AttachEvent::Failed {
+                        attempts: tracker.attempts_since_detach,
+                    });
+                }
+                tracker.attempts_since_detach = tracker.attempts_since_detach.saturating_add(1);
+            }
+        } else if tracker.role == otDeviceRole_OT_DEVICE_ROLE_DETACHED {
+            if let Some(role) = attached_role(role) {
+                callback(AttachEvent::Succeeded {
+                    role,
+                    attempts: tracker.attempts_since_detach.max(1),
+                });
+            }
+        }
+
+        tracker.role = role;
+        tracker.attach_attempts = attach_attempts;
+        cell.set(tracker);
+    });
+}
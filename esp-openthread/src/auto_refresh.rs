@@ -0,0 +1,247 @@
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_ALREADY, otError_OT_ERROR_NO_BUFS, otInstance, otIp6SubscribeMulticastAddress,
+    otIp6UnsubscribeMulticastAddress, otThreadGetMeshLocalPrefix,
+};
+#[cfg(feature = "srp-client")]
+use esp_openthread_sys::bindings::{
+    otSrpClientEnableAutoStartMode, otSrpClientGetServerAddress, otSrpClientIsAutoStartModeEnabled,
+    otSrpClientIsRunning, otSrpClientStart, otSrpClientStop,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{
+    checked, ot_ip6_address_from_ipv6_addr, ChangedFlags, Error, MeshLocalPrefix, OpenThread,
+};
+
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Number of multicast groups kept subscribed via [OpenThread::auto_refresh_subscribe]
+pub const AUTO_REFRESH_MAX_GROUPS: usize = 8;
+
+/// Changes noted by the state changed callback, handled in [OpenThread::process]
+static PENDING_CHANGES: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) static AUTO_REFRESH: Mutex<RefCell<AutoRefresh>> =
+    Mutex::new(RefCell::new(AutoRefresh::DEFAULT));
+
+pub(crate) static AUTO_REFRESH_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(MeshLocalPrefix) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// A multicast group kept subscribed across mesh-local prefix changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MulticastGroup {
+    /// A fixed group address
+    Address(Ipv6Addr),
+    /// A unicast-prefix-based group (RFC 3306) on the mesh-local prefix, i.e.
+    /// `ff3<scope>:40:<mesh-local-prefix>:<group_id>`, which moves along with the prefix
+    MeshLocal { scope: u8, group_id: u32 },
+}
+
+impl MulticastGroup {
+    /// The group address with the given mesh-local prefix
+    pub fn address(&self, mesh_local_prefix: &MeshLocalPrefix) -> Ipv6Addr {
+        match *self {
+            MulticastGroup::Address(address) => address,
+            MulticastGroup::MeshLocal { scope, group_id } => {
+                let mut octets = [0u8; 16];
+                octets[0] = 0xff;
+                octets[1] = 0x30 | (scope & 0x0f);
+                // prefix length
+                octets[3] = 64;
+                octets[4..12].copy_from_slice(mesh_local_prefix.as_bytes());
+                octets[12..].copy_from_slice(&group_id.to_be_bytes());
+                Ipv6Addr::from(octets)
+            }
+        }
+    }
+}
+
+pub(crate) struct AutoRefresh {
+    groups: heapless::Vec<MulticastGroup, AUTO_REFRESH_MAX_GROUPS>,
+    #[cfg(feature = "srp-client")]
+    srp_client: bool,
+    /// Mesh-local prefix the groups are subscribed with
+    mesh_local_prefix: Option<MeshLocalPrefix>,
+}
+
+impl AutoRefresh {
+    pub(crate) const DEFAULT: Self = Self {
+        groups: heapless::Vec::new(),
+        #[cfg(feature = "srp-client")]
+        srp_client: false,
+        mesh_local_prefix: None,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Subscribe to a multicast group and keep it subscribed, e.g. a CoAP group
+    ///
+    /// After the mesh-local prefix changed, a [MulticastGroup::MeshLocal] group is moved to the
+    /// new prefix. All groups are subscribed again once the Thread interface comes back up.
+    pub fn auto_refresh_subscribe(&mut self, group: MulticastGroup) -> Result<(), Error> {
+        let mesh_local_prefix = self.mesh_local_prefix();
+
+        critical_section::with(|cs| {
+            let mut auto_refresh = AUTO_REFRESH.borrow_ref_mut(cs);
+            if !auto_refresh.groups.contains(&group) {
+                auto_refresh.groups.push(group).map_err(|_| NO_BUFS)?;
+            }
+            auto_refresh.mesh_local_prefix = Some(mesh_local_prefix);
+            Ok::<(), Error>(())
+        })?;
+
+        let res = subscribe(self.instance, group.address(&mesh_local_prefix));
+        if res.is_err() {
+            critical_section::with(|cs| {
+                AUTO_REFRESH
+                    .borrow_ref_mut(cs)
+                    .groups
+                    .retain(|subscribed| *subscribed != group)
+            });
+        }
+        res
+    }
+
+    /// Unsubscribe from a group subscribed via [OpenThread::auto_refresh_subscribe]
+    pub fn auto_refresh_unsubscribe(&mut self, group: MulticastGroup) -> Result<(), Error> {
+        critical_section::with(|cs| {
+            AUTO_REFRESH
+                .borrow_ref_mut(cs)
+                .groups
+                .retain(|subscribed| *subscribed != group)
+        });
+
+        let address = ot_ip6_address_from_ipv6_addr(group.address(&self.mesh_local_prefix()));
+        checked!(unsafe { otIp6UnsubscribeMulticastAddress(self.instance, &address) })
+    }
+
+    /// Restart the SRP client after the mesh-local prefix changed, so host and services are
+    /// registered again with the new addresses
+    #[cfg(feature = "srp-client")]
+    pub fn auto_refresh_set_srp_client_enabled(&mut self, enabled: bool) {
+        let mesh_local_prefix = self.mesh_local_prefix();
+        critical_section::with(|cs| {
+            let mut auto_refresh = AUTO_REFRESH.borrow_ref_mut(cs);
+            auto_refresh.srp_client = enabled;
+            auto_refresh.mesh_local_prefix = Some(mesh_local_prefix);
+        });
+    }
+
+    /// Set a callback for when the mesh-local prefix changed, after the groups and the SRP client
+    /// got refreshed, e.g. to announce CoAP resources again
+    pub fn set_auto_refresh_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(MeshLocalPrefix) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut auto_refresh_callback = AUTO_REFRESH_CALLBACK.borrow_ref_mut(cs);
+            *auto_refresh_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    fn mesh_local_prefix(&self) -> MeshLocalPrefix {
+        MeshLocalPrefix::from(unsafe { *otThreadGetMeshLocalPrefix(self.instance) })
+    }
+}
+
+/// Subscribe to a group, being subscribed already is fine
+fn subscribe(instance: *mut otInstance, address: Ipv6Addr) -> Result<(), Error> {
+    let address = ot_ip6_address_from_ipv6_addr(address);
+    #[allow(non_upper_case_globals)]
+    match unsafe { otIp6SubscribeMulticastAddress(instance, &address) } {
+        otError_OT_ERROR_ALREADY => Ok(()),
+        error => checked!(error),
+    }
+}
+
+/// Note the changes reported by OpenThread's state changed callback
+pub(crate) fn note_changes(flags: ChangedFlags) {
+    let relevant = ChangedFlags::ThreadMeshLocalAddressChanged
+        | ChangedFlags::ThreadNetworkInterfaceStateChanged;
+    PENDING_CHANGES.fetch_or((flags & relevant).bits(), Ordering::Relaxed);
+}
+
+/// Refresh after noted changes, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let changes = ChangedFlags::from_bits_retain(PENDING_CHANGES.swap(0, Ordering::Relaxed));
+    if changes.is_empty() {
+        return;
+    }
+
+    let mesh_local_prefix = MeshLocalPrefix::from(unsafe { *otThreadGetMeshLocalPrefix(instance) });
+
+    let prefix_changed = critical_section::with(|cs| {
+        let mut auto_refresh = AUTO_REFRESH.borrow_ref_mut(cs);
+        let Some(previous) = auto_refresh.mesh_local_prefix else {
+            // nothing subscribed nor the SRP client to follow
+            return false;
+        };
+        let prefix_changed = previous != mesh_local_prefix;
+        if !prefix_changed && !changes.contains(ChangedFlags::ThreadNetworkInterfaceStateChanged) {
+            return false;
+        }
+
+        for group in auto_refresh.groups.iter() {
+            let old = group.address(&previous);
+            let new = group.address(&mesh_local_prefix);
+            if old != new {
+                let old = ot_ip6_address_from_ipv6_addr(old);
+                unsafe { otIp6UnsubscribeMulticastAddress(instance, &old) };
+            }
+            if let Err(error) = subscribe(instance, new) {
+                log::warn!("auto refresh failed to subscribe {}: {:?}", new, error);
+            }
+        }
+        auto_refresh.mesh_local_prefix = Some(mesh_local_prefix);
+
+        #[cfg(feature = "srp-client")]
+        if prefix_changed && auto_refresh.srp_client {
+            restart_srp_client(instance);
+        }
+
+        prefix_changed
+    });
+
+    if prefix_changed {
+        log::debug!("auto refresh for mesh-local prefix {}", mesh_local_prefix);
+        critical_section::with(|cs| {
+            let mut callback = AUTO_REFRESH_CALLBACK.borrow_ref_mut(cs);
+            if let Some(callback) = callback.as_mut() {
+                callback(mesh_local_prefix);
+            }
+        });
+    }
+}
+
+/// Stop and start the SRP client, which registers host and services again
+#[cfg(feature = "srp-client")]
+fn restart_srp_client(instance: *mut otInstance) {
+    unsafe {
+        if !otSrpClientIsRunning(instance) {
+            return;
+        }
+
+        if otSrpClientIsAutoStartModeEnabled(instance) {
+            // stopping disables the auto-start mode, enabling it again selects a server and starts
+            otSrpClientStop(instance);
+            otSrpClientEnableAutoStartMode(
+                instance,
+                crate::srp_client::auto_start_callback(),
+                core::ptr::null_mut(),
+            );
+        } else {
+            let server = *otSrpClientGetServerAddress(instance);
+            otSrpClientStop(instance);
+            if let Err(error) = checked!(otSrpClientStart(instance, &server)) {
+                log::warn!("auto refresh failed to restart the SRP client: {:?}", error);
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otInstance, otLinkGetChannel, otLinkGetCounters, otMacCounters,
+};
+
+use crate::{radio::channel_index, timer::current_millis, OpenThread, RADIO_CHANNEL_COUNT};
+
+/// How often (in milliseconds) OpenThread's MAC counters are sampled into the channel statistics
+pub const CHANNEL_STATS_SAMPLE_INTERVAL: u64 = 1000;
+
+/// Weight (in 1/8) the previous value keeps in [ChannelStats::recent_cca_failure_rate]
+const RECENT_RATE_WEIGHT: u32 = 7;
+
+pub(crate) static CHANNEL_STATS: Mutex<RefCell<ChannelTelemetry>> =
+    Mutex::new(RefCell::new(ChannelTelemetry::DEFAULT));
+
+/// Transmit failures seen on one channel, see [OpenThread::link_get_channel_stats]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Transmission attempts, retries included
+    pub tx_attempts: u32,
+    /// Clear channel assessments that found the channel busy
+    pub cca_failures: u32,
+    /// Transmissions given up because CSMA/CA never found the channel clear
+    pub busy_channel: u32,
+    /// Transmissions aborted, e.g. by a higher priority radio operation
+    pub aborted: u32,
+    /// Transmissions given up because no retry was acknowledged
+    pub retries_exhausted: u32,
+    /// Milliseconds the channel was in use while sampling
+    pub time: u64,
+    /// CCA failures per 1000 attempts over the last samples, older samples fading out
+    pub recent_cca_failure_rate: u16,
+}
+
+impl ChannelStats {
+    const DEFAULT: Self = Self {
+        tx_attempts: 0,
+        cca_failures: 0,
+        busy_channel: 0,
+        aborted: 0,
+        retries_exhausted: 0,
+        time: 0,
+        recent_cca_failure_rate: 0,
+    };
+
+    /// CCA failures per 1000 attempts since the statistics were reset
+    pub fn cca_failure_rate(&self) -> u16 {
+        per_mille(self.cca_failures, self.tx_attempts)
+    }
+}
+
+/// The MAC counters relevant for [ChannelStats]
+#[derive(Clone, Copy)]
+struct Sample {
+    tx_attempts: u32,
+    cca_failures: u32,
+    busy_channel: u32,
+    aborted: u32,
+    retries_exhausted: u32,
+}
+
+impl Sample {
+    fn from_raw(counters: &otMacCounters) -> Self {
+        Self {
+            tx_attempts: counters.mTxTotal.wrapping_add(counters.mTxRetry),
+            cca_failures: counters.mTxErrCca,
+            busy_channel: counters.mTxErrBusyChannel,
+            aborted: counters.mTxErrAbort,
+            retries_exhausted: counters
+                .mTxDirectMaxRetryExpiry
+                .wrapping_add(counters.mTxIndirectMaxRetryExpiry),
+        }
+    }
+
+    /// Counts since `previous`, everything counts as new if the counters were reset in between
+    fn since(&self, previous: &Sample) -> Sample {
+        let reset = self.tx_attempts < previous.tx_attempts;
+        let delta = |current: u32, previous: u32| {
+            if reset {
+                current
+            } else {
+                current.saturating_sub(previous)
+            }
+        };
+
+        Sample {
+            tx_attempts: delta(self.tx_attempts, previous.tx_attempts),
+            cca_failures: delta(self.cca_failures, previous.cca_failures),
+            busy_channel: delta(self.busy_channel, previous.busy_channel),
+            aborted: delta(self.aborted, previous.aborted),
+            retries_exhausted: delta(self.retries_exhausted, previous.retries_exhausted),
+        }
+    }
+}
+
+pub(crate) struct ChannelTelemetry {
+    channels: [ChannelStats; RADIO_CHANNEL_COUNT],
+    previous: Option<Sample>,
+    /// milliseconds
+    last_sample: u64,
+}
+
+impl ChannelTelemetry {
+    pub(crate) const DEFAULT: Self = Self {
+        channels: [ChannelStats::DEFAULT; RADIO_CHANNEL_COUNT],
+        previous: None,
+        last_sample: 0,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the transmit failures seen on `channel` (11 to 26), `None` for other channels
+    ///
+    /// OpenThread's MAC counters are sampled every [CHANNEL_STATS_SAMPLE_INTERVAL] from
+    /// [OpenThread::process] and attributed to the channel in use at that time. A high CCA
+    /// failure rate means the channel is busy, e.g. with Wi-Fi, and a candidate to move away from.
+    pub fn link_get_channel_stats(&self, channel: u8) -> Option<ChannelStats> {
+        let index = channel_index(channel)?;
+        Some(critical_section::with(|cs| {
+            CHANNEL_STATS.borrow_ref(cs).channels[index]
+        }))
+    }
+
+    /// Get the transmit failures of all channels, index 0 is channel 11
+    pub fn link_get_all_channel_stats(&self) -> [ChannelStats; RADIO_CHANNEL_COUNT] {
+        critical_section::with(|cs| CHANNEL_STATS.borrow_ref(cs).channels)
+    }
+
+    /// Reset the statistics of all channels
+    pub fn link_reset_channel_stats(&mut self) {
+        critical_section::with(|cs| {
+            CHANNEL_STATS.borrow_ref_mut(cs).channels =
+                [ChannelStats::DEFAULT; RADIO_CHANNEL_COUNT];
+        });
+    }
+}
+
+/// Sample the MAC counters if due, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let now = current_millis();
+    let due = critical_section::with(|cs| {
+        let telemetry = CHANNEL_STATS.borrow_ref(cs);
+        let elapsed = now - telemetry.last_sample;
+        telemetry.previous.is_none() || elapsed >= CHANNEL_STATS_SAMPLE_INTERVAL
+    });
+    if !due {
+        return;
+    }
+
+    let sample = Sample::from_raw(unsafe { &*otLinkGetCounters(instance) });
+    let channel = unsafe { otLinkGetChannel(instance) };
+
+    critical_section::with(|cs| {
+        let mut telemetry = CHANNEL_STATS.borrow_ref_mut(cs);
+        let previous = telemetry.previous.replace(sample);
+        let elapsed = now - telemetry.last_sample;
+        telemetry.last_sample = now;
+
+        let (Some(previous), Some(index)) = (previous, channel_index(channel)) else {
+            return;
+        };
+        let delta = sample.since(&previous);
+
+        let stats = &mut telemetry.channels[index];
+        stats.tx_attempts = stats.tx_attempts.wrapping_add(delta.tx_attempts);
+        stats.cca_failures = stats.cca_failures.wrapping_add(delta.cca_failures);
+        stats.busy_channel = stats.busy_channel.wrapping_add(delta.busy_channel);
+        stats.aborted = stats.aborted.wrapping_add(delta.aborted);
+        stats.retries_exhausted = stats
+            .retries_exhausted
+            .wrapping_add(delta.retries_exhausted);
+        stats.time += elapsed;
+
+        if delta.tx_attempts > 0 {
+            let rate = per_mille(delta.cca_failures, delta.tx_attempts) as u32;
+            stats.recent_cca_failure_rate =
+                ((stats.recent_cca_failure_rate as u32 * RECENT_RATE_WEIGHT + rate)
+                    / (RECENT_RATE_WEIGHT + 1)) as u16;
+        }
+    });
+}
+
+fn per_mille(count: u32, total: u32) -> u16 {
+    if total == 0 {
+        return 0;
+    }
+
+    (count as u64 * 1000 / total as u64).min(1000) as u16
+}
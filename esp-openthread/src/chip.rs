@@ -0,0 +1,90 @@
+// what differs between the Espressif parts with an IEEE 802.15.4 radio
+//
+// Adding a part (e.g. the ESP32-C5 once `esp-hal` and `esp-ieee802154` support it) takes a feature
+// in Cargo.toml forwarding to both, a `ChipOps` implementation below overriding the radio or timer
+// setup where the part differs and, if its ISA differs from the ESP32-C6,
+// `OT_TARGET=<chip> ./build.sh` in `build_openthread`.
+
+use esp_hal::interrupt::Priority;
+use esp_hal::peripherals::Interrupt;
+use esp_hal::systimer::{Alarm, Target};
+use esp_ieee802154::Ieee802154;
+
+use crate::{radio, timer, TimerAlarm};
+
+#[cfg(not(any(feature = "esp32c6", feature = "esp32h2")))]
+compile_error!("Select the target chip via one of the `esp32c6` and `esp32h2` features");
+
+#[cfg(all(feature = "esp32c6", feature = "esp32h2"))]
+compile_error!("Only one of the `esp32c6` and `esp32h2` features can be enabled");
+
+/// Chip specific constants and setup of the radio and OpenThread's timers
+pub(crate) trait ChipOps {
+    /// Name of the chip, e.g. for logs
+    const NAME: &'static str;
+
+    /// Interrupt of the IEEE 802.15.4 MAC
+    const RADIO_INTERRUPT: Interrupt;
+
+    /// Frequency of the systimer counter driving OpenThread's timers
+    const SYSTIMER_TICKS_PER_SECOND: u64;
+
+    /// Hand the systimer alarm to OpenThread's timers and enable its interrupt
+    fn init_timer<const N: u8>(timer: Alarm<Target, N>, priority: Priority)
+    where
+        Alarm<Target, N>: TimerAlarm,
+    {
+        timer::install_isr(timer, priority);
+    }
+
+    /// Hook the receive queue and transmit completion into the radio driver and enable the MAC
+    /// interrupt, unless it's left to the driver (`priority` is `None`)
+    fn init_radio(radio: &mut Ieee802154, priority: Option<Priority>) {
+        radio::init_rx_queue();
+        radio.set_tx_done_callback_fn(radio::trigger_tx_done);
+        radio.set_rx_available_callback_fn(radio::rx_available);
+
+        if let Some(priority) = priority {
+            esp_hal::interrupt::enable(Self::RADIO_INTERRUPT, priority).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "esp32c6")]
+pub(crate) struct Esp32c6;
+
+#[cfg(feature = "esp32c6")]
+impl ChipOps for Esp32c6 {
+    const NAME: &'static str = "ESP32-C6";
+    const RADIO_INTERRUPT: Interrupt = Interrupt::ZB_MAC;
+    const SYSTIMER_TICKS_PER_SECOND: u64 = 16_000_000;
+
+    fn init_timer<const N: u8>(timer: Alarm<Target, N>, priority: Priority)
+    where
+        Alarm<Target, N>: TimerAlarm,
+    {
+        // `esp-wifi` drives its own timers from alarm 0 and takes its interrupt
+        #[cfg(feature = "wifi-coex")]
+        assert!(N != 0, "systimer alarm 0 is taken by esp-wifi");
+
+        timer::install_isr(timer, priority);
+    }
+}
+
+#[cfg(feature = "esp32h2")]
+pub(crate) struct Esp32h2;
+
+#[cfg(feature = "esp32h2")]
+impl ChipOps for Esp32h2 {
+    const NAME: &'static str = "ESP32-H2";
+    const RADIO_INTERRUPT: Interrupt = Interrupt::ZB_MAC;
+    const SYSTIMER_TICKS_PER_SECOND: u64 = 16_000_000;
+}
+
+/// The chip selected via its feature
+#[cfg(feature = "esp32c6")]
+pub(crate) type Chip = Esp32c6;
+
+/// The chip selected via its feature
+#[cfg(feature = "esp32h2")]
+pub(crate) type Chip = Esp32h2;
@@ -0,0 +1,573 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otCoapCode, otCoapCode_OT_COAP_CODE_DELETE, otCoapCode_OT_COAP_CODE_GET,
+        otCoapCode_OT_COAP_CODE_POST, otCoapCode_OT_COAP_CODE_PUT, otCoapMessageAppendUintOption,
+        otCoapMessageAppendUriPathOptions, otCoapMessageGenerateToken, otCoapMessageGetCode,
+        otCoapMessageGetType, otCoapMessageInit, otCoapMessageInitResponse,
+        otCoapMessageSetPayloadMarker, otCoapNewMessage, otCoapOptionIterator,
+        otCoapOptionIteratorGetFirstOptionMatching, otCoapOptionIteratorGetNextOptionMatching,
+        otCoapOptionIteratorGetOptionUintValue, otCoapOptionIteratorGetOptionValue,
+        otCoapOptionIteratorInit, otCoapOptionType_OT_COAP_OPTION_CONTENT_FORMAT,
+        otCoapOptionType_OT_COAP_OPTION_URI_PATH, otCoapSecureConnect, otCoapSecureDisconnect,
+        otCoapSecureIsConnected, otCoapSecureIsConnectionActive, otCoapSecureSendRequest,
+        otCoapSecureSendResponse, otCoapSecureSetClientConnectedCallback,
+        otCoapSecureSetDefaultHandler, otCoapSecureSetPsk, otCoapSecureStart, otCoapSecureStop,
+        otCoapType, otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT, otCoapType_OT_COAP_TYPE_CONFIRMABLE,
+        otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE, otError, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_NONE, otError_OT_ERROR_NO_BUFS, otError_OT_ERROR_PARSE, otInstance,
+        otMessage, otMessageAppend, otMessageFree, otMessageInfo, otSockAddr,
+        OT_COAP_DEFAULT_TOKEN_LENGTH, OT_DEFAULT_COAP_SECURE_PORT,
+    },
+    c_types::{c_char, c_void},
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{checked, ot_ip6_address_from_ipv6_addr, Error, MessageReader, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+const PARSE: Error = Error::InternalError(otError_OT_ERROR_PARSE);
+
+/// UDP port CoAP Secure is served on by default
+pub const COAP_SECURE_DEFAULT_PORT: u16 = OT_DEFAULT_COAP_SECURE_PORT as u16;
+
+/// Longest PSK accepted by mbedTLS (`MBEDTLS_PSK_MAX_LEN`)
+pub const COAP_SECURE_PSK_MAX_SIZE: usize = 32;
+
+/// Longest PSK identity kept for the DTLS handshake
+pub const COAP_SECURE_PSK_IDENTITY_MAX_SIZE: usize = 64;
+
+/// Longest Uri-Path of a request, the segments joined by `/`
+pub const COAP_URI_PATH_MAX_SIZE: usize = 64;
+
+/// Largest payload of a response filled in by the request callback
+pub const COAP_SECURE_RESPONSE_MAX_SIZE: usize = 256;
+
+pub(crate) static COAP_SECURE: Mutex<RefCell<CoapSecure>> =
+    Mutex::new(RefCell::new(CoapSecure::DEFAULT));
+
+pub(crate) static COAP_SECURE_REQUEST_CALLBACK: Mutex<
+    RefCell<
+        Option<&'static mut (dyn FnMut(&mut CoapSecureRequest, &mut CoapSecureResponse) + Send)>,
+    >,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static COAP_SECURE_REPLY_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(Result<CoapSecureReply, Error>) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static COAP_SECURE_CONNECTION_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(bool) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// The credentials handed to OpenThread, which keeps pointers to them rather than a copy
+pub(crate) struct CoapSecure {
+    psk: heapless::Vec<u8, COAP_SECURE_PSK_MAX_SIZE>,
+    identity: heapless::Vec<u8, COAP_SECURE_PSK_IDENTITY_MAX_SIZE>,
+}
+
+impl CoapSecure {
+    pub(crate) const DEFAULT: Self = Self {
+        psk: heapless::Vec::new(),
+        identity: heapless::Vec::new(),
+    };
+}
+
+/// Method of a CoAP request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoapMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl CoapMethod {
+    #[allow(non_upper_case_globals)]
+    fn from_raw(code: otCoapCode) -> Option<Self> {
+        match code {
+            otCoapCode_OT_COAP_CODE_GET => Some(Self::Get),
+            otCoapCode_OT_COAP_CODE_POST => Some(Self::Post),
+            otCoapCode_OT_COAP_CODE_PUT => Some(Self::Put),
+            otCoapCode_OT_COAP_CODE_DELETE => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    fn to_raw(self) -> otCoapCode {
+        match self {
+            Self::Get => otCoapCode_OT_COAP_CODE_GET,
+            Self::Post => otCoapCode_OT_COAP_CODE_POST,
+            Self::Put => otCoapCode_OT_COAP_CODE_PUT,
+            Self::Delete => otCoapCode_OT_COAP_CODE_DELETE,
+        }
+    }
+}
+
+/// Code of a CoAP response, class and detail packed as in the header, e.g. `0x45` for 2.05
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoapCode(pub u8);
+
+impl CoapCode {
+    pub const CREATED: Self = Self::new(2, 1);
+    pub const DELETED: Self = Self::new(2, 2);
+    pub const VALID: Self = Self::new(2, 3);
+    pub const CHANGED: Self = Self::new(2, 4);
+    pub const CONTENT: Self = Self::new(2, 5);
+    pub const BAD_REQUEST: Self = Self::new(4, 0);
+    pub const UNAUTHORIZED: Self = Self::new(4, 1);
+    pub const BAD_OPTION: Self = Self::new(4, 2);
+    pub const FORBIDDEN: Self = Self::new(4, 3);
+    pub const NOT_FOUND: Self = Self::new(4, 4);
+    pub const METHOD_NOT_ALLOWED: Self = Self::new(4, 5);
+    pub const REQUEST_TOO_LARGE: Self = Self::new(4, 13);
+    pub const UNSUPPORTED_FORMAT: Self = Self::new(4, 15);
+    pub const INTERNAL_ERROR: Self = Self::new(5, 0);
+    pub const NOT_IMPLEMENTED: Self = Self::new(5, 1);
+    pub const SERVICE_UNAVAILABLE: Self = Self::new(5, 3);
+
+    /// The code `class.detail`, e.g. `CoapCode::new(4, 4)` for 4.04
+    pub const fn new(class: u8, detail: u8) -> Self {
+        Self((class << 5) | (detail & 0x1f))
+    }
+
+    pub fn class(&self) -> u8 {
+        self.0 >> 5
+    }
+
+    pub fn detail(&self) -> u8 {
+        self.0 & 0x1f
+    }
+
+    /// Returns `true` for the 2.xx codes
+    pub fn is_success(&self) -> bool {
+        self.class() == 2
+    }
+}
+
+/// A request received by the CoAP Secure server, see
+/// [OpenThread::set_coap_secure_request_callback]
+pub struct CoapSecureRequest<'r> {
+    pub method: CoapMethod,
+    /// The Uri-Path options joined by `/`, without a leading `/`
+    pub uri_path: &'r str,
+    pub content_format: Option<u16>,
+    pub confirmable: bool,
+    pub payload: MessageReader<'r>,
+}
+
+/// The response to a [CoapSecureRequest], filled in by the request callback
+///
+/// Starts out as an empty 4.04 (Not Found), so paths the callback doesn't handle are answered
+/// properly.
+#[derive(Debug, Clone)]
+pub struct CoapSecureResponse {
+    pub code: CoapCode,
+    pub content_format: Option<u16>,
+    pub payload: heapless::Vec<u8, COAP_SECURE_RESPONSE_MAX_SIZE>,
+}
+
+/// A response to a request sent via [OpenThread::coap_secure_send_request]
+pub struct CoapSecureReply<'r> {
+    pub code: CoapCode,
+    pub content_format: Option<u16>,
+    pub payload: MessageReader<'r>,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the Pre-Shared Key and its identity for the DTLS handshake of CoAP Secure
+    ///
+    /// Needs to be set before [OpenThread::coap_secure_start] or
+    /// [OpenThread::coap_secure_connect]. With a PSK OpenThread's DTLS offers the
+    /// `TLS_PSK_WITH_AES_128_CCM_8` cipher suite only, there's no other to choose. Fails with
+    /// `OT_ERROR_INVALID_ARGS` for an empty or too long PSK or identity.
+    pub fn coap_secure_set_psk(&mut self, psk: &[u8], identity: &[u8]) -> Result<(), Error> {
+        if psk.is_empty() || identity.is_empty() {
+            return Err(INVALID_ARGS);
+        }
+
+        critical_section::with(|cs| {
+            let mut coap_secure = COAP_SECURE.borrow_ref_mut(cs);
+            coap_secure.psk = heapless::Vec::from_slice(psk).map_err(|_| INVALID_ARGS)?;
+            coap_secure.identity = heapless::Vec::from_slice(identity).map_err(|_| INVALID_ARGS)?;
+
+            // the static outlives OpenThread's use of the pointers
+            unsafe {
+                otCoapSecureSetPsk(
+                    self.instance,
+                    coap_secure.psk.as_ptr(),
+                    coap_secure.psk.len() as u16,
+                    coap_secure.identity.as_ptr(),
+                    coap_secure.identity.len() as u16,
+                );
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Start CoAP Secure on `port`, e.g. [COAP_SECURE_DEFAULT_PORT]
+    ///
+    /// Serves requests via the callback set with [OpenThread::set_coap_secure_request_callback]
+    /// and reports clients connecting and disconnecting to the callback set with
+    /// [OpenThread::set_coap_secure_connection_callback]. A client only needs an ephemeral port,
+    /// start it on port `0` before [OpenThread::coap_secure_connect].
+    pub fn coap_secure_start(&mut self, port: u16) -> Result<(), Error> {
+        unsafe {
+            otCoapSecureSetDefaultHandler(
+                self.instance,
+                Some(request_handler),
+                self.instance as *mut c_void,
+            );
+            otCoapSecureSetClientConnectedCallback(
+                self.instance,
+                Some(connection_handler),
+                core::ptr::null_mut(),
+            );
+        }
+
+        checked!(unsafe { otCoapSecureStart(self.instance, port) })
+    }
+
+    /// Stop CoAP Secure, closing the DTLS session
+    pub fn coap_secure_stop(&mut self) {
+        unsafe { otCoapSecureStop(self.instance) }
+    }
+
+    /// Set the callback answering requests to the CoAP Secure server
+    ///
+    /// The callback fills in the response, which is sent piggybacked on the acknowledgement of
+    /// confirmable requests. Without a callback every request is answered with 4.04.
+    pub fn set_coap_secure_request_callback(
+        &mut self,
+        callback: Option<
+            &'a mut (dyn FnMut(&mut CoapSecureRequest, &mut CoapSecureResponse) + Send),
+        >,
+    ) {
+        critical_section::with(|cs| {
+            let mut request_callback = COAP_SECURE_REQUEST_CALLBACK.borrow_ref_mut(cs);
+            *request_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Set the callback told whenever the DTLS session is established (`true`) or closed
+    /// (`false`), both as client and as server
+    pub fn set_coap_secure_connection_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(bool) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut connection_callback = COAP_SECURE_CONNECTION_CALLBACK.borrow_ref_mut(cs);
+            *connection_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Set the callback for the responses to [OpenThread::coap_secure_send_request]
+    ///
+    /// Requests that got no response report `OT_ERROR_RESPONSE_TIMEOUT`, requests aborted by
+    /// closing the session `OT_ERROR_ABORT`.
+    pub fn set_coap_secure_reply_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(Result<CoapSecureReply, Error>) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut reply_callback = COAP_SECURE_REPLY_CALLBACK.borrow_ref_mut(cs);
+            *reply_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Start the DTLS handshake with a CoAP Secure server
+    ///
+    /// Needs CoAP Secure started and the PSK set. The outcome is reported to the callback set
+    /// with [OpenThread::set_coap_secure_connection_callback].
+    pub fn coap_secure_connect(&mut self, address: Ipv6Addr, port: u16) -> Result<(), Error> {
+        let sock_addr = otSockAddr {
+            mAddress: ot_ip6_address_from_ipv6_addr(address),
+            mPort: port,
+        };
+
+        checked!(unsafe {
+            otCoapSecureConnect(
+                self.instance,
+                &sock_addr,
+                Some(connection_handler),
+                core::ptr::null_mut(),
+            )
+        })
+    }
+
+    /// Close the DTLS session
+    pub fn coap_secure_disconnect(&mut self) {
+        unsafe { otCoapSecureDisconnect(self.instance) }
+    }
+
+    /// Returns `true` once the DTLS handshake completed
+    pub fn coap_secure_is_connected(&self) -> bool {
+        unsafe { otCoapSecureIsConnected(self.instance) }
+    }
+
+    /// Returns `true` while a DTLS session is being set up or established
+    pub fn coap_secure_is_connection_active(&self) -> bool {
+        unsafe { otCoapSecureIsConnectionActive(self.instance) }
+    }
+
+    /// Send a request over the established DTLS session
+    ///
+    /// `uri_path` is given without a leading `/`, e.g. `sensors/temperature`. The response is
+    /// reported to the callback set with [OpenThread::set_coap_secure_reply_callback].
+    pub fn coap_secure_send_request(
+        &mut self,
+        method: CoapMethod,
+        uri_path: &str,
+        content_format: Option<u16>,
+        payload: &[u8],
+        confirmable: bool,
+    ) -> Result<(), Error> {
+        let mut raw_uri_path = [0u8; COAP_URI_PATH_MAX_SIZE + 1];
+        if uri_path.len() > COAP_URI_PATH_MAX_SIZE || uri_path.as_bytes().contains(&0) {
+            return Err(INVALID_ARGS);
+        }
+        raw_uri_path[..uri_path.len()].copy_from_slice(uri_path.as_bytes());
+
+        let message = unsafe { otCoapNewMessage(self.instance, core::ptr::null()) };
+        if message.is_null() {
+            return Err(NO_BUFS);
+        }
+
+        let message_type = if confirmable {
+            otCoapType_OT_COAP_TYPE_CONFIRMABLE
+        } else {
+            otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE
+        };
+        let res = unsafe {
+            otCoapMessageInit(message, message_type, method.to_raw());
+            otCoapMessageGenerateToken(message, OT_COAP_DEFAULT_TOKEN_LENGTH as u8);
+
+            let res =
+                otCoapMessageAppendUriPathOptions(message, raw_uri_path.as_ptr() as *const c_char);
+            if res == otError_OT_ERROR_NONE {
+                append_content(message, content_format, payload)
+            } else {
+                res
+            }
+        };
+
+        let res = if res == otError_OT_ERROR_NONE {
+            unsafe {
+                otCoapSecureSendRequest(
+                    self.instance,
+                    message,
+                    Some(response_handler),
+                    core::ptr::null_mut(),
+                )
+            }
+        } else {
+            res
+        };
+
+        // OpenThread only takes ownership of messages it sent
+        if res != otError_OT_ERROR_NONE {
+            unsafe { otMessageFree(message) };
+        }
+
+        checked!(res)
+    }
+}
+
+/// Append the Content-Format option and the payload, options have to come first
+unsafe fn append_content(
+    message: *mut otMessage,
+    content_format: Option<u16>,
+    payload: &[u8],
+) -> otError {
+    if let Some(content_format) = content_format {
+        let res = otCoapMessageAppendUintOption(
+            message,
+            otCoapOptionType_OT_COAP_OPTION_CONTENT_FORMAT as u16,
+            content_format as u32,
+        );
+        if res != otError_OT_ERROR_NONE {
+            return res;
+        }
+    }
+
+    if payload.is_empty() {
+        return otError_OT_ERROR_NONE;
+    }
+
+    let res = otCoapMessageSetPayloadMarker(message);
+    if res != otError_OT_ERROR_NONE {
+        return res;
+    }
+    otMessageAppend(
+        message,
+        payload.as_ptr() as *const c_void,
+        payload.len() as u16,
+    )
+}
+
+/// Content-Format option of `message`, if any
+unsafe fn content_format(message: *const otMessage) -> Option<u16> {
+    let mut iterator: otCoapOptionIterator = core::mem::zeroed();
+    if otCoapOptionIteratorInit(&mut iterator, message) != otError_OT_ERROR_NONE {
+        return None;
+    }
+
+    let option = otCoapOptionIteratorGetFirstOptionMatching(
+        &mut iterator,
+        otCoapOptionType_OT_COAP_OPTION_CONTENT_FORMAT as u16,
+    );
+    if option.is_null() {
+        return None;
+    }
+
+    let mut value = 0u64;
+    if otCoapOptionIteratorGetOptionUintValue(&mut iterator, &mut value) != otError_OT_ERROR_NONE {
+        return None;
+    }
+    u16::try_from(value).ok()
+}
+
+/// Join the Uri-Path options of `message` with `/`
+unsafe fn uri_path(
+    message: *const otMessage,
+) -> Result<heapless::String<COAP_URI_PATH_MAX_SIZE>, Error> {
+    let mut iterator: otCoapOptionIterator = core::mem::zeroed();
+    checked!(otCoapOptionIteratorInit(&mut iterator, message))?;
+
+    let mut path = heapless::Vec::<u8, COAP_URI_PATH_MAX_SIZE>::new();
+    let number = otCoapOptionType_OT_COAP_OPTION_URI_PATH as u16;
+    let mut option = otCoapOptionIteratorGetFirstOptionMatching(&mut iterator, number);
+    while !option.is_null() {
+        if !path.is_empty() {
+            path.push(b'/').map_err(|_| NO_BUFS)?;
+        }
+
+        let start = path.len();
+        let len = (*option).mLength as usize;
+        path.resize_default(start + len).map_err(|_| NO_BUFS)?;
+        checked!(otCoapOptionIteratorGetOptionValue(
+            &mut iterator,
+            path[start..].as_mut_ptr() as *mut c_void,
+        ))?;
+
+        option = otCoapOptionIteratorGetNextOptionMatching(&mut iterator, number);
+    }
+
+    heapless::String::from_utf8(path).map_err(|_| PARSE)
+}
+
+unsafe extern "C" fn request_handler(
+    context: *mut c_void,
+    message: *mut otMessage,
+    message_info: *const otMessageInfo,
+) {
+    let instance = context as *mut otInstance;
+
+    let mut response = CoapSecureResponse {
+        code: CoapCode::NOT_FOUND,
+        content_format: None,
+        payload: heapless::Vec::new(),
+    };
+
+    let method = CoapMethod::from_raw(otCoapMessageGetCode(message));
+    match (method, uri_path(message)) {
+        (Some(method), Ok(uri_path)) => {
+            let mut request = CoapSecureRequest {
+                method,
+                uri_path: &uri_path,
+                content_format: content_format(message),
+                confirmable: otCoapMessageGetType(message) == otCoapType_OT_COAP_TYPE_CONFIRMABLE,
+                payload: MessageReader::new(message),
+            };
+            log::debug!("coap secure request {:?} {}", method, request.uri_path);
+
+            critical_section::with(|cs| {
+                let mut callback = COAP_SECURE_REQUEST_CALLBACK.borrow_ref_mut(cs);
+                if let Some(callback) = callback.as_mut() {
+                    callback(&mut request, &mut response);
+                }
+            });
+        }
+        (None, _) => response.code = CoapCode::METHOD_NOT_ALLOWED,
+        (_, Err(_)) => response.code = CoapCode::BAD_OPTION,
+    }
+
+    let res = send_response(instance, message, message_info, &response);
+    if res != otError_OT_ERROR_NONE {
+        log::warn!("coap secure response not sent {}", res);
+    }
+}
+
+unsafe fn send_response(
+    instance: *mut otInstance,
+    request: *const otMessage,
+    message_info: *const otMessageInfo,
+    response: &CoapSecureResponse,
+) -> otError {
+    let message = otCoapNewMessage(instance, core::ptr::null());
+    if message.is_null() {
+        return otError_OT_ERROR_NO_BUFS;
+    }
+
+    let request_type: otCoapType = otCoapMessageGetType(request);
+    let response_type = if request_type == otCoapType_OT_COAP_TYPE_CONFIRMABLE {
+        otCoapType_OT_COAP_TYPE_ACKNOWLEDGMENT
+    } else {
+        otCoapType_OT_COAP_TYPE_NON_CONFIRMABLE
+    };
+
+    let mut res = otCoapMessageInitResponse(
+        message,
+        request,
+        response_type,
+        response.code.0 as otCoapCode,
+    );
+    if res == otError_OT_ERROR_NONE {
+        res = append_content(message, response.content_format, &response.payload);
+    }
+    if res == otError_OT_ERROR_NONE {
+        res = otCoapSecureSendResponse(instance, message, message_info);
+    }
+
+    if res != otError_OT_ERROR_NONE {
+        otMessageFree(message);
+    }
+    res
+}
+
+unsafe extern "C" fn response_handler(
+    _context: *mut c_void,
+    message: *mut otMessage,
+    _message_info: *const otMessageInfo,
+    error: otError,
+) {
+    log::debug!("coap secure response {}", error);
+
+    let reply = checked!(error).map(|_| CoapSecureReply {
+        code: CoapCode(otCoapMessageGetCode(message) as u8),
+        content_format: content_format(message),
+        payload: MessageReader::new(message),
+    });
+
+    critical_section::with(|cs| {
+        let mut callback = COAP_SECURE_REPLY_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(reply);
+        }
+    });
+}
+
+unsafe extern "C" fn connection_handler(connected: bool, _context: *mut c_void) {
+    log::debug!("coap secure connected {}", connected);
+
+    critical_section::with(|cs| {
+        let mut callback = COAP_SECURE_CONNECTION_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(connected);
+        }
+    });
+}
@@ -0,0 +1,120 @@
+use esp_openthread_sys::{
+    bindings::{
+        otCryptoContext, otError_OT_ERROR_INVALID_ARGS, otPlatCryptoSha256Deinit,
+        otPlatCryptoSha256Finish, otPlatCryptoSha256Init, otPlatCryptoSha256Start,
+        otPlatCryptoSha256Update, OT_CRYPTO_SHA256_HASH_SIZE,
+    },
+    c_types::c_void,
+};
+
+use crate::{checked, Error};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Room for the SHA-256 context of OpenThread's crypto backend, a `mbedtls_sha256_context`
+const SHA256_CONTEXT_SIZE: usize = 256;
+
+/// Verhoeff multiplication table, the dihedral group D5
+const VERHOEFF_MULTIPLICATION: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// Verhoeff permutation table, row `i` is applied to the `i`-th digit from the right (mod 8)
+const VERHOEFF_PERMUTATION: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+/// Verhoeff inverse table
+const VERHOEFF_INVERSE: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Compute the Verhoeff check digit of a decimal code, e.g. of a numeric commissioning
+/// credential before appending it
+///
+/// Fails with `OT_ERROR_INVALID_ARGS` if `digits` is empty or has anything but `0` to `9`.
+pub fn verhoeff_checksum(digits: &str) -> Result<char, Error> {
+    // the check digit takes position 0, the digits start at position 1
+    let check = verhoeff(digits, 1)?;
+    Ok((b'0' + VERHOEFF_INVERSE[check as usize]) as char)
+}
+
+/// Returns `true` if the last digit of `code` is the Verhoeff check digit of the ones before,
+/// e.g. for a commissioning credential typed in by the user
+pub fn verhoeff_validate(code: &str) -> bool {
+    code.len() >= 2 && verhoeff(code, 0) == Ok(0)
+}
+
+/// Run the Verhoeff algorithm over `digits` from the right, the rightmost at position `offset`
+fn verhoeff(digits: &str, offset: usize) -> Result<u8, Error> {
+    if digits.is_empty() {
+        return Err(INVALID_ARGS);
+    }
+
+    digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .try_fold(0u8, |check, (position, digit)| {
+            if !digit.is_ascii_digit() {
+                return Err(INVALID_ARGS);
+            }
+            let permuted = VERHOEFF_PERMUTATION[(position + offset) % 8][(digit - b'0') as usize];
+            Ok(VERHOEFF_MULTIPLICATION[check as usize][permuted as usize])
+        })
+}
+
+/// Derive the Joiner ID from the EUI-64 of a joiner, as commissioners do to steer and
+/// authenticate it
+///
+/// The Joiner ID is the first 64 bits of the SHA-256 hash of the EUI-64, with the
+/// locally administered bit set. On the joiner itself it's also available via
+/// `joiner_get_id` (feature `joiner`), this works without OpenThread being a joiner, e.g. when
+/// printing install codes on the production line. Uses OpenThread's crypto backend.
+pub fn joiner_id_from_eui64(eui64: [u8; 8]) -> Result<[u8; 8], Error> {
+    let mut storage = [0u64; SHA256_CONTEXT_SIZE / 8];
+    let mut context = otCryptoContext {
+        mContext: storage.as_mut_ptr() as *mut c_void,
+        mContextSize: SHA256_CONTEXT_SIZE as u16,
+    };
+    let mut hash = [0u8; OT_CRYPTO_SHA256_HASH_SIZE as usize];
+
+    checked!(unsafe { otPlatCryptoSha256Init(&mut context) })?;
+    let result = checked!(unsafe { otPlatCryptoSha256Start(&mut context) })
+        .and_then(|_| {
+            checked!(unsafe {
+                otPlatCryptoSha256Update(
+                    &mut context,
+                    eui64.as_ptr() as *const c_void,
+                    eui64.len() as u16,
+                )
+            })
+        })
+        .and_then(|_| {
+            checked!(unsafe {
+                otPlatCryptoSha256Finish(&mut context, hash.as_mut_ptr(), hash.len() as u16)
+            })
+        });
+    unsafe { otPlatCryptoSha256Deinit(&mut context) };
+    result?;
+
+    let mut joiner_id = [0u8; 8];
+    joiner_id.copy_from_slice(&hash[..8]);
+    // locally administered, as for any random extended address
+    joiner_id[0] |= 0x02;
+    Ok(joiner_id)
+}
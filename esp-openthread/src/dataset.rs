@@ -0,0 +1,282 @@
+use core::{fmt, str::FromStr};
+
+#[cfg(any(feature = "embedded-dataset", feature = "provisioning"))]
+use esp_openthread_sys::bindings::{
+    otDatasetParseTlvs, otOperationalDataset, otOperationalDatasetComponents,
+    otOperationalDatasetTlvs,
+};
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_INVALID_ARGS, otExtendedPanId, otMeshLocalPrefix, otNetworkName,
+    OT_NETWORK_NAME_MAX_SIZE,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::Error;
+#[cfg(any(feature = "embedded-dataset", feature = "provisioning"))]
+use crate::{checked, SecurityPolicy};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Check dataset TLVs before handing them to OpenThread, returning which components they carry
+///
+/// Fails with `OT_ERROR_INVALID_ARGS` if the TLVs don't parse or carry a security policy which
+/// doesn't pass [SecurityPolicy::validate]. The parsed dataset is wiped from the stack.
+#[cfg(any(feature = "embedded-dataset", feature = "provisioning"))]
+pub(crate) fn validate_tlvs(
+    tlvs: &otOperationalDatasetTlvs,
+) -> Result<otOperationalDatasetComponents, Error> {
+    let mut raw_dataset: otOperationalDataset = unsafe { core::mem::zeroed() };
+    let parsed = checked!(unsafe { otDatasetParseTlvs(tlvs, &mut raw_dataset) })
+        .map_err(|_| INVALID_ARGS)
+        .and_then(|_| {
+            if raw_dataset.mComponents.mIsSecurityPolicyPresent() {
+                SecurityPolicy::from(raw_dataset.mSecurityPolicy).validate()
+            } else {
+                Ok(())
+            }
+        })
+        .map(|_| raw_dataset.mComponents);
+    unsafe { core::ptr::write_volatile(&mut raw_dataset, core::mem::zeroed()) };
+    parsed
+}
+
+/// Parse exactly `N` bytes given as hex digits, an optional `0x` prefix is accepted
+fn parse_hex<const N: usize>(hex: &str) -> Result<[u8; N], Error> {
+    let hex = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+
+    // from_str_radix alone would accept a sign, e.g. `+f`
+    if hex.len() != N * 2 || !hex.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(INVALID_ARGS);
+    }
+
+    let mut result = [0u8; N];
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..][..2], 16).map_err(|_| INVALID_ARGS)?;
+    }
+    Ok(result)
+}
+
+/// Thread Extended PAN ID
+///
+/// Formatted and parsed as 16 hex digits, e.g. `3a90e3a319a90494`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExtendedPanId(pub [u8; 8]);
+
+impl ExtendedPanId {
+    /// Parse from 16 hex digits
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Ok(Self(parse_hex(hex)?))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ExtendedPanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<[u8; 8]> for ExtendedPanId {
+    fn from(value: [u8; 8]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ExtendedPanId> for [u8; 8] {
+    fn from(value: ExtendedPanId) -> Self {
+        value.0
+    }
+}
+
+impl From<otExtendedPanId> for ExtendedPanId {
+    fn from(value: otExtendedPanId) -> Self {
+        Self(value.m8)
+    }
+}
+
+impl From<ExtendedPanId> for otExtendedPanId {
+    fn from(value: ExtendedPanId) -> Self {
+        otExtendedPanId { m8: value.0 }
+    }
+}
+
+/// Thread Mesh Local Prefix (always a /64)
+///
+/// Formatted like `fd1f:a298:dbd1:e329::/64`, parsed from either that form or 16 hex digits.
+/// Created via [MeshLocalPrefix::new] or one of the parsers, which reject multicast prefixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MeshLocalPrefix([u8; 8]);
+
+impl MeshLocalPrefix {
+    /// Parse from 16 hex digits
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::new(parse_hex(hex)?)
+    }
+
+    /// Parse from an IPv6 prefix like `fd1f:a298:dbd1:e329::/64` or from 16 hex digits
+    pub fn parse(prefix: &str) -> Result<Self, Error> {
+        if !prefix.contains(':') {
+            return Self::from_hex(prefix);
+        }
+
+        let prefix = crate::Ipv6Prefix::parse(prefix)?;
+        if prefix.length != 64 {
+            return Err(INVALID_ARGS);
+        }
+        Self::from_ipv6_addr(prefix.prefix)
+    }
+
+    /// Create a prefix, rejecting multicast prefixes which are not valid as Mesh Local Prefix
+    pub fn new(prefix: [u8; 8]) -> Result<Self, Error> {
+        if prefix[0] == 0xff {
+            return Err(INVALID_ARGS);
+        }
+        Ok(Self(prefix))
+    }
+
+    /// Take the upper 64 bits of the given address
+    pub fn from_ipv6_addr(address: Ipv6Addr) -> Result<Self, Error> {
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&address.octets()[..8]);
+        Self::new(prefix)
+    }
+
+    /// The prefix as an IPv6 address with all interface identifier bits cleared
+    pub fn to_ipv6_addr(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets[..8].copy_from_slice(&self.0);
+        Ipv6Addr::from(octets)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for MeshLocalPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/64", self.to_ipv6_addr())
+    }
+}
+
+impl TryFrom<[u8; 8]> for MeshLocalPrefix {
+    type Error = Error;
+
+    fn try_from(value: [u8; 8]) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<MeshLocalPrefix> for [u8; 8] {
+    fn from(value: MeshLocalPrefix) -> Self {
+        value.0
+    }
+}
+
+impl From<otMeshLocalPrefix> for MeshLocalPrefix {
+    fn from(value: otMeshLocalPrefix) -> Self {
+        Self(value.m8)
+    }
+}
+
+impl From<MeshLocalPrefix> for otMeshLocalPrefix {
+    fn from(value: MeshLocalPrefix) -> Self {
+        otMeshLocalPrefix { m8: value.0 }
+    }
+}
+
+/// Thread Network Name, at most 16 bytes of UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NetworkName {
+    // unused bytes are always zero
+    bytes: [u8; OT_NETWORK_NAME_MAX_SIZE as usize],
+    len: u8,
+}
+
+impl NetworkName {
+    /// Create a network name, rejecting names longer than 16 bytes or containing a NUL
+    pub fn new(name: &str) -> Result<Self, Error> {
+        Self::from_bytes(name.as_bytes())
+    }
+
+    /// Create a network name from its UTF-8 encoding
+    pub fn from_bytes(name: &[u8]) -> Result<Self, Error> {
+        if name.len() > OT_NETWORK_NAME_MAX_SIZE as usize
+            || name.contains(&0)
+            || core::str::from_utf8(name).is_err()
+        {
+            return Err(INVALID_ARGS);
+        }
+
+        let mut bytes = [0u8; OT_NETWORK_NAME_MAX_SIZE as usize];
+        bytes[..name.len()].copy_from_slice(name);
+        Ok(Self {
+            bytes,
+            len: name.len() as u8,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only valid UTF-8 gets in
+        core::str::from_utf8(self.as_bytes()).unwrap_or_default()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl fmt::Display for NetworkName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NetworkName {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::new(name)
+    }
+}
+
+impl TryFrom<&str> for NetworkName {
+    type Error = Error;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        Self::new(name)
+    }
+}
+
+impl TryFrom<&otNetworkName> for NetworkName {
+    type Error = Error;
+
+    fn try_from(value: &otNetworkName) -> Result<Self, Self::Error> {
+        let mut bytes = [0u8; OT_NETWORK_NAME_MAX_SIZE as usize];
+        for (byte, raw) in bytes.iter_mut().zip(value.m8) {
+            *byte = raw as u8;
+        }
+        let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+        Self::from_bytes(&bytes[..len])
+    }
+}
+
+impl From<NetworkName> for otNetworkName {
+    fn from(value: NetworkName) -> Self {
+        // the last byte stays the terminating NUL
+        let mut m8 = [0; OT_NETWORK_NAME_MAX_SIZE as usize + 1];
+        for (raw, byte) in m8.iter_mut().zip(value.as_bytes()) {
+            *raw = *byte as _;
+        }
+        otNetworkName { m8 }
+    }
+}
@@ -0,0 +1,262 @@
+use core::cell::RefCell;
+
+use bitflags::bitflags;
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otDatasetGetPending, otDatasetIsCommissioned, otDatasetSendMgmtPendingSet,
+        otDeviceRole_OT_DEVICE_ROLE_CHILD, otDeviceRole_OT_DEVICE_ROLE_LEADER,
+        otDeviceRole_OT_DEVICE_ROLE_ROUTER, otError, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_NONE, otLinkSetChannel, otThreadGetDeviceRole,
+    },
+    c_types::c_void,
+};
+
+use crate::{
+    checked, dataset_from_raw_dataset, raw_dataset_from_dataset, validate_security_policy, Error,
+    OpenThread, OperationalDataset, ThreadTimestamp,
+};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Delay (in milliseconds) of a channel change via [OpenThread::request_channel_change],
+/// OpenThread's channel manager uses the same minimum
+pub const CHANNEL_CHANGE_DELAY: u32 = 120_000;
+
+pub(crate) static MIGRATION_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(Result<(), Error>) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+bitflags! {
+    /// Components of an [OperationalDataset], e.g. the ones differing between two datasets
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DatasetComponents: u16 {
+        const ActiveTimestamp = 1;
+        const PendingTimestamp = 2;
+        const NetworkKey = 4;
+        const NetworkName = 8;
+        const ExtendedPanId = 16;
+        const MeshLocalPrefix = 32;
+        const Delay = 64;
+        const PanId = 128;
+        const Channel = 256;
+        const Pskc = 512;
+        const SecurityPolicy = 1024;
+        const ChannelMask = 2048;
+    }
+}
+
+impl OperationalDataset {
+    /// Get the components present in this dataset
+    pub fn components(&self) -> DatasetComponents {
+        self.diff(&OperationalDataset::default())
+    }
+
+    /// Get the components that differ from `other`, a component present in only one of the
+    /// datasets differs as well
+    pub fn diff(&self, other: &OperationalDataset) -> DatasetComponents {
+        let mut components = DatasetComponents::empty();
+
+        components.set(
+            DatasetComponents::ActiveTimestamp,
+            self.active_timestamp != other.active_timestamp,
+        );
+        components.set(
+            DatasetComponents::PendingTimestamp,
+            self.pending_timestamp != other.pending_timestamp,
+        );
+        components.set(
+            DatasetComponents::NetworkKey,
+            self.network_key != other.network_key,
+        );
+        components.set(
+            DatasetComponents::NetworkName,
+            self.network_name != other.network_name,
+        );
+        components.set(
+            DatasetComponents::ExtendedPanId,
+            self.extended_pan_id != other.extended_pan_id,
+        );
+        components.set(
+            DatasetComponents::MeshLocalPrefix,
+            self.mesh_local_prefix != other.mesh_local_prefix,
+        );
+        components.set(DatasetComponents::Delay, self.delay != other.delay);
+        components.set(DatasetComponents::PanId, self.pan_id != other.pan_id);
+        components.set(DatasetComponents::Channel, self.channel != other.channel);
+        components.set(DatasetComponents::Pskc, self.pskc != other.pskc);
+        components.set(
+            DatasetComponents::SecurityPolicy,
+            self.security_policy != other.security_policy,
+        );
+        components.set(
+            DatasetComponents::ChannelMask,
+            self.channel_mask != other.channel_mask,
+        );
+
+        components
+    }
+
+    /// Take every component present in `other`
+    fn merge(&mut self, other: OperationalDataset) {
+        macro_rules! take {
+            ($($field:ident),*) => {
+                $(if other.$field.is_some() {
+                    self.$field = other.$field;
+                })*
+            };
+        }
+
+        take!(
+            active_timestamp,
+            pending_timestamp,
+            network_key,
+            network_name,
+            extended_pan_id,
+            mesh_local_prefix,
+            delay,
+            pan_id,
+            channel,
+            pskc,
+            security_policy,
+            channel_mask
+        );
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Returns the currently pending Dataset
+    pub fn get_pending_dataset(&self) -> Result<OperationalDataset, Error> {
+        let mut raw_dataset = raw_dataset_from_dataset(OperationalDataset::default());
+        checked!(unsafe { otDatasetGetPending(self.instance, &mut raw_dataset) })?;
+
+        Ok(dataset_from_raw_dataset(raw_dataset))
+    }
+
+    /// Set the callback for the leader's response to [OpenThread::migrate_to]
+    pub fn set_migration_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(Result<(), Error>) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut migration_callback = MIGRATION_CALLBACK.borrow_ref_mut(cs);
+            *migration_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Move the whole network to `dataset` in `delay` milliseconds, e.g. to change the channel
+    /// or rotate the network key
+    ///
+    /// Only the components present in `dataset` change, the rest is taken from the active
+    /// dataset. Unless given, the active and pending timestamps are set one second past the
+    /// current ones. The result is sent to the leader as Pending Dataset, which distributes it
+    /// and has every device switch once the delay expired. The leader's response is reported to
+    /// the callback set via [OpenThread::set_migration_callback].
+    pub fn migrate_to(&mut self, dataset: OperationalDataset, delay: u32) -> Result<(), Error> {
+        validate_security_policy(&dataset)?;
+
+        let active = self.get_active_dataset()?;
+        let pending = self.get_pending_dataset().ok();
+
+        let mut target = OperationalDataset {
+            pending_timestamp: None,
+            delay: None,
+            ..active.clone()
+        };
+        target.merge(dataset);
+
+        if target.active_timestamp == active.active_timestamp {
+            target.active_timestamp = Some(next_timestamp(active.active_timestamp));
+        }
+        if target.pending_timestamp.is_none() {
+            let newest = pending
+                .and_then(|pending| pending.pending_timestamp)
+                .into_iter()
+                .chain(active.active_timestamp)
+                .max_by_key(|timestamp| (timestamp.seconds, timestamp.ticks));
+            target.pending_timestamp = Some(next_timestamp(newest));
+        }
+        target.delay = Some(delay);
+
+        log::debug!("migrate_to changing {:?}", target.diff(&active));
+
+        let raw_dataset = raw_dataset_from_dataset(target);
+        checked!(unsafe {
+            otDatasetSendMgmtPendingSet(
+                self.instance,
+                &raw_dataset,
+                core::ptr::null(),
+                0,
+                Some(migration_callback),
+                core::ptr::null_mut(),
+            )
+        })
+    }
+
+    /// Switch to the IEEE 802.15.4 `channel` (11 to 26) the way Thread allows in the current role
+    ///
+    /// While attached the whole network moves, like OpenThread's channel manager does: the
+    /// channel is sent to the leader as Pending Dataset (see [OpenThread::migrate_to]) taking
+    /// effect after [CHANNEL_CHANGE_DELAY], the leader's response goes to the callback set via
+    /// [OpenThread::set_migration_callback]. Otherwise only this device changes the channel, in
+    /// the active dataset if commissioned and via `otLinkSetChannel` if not.
+    pub fn request_channel_change(&mut self, channel: u8) -> Result<(), Error> {
+        if !(11..=26).contains(&channel) {
+            return Err(INVALID_ARGS);
+        }
+
+        #[allow(non_upper_case_globals)]
+        match unsafe { otThreadGetDeviceRole(self.instance) } {
+            otDeviceRole_OT_DEVICE_ROLE_CHILD
+            | otDeviceRole_OT_DEVICE_ROLE_ROUTER
+            | otDeviceRole_OT_DEVICE_ROLE_LEADER => self.migrate_to(
+                OperationalDataset {
+                    channel: Some(channel as u16),
+                    ..OperationalDataset::default()
+                },
+                CHANNEL_CHANGE_DELAY,
+            ),
+            _ if unsafe { otDatasetIsCommissioned(self.instance) } => {
+                // otLinkSetChannel would invalidate the datasets. Only the components present are
+                // written back, the newer timestamp has the network adopt the channel once this
+                // device attaches
+                let active = self.get_active_dataset()?;
+                self.set_active_dataset(OperationalDataset {
+                    active_timestamp: Some(next_timestamp(active.active_timestamp)),
+                    channel: Some(channel as u16),
+                    pending_timestamp: None,
+                    delay: None,
+                    ..active
+                })
+            }
+            _ => checked!(unsafe { otLinkSetChannel(self.instance, channel) }),
+        }
+    }
+}
+
+fn next_timestamp(timestamp: Option<ThreadTimestamp>) -> ThreadTimestamp {
+    ThreadTimestamp {
+        seconds: timestamp.map_or(1, |timestamp| timestamp.seconds + 1),
+        ticks: 0,
+        authoritative: false,
+    }
+}
+
+unsafe extern "C" fn migration_callback(result: otError, _context: *mut c_void) {
+    log::debug!("migration_callback {}", result);
+
+    let result = if result == otError_OT_ERROR_NONE {
+        Ok(())
+    } else {
+        Err(Error::InternalError(result))
+    };
+
+    crate::key_rotation::on_migration_result(result);
+
+    critical_section::with(|cs| {
+        let mut callback = MIGRATION_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(result);
+        }
+    });
+}
@@ -0,0 +1,352 @@
+use core::fmt::{self, Write};
+
+use esp_openthread_sys::bindings::{
+    otDeviceRole_OT_DEVICE_ROLE_CHILD, otError_OT_ERROR_NONE, otError_OT_ERROR_NO_BUFS,
+    otLinkGetCounters, otLinkGetExtendedAddress, otRouterInfo, otThreadDeviceRoleToString,
+    otThreadGetDeviceRole, otThreadGetMleCounters, otThreadGetParentAverageRssi,
+    otThreadGetParentInfo, otThreadGetParentLastRssi, otThreadGetPartitionId, otThreadGetRloc16,
+    otThreadGetVersion,
+};
+
+use crate::{Error, OpenThread};
+
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Longest string value, e.g. an IPv6 address or a network name
+const MAX_STRING_SIZE: usize = 48;
+
+/// Deepest nesting of maps and arrays
+const MAX_DEPTH: usize = 4;
+
+/// Encoding of [OpenThread::device_info]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceInfoFormat {
+    /// CBOR (RFC 8949) with indefinite length maps and arrays, e.g. for CoAP content format 60
+    Cbor,
+    /// Compact JSON
+    Json,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Render the state of the device into `buffer`, e.g. as the payload of a CoAP `/diag`
+    /// resource
+    ///
+    /// The result is a map of the role, RLOC16, extended address, partition ID and Thread
+    /// version, the assigned unicast addresses, a summary of the active dataset without network
+    /// key and PSKc, the parent (while being a child) and MAC and MLE counters. Returns the number
+    /// of bytes written, or `OT_ERROR_NO_BUFS` if they don't fit into `buffer`.
+    pub fn device_info(&self, format: DeviceInfoFormat, buffer: &mut [u8]) -> Result<usize, Error> {
+        let mut w = Writer::new(format, buffer);
+        w.map_begin();
+
+        let role = unsafe { otThreadGetDeviceRole(self.instance) };
+        w.key("role");
+        w.string(
+            unsafe { core::ffi::CStr::from_ptr(otThreadDeviceRoleToString(role)) }
+                .to_str()
+                .unwrap_or_default(),
+        );
+        w.key("rloc16");
+        w.unsigned(unsafe { otThreadGetRloc16(self.instance) } as u64);
+        w.key("ext_address");
+        w.display(Hex(&unsafe { *otLinkGetExtendedAddress(self.instance) }.m8));
+        w.key("partition_id");
+        w.unsigned(unsafe { otThreadGetPartitionId(self.instance) } as u64);
+        w.key("thread_version");
+        w.unsigned(unsafe { otThreadGetVersion() } as u64);
+
+        w.key("addresses");
+        w.array_begin();
+        for address in self.unicast_addresses() {
+            w.display(address.address);
+        }
+        w.end();
+
+        w.key("network");
+        match self.get_active_dataset() {
+            Ok(dataset) => {
+                // no network key nor PSKc
+                w.map_begin();
+                if let Some(network_name) = dataset.network_name {
+                    w.key("name");
+                    w.display(network_name);
+                }
+                if let Some(extended_pan_id) = dataset.extended_pan_id {
+                    w.key("ext_pan_id");
+                    w.display(extended_pan_id);
+                }
+                if let Some(pan_id) = dataset.pan_id {
+                    w.key("pan_id");
+                    w.unsigned(pan_id as u64);
+                }
+                if let Some(channel) = dataset.channel {
+                    w.key("channel");
+                    w.unsigned(channel as u64);
+                }
+                if let Some(mesh_local_prefix) = dataset.mesh_local_prefix {
+                    w.key("mesh_local_prefix");
+                    w.display(mesh_local_prefix);
+                }
+                w.end();
+            }
+            Err(_) => w.null(),
+        }
+
+        w.key("parent");
+        let mut parent: otRouterInfo = unsafe { core::mem::zeroed() };
+        if role == otDeviceRole_OT_DEVICE_ROLE_CHILD
+            && unsafe { otThreadGetParentInfo(self.instance, &mut parent) } == otError_OT_ERROR_NONE
+        {
+            w.map_begin();
+            w.key("rloc16");
+            w.unsigned(parent.mRloc16 as u64);
+            w.key("ext_address");
+            w.display(Hex(&parent.mExtAddress.m8));
+            w.key("link_quality_in");
+            w.unsigned(parent.mLinkQualityIn as u64);
+            w.key("age");
+            w.unsigned(parent.mAge as u64);
+            let mut rssi = 0;
+            if unsafe { otThreadGetParentAverageRssi(self.instance, &mut rssi) }
+                == otError_OT_ERROR_NONE
+            {
+                w.key("average_rssi");
+                w.signed(rssi as i64);
+            }
+            if unsafe { otThreadGetParentLastRssi(self.instance, &mut rssi) }
+                == otError_OT_ERROR_NONE
+            {
+                w.key("last_rssi");
+                w.signed(rssi as i64);
+            }
+            w.end();
+        } else {
+            w.null();
+        }
+
+        let mac = unsafe { &*otLinkGetCounters(self.instance) };
+        let mle = unsafe { &*otThreadGetMleCounters(self.instance) };
+        w.key("counters");
+        w.map_begin();
+        for (name, value) in [
+            ("mac_tx_total", mac.mTxTotal),
+            ("mac_tx_retry", mac.mTxRetry),
+            ("mac_tx_err_cca", mac.mTxErrCca),
+            ("mac_tx_err_abort", mac.mTxErrAbort),
+            ("mac_rx_total", mac.mRxTotal),
+            ("mac_rx_err_fcs", mac.mRxErrFcs),
+            ("mle_attach_attempts", mle.mAttachAttempts as u32),
+            ("mle_partition_id_changes", mle.mPartitionIdChanges as u32),
+            ("mle_parent_changes", mle.mParentChanges as u32),
+        ] {
+            w.key(name);
+            w.unsigned(value as u64);
+        }
+        w.end();
+
+        w.end();
+        w.finish()
+    }
+}
+
+/// Bytes as lowercase hex digits
+struct Hex<'h>(&'h [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes maps, arrays and values as CBOR or JSON, remembering if something didn't fit
+struct Writer<'b> {
+    format: DeviceInfoFormat,
+    buffer: &'b mut [u8],
+    len: usize,
+    overflow: bool,
+    /// JSON needs a comma before the next key or value
+    separate: bool,
+    /// How the open JSON maps and arrays are closed, innermost last
+    closing: heapless::Vec<u8, MAX_DEPTH>,
+}
+
+impl<'b> Writer<'b> {
+    fn new(format: DeviceInfoFormat, buffer: &'b mut [u8]) -> Self {
+        Self {
+            format,
+            buffer,
+            len: 0,
+            overflow: false,
+            separate: false,
+            closing: heapless::Vec::new(),
+        }
+    }
+
+    fn finish(self) -> Result<usize, Error> {
+        if self.overflow {
+            Err(NO_BUFS)
+        } else {
+            Ok(self.len)
+        }
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        match self.buffer.get_mut(self.len..self.len + bytes.len()) {
+            Some(target) => {
+                target.copy_from_slice(bytes);
+                self.len += bytes.len();
+            }
+            None => self.overflow = true,
+        }
+    }
+
+    /// CBOR head of `major` type with `argument`
+    fn head(&mut self, major: u8, argument: u64) {
+        let major = major << 5;
+        if argument < 24 {
+            self.put(&[major | argument as u8]);
+        } else if argument <= u8::MAX as u64 {
+            self.put(&[major | 24, argument as u8]);
+        } else if argument <= u16::MAX as u64 {
+            self.put(&[major | 25]);
+            self.put(&(argument as u16).to_be_bytes());
+        } else if argument <= u32::MAX as u64 {
+            self.put(&[major | 26]);
+            self.put(&(argument as u32).to_be_bytes());
+        } else {
+            self.put(&[major | 27]);
+            self.put(&argument.to_be_bytes());
+        }
+    }
+
+    /// Start a JSON value, after a separator if needed
+    fn json_value(&mut self) {
+        if self.separate {
+            self.put(b",");
+        }
+        self.separate = true;
+    }
+
+    fn map_begin(&mut self) {
+        match self.format {
+            DeviceInfoFormat::Cbor => self.put(&[0xbf]),
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.put(b"{");
+                self.separate = false;
+                self.overflow |= self.closing.push(b'}').is_err();
+            }
+        }
+    }
+
+    fn array_begin(&mut self) {
+        match self.format {
+            DeviceInfoFormat::Cbor => self.put(&[0x9f]),
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.put(b"[");
+                self.separate = false;
+                self.overflow |= self.closing.push(b']').is_err();
+            }
+        }
+    }
+
+    /// End the innermost map or array
+    fn end(&mut self) {
+        match self.format {
+            DeviceInfoFormat::Cbor => self.put(&[0xff]),
+            DeviceInfoFormat::Json => {
+                if let Some(closing) = self.closing.pop() {
+                    self.put(&[closing]);
+                }
+                self.separate = true;
+            }
+        }
+    }
+
+    fn key(&mut self, key: &str) {
+        self.string(key);
+        if self.format == DeviceInfoFormat::Json {
+            self.put(b":");
+            self.separate = false;
+        }
+    }
+
+    fn null(&mut self) {
+        match self.format {
+            DeviceInfoFormat::Cbor => self.put(&[0xf6]),
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.put(b"null");
+            }
+        }
+    }
+
+    fn unsigned(&mut self, value: u64) {
+        match self.format {
+            DeviceInfoFormat::Cbor => self.head(0, value),
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.display_raw(value);
+            }
+        }
+    }
+
+    fn signed(&mut self, value: i64) {
+        match self.format {
+            DeviceInfoFormat::Cbor if value < 0 => self.head(1, !(value as u64)),
+            DeviceInfoFormat::Cbor => self.head(0, value as u64),
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.display_raw(value);
+            }
+        }
+    }
+
+    fn string(&mut self, value: &str) {
+        match self.format {
+            DeviceInfoFormat::Cbor => {
+                self.head(3, value.len() as u64);
+                self.put(value.as_bytes());
+            }
+            DeviceInfoFormat::Json => {
+                self.json_value();
+                self.put(b"\"");
+                for c in value.chars() {
+                    match c {
+                        '"' => self.put(b"\\\""),
+                        '\\' => self.put(b"\\\\"),
+                        c if c.is_control() => {
+                            let mut escaped = heapless::String::<6>::new();
+                            // `\u` and 4 digits fit
+                            let _ = write!(escaped, "\\u{:04x}", c as u32);
+                            self.put(escaped.as_bytes());
+                        }
+                        c => self.put(c.encode_utf8(&mut [0u8; 4]).as_bytes()),
+                    }
+                }
+                self.put(b"\"");
+            }
+        }
+    }
+
+    /// A string value rendered via [fmt::Display]
+    fn display(&mut self, value: impl fmt::Display) {
+        let mut string = heapless::String::<MAX_STRING_SIZE>::new();
+        if write!(string, "{}", value).is_err() {
+            self.overflow = true;
+        }
+        self.string(&string);
+    }
+
+    /// A JSON number
+    fn display_raw(&mut self, value: impl fmt::Display) {
+        let mut number = heapless::String::<20>::new();
+        // `u64::MAX` and `i64::MIN` have 20 characters
+        let _ = write!(number, "{}", value);
+        self.put(number.as_bytes());
+    }
+}
@@ -0,0 +1,338 @@
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otDiagIsEnabled, otDiagProcessCmdLine, otError, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_INVALID_COMMAND, otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND,
+        otError_OT_ERROR_NOT_IMPLEMENTED, otGpioMode, otGpioMode_OT_GPIO_MODE_INPUT,
+        otGpioMode_OT_GPIO_MODE_OUTPUT, otInstance, otRadioFrame,
+    },
+    c_types::c_char,
+};
+
+use crate::{
+    checked,
+    radio::{calibrated_tx_power, transmit_power, TRANSMIT_POWER},
+    Error, OpenThread,
+};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Longest command line [OpenThread::diag_process_command_line] accepts, matches OpenThread's
+/// default `OPENTHREAD_CONFIG_DIAG_CMD_LINE_BUFFER_SIZE`
+pub const DIAG_CMD_LINE_MAX_SIZE: usize = 256;
+
+/// The raw power setting is the transmit power (in dBm) handed to the radio driver as is
+const RAW_POWER_SETTING_SIZE: u16 = 1;
+
+/// Set by OpenThread on `diag start` and `diag stop`
+pub(crate) static DIAG_MODE: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
+
+pub(crate) static DIAG_GPIO: Mutex<RefCell<Option<&'static mut (dyn DiagGpio + Send)>>> =
+    Mutex::new(RefCell::new(None));
+
+pub(crate) static RAW_POWER_SETTING: Mutex<Cell<RawPowerSetting>> =
+    Mutex::new(Cell::new(RawPowerSetting::DEFAULT));
+
+#[derive(Clone, Copy)]
+pub(crate) struct RawPowerSetting {
+    power: Option<i8>,
+    enabled: bool,
+}
+
+impl RawPowerSetting {
+    pub(crate) const DEFAULT: Self = Self {
+        power: None,
+        enabled: false,
+    };
+}
+
+/// Mode of a GPIO driven by the `diag gpio` commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpioMode {
+    Input,
+    Output,
+}
+
+/// Board GPIOs the `diag gpio` commands drive, e.g. to toggle a PA enable line or read a test
+/// fixture's strap
+///
+/// GPIO numbers are passed on as given on the command line, the board decides what they map to.
+pub trait DiagGpio {
+    /// Drive `gpio` high or low, `false` if the board doesn't expose it
+    fn set(&mut self, gpio: u32, value: bool) -> bool;
+
+    /// Read the level of `gpio`, `None` if the board doesn't expose it
+    fn get(&mut self, gpio: u32) -> Option<bool>;
+
+    /// Switch `gpio` between input and output, `false` if the board doesn't expose it
+    fn set_mode(&mut self, gpio: u32, mode: GpioMode) -> bool;
+
+    /// Get the mode of `gpio`, `None` if the board doesn't expose it
+    fn get_mode(&mut self, gpio: u32) -> Option<GpioMode>;
+}
+
+impl<'a> OpenThread<'a> {
+    /// Run a factory diagnostics command line, e.g. `diag start`, `diag power 10`,
+    /// `diag powersettings` or `diag gpio set 4 1`
+    ///
+    /// The command's output is written to `output` and returned. While diagnostics are started
+    /// OpenThread drives the radio directly, Thread needs to be disabled for that.
+    pub fn diag_process_command_line<'b>(
+        &mut self,
+        command_line: &str,
+        output: &'b mut [u8],
+    ) -> Result<&'b str, Error> {
+        if command_line.len() >= DIAG_CMD_LINE_MAX_SIZE || output.is_empty() {
+            return Err(INVALID_ARGS);
+        }
+
+        let mut line = [0u8; DIAG_CMD_LINE_MAX_SIZE];
+        line[..command_line.len()].copy_from_slice(command_line.as_bytes());
+        output[0] = 0;
+
+        checked!(unsafe {
+            otDiagProcessCmdLine(
+                self.instance,
+                line.as_ptr() as *const c_char,
+                output.as_mut_ptr() as *mut c_char,
+                output.len(),
+            )
+        })?;
+
+        let len = output.iter().position(|&b| b == 0).unwrap_or(output.len());
+        Ok(core::str::from_utf8(&output[..len]).unwrap_or_default())
+    }
+
+    /// Returns `true` while factory diagnostics are started
+    pub fn diag_is_enabled(&self) -> bool {
+        unsafe { otDiagIsEnabled(self.instance) }
+    }
+
+    /// Set the GPIOs for the `diag gpio` commands, without them the commands fail as not
+    /// implemented
+    pub fn set_diag_gpio(&mut self, gpio: Option<&'a mut (dyn DiagGpio + Send)>) {
+        critical_section::with(|cs| {
+            let mut diag_gpio = DIAG_GPIO.borrow_ref_mut(cs);
+            *diag_gpio = unsafe { core::mem::transmute(gpio) };
+        });
+    }
+}
+
+/// Whether radio and alarm events go to the diagnostics module instead of Thread
+pub(crate) fn is_diag_mode() -> bool {
+    critical_section::with(|cs| DIAG_MODE.borrow(cs).get())
+}
+
+/// Transmit power set via `diag rawpowersetting`, if enabled
+pub(crate) fn raw_tx_power() -> Option<i8> {
+    let raw_power_setting = critical_section::with(|cs| RAW_POWER_SETTING.borrow(cs).get());
+    raw_power_setting
+        .enabled
+        .then_some(raw_power_setting.power)
+        .flatten()
+}
+
+fn with_gpio(f: impl FnOnce(&mut dyn DiagGpio) -> bool) -> otError {
+    critical_section::with(|cs| {
+        let mut gpio = DIAG_GPIO.borrow_ref_mut(cs);
+        let Some(gpio) = gpio.as_mut() else {
+            return otError_OT_ERROR_NOT_IMPLEMENTED;
+        };
+
+        if f(&mut **gpio) {
+            otError_OT_ERROR_NONE
+        } else {
+            otError_OT_ERROR_INVALID_ARGS
+        }
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagModeSet(mode: bool) {
+    log::info!("otPlatDiagModeSet {mode}");
+    critical_section::with(|cs| DIAG_MODE.borrow(cs).set(mode));
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagModeGet() -> bool {
+    is_diag_mode()
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagChannelSet(channel: u8) {
+    // OpenThread passes the channel along with every transmit and receive
+    log::info!("otPlatDiagChannelSet {channel}");
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagTxPowerSet(tx_power: i8) {
+    log::info!("otPlatDiagTxPowerSet {tx_power}");
+    critical_section::with(|cs| TRANSMIT_POWER.borrow(cs).set(Some(tx_power)));
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagRadioReceived(
+    _instance: *mut otInstance,
+    _frame: *mut otRadioFrame,
+    _error: otError,
+) {
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagAlarmCallback(_instance: *mut otInstance) {}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagProcess(
+    _instance: *mut otInstance,
+    _args_length: u8,
+    _args: *mut *mut c_char,
+    _output: *mut c_char,
+    _output_max_len: usize,
+) -> otError {
+    // no platform specific commands
+    otError_OT_ERROR_INVALID_COMMAND
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagGpioSet(gpio: u32, value: bool) -> otError {
+    log::debug!("otPlatDiagGpioSet {gpio} {value}");
+    with_gpio(|diag_gpio| diag_gpio.set(gpio, value))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatDiagGpioGet(gpio: u32, value: *mut bool) -> otError {
+    log::debug!("otPlatDiagGpioGet {gpio}");
+    with_gpio(|diag_gpio| {
+        diag_gpio
+            .get(gpio)
+            .map(|level| unsafe { *value = level })
+            .is_some()
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagGpioSetMode(gpio: u32, mode: otGpioMode) -> otError {
+    log::debug!("otPlatDiagGpioSetMode {gpio} {mode}");
+
+    #[allow(non_upper_case_globals)]
+    let mode = match mode {
+        otGpioMode_OT_GPIO_MODE_INPUT => GpioMode::Input,
+        otGpioMode_OT_GPIO_MODE_OUTPUT => GpioMode::Output,
+        _ => return otError_OT_ERROR_INVALID_ARGS,
+    };
+    with_gpio(|diag_gpio| diag_gpio.set_mode(gpio, mode))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatDiagGpioGetMode(gpio: u32, mode: *mut otGpioMode) -> otError {
+    log::debug!("otPlatDiagGpioGetMode {gpio}");
+    with_gpio(|diag_gpio| {
+        diag_gpio
+            .get_mode(gpio)
+            .map(|gpio_mode| unsafe {
+                *mode = match gpio_mode {
+                    GpioMode::Input => otGpioMode_OT_GPIO_MODE_INPUT,
+                    GpioMode::Output => otGpioMode_OT_GPIO_MODE_OUTPUT,
+                }
+            })
+            .is_some()
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatDiagRadioSetRawPowerSetting(
+    _instance: *mut otInstance,
+    raw_power_setting: *const u8,
+    raw_power_setting_length: u16,
+) -> otError {
+    if raw_power_setting.is_null() || raw_power_setting_length != RAW_POWER_SETTING_SIZE {
+        return otError_OT_ERROR_INVALID_ARGS;
+    }
+
+    let power = *raw_power_setting as i8;
+    log::info!("otPlatDiagRadioSetRawPowerSetting {power}");
+    critical_section::with(|cs| {
+        let cell = RAW_POWER_SETTING.borrow(cs);
+        cell.set(RawPowerSetting {
+            power: Some(power),
+            ..cell.get()
+        });
+    });
+
+    otError_OT_ERROR_NONE
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatDiagRadioGetRawPowerSetting(
+    _instance: *mut otInstance,
+    raw_power_setting: *mut u8,
+    raw_power_setting_length: *mut u16,
+) -> otError {
+    if raw_power_setting.is_null()
+        || raw_power_setting_length.is_null()
+        || *raw_power_setting_length < RAW_POWER_SETTING_SIZE
+    {
+        return otError_OT_ERROR_INVALID_ARGS;
+    }
+
+    let power = critical_section::with(|cs| RAW_POWER_SETTING.borrow(cs).get().power);
+    let Some(power) = power else {
+        return otError_OT_ERROR_NOT_FOUND;
+    };
+
+    *raw_power_setting = power as u8;
+    *raw_power_setting_length = RAW_POWER_SETTING_SIZE;
+    otError_OT_ERROR_NONE
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatDiagRadioRawPowerSettingEnable(
+    _instance: *mut otInstance,
+    enable: bool,
+) -> otError {
+    log::info!("otPlatDiagRadioRawPowerSettingEnable {enable}");
+    critical_section::with(|cs| {
+        let cell = RAW_POWER_SETTING.borrow(cs);
+        cell.set(RawPowerSetting {
+            enabled: enable,
+            ..cell.get()
+        });
+    });
+
+    otError_OT_ERROR_NONE
+}
+
+/// Reports the power for each channel on `diag powersettings`: the target is the power set via
+/// `diag power` (or the driver's default), the actual power has the channel's calibration
+/// applied and is also the raw setting handed to the driver
+#[no_mangle]
+pub unsafe extern "C" fn otPlatDiagRadioGetPowerSettings(
+    _instance: *mut otInstance,
+    channel: u8,
+    target_power: *mut i16,
+    actual_power: *mut i16,
+    raw_power_setting: *mut u8,
+    raw_power_setting_length: *mut u16,
+) -> otError {
+    if target_power.is_null()
+        || actual_power.is_null()
+        || raw_power_setting.is_null()
+        || raw_power_setting_length.is_null()
+        || *raw_power_setting_length < RAW_POWER_SETTING_SIZE
+    {
+        return otError_OT_ERROR_INVALID_ARGS;
+    }
+
+    let target = transmit_power();
+    let actual = calibrated_tx_power(channel);
+
+    // in 0.01 dBm
+    *target_power = target as i16 * 100;
+    *actual_power = actual as i16 * 100;
+    *raw_power_setting = actual as u8;
+    *raw_power_setting_length = RAW_POWER_SETTING_SIZE;
+
+    otError_OT_ERROR_NONE
+}
@@ -0,0 +1,291 @@
+use core::{
+    cell::RefCell,
+    future::poll_fn,
+    task::{Poll, Waker},
+};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otDnsAddressResponse, otDnsAddressResponseGetAddress, otDnsClientGetDefaultConfig,
+        otDnsClientResolveAddress, otDnsClientSetDefaultConfig, otDnsNat64Mode,
+        otDnsNat64Mode_OT_DNS_NAT64_ALLOW, otDnsNat64Mode_OT_DNS_NAT64_DISALLOW,
+        otDnsNat64Mode_OT_DNS_NAT64_UNSPECIFIED, otDnsQueryConfig, otDnsRecursionFlag,
+        otDnsRecursionFlag_OT_DNS_FLAG_NO_RECURSION,
+        otDnsRecursionFlag_OT_DNS_FLAG_RECURSION_DESIRED,
+        otDnsRecursionFlag_OT_DNS_FLAG_UNSPECIFIED, otError, otError_OT_ERROR_BUSY,
+        otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE, otInstance, otIp6Address, otSockAddr,
+        OT_DNS_MAX_NAME_SIZE,
+    },
+    c_types::{c_char, c_void},
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{
+    checked,
+    ip6::{ipv6_addr_from_ot_ip6_address, ot_ip6_address_from_ipv6_addr},
+    Error, OpenThread,
+};
+
+const BUSY: Error = Error::InternalError(otError_OT_ERROR_BUSY);
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Maximum number of addresses kept from a response to
+/// [OpenThread::dns_client_resolve_address]
+pub const DNS_CLIENT_MAX_ADDRESSES: usize = 8;
+
+pub(crate) static DNS_RESOLVE: Mutex<RefCell<DnsResolve>> =
+    Mutex::new(RefCell::new(DnsResolve::DEFAULT));
+
+/// The address resolution in flight, OpenThread reports it from [OpenThread::process]
+pub(crate) struct DnsResolve {
+    pending: bool,
+    result: Option<Result<heapless::Vec<Ipv6Addr, DNS_CLIENT_MAX_ADDRESSES>, Error>>,
+    waker: Option<Waker>,
+}
+
+impl DnsResolve {
+    pub(crate) const DEFAULT: Self = Self {
+        pending: false,
+        result: None,
+        waker: None,
+    };
+}
+
+/// Whether the DNS server is asked to resolve queries recursively
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DnsRecursion {
+    /// Use the OpenThread default
+    #[default]
+    Unspecified,
+    Desired,
+    NotDesired,
+}
+
+impl From<otDnsRecursionFlag> for DnsRecursion {
+    #[allow(non_upper_case_globals)]
+    fn from(value: otDnsRecursionFlag) -> Self {
+        match value {
+            otDnsRecursionFlag_OT_DNS_FLAG_RECURSION_DESIRED => DnsRecursion::Desired,
+            otDnsRecursionFlag_OT_DNS_FLAG_NO_RECURSION => DnsRecursion::NotDesired,
+            _ => DnsRecursion::Unspecified,
+        }
+    }
+}
+
+impl From<DnsRecursion> for otDnsRecursionFlag {
+    fn from(value: DnsRecursion) -> Self {
+        match value {
+            DnsRecursion::Unspecified => otDnsRecursionFlag_OT_DNS_FLAG_UNSPECIFIED,
+            DnsRecursion::Desired => otDnsRecursionFlag_OT_DNS_FLAG_RECURSION_DESIRED,
+            DnsRecursion::NotDesired => otDnsRecursionFlag_OT_DNS_FLAG_NO_RECURSION,
+        }
+    }
+}
+
+/// Whether IPv4 addresses in responses may be translated to IPv6 via the NAT64 prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DnsNat64Mode {
+    /// Use the OpenThread default
+    #[default]
+    Unspecified,
+    Allow,
+    Disallow,
+}
+
+impl From<otDnsNat64Mode> for DnsNat64Mode {
+    #[allow(non_upper_case_globals)]
+    fn from(value: otDnsNat64Mode) -> Self {
+        match value {
+            otDnsNat64Mode_OT_DNS_NAT64_ALLOW => DnsNat64Mode::Allow,
+            otDnsNat64Mode_OT_DNS_NAT64_DISALLOW => DnsNat64Mode::Disallow,
+            _ => DnsNat64Mode::Unspecified,
+        }
+    }
+}
+
+impl From<DnsNat64Mode> for otDnsNat64Mode {
+    fn from(value: DnsNat64Mode) -> Self {
+        match value {
+            DnsNat64Mode::Unspecified => otDnsNat64Mode_OT_DNS_NAT64_UNSPECIFIED,
+            DnsNat64Mode::Allow => otDnsNat64Mode_OT_DNS_NAT64_ALLOW,
+            DnsNat64Mode::Disallow => otDnsNat64Mode_OT_DNS_NAT64_DISALLOW,
+        }
+    }
+}
+
+/// Configuration used by the DNS client for queries
+///
+/// Unspecified (zero / `None`) values are replaced by OpenThread's build time defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DnsQueryConfig {
+    /// Address and port of the DNS server (e.g. a DNS64 resolver)
+    pub server: Option<(Ipv6Addr, u16)>,
+    /// Time to wait for a response (in milliseconds)
+    pub response_timeout: u32,
+    /// Maximum number of transmissions before a query fails
+    pub max_tx_attempts: u8,
+    pub recursion: DnsRecursion,
+    pub nat64_mode: DnsNat64Mode,
+}
+
+impl From<&otDnsQueryConfig> for DnsQueryConfig {
+    fn from(value: &otDnsQueryConfig) -> Self {
+        let address = ipv6_addr_from_ot_ip6_address(&value.mServerSockAddr.mAddress);
+        let port = value.mServerSockAddr.mPort;
+
+        Self {
+            server: if address.is_unspecified() || port == 0 {
+                None
+            } else {
+                Some((address, port))
+            },
+            response_timeout: value.mResponseTimeout,
+            max_tx_attempts: value.mMaxTxAttempts,
+            recursion: value.mRecursionFlag.into(),
+            nat64_mode: value.mNat64Mode.into(),
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the configuration used for DNS queries
+    ///
+    /// `None` restores OpenThread's build time defaults. Queries already in progress keep
+    /// their configuration.
+    pub fn dns_client_set_default_config(&mut self, config: Option<&DnsQueryConfig>) {
+        let Some(config) = config else {
+            unsafe { otDnsClientSetDefaultConfig(self.instance, core::ptr::null()) };
+            return;
+        };
+
+        let raw_config = raw_config(self.instance, config);
+        unsafe { otDnsClientSetDefaultConfig(self.instance, &raw_config) };
+    }
+
+    /// Get the configuration used for DNS queries
+    pub fn dns_client_get_default_config(&self) -> DnsQueryConfig {
+        unsafe { &*otDnsClientGetDefaultConfig(self.instance) }.into()
+    }
+
+    /// Resolve the IPv6 addresses (AAAA records) of `host_name`, e.g. `example.com` via the DNS
+    /// resolver of a border router
+    ///
+    /// Returns up to `N` (at most [DNS_CLIENT_MAX_ADDRESSES]) addresses, IPv4 ones translated
+    /// via the NAT64 prefix if the configuration allows it. `config` overrides the default
+    /// configuration for this query. Needs OpenThread driven concurrently, e.g. by
+    /// [OpenThread::run]. One resolution runs at a time, fails with `OT_ERROR_BUSY` while
+    /// another one is in flight, also one whose future was dropped. The outcome of the query,
+    /// e.g. `OT_ERROR_NOT_FOUND` for an unknown name or `OT_ERROR_RESPONSE_TIMEOUT`, is
+    /// returned as error.
+    pub async fn dns_client_resolve_address<const N: usize>(
+        &self,
+        host_name: &str,
+        config: Option<&DnsQueryConfig>,
+    ) -> Result<heapless::Vec<Ipv6Addr, N>, Error> {
+        // room for the terminating NUL
+        let mut raw_name = [0u8; OT_DNS_MAX_NAME_SIZE as usize];
+        if host_name.is_empty()
+            || host_name.len() >= raw_name.len()
+            || host_name.as_bytes().contains(&0)
+        {
+            return Err(INVALID_ARGS);
+        }
+        raw_name[..host_name.len()].copy_from_slice(host_name.as_bytes());
+        let raw_config = config.map(|config| raw_config(self.instance, config));
+
+        critical_section::with(|cs| {
+            let mut resolve = DNS_RESOLVE.borrow_ref_mut(cs);
+            if resolve.pending {
+                return Err(BUSY);
+            }
+            resolve.pending = true;
+            resolve.result = None;
+            Ok(())
+        })?;
+
+        // OpenThread copies the name and the configuration into the query
+        let res = unsafe {
+            otDnsClientResolveAddress(
+                self.instance,
+                raw_name.as_ptr() as *const c_char,
+                Some(dns_address_callback),
+                core::ptr::null_mut(),
+                raw_config.as_ref().map_or(core::ptr::null(), |config| {
+                    config as *const otDnsQueryConfig
+                }),
+            )
+        };
+        if res != otError_OT_ERROR_NONE {
+            critical_section::with(|cs| DNS_RESOLVE.borrow_ref_mut(cs).pending = false);
+            return checked!(res).map(|_| heapless::Vec::new());
+        }
+
+        let addresses = poll_fn(|cx| {
+            critical_section::with(|cs| {
+                let mut resolve = DNS_RESOLVE.borrow_ref_mut(cs);
+                match resolve.result.take() {
+                    Some(result) => Poll::Ready(result),
+                    None => {
+                        resolve.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            })
+        })
+        .await?;
+
+        Ok(addresses.into_iter().take(N).collect())
+    }
+}
+
+/// The default configuration with `config` applied
+fn raw_config(instance: *mut otInstance, config: &DnsQueryConfig) -> otDnsQueryConfig {
+    // keep the service resolution mode and transport, they aren't configurable here
+    let mut raw_config = unsafe { *otDnsClientGetDefaultConfig(instance) };
+    let (address, port) = config.server.unwrap_or((Ipv6Addr::UNSPECIFIED, 0));
+    raw_config.mServerSockAddr = otSockAddr {
+        mAddress: ot_ip6_address_from_ipv6_addr(address),
+        mPort: port,
+    };
+    raw_config.mResponseTimeout = config.response_timeout;
+    raw_config.mMaxTxAttempts = config.max_tx_attempts;
+    raw_config.mRecursionFlag = config.recursion.into();
+    raw_config.mNat64Mode = config.nat64_mode.into();
+    raw_config
+}
+
+unsafe extern "C" fn dns_address_callback(
+    error: otError,
+    response: *const otDnsAddressResponse,
+    _context: *mut c_void,
+) {
+    log::debug!("dns_address_callback {}", error);
+
+    let result = checked!(error).map(|_| {
+        let mut addresses = heapless::Vec::new();
+        let mut address: otIp6Address = core::mem::zeroed();
+        for index in 0..DNS_CLIENT_MAX_ADDRESSES as u16 {
+            let res = otDnsAddressResponseGetAddress(
+                response,
+                index,
+                &mut address,
+                core::ptr::null_mut(),
+            );
+            if res != otError_OT_ERROR_NONE {
+                break;
+            }
+            let _ = addresses.push(ipv6_addr_from_ot_ip6_address(&address));
+        }
+        addresses
+    });
+
+    critical_section::with(|cs| {
+        let mut resolve = DNS_RESOLVE.borrow_ref_mut(cs);
+        resolve.pending = false;
+        resolve.result = Some(result);
+        if let Some(waker) = resolve.waker.take() {
+            waker.wake();
+        }
+    });
+}
@@ -0,0 +1,74 @@
+use esp_hal::systimer::{Alarm, Target};
+use esp_ieee802154::Ieee802154;
+
+use crate::{InterruptPriorities, OpenThread, TimerAlarm};
+
+/// Coexistence settings of the Thread side of a [NetStack]
+///
+/// The Wi-Fi and 802.15.4 radios share the antenna, the arbitration between them is done by
+/// `esp-wifi`, which needs its `coex` feature enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct CoexConfig {
+    /// Maximum number of frame retries during direct transmission, frames lost while Wi-Fi holds
+    /// the antenna are retried rather than dropped
+    pub max_frame_retries_direct: u8,
+    /// Interrupt priorities of OpenThread
+    pub priorities: InterruptPriorities,
+}
+
+impl Default for CoexConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_retries_direct: 6,
+            priorities: InterruptPriorities::default(),
+        }
+    }
+}
+
+/// Wi-Fi and Thread running side by side on the ESP32-C6
+///
+/// `W` is whatever the application gets from initializing `esp-wifi`, e.g. its controller and
+/// interfaces.
+pub struct NetStack<'a, W> {
+    pub wifi: W,
+    pub thread: OpenThread<'a>,
+}
+
+impl<'a, W> NetStack<'a, W> {
+    /// Bring up Wi-Fi and Thread in the order they need
+    ///
+    /// `init_wifi` runs first with a copy of `rng`, it is expected to call `esp_wifi::initialize`
+    /// with systimer alarm 0 and set up the Wi-Fi interfaces. OpenThread is initialized
+    /// afterwards on systimer alarm `N` with the same RNG and the coexistence settings applied.
+    /// Wi-Fi failing to start is returned as is, OpenThread isn't initialized then.
+    ///
+    /// `esp-wifi` drives its own timers from alarm 0, so `N` has to be 1 or 2.
+    pub fn new<E, const N: u8>(
+        init_wifi: impl FnOnce(esp_hal::Rng) -> Result<W, E>,
+        radio: &'a mut Ieee802154,
+        timer: Alarm<Target, N>,
+        rng: esp_hal::Rng,
+        config: CoexConfig,
+    ) -> Result<Self, E>
+    where
+        Alarm<Target, N>: TimerAlarm,
+    {
+        // `Rng` is a `Copy` handle on the hardware generator, both stacks can use it
+        let wifi = init_wifi(rng)?;
+
+        let mut thread = OpenThread::new_with_priorities(radio, timer, rng, config.priorities);
+        thread.link_set_max_frame_retries_direct(config.max_frame_retries_direct);
+
+        Ok(Self { wifi, thread })
+    }
+
+    /// Run OpenThread's timers, received frames and tasklets, Wi-Fi is driven by `esp-wifi`
+    pub fn process(&self) {
+        self.thread.process();
+        self.thread.run_tasklets();
+    }
+
+    pub fn into_parts(self) -> (W, OpenThread<'a>) {
+        (self.wifi, self.thread)
+    }
+}
@@ -0,0 +1,145 @@
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_INVALID_ARGS, otPlatCryptoEcdsaGenerateKey, otPlatCryptoEcdsaGetPublicKey,
+    otPlatCryptoEcdsaKeyPair, otPlatCryptoEcdsaPublicKey, otPlatCryptoEcdsaSign,
+    otPlatCryptoEcdsaSignature, otPlatCryptoEcdsaVerify, otPlatCryptoSha256Hash,
+    OT_CRYPTO_ECDSA_MAX_DER_SIZE, OT_CRYPTO_ECDSA_PUBLIC_KEY_SIZE, OT_CRYPTO_ECDSA_SIGNATURE_SIZE,
+    OT_CRYPTO_SHA256_HASH_SIZE,
+};
+#[cfg(feature = "ecdsa-key-references")]
+use esp_openthread_sys::bindings::{
+    otPlatCryptoEcdsaExportPublicKey, otPlatCryptoEcdsaGenerateAndImportKey,
+    otPlatCryptoEcdsaSignUsingKeyRef,
+};
+
+use crate::{checked, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// SHA-256 hash of the data to be signed
+pub type Sha256Hash = [u8; OT_CRYPTO_SHA256_HASH_SIZE as usize];
+
+/// P-256 public key as uncompressed curve point (without the leading `0x04`)
+pub type EcdsaPublicKey = [u8; OT_CRYPTO_ECDSA_PUBLIC_KEY_SIZE as usize];
+
+/// P-256 signature as concatenated `r` and `s`
+pub type EcdsaSignature = [u8; OT_CRYPTO_ECDSA_SIGNATURE_SIZE as usize];
+
+/// P-256 key pair, held in RAM in DER format (RFC 5915)
+#[derive(Clone, Copy)]
+pub struct EcdsaKeyPair(otPlatCryptoEcdsaKeyPair);
+
+impl EcdsaKeyPair {
+    /// Use a DER encoded (RFC 5915) key pair, e.g. loaded from flash
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        if der.is_empty() || der.len() > OT_CRYPTO_ECDSA_MAX_DER_SIZE as usize {
+            return Err(INVALID_ARGS);
+        }
+
+        let mut key_pair = otPlatCryptoEcdsaKeyPair {
+            mDerBytes: [0u8; OT_CRYPTO_ECDSA_MAX_DER_SIZE as usize],
+            mDerLength: der.len() as u8,
+        };
+        key_pair.mDerBytes[..der.len()].copy_from_slice(der);
+
+        Ok(Self(key_pair))
+    }
+
+    /// DER encoding (RFC 5915) of the key pair, e.g. to persist it
+    pub fn as_der(&self) -> &[u8] {
+        &self.0.mDerBytes[..self.0.mDerLength as usize]
+    }
+}
+
+impl core::fmt::Debug for EcdsaKeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EcdsaKeyPair").finish_non_exhaustive()
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Generate a new P-256 key pair using OpenThread's crypto backend
+    pub fn ecdsa_generate_key_pair(&self) -> Result<EcdsaKeyPair, Error> {
+        let mut key_pair = otPlatCryptoEcdsaKeyPair {
+            mDerBytes: [0u8; OT_CRYPTO_ECDSA_MAX_DER_SIZE as usize],
+            mDerLength: 0,
+        };
+        checked!(unsafe { otPlatCryptoEcdsaGenerateKey(&mut key_pair) })?;
+
+        Ok(EcdsaKeyPair(key_pair))
+    }
+
+    /// Get the public key of a key pair
+    pub fn ecdsa_public_key(&self, key_pair: &EcdsaKeyPair) -> Result<EcdsaPublicKey, Error> {
+        let mut public_key = otPlatCryptoEcdsaPublicKey {
+            m8: [0u8; OT_CRYPTO_ECDSA_PUBLIC_KEY_SIZE as usize],
+        };
+        checked!(unsafe { otPlatCryptoEcdsaGetPublicKey(&key_pair.0, &mut public_key) })?;
+
+        Ok(public_key.m8)
+    }
+
+    /// Sign a SHA-256 hash (deterministic ECDSA, RFC 6979)
+    pub fn ecdsa_sign(
+        &self,
+        key_pair: &EcdsaKeyPair,
+        hash: &Sha256Hash,
+    ) -> Result<EcdsaSignature, Error> {
+        let hash = otPlatCryptoSha256Hash { m8: *hash };
+        let mut signature = new_signature();
+        checked!(unsafe { otPlatCryptoEcdsaSign(&key_pair.0, &hash, &mut signature) })?;
+
+        Ok(signature.m8)
+    }
+
+    /// Verify the signature of a SHA-256 hash
+    pub fn ecdsa_verify(
+        &self,
+        public_key: &EcdsaPublicKey,
+        hash: &Sha256Hash,
+        signature: &EcdsaSignature,
+    ) -> Result<(), Error> {
+        let public_key = otPlatCryptoEcdsaPublicKey { m8: *public_key };
+        let hash = otPlatCryptoSha256Hash { m8: *hash };
+        let signature = otPlatCryptoEcdsaSignature { m8: *signature };
+        checked!(unsafe { otPlatCryptoEcdsaVerify(&public_key, &hash, &signature) })
+    }
+
+    /// Generate a new P-256 key pair in the key storage slot `key_ref`
+    ///
+    /// The private key never leaves the key storage.
+    #[cfg(feature = "ecdsa-key-references")]
+    pub fn ecdsa_generate_key_ref(&self, key_ref: u32) -> Result<(), Error> {
+        checked!(unsafe { otPlatCryptoEcdsaGenerateAndImportKey(key_ref) })
+    }
+
+    /// Get the public key of the key pair in the key storage slot `key_ref`
+    #[cfg(feature = "ecdsa-key-references")]
+    pub fn ecdsa_key_ref_public_key(&self, key_ref: u32) -> Result<EcdsaPublicKey, Error> {
+        let mut public_key = otPlatCryptoEcdsaPublicKey {
+            m8: [0u8; OT_CRYPTO_ECDSA_PUBLIC_KEY_SIZE as usize],
+        };
+        checked!(unsafe { otPlatCryptoEcdsaExportPublicKey(key_ref, &mut public_key) })?;
+
+        Ok(public_key.m8)
+    }
+
+    /// Sign a SHA-256 hash with the key pair in the key storage slot `key_ref`
+    #[cfg(feature = "ecdsa-key-references")]
+    pub fn ecdsa_sign_with_key_ref(
+        &self,
+        key_ref: u32,
+        hash: &Sha256Hash,
+    ) -> Result<EcdsaSignature, Error> {
+        let hash = otPlatCryptoSha256Hash { m8: *hash };
+        let mut signature = new_signature();
+        checked!(unsafe { otPlatCryptoEcdsaSignUsingKeyRef(key_ref, &hash, &mut signature) })?;
+
+        Ok(signature.m8)
+    }
+}
+
+fn new_signature() -> otPlatCryptoEcdsaSignature {
+    otPlatCryptoEcdsaSignature {
+        m8: [0u8; OT_CRYPTO_ECDSA_SIGNATURE_SIZE as usize],
+    }
+}
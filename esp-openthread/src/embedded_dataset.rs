@@ -0,0 +1,170 @@
+use esp_openthread_sys::bindings::{
+    otDatasetSetActiveTlvs, otError_OT_ERROR_INVALID_ARGS, otOperationalDatasetTlvs,
+    OT_OPERATIONAL_DATASET_MAX_LENGTH,
+};
+
+use crate::{checked, dataset::validate_tlvs, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+const DATASET_MAX_LENGTH: usize = OT_OPERATIONAL_DATASET_MAX_LENGTH as usize;
+
+/// Embed an Active Operational Dataset in the firmware at build time, see [EmbeddedDataset]
+///
+/// Takes the dataset TLVs as hex digits, e.g. the output of `ot-ctl dataset active -x` passed in
+/// via `env!`. With `xor = key` the TLVs are stored XORed with `key`, which has to be passed to
+/// [OpenThread::load_embedded_dataset] as [DatasetTransform::Xor]. Malformed hex fails the build.
+///
+/// ```ignore
+/// static DATASET: EmbeddedDataset = embed_dataset!(env!("OT_DATASET"), xor = b"not in flash");
+/// ```
+#[macro_export]
+macro_rules! embed_dataset {
+    ($hex:expr) => {{
+        const DATASET: $crate::EmbeddedDataset = $crate::EmbeddedDataset::from_hex($hex, &[]);
+        DATASET
+    }};
+    ($hex:expr, xor = $key:expr) => {{
+        const DATASET: $crate::EmbeddedDataset = $crate::EmbeddedDataset::from_hex($hex, $key);
+        DATASET
+    }};
+}
+
+/// Dataset TLVs embedded at build time via [embed_dataset], optionally obfuscated
+///
+/// The XOR transform only keeps the network key from showing up in a plain flash dump. For real
+/// protection embed the TLVs encrypted by a key only the device knows (e.g. derived via the eFuse
+/// HMAC peripheral) and decrypt them in a [DatasetTransform::Custom].
+#[derive(Clone, Copy)]
+pub struct EmbeddedDataset {
+    tlvs: [u8; DATASET_MAX_LENGTH],
+    len: u8,
+}
+
+impl EmbeddedDataset {
+    /// Parse `hex` at compile time and XOR the TLVs with `key`, repeated as needed, unless empty
+    ///
+    /// Used by [embed_dataset], panics on malformed hex or more than 254 bytes of TLVs.
+    pub const fn from_hex(hex: &str, key: &[u8]) -> Self {
+        let hex = hex.as_bytes();
+        let len = hex.len() / 2;
+        if len * 2 != hex.len() || len > DATASET_MAX_LENGTH {
+            panic!("dataset TLVs need an even number of hex digits, at most 508");
+        }
+
+        let mut tlvs = [0u8; DATASET_MAX_LENGTH];
+        let mut i = 0;
+        while i < len {
+            let mut byte = (hex_digit(hex[i * 2]) << 4) | hex_digit(hex[i * 2 + 1]);
+            if !key.is_empty() {
+                byte ^= key[i % key.len()];
+            }
+            tlvs[i] = byte;
+            i += 1;
+        }
+
+        Self {
+            tlvs,
+            len: len as u8,
+        }
+    }
+
+    /// Number of bytes of TLVs
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl core::fmt::Debug for EmbeddedDataset {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EmbeddedDataset")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Undoes the obfuscation of an [EmbeddedDataset] before it's used
+pub enum DatasetTransform<'t> {
+    /// The TLVs were embedded as they are
+    None,
+    /// The TLVs were embedded with `xor = key`, e.g. a key read from eFuse at runtime
+    Xor(&'t [u8]),
+    /// Turn the embedded bytes into plain TLVs in place, e.g. by decrypting them
+    ///
+    /// Returns the length of the plain TLVs, which may be shorter, or `None` if that fails.
+    Custom(&'t mut dyn FnMut(&mut [u8]) -> Option<usize>),
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the dataset embedded via [embed_dataset] as Active Operational Dataset
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` if the TLVs don't parse after the transform (e.g. the
+    /// key was wrong) or carry a security policy which doesn't pass
+    /// [crate::SecurityPolicy::validate]. The plain TLVs are wiped from the stack afterwards.
+    pub fn load_embedded_dataset(
+        &mut self,
+        dataset: &EmbeddedDataset,
+        transform: DatasetTransform,
+    ) -> Result<(), Error> {
+        let mut tlvs = otOperationalDatasetTlvs {
+            mTlvs: dataset.tlvs,
+            mLength: dataset.len,
+        };
+
+        let result = set_active_tlvs(self, &mut tlvs, transform);
+
+        // don't leave the network key on the stack
+        unsafe {
+            core::ptr::write_volatile(
+                &mut tlvs,
+                otOperationalDatasetTlvs {
+                    mTlvs: [0u8; DATASET_MAX_LENGTH],
+                    mLength: 0,
+                },
+            )
+        };
+        result
+    }
+}
+
+fn set_active_tlvs(
+    ot: &mut OpenThread,
+    tlvs: &mut otOperationalDatasetTlvs,
+    transform: DatasetTransform,
+) -> Result<(), Error> {
+    let len = tlvs.mLength as usize;
+    match transform {
+        DatasetTransform::None => (),
+        DatasetTransform::Xor(key) => {
+            if !key.is_empty() {
+                for (i, byte) in tlvs.mTlvs[..len].iter_mut().enumerate() {
+                    *byte ^= key[i % key.len()];
+                }
+            }
+        }
+        DatasetTransform::Custom(transform) => {
+            let len = transform(&mut tlvs.mTlvs[..len]).ok_or(INVALID_ARGS)?;
+            if len > DATASET_MAX_LENGTH {
+                return Err(INVALID_ARGS);
+            }
+            tlvs.mLength = len as u8;
+        }
+    }
+
+    validate_tlvs(tlvs)?;
+
+    checked!(unsafe { otDatasetSetActiveTlvs(ot.instance, tlvs) })
+}
+
+const fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        b'A'..=b'F' => digit - b'A' + 10,
+        _ => panic!("dataset TLVs contain a character which is not a hex digit"),
+    }
+}
@@ -13,6 +13,8 @@ pub fn init_rng(rng: Rng) {
 #[no_mangle]
 pub extern "C" fn otPlatEntropyGet(output: *mut u8, len: u16) -> otError {
     log::trace!("otPlatEntropyGet");
+    #[cfg(feature = "instrumentation")]
+    crate::health::sample_stack();
     unsafe {
         let rng = crate::entropy::RANDOM_GENERATOR.as_mut().unwrap();
 
@@ -0,0 +1,137 @@
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole_OT_DEVICE_ROLE_CHILD, otError_OT_ERROR_INVALID_STATE, otInstance,
+    otLinkGetPollPeriod, otLinkSendDataRequest, otLinkSetPollPeriod, otThreadGetDeviceRole,
+    otThreadGetLinkMode,
+};
+use fugit::MillisDurationU64;
+
+use crate::{checked, timer::current_millis, ChangedFlags, Error, OpenThread};
+
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// Poll period (in milliseconds) used by [OpenThread::fast_poll_for]
+pub const FAST_POLL_PERIOD: u32 = 100;
+
+pub(crate) static FAST_POLL: Mutex<Cell<FastPoll>> = Mutex::new(Cell::new(FastPoll::DEFAULT));
+
+#[derive(Clone, Copy)]
+pub(crate) struct FastPoll {
+    /// milliseconds, 0 for OpenThread's default derived from the child timeout
+    user_period: u32,
+    /// milliseconds
+    until: Option<u64>,
+}
+
+impl FastPoll {
+    pub(crate) const DEFAULT: Self = Self {
+        user_period: 0,
+        until: None,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the poll period (in milliseconds) of a sleepy end device, 0 for OpenThread's default
+    ///
+    /// While fast polling the period takes effect once [OpenThread::fast_poll_for] ends.
+    pub fn link_set_poll_period(&mut self, period: u32) -> Result<(), Error> {
+        let fast = critical_section::with(|cs| FAST_POLL.borrow(cs).get().until.is_some());
+        if !fast {
+            checked!(unsafe { otLinkSetPollPeriod(self.instance, period) })?;
+        }
+
+        update(|fast_poll| fast_poll.user_period = period);
+        Ok(())
+    }
+
+    /// Get the poll period (in milliseconds) currently in use
+    pub fn link_get_poll_period(&self) -> u32 {
+        unsafe { otLinkGetPollPeriod(self.instance) }
+    }
+
+    /// Poll the parent every [FAST_POLL_PERIOD] for `duration`, e.g. while waiting for the
+    /// response to a request
+    ///
+    /// A data request is sent right away. Calling it again while fast polling extends the
+    /// duration if it ends later. Afterwards, or if the role changes before, the poll period set
+    /// via [OpenThread::link_set_poll_period] is restored as part of [OpenThread::process]. Fails
+    /// unless attached as a sleepy child.
+    pub fn fast_poll_for(&mut self, duration: MillisDurationU64) -> Result<(), Error> {
+        let sleepy = unsafe { !otThreadGetLinkMode(self.instance).mRxOnWhenIdle() };
+        if !sleepy
+            || unsafe { otThreadGetDeviceRole(self.instance) } != otDeviceRole_OT_DEVICE_ROLE_CHILD
+        {
+            return Err(INVALID_STATE);
+        }
+
+        checked!(unsafe { otLinkSetPollPeriod(self.instance, FAST_POLL_PERIOD) })?;
+
+        let until = current_millis() + duration.to_millis();
+        update(|fast_poll| {
+            fast_poll.until = Some(fast_poll.until.map_or(until, |current| current.max(until)));
+        });
+
+        // the poll timer restarts with the next poll, don't wait for the old period to pass
+        unsafe { otLinkSendDataRequest(self.instance) };
+        Ok(())
+    }
+
+    /// Stop fast polling early and restore the poll period
+    pub fn fast_poll_cancel(&mut self) {
+        restore(self.instance);
+    }
+
+    /// Returns `true` while [OpenThread::fast_poll_for] is in effect
+    pub fn is_fast_polling(&self) -> bool {
+        critical_section::with(|cs| FAST_POLL.borrow(cs).get().until.is_some())
+    }
+}
+
+fn update(f: impl FnOnce(&mut FastPoll)) {
+    critical_section::with(|cs| {
+        let cell = FAST_POLL.borrow(cs);
+        let mut fast_poll = cell.get();
+        f(&mut fast_poll);
+        cell.set(fast_poll);
+    });
+}
+
+/// End fast polling, if active, and go back to the user's poll period
+fn restore(instance: *mut otInstance) {
+    let user_period = critical_section::with(|cs| {
+        let cell = FAST_POLL.borrow(cs);
+        let mut fast_poll = cell.get();
+        fast_poll.until.take()?;
+        cell.set(fast_poll);
+        Some(fast_poll.user_period)
+    });
+
+    if let Some(user_period) = user_period {
+        log::debug!("fast poll ended, poll period {}", user_period);
+        unsafe { otLinkSetPollPeriod(instance, user_period) };
+    }
+}
+
+/// Restore the poll period once fast polling expired, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let expired = critical_section::with(|cs| {
+        FAST_POLL
+            .borrow(cs)
+            .get()
+            .until
+            .is_some_and(|until| current_millis() >= until)
+    });
+
+    if expired {
+        restore(instance);
+    }
+}
+
+/// Cancel fast polling when the role changes, e.g. after detaching from the parent
+pub(crate) fn on_changes(instance: *mut otInstance, flags: ChangedFlags) {
+    if flags.contains(ChangedFlags::ThreadRoleChanged) {
+        restore(instance);
+    }
+}
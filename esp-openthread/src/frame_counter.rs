@@ -0,0 +1,81 @@
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND, otPlatSettingsGet,
+    otThreadGetKeySequenceCounter, otThreadSetKeySequenceCounter, OT_SETTINGS_KEY_NETWORK_INFO,
+};
+
+use crate::{Error, OpenThread};
+
+// layout of OpenThread's `Settings::NetworkInfo` record: role, device mode, RLOC16, key sequence,
+// MLE and MAC frame counters, previous partition ID, extended address, mesh-local IID and version
+const NETWORK_INFO_SIZE: usize = 38;
+const MLE_FRAME_COUNTER_OFFSET: usize = 8;
+const MAC_FRAME_COUNTER_OFFSET: usize = 12;
+
+/// Outgoing frame counters as reserved by OpenThread in its settings
+///
+/// OpenThread stores the counters ahead of the ones in use (by 1000 unless changed via
+/// `OPENTHREAD_CONFIG_STORE_FRAME_COUNTER_AHEAD`) and stores them again once that reserve is
+/// used up, so the counters in use are at most that much lower.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameCounters {
+    pub mac: u32,
+    pub mle: u32,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the outgoing MAC and MLE frame counters reserved in OpenThread's settings
+    ///
+    /// `None` if they weren't stored yet, i.e. the device never attached. OpenThread has no API
+    /// to read the exact counters in use, nor one to preset them; they are only restored from
+    /// the settings when the instance is initialized.
+    ///
+    /// Presetting them isn't offered: [OpenThread::new] initializes the instance, so a record
+    /// written to the settings afterwards would only take effect on the next boot, and is
+    /// overwritten whenever OpenThread stores its own reserve in between. To make sure a
+    /// restored device doesn't reuse counters, move it to a new key with
+    /// [OpenThread::thread_set_key_sequence_counter] instead, which restarts them at 0.
+    pub fn thread_get_frame_counters(&self) -> Result<Option<FrameCounters>, Error> {
+        let mut network_info = [0u8; NETWORK_INFO_SIZE];
+        let mut len = NETWORK_INFO_SIZE as u16;
+
+        let res = unsafe {
+            otPlatSettingsGet(
+                self.instance,
+                OT_SETTINGS_KEY_NETWORK_INFO as u16,
+                0,
+                network_info.as_mut_ptr(),
+                &mut len,
+            )
+        };
+
+        if res == otError_OT_ERROR_NOT_FOUND {
+            return Ok(None);
+        } else if res != otError_OT_ERROR_NONE {
+            return Err(Error::InternalError(res));
+        }
+
+        // written by a different OpenThread version
+        if (len as usize) < NETWORK_INFO_SIZE {
+            return Ok(None);
+        }
+
+        let counter =
+            |offset: usize| u32::from_le_bytes(network_info[offset..][..4].try_into().unwrap());
+
+        Ok(Some(FrameCounters {
+            mac: counter(MAC_FRAME_COUNTER_OFFSET),
+            mle: counter(MLE_FRAME_COUNTER_OFFSET),
+        }))
+    }
+
+    /// Get the key sequence counter, the frame counters restart at 0 whenever it changes
+    pub fn thread_get_key_sequence_counter(&self) -> u32 {
+        unsafe { otThreadGetKeySequenceCounter(self.instance) }
+    }
+
+    /// Set the key sequence counter, e.g. to move a restored device past the key its old frame
+    /// counters belong to
+    pub fn thread_set_key_sequence_counter(&mut self, key_sequence_counter: u32) {
+        unsafe { otThreadSetKeySequenceCounter(self.instance, key_sequence_counter) }
+    }
+}
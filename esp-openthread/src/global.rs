@@ -0,0 +1,66 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::OpenThread;
+
+/// Set while the global instance is borrowed, or being installed or taken
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+// only accessed while holding `LOCKED`
+static mut OPENTHREAD: Option<OpenThread<'static>> = None;
+
+/// Releases `LOCKED` when dropped
+struct Guard;
+
+impl Guard {
+    fn lock() -> Option<Self> {
+        LOCKED
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        LOCKED.store(false, Ordering::Release);
+    }
+}
+
+impl OpenThread<'static> {
+    /// Hand the instance to the crate, so any module can reach it via [with_openthread]
+    ///
+    /// Needs the radio borrowed for `'static`, e.g. via `static_cell`. Gives the instance back if
+    /// one is installed already.
+    pub fn install_global(self) -> Result<(), Self> {
+        let Some(_guard) = Guard::lock() else {
+            return Err(self);
+        };
+
+        let openthread = unsafe { &mut *core::ptr::addr_of_mut!(OPENTHREAD) };
+        if openthread.is_some() {
+            return Err(self);
+        }
+        *openthread = Some(self);
+        Ok(())
+    }
+}
+
+/// Run `f` with the instance installed via [OpenThread::install_global]
+///
+/// Returns `None` if none is installed or it's in use, i.e. when called from within `f` (e.g.
+/// from a callback OpenThread invokes during [OpenThread::process]) or from an interrupt which
+/// preempted another call. Interrupts stay enabled while `f` runs, so the main loop can drive
+/// [OpenThread::process] through it as well.
+pub fn with_openthread<R>(f: impl FnOnce(&mut OpenThread<'static>) -> R) -> Option<R> {
+    let _guard = Guard::lock()?;
+    let openthread = unsafe { &mut *core::ptr::addr_of_mut!(OPENTHREAD) };
+    openthread.as_mut().map(f)
+}
+
+/// Take back the instance installed via [OpenThread::install_global], e.g. to drop it
+///
+/// Returns `None` if none is installed or it's in use.
+pub fn take_global_openthread() -> Option<OpenThread<'static>> {
+    let _guard = Guard::lock()?;
+    unsafe { (*core::ptr::addr_of_mut!(OPENTHREAD)).take() }
+}
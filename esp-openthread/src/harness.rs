@@ -0,0 +1,115 @@
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{otError_OT_ERROR_INVALID_ARGS, otRadioFrame};
+
+use crate::{receive_frame, timer::current_millis, Error, OpenThread};
+
+pub(crate) static TRANSMIT_CAPTURE: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(&TransmittedFrame) -> bool + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// A captured frame wasn't sent, its transmission still needs to be reported as done
+static TX_DONE_PENDING: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
+
+/// A crafted frame to be handed to OpenThread as if it was received
+#[derive(Debug, Clone, Copy)]
+pub struct InjectedFrame<'a> {
+    /// PSDU including the FCS, at most 127 bytes
+    pub psdu: &'a [u8],
+    pub channel: u8,
+    pub rssi: i8,
+    /// Receive timestamp (in microseconds), `None` uses the current time
+    pub timestamp: Option<u64>,
+}
+
+/// A frame OpenThread attempted to transmit
+#[derive(Debug, Clone, Copy)]
+pub struct TransmittedFrame<'a> {
+    /// PSDU including the FCS
+    pub psdu: &'a [u8],
+    pub channel: u8,
+    pub max_csma_backoffs: u8,
+    pub max_frame_retries: u8,
+    /// The frame is a retransmission
+    pub is_retransmission: bool,
+    pub csma_ca_enabled: bool,
+    /// Security was already applied by OpenThread
+    pub is_security_processed: bool,
+    /// Frame counter and key id were already set by OpenThread
+    pub is_header_updated: bool,
+}
+
+impl<'a> TransmittedFrame<'a> {
+    pub(crate) fn from_raw(frame: &'a otRadioFrame) -> Self {
+        let psdu = unsafe { core::slice::from_raw_parts(frame.mPsdu, frame.mLength as usize) };
+        let tx_info = unsafe { &frame.mInfo.mTxInfo };
+
+        Self {
+            psdu,
+            channel: frame.mChannel,
+            max_csma_backoffs: tx_info.mMaxCsmaBackoffs,
+            max_frame_retries: tx_info.mMaxFrameRetries,
+            is_retransmission: tx_info.mIsARetx(),
+            csma_ca_enabled: tx_info.mCsmaCaEnabled(),
+            is_security_processed: tx_info.mIsSecurityProcessed(),
+            is_header_updated: tx_info.mIsHeaderUpdated(),
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Hand a crafted frame to OpenThread as if the radio received it
+    pub fn inject_received_frame(&self, frame: &InjectedFrame) -> Result<(), Error> {
+        if frame.psdu.len() > 127 {
+            return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+        }
+
+        receive_frame(
+            self.instance,
+            frame.psdu,
+            frame.channel,
+            frame.rssi,
+            frame.timestamp.unwrap_or_else(|| current_millis() * 1000),
+        );
+
+        Ok(())
+    }
+
+    /// Set a callback capturing every frame OpenThread attempts to transmit
+    ///
+    /// The frame is only sent over the air if the callback returns `true`. Otherwise the
+    /// transmission is reported as done by the next call to [OpenThread::process].
+    pub fn set_transmit_capture(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(&TransmittedFrame) -> bool + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut transmit_capture = TRANSMIT_CAPTURE.borrow_ref_mut(cs);
+            *transmit_capture = unsafe { core::mem::transmute(callback) };
+        });
+    }
+}
+
+/// Pass a frame about to be transmitted to the capture callback
+///
+/// Returns `true` if the frame should still be sent over the air.
+pub(crate) fn capture_transmit(frame: &otRadioFrame) -> bool {
+    critical_section::with(|cs| {
+        let mut callback = TRANSMIT_CAPTURE.borrow_ref_mut(cs);
+        let send = match callback.as_mut() {
+            Some(callback) => callback(&TransmittedFrame::from_raw(frame)),
+            None => true,
+        };
+
+        if !send {
+            TX_DONE_PENDING.borrow(cs).set(true);
+        }
+        send
+    })
+}
+
+/// Returns `true` once after a captured frame wasn't sent
+pub(crate) fn take_tx_done_pending() -> bool {
+    critical_section::with(|cs| TX_DONE_PENDING.borrow(cs).replace(false))
+}
@@ -0,0 +1,70 @@
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use crate::{timer::current_micros, OpenThread};
+
+/// Stack pointer when entering OpenThread via `process()` or `run_tasklets()`, `0` outside
+static STACK_BASE: AtomicUsize = AtomicUsize::new(0);
+static MAX_STACK_DEPTH: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static MAX_PROCESS_TIME: AtomicU32 = AtomicU32::new(0);
+pub(crate) static MAX_TASKLETS_TIME: AtomicU32 = AtomicU32::new(0);
+
+/// Worst cases observed since start or the last [OpenThread::reset_health_stats]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HealthStats {
+    /// Deepest stack use inside OpenThread (in bytes)
+    ///
+    /// Sampled whenever OpenThread calls into the platform layer, so the real maximum can be
+    /// somewhat higher. Leave some headroom when sizing stacks.
+    pub max_stack_depth: usize,
+    /// Longest [OpenThread::process] call (in microseconds)
+    pub max_process_time: u32,
+    /// Longest [OpenThread::run_tasklets] call (in microseconds)
+    pub max_tasklets_time: u32,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the worst case stack depth and execution times observed so far
+    pub fn health_stats(&self) -> HealthStats {
+        HealthStats {
+            max_stack_depth: MAX_STACK_DEPTH.load(Ordering::Relaxed),
+            max_process_time: MAX_PROCESS_TIME.load(Ordering::Relaxed),
+            max_tasklets_time: MAX_TASKLETS_TIME.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Start observing the worst cases from scratch
+    pub fn reset_health_stats(&mut self) {
+        MAX_STACK_DEPTH.store(0, Ordering::Relaxed);
+        MAX_PROCESS_TIME.store(0, Ordering::Relaxed);
+        MAX_TASKLETS_TIME.store(0, Ordering::Relaxed);
+    }
+}
+
+fn stack_pointer() -> usize {
+    let sp: usize;
+    unsafe { core::arch::asm!("mv {}, sp", out(reg) sp) };
+    sp
+}
+
+/// Mark entering OpenThread, returns the start time to pass to [leave]
+pub(crate) fn enter() -> u64 {
+    STACK_BASE.store(stack_pointer(), Ordering::Relaxed);
+    current_micros()
+}
+
+/// Mark leaving OpenThread, tracking the execution time since [enter] in `max_time`
+pub(crate) fn leave(start: u64, max_time: &AtomicU32) {
+    let elapsed = u32::try_from(current_micros() - start).unwrap_or(u32::MAX);
+    max_time.fetch_max(elapsed, Ordering::Relaxed);
+    STACK_BASE.store(0, Ordering::Relaxed);
+}
+
+/// Record the current stack depth, called from the platform functions OpenThread invokes
+pub(crate) fn sample_stack() {
+    let base = STACK_BASE.load(Ordering::Relaxed);
+    if base == 0 {
+        return;
+    }
+
+    MAX_STACK_DEPTH.fetch_max(base.saturating_sub(stack_pointer()), Ordering::Relaxed);
+}
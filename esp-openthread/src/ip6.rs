@@ -0,0 +1,375 @@
+use core::{cell::RefCell, fmt};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otError_OT_ERROR_INVALID_ARGS, otInstance, otIp6Address, otIp6AddressFromString,
+        otIp6AddressInfo, otIp6AddressToString, otIp6Address__bindgen_ty_1,
+        otIp6GetUnicastAddresses, otIp6IsMulticastPromiscuousEnabled, otIp6IsReceiveFilterEnabled,
+        otIp6Prefix, otIp6SelectSourceAddress, otIp6SetAddressCallback,
+        otIp6SetMulticastPromiscuousEnabled, otIp6SetReceiveCallback, otIp6SetReceiveFilterEnabled,
+        otMessage, otMessageFree, otNetifAddress, otNetifIdentifier,
+        otNetifIdentifier_OT_NETIF_BACKBONE, otNetifIdentifier_OT_NETIF_THREAD,
+        otNetifIdentifier_OT_NETIF_UNSPECIFIED, OT_IP6_ADDRESS_STRING_SIZE,
+    },
+    c_types::c_void,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{checked, new_message_info, Error, MessageReader, OpenThread};
+
+pub(crate) static ADDRESS_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(Ipv6AddressInfo, bool) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static RECEIVE_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(&mut MessageReader) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Network interface a socket is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetifIdentifier {
+    /// Not bound to an interface
+    Unspecified,
+    /// The Thread interface
+    Thread,
+    /// The Backbone interface of a Border Router, needs OpenThread built with
+    /// `OPENTHREAD_CONFIG_PLATFORM_UDP_ENABLE`
+    Backbone,
+}
+
+impl From<NetifIdentifier> for otNetifIdentifier {
+    fn from(netif: NetifIdentifier) -> Self {
+        match netif {
+            NetifIdentifier::Unspecified => otNetifIdentifier_OT_NETIF_UNSPECIFIED,
+            NetifIdentifier::Thread => otNetifIdentifier_OT_NETIF_THREAD,
+            NetifIdentifier::Backbone => otNetifIdentifier_OT_NETIF_BACKBONE,
+        }
+    }
+}
+
+/// Convert an [Ipv6Addr] into the OpenThread representation
+pub fn ot_ip6_address_from_ipv6_addr(address: Ipv6Addr) -> otIp6Address {
+    otIp6Address {
+        mFields: otIp6Address__bindgen_ty_1 {
+            m8: address.octets(),
+        },
+    }
+}
+
+/// Convert an OpenThread IPv6 address into an [Ipv6Addr]
+pub fn ipv6_addr_from_ot_ip6_address(address: &otIp6Address) -> Ipv6Addr {
+    Ipv6Addr::from(unsafe { address.mFields.m8 })
+}
+
+/// Parse a human-readable IPv6 address using OpenThread's parser
+pub fn parse_ipv6_addr(address: &str) -> Result<Ipv6Addr, Error> {
+    // room for the terminating NUL
+    let mut raw = [0u8; OT_IP6_ADDRESS_STRING_SIZE as usize];
+    if address.len() >= raw.len() {
+        return Err(INVALID_ARGS);
+    }
+    raw[..address.len()].copy_from_slice(address.as_bytes());
+
+    let mut ot_address = ot_ip6_address_from_ipv6_addr(Ipv6Addr::UNSPECIFIED);
+    checked!(unsafe { otIp6AddressFromString(raw.as_ptr() as *const _, &mut ot_address) })?;
+
+    Ok(ipv6_addr_from_ot_ip6_address(&ot_address))
+}
+
+/// Format an IPv6 address the way OpenThread does (e.g. in the CLI)
+pub fn ipv6_addr_to_string(
+    address: Ipv6Addr,
+) -> heapless::String<{ OT_IP6_ADDRESS_STRING_SIZE as usize }> {
+    let ot_address = ot_ip6_address_from_ipv6_addr(address);
+    let mut raw = [0u8; OT_IP6_ADDRESS_STRING_SIZE as usize];
+    unsafe {
+        otIp6AddressToString(&ot_address, raw.as_mut_ptr() as *mut _, raw.len() as u16);
+    }
+
+    let len = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+    let mut result = heapless::String::new();
+    // the output is plain ASCII and fits by construction
+    result
+        .push_str(core::str::from_utf8(&raw[..len]).unwrap_or_default())
+        .ok();
+    result
+}
+
+/// Get the scope of an address the way OpenThread does
+///
+/// For multicast addresses that's their scope field, e.g. 3 for realm-local groups. Link-local
+/// unicast addresses have scope 2, the loopback address 0 and all others 14 (global), mesh-local
+/// addresses included.
+pub fn ipv6_addr_scope(address: &Ipv6Addr) -> u8 {
+    let octets = address.octets();
+    if octets[0] == 0xff {
+        octets[1] & 0x0f
+    } else if octets[0] == 0xfe && octets[1] & 0xc0 == 0x80 {
+        2
+    } else if *address == Ipv6Addr::LOCALHOST {
+        0
+    } else {
+        14
+    }
+}
+
+/// IPv6 prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv6Prefix {
+    /// The prefix, bits beyond `length` are ignored
+    pub prefix: Ipv6Addr,
+    /// The prefix length (in bits)
+    pub length: u8,
+}
+
+impl Ipv6Prefix {
+    pub fn new(prefix: Ipv6Addr, length: u8) -> Self {
+        Self { prefix, length }
+    }
+
+    /// Parse a prefix in the form `fd00:db8::/64`
+    pub fn parse(prefix: &str) -> Result<Self, Error> {
+        let (address, length) = prefix.split_once('/').ok_or(INVALID_ARGS)?;
+        let length: u8 = length.parse().map_err(|_| INVALID_ARGS)?;
+        if length > 128 {
+            return Err(INVALID_ARGS);
+        }
+
+        Ok(Self {
+            prefix: parse_ipv6_addr(address)?,
+            length,
+        })
+    }
+
+    /// Returns `true` if the given address is covered by this prefix
+    pub fn contains(&self, address: &Ipv6Addr) -> bool {
+        let mask = match self.length {
+            0 => 0,
+            len => u128::MAX << (128 - u32::from(len.min(128))),
+        };
+        u128::from_be_bytes(self.prefix.octets()) & mask
+            == u128::from_be_bytes(address.octets()) & mask
+    }
+}
+
+impl fmt::Display for Ipv6Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.prefix, self.length)
+    }
+}
+
+impl From<&otIp6Prefix> for Ipv6Prefix {
+    fn from(value: &otIp6Prefix) -> Self {
+        // the struct is packed, copy out the address before borrowing it
+        let prefix = value.mPrefix;
+        Self {
+            prefix: ipv6_addr_from_ot_ip6_address(&prefix),
+            length: value.mLength,
+        }
+    }
+}
+
+impl From<Ipv6Prefix> for otIp6Prefix {
+    fn from(value: Ipv6Prefix) -> Self {
+        otIp6Prefix {
+            mPrefix: ot_ip6_address_from_ipv6_addr(value.prefix),
+            mLength: value.length,
+        }
+    }
+}
+
+/// An IPv6 address which was added to or removed from the Thread interface
+#[derive(Debug, Clone, Copy)]
+pub struct Ipv6AddressInfo {
+    /// The IPv6 address
+    pub address: Ipv6Addr,
+    /// The prefix length (in bits) if it is a unicast address
+    pub prefix_length: u8,
+    /// The scope of the address
+    pub scope: u8,
+    /// Whether the address is preferred
+    pub preferred: bool,
+    /// The IPv6 address origin (see `OT_ADDRESS_ORIGIN_*`)
+    ///
+    /// Only known for unicast addresses which are still assigned, i.e. not for removals.
+    pub origin: Option<u8>,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the IPv6 address callback
+    ///
+    /// The callback gets the address and `true` if it was added or `false` if it was removed.
+    /// This includes multicast subscriptions.
+    pub fn set_ipv6_address_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(Ipv6AddressInfo, bool) + Send)>,
+    ) {
+        let enabled = callback.is_some();
+        critical_section::with(|cs| {
+            let mut address_callback = ADDRESS_CALLBACK.borrow_ref_mut(cs);
+            *address_callback = unsafe { core::mem::transmute(callback) };
+        });
+
+        unsafe {
+            otIp6SetAddressCallback(
+                self.instance,
+                if enabled {
+                    Some(address_callback)
+                } else {
+                    None
+                },
+                self.instance as *mut c_void,
+            );
+        }
+    }
+
+    /// Set the callback for raw IPv6 datagrams received on the Thread interface
+    ///
+    /// The reader starts at the IPv6 header. By default Thread control traffic is not passed
+    /// to the callback, see [OpenThread::ipv6_set_receive_filter_enabled].
+    ///
+    /// The `NetDriver` of the `embassy-net` feature uses the same hook, whichever was set last
+    /// gets the datagrams.
+    pub fn set_ipv6_receive_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(&mut MessageReader) + Send)>,
+    ) {
+        let enabled = callback.is_some();
+        critical_section::with(|cs| {
+            let mut receive_callback = RECEIVE_CALLBACK.borrow_ref_mut(cs);
+            *receive_callback = unsafe { core::mem::transmute(callback) };
+        });
+
+        unsafe {
+            otIp6SetReceiveCallback(
+                self.instance,
+                if enabled {
+                    Some(receive_callback)
+                } else {
+                    None
+                },
+                core::ptr::null_mut(),
+            );
+        }
+    }
+
+    /// Set whether Thread control traffic is filtered out before datagrams are passed to the
+    /// IPv6 receive callback
+    pub fn ipv6_set_receive_filter_enabled(&mut self, enabled: bool) {
+        unsafe { otIp6SetReceiveFilterEnabled(self.instance, enabled) }
+    }
+
+    /// Returns `true` if Thread control traffic is filtered out before datagrams are passed to
+    /// the IPv6 receive callback
+    pub fn ipv6_is_receive_filter_enabled(&self) -> bool {
+        unsafe { otIp6IsReceiveFilterEnabled(self.instance) }
+    }
+
+    /// Set whether datagrams to all multicast groups are passed up, not only to subscribed ones
+    pub fn ipv6_set_multicast_promiscuous_enabled(&mut self, enabled: bool) {
+        unsafe { otIp6SetMulticastPromiscuousEnabled(self.instance, enabled) }
+    }
+
+    /// Returns `true` if datagrams to all multicast groups are passed up
+    pub fn ipv6_is_multicast_promiscuous_enabled(&self) -> bool {
+        unsafe { otIp6IsMulticastPromiscuousEnabled(self.instance) }
+    }
+
+    /// Get the local address OpenThread uses as source when sending to `destination`
+    ///
+    /// The address is chosen by OpenThread's source address selection, so it's the one a
+    /// [crate::UdpSocket] not bound to an address would send from: an address of at least the
+    /// destination's scope, preferring the longest common prefix, with the mesh-local EID over
+    /// the RLOC. Useful to build raw packets or to show in diagnostics. Fails with
+    /// `OT_ERROR_NOT_FOUND` if no address fits.
+    pub fn select_source_address(&self, destination: Ipv6Addr) -> Result<Ipv6AddressInfo, Error> {
+        let mut message_info = new_message_info();
+        message_info.mPeerAddr = ot_ip6_address_from_ipv6_addr(destination);
+        checked!(unsafe { otIp6SelectSourceAddress(self.instance, &mut message_info) })?;
+
+        let address = ipv6_addr_from_ot_ip6_address(&message_info.mSockAddr);
+        // the selected address is one of the assigned ones
+        let (prefix_length, preferred, origin) =
+            match unsafe { unicast_address(self.instance, &address) } {
+                Some(a) => (a.mPrefixLength, a.mPreferred(), Some(a.mAddressOrigin)),
+                None => (64, true, None),
+            };
+        Ok(Ipv6AddressInfo {
+            address,
+            prefix_length,
+            scope: ipv6_addr_scope(&address),
+            preferred,
+            origin,
+        })
+    }
+}
+
+/// Find the origin of an assigned unicast address
+unsafe fn unicast_address_origin(instance: *mut otInstance, address: &Ipv6Addr) -> Option<u8> {
+    unicast_address(instance, address).map(|a| a.mAddressOrigin)
+}
+
+/// Find an assigned unicast address
+unsafe fn unicast_address<'i>(
+    instance: *mut otInstance,
+    address: &Ipv6Addr,
+) -> Option<&'i otNetifAddress> {
+    let mut addr = otIp6GetUnicastAddresses(instance);
+
+    while !addr.is_null() {
+        let a = &*addr;
+        if ipv6_addr_from_ot_ip6_address(&a.mAddress) == *address {
+            return Some(a);
+        }
+        addr = a.mNext;
+    }
+
+    None
+}
+
+unsafe extern "C" fn address_callback(
+    address_info: *const otIp6AddressInfo,
+    is_added: bool,
+    context: *mut c_void,
+) {
+    let info = &*address_info;
+    let address = ipv6_addr_from_ot_ip6_address(&*info.mAddress);
+    log::debug!("address_callback {} added={}", address, is_added);
+
+    let origin = if is_added && !address.is_multicast() {
+        unicast_address_origin(context as *mut otInstance, &address)
+    } else {
+        None
+    };
+
+    let info = Ipv6AddressInfo {
+        address,
+        prefix_length: info.mPrefixLength,
+        scope: info.mScope(),
+        preferred: info.mPreferred(),
+        origin,
+    };
+
+    critical_section::with(|cs| {
+        let mut callback = ADDRESS_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(info, is_added);
+        }
+    });
+}
+
+unsafe extern "C" fn receive_callback(message: *mut otMessage, _context: *mut c_void) {
+    critical_section::with(|cs| {
+        let mut callback = RECEIVE_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            let mut reader = MessageReader::new(message);
+            callback(&mut reader);
+        }
+    });
+
+    // we own the message
+    otMessageFree(message);
+}
@@ -0,0 +1,265 @@
+use core::{
+    cell::RefCell,
+    fmt::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otError, otError_OT_ERROR_INVALID_ARGS, otInstance, otJoinerGetId, otJoinerGetState,
+        otJoinerStart, otJoinerState, otJoinerState_OT_JOINER_STATE_CONNECT,
+        otJoinerState_OT_JOINER_STATE_CONNECTED, otJoinerState_OT_JOINER_STATE_DISCOVER,
+        otJoinerState_OT_JOINER_STATE_ENTRUST, otJoinerState_OT_JOINER_STATE_JOINED, otJoinerStop,
+        otThreadSetJoinerAdvertisement, OT_JOINER_MAX_PSKD_LENGTH, OT_PROVISIONING_URL_MAX_SIZE,
+    },
+    c_types::{c_char, c_void},
+};
+
+use crate::{checked, ChangedFlags, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Longest vendor data OpenThread sends in the Joiner Finalize request, the vendor name and model
+/// are limited to 32 and the software version to 16 bytes
+const VENDOR_DATA_MAX_LENGTH: usize = 64;
+
+/// Longest payload built by [joiner_qr_payload], with a 32 character PSKd
+pub const JOINER_QR_PAYLOAD_MAX_SIZE: usize = 62;
+
+/// Longest data of a joiner advertisement, same as OpenThread's `OT_JOINER_ADVDATA_MAX_LENGTH`
+pub const JOINER_ADVERTISEMENT_DATA_MAX_LENGTH: usize = 64;
+
+/// Shortest PSKd accepted by OpenThread
+const PSKD_MIN_LENGTH: usize = 6;
+
+/// Commissioning code of a joiner as encoded in its QR code, see [joiner_qr_payload]
+pub type JoinerQrPayload = heapless::String<JOINER_QR_PAYLOAD_MAX_SIZE>;
+
+/// Set once a join succeeded, until the Active Operational Dataset arrived
+pub(crate) static DATASET_PENDING: AtomicBool = AtomicBool::new(false);
+
+pub(crate) static JOINER_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(JoinerEvent) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// State of the Joiner role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinerState {
+    Idle,
+    /// Scanning for joiner routers of networks accepting this joiner
+    Discover,
+    /// DTLS handshake with the commissioner, authenticated by the PSKd
+    Connect,
+    /// DTLS session established, the Joiner Finalize request is being exchanged
+    Connected,
+    /// Waiting for the Joiner Entrust message carrying the network key
+    Entrust,
+    /// The network key was received
+    Joined,
+}
+
+impl JoinerState {
+    #[allow(non_upper_case_globals)]
+    fn from_raw(state: otJoinerState) -> Self {
+        match state {
+            otJoinerState_OT_JOINER_STATE_DISCOVER => Self::Discover,
+            otJoinerState_OT_JOINER_STATE_CONNECT => Self::Connect,
+            otJoinerState_OT_JOINER_STATE_CONNECTED => Self::Connected,
+            otJoinerState_OT_JOINER_STATE_ENTRUST => Self::Entrust,
+            otJoinerState_OT_JOINER_STATE_JOINED => Self::Joined,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// Progress of [OpenThread::joiner_start]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinerEvent {
+    /// The joiner moved on to the next stage
+    Progress(JoinerState),
+    /// The join finished, on success the network key is set and Thread can be enabled
+    Completed(Result<(), Error>),
+    /// The device attached after joining and received the Active Operational Dataset
+    DatasetReceived,
+}
+
+/// Information about the device sent to the commissioner when joining
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JoinerVendorInfo<'a> {
+    /// Up to 32 bytes
+    pub name: Option<&'a str>,
+    /// Up to 32 bytes
+    pub model: Option<&'a str>,
+    /// Up to 16 bytes
+    pub sw_version: Option<&'a str>,
+    /// Up to 64 bytes
+    pub data: Option<&'a str>,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the callback reporting the progress of [OpenThread::joiner_start], e.g. to show the
+    /// commissioning stages
+    pub fn set_joiner_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(JoinerEvent) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut joiner_callback = JOINER_CALLBACK.borrow_ref_mut(cs);
+            *joiner_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Start the Joiner role to get the network credentials from a commissioner
+    ///
+    /// Needs IPv6 enabled but Thread disabled. The joiner authenticates with `pskd` (6 to 32
+    /// uppercase alphanumeric characters, without I, O, Q and Z). The callback set via
+    /// [OpenThread::set_joiner_callback] gets each stage, the outcome and, once Thread was
+    /// enabled after a successful join, the reception of the Active Operational Dataset.
+    pub fn joiner_start(
+        &mut self,
+        pskd: &str,
+        provisioning_url: Option<&str>,
+        vendor_info: &JoinerVendorInfo,
+    ) -> Result<(), Error> {
+        let pskd = c_string::<{ OT_JOINER_MAX_PSKD_LENGTH as usize + 1 }>(Some(pskd))?;
+        let provisioning_url =
+            c_string::<{ OT_PROVISIONING_URL_MAX_SIZE as usize + 1 }>(provisioning_url)?;
+        let vendor_name = c_string::<33>(vendor_info.name)?;
+        let vendor_model = c_string::<33>(vendor_info.model)?;
+        let vendor_sw_version = c_string::<17>(vendor_info.sw_version)?;
+        let vendor_data = c_string::<{ VENDOR_DATA_MAX_LENGTH + 1 }>(vendor_info.data)?;
+
+        DATASET_PENDING.store(false, Ordering::Relaxed);
+
+        // OpenThread prepares the Joiner Finalize request right away, the strings don't need to
+        // outlive the call
+        checked!(unsafe {
+            otJoinerStart(
+                self.instance,
+                c_str_ptr(&pskd),
+                c_str_ptr(&provisioning_url),
+                c_str_ptr(&vendor_name),
+                c_str_ptr(&vendor_model),
+                c_str_ptr(&vendor_sw_version),
+                c_str_ptr(&vendor_data),
+                Some(joiner_callback),
+                core::ptr::null_mut(),
+            )
+        })
+    }
+
+    /// Stop the Joiner role
+    pub fn joiner_stop(&mut self) {
+        unsafe { otJoinerStop(self.instance) }
+    }
+
+    /// Get the state of the Joiner role
+    pub fn joiner_get_state(&self) -> JoinerState {
+        JoinerState::from_raw(unsafe { otJoinerGetState(self.instance) })
+    }
+
+    /// Include the vendor's IEEE `oui` and `data` (up to
+    /// [JOINER_ADVERTISEMENT_DATA_MAX_LENGTH] bytes) in the discovery requests of the joiner
+    ///
+    /// Lets commissioners identify the product (e.g. vendor and product ID) before a PSKd is
+    /// entered, some ecosystems filter joiners by it. Set it before [OpenThread::joiner_start].
+    pub fn joiner_set_advertisement(&mut self, oui: [u8; 3], data: &[u8]) -> Result<(), Error> {
+        if data.len() > JOINER_ADVERTISEMENT_DATA_MAX_LENGTH {
+            return Err(INVALID_ARGS);
+        }
+
+        let oui = u32::from_be_bytes([0, oui[0], oui[1], oui[2]]);
+        checked!(unsafe {
+            otThreadSetJoinerAdvertisement(self.instance, oui, data.as_ptr(), data.len() as u8)
+        })
+    }
+
+    /// Get the Joiner ID, which is also the extended address while joining
+    pub fn joiner_get_id(&self) -> [u8; 8] {
+        unsafe { (*otJoinerGetId(self.instance)).m8 }
+    }
+}
+
+/// Build the QR code payload of a joiner, `v=1&&eui=<EUI-64>&&cc=<PSKd>`, as scanned by
+/// commissioning apps
+///
+/// E.g. for manufacturing firmware printing or displaying the commissioning code of a device. The
+/// `pskd` has to be valid for [OpenThread::joiner_start], i.e. 6 to 32 uppercase alphanumeric
+/// characters without I, O, Q and Z.
+pub fn joiner_qr_payload(eui64: [u8; 8], pskd: &str) -> Result<JoinerQrPayload, Error> {
+    let valid = (PSKD_MIN_LENGTH..=OT_JOINER_MAX_PSKD_LENGTH as usize).contains(&pskd.len())
+        && pskd
+            .bytes()
+            .all(|c| matches!(c, b'0'..=b'9' | b'A'..=b'Z') && !b"IOQZ".contains(&c));
+    if !valid {
+        return Err(INVALID_ARGS);
+    }
+
+    let mut payload = JoinerQrPayload::new();
+    // fits by construction, see `JOINER_QR_PAYLOAD_MAX_SIZE`
+    let _ = payload.push_str("v=1&&eui=");
+    for byte in eui64 {
+        let _ = write!(payload, "{:02x}", byte);
+    }
+    let _ = write!(payload, "&&cc={}", pskd);
+    Ok(payload)
+}
+
+/// A NUL terminated copy of `value`, if any
+fn c_string<const N: usize>(value: Option<&str>) -> Result<Option<[u8; N]>, Error> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    // room for the terminating NUL
+    let mut raw = [0u8; N];
+    if value.len() >= N || value.as_bytes().contains(&0) {
+        return Err(INVALID_ARGS);
+    }
+    raw[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(Some(raw))
+}
+
+fn c_str_ptr<const N: usize>(value: &Option<[u8; N]>) -> *const c_char {
+    value
+        .as_ref()
+        .map_or(core::ptr::null(), |value| value.as_ptr() as *const c_char)
+}
+
+fn report(event: JoinerEvent) {
+    log::debug!("joiner {:?}", event);
+
+    critical_section::with(|cs| {
+        let mut callback = JOINER_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(event);
+        }
+    });
+}
+
+/// Report joiner stages and the dataset reception
+pub(crate) fn on_changes(instance: *mut otInstance, flags: ChangedFlags) {
+    if flags.contains(ChangedFlags::JoinerStateChanged) {
+        let state = JoinerState::from_raw(unsafe { otJoinerGetState(instance) });
+        // the outcome is reported by the join callback
+        if state != JoinerState::Idle {
+            report(JoinerEvent::Progress(state));
+        }
+    }
+
+    if flags.contains(ChangedFlags::ActiveDatasetChanged)
+        && DATASET_PENDING.swap(false, Ordering::Relaxed)
+    {
+        report(JoinerEvent::DatasetReceived);
+    }
+}
+
+unsafe extern "C" fn joiner_callback(error: otError, _context: *mut c_void) {
+    let result = checked!(error);
+    if result.is_ok() {
+        DATASET_PENDING.store(true, Ordering::Relaxed);
+    }
+
+    report(JoinerEvent::Completed(result));
+}
@@ -0,0 +1,409 @@
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole, otDeviceRole_OT_DEVICE_ROLE_CHILD, otDeviceRole_OT_DEVICE_ROLE_DETACHED,
+    otDeviceRole_OT_DEVICE_ROLE_DISABLED, otDeviceRole_OT_DEVICE_ROLE_LEADER,
+    otDeviceRole_OT_DEVICE_ROLE_ROUTER, otError_OT_ERROR_NONE, otInstance, otRouterInfo,
+    otThreadGetDeviceRole, otThreadGetParentInfo,
+};
+
+use crate::{timer::current_millis, ChangedFlags, OpenThread, ThreadDeviceRole};
+
+/// Number of events kept in RAM, the oldest is dropped (after handing it to the flush hook)
+/// once the journal is full
+pub const JOURNAL_MAX_ENTRIES: usize = 32;
+
+/// Size of an encoded [JournalEntry], see [JournalEntry::to_bytes]
+pub const JOURNAL_ENTRY_SIZE: usize = 19;
+
+const KIND_ROLE_CHANGED: u8 = 0;
+const KIND_PARENT_CHANGED: u8 = 1;
+const KIND_DETACHED: u8 = 2;
+
+pub(crate) static JOURNAL: Mutex<RefCell<Journal>> = Mutex::new(RefCell::new(Journal::DEFAULT));
+
+pub(crate) static JOURNAL_FLUSH_HOOK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(&JournalEntry) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Bumped whenever the flush hook is set or cleared, so a flush doesn't reinstall a hook replaced
+/// while it ran
+pub(crate) static JOURNAL_FLUSH_HOOK_GENERATION: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+
+/// Why the device detached, inferred from the previous role and what changed along with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetachReason {
+    /// Thread was disabled
+    Stopped,
+    /// The network key, channel, PAN ID or active dataset changed
+    ConfigurationChanged,
+    /// The child lost its parent
+    ParentLost,
+    /// The router or leader lost its partition
+    PartitionLost,
+}
+
+/// An event recorded in the journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalEvent {
+    RoleChanged {
+        from: ThreadDeviceRole,
+        to: ThreadDeviceRole,
+    },
+    /// The child attached to another parent
+    ParentChanged {
+        ext_address: [u8; 8],
+        rloc16: u16,
+    },
+    Detached {
+        reason: DetachReason,
+    },
+}
+
+/// A journal event and when it happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// Milliseconds since boot, see `current_millis`
+    pub timestamp: u64,
+    pub event: JournalEvent,
+}
+
+impl JournalEntry {
+    /// Encode the entry, e.g. to append it to a log in flash
+    pub fn to_bytes(self) -> [u8; JOURNAL_ENTRY_SIZE] {
+        let mut bytes = [0u8; JOURNAL_ENTRY_SIZE];
+        bytes[..8].copy_from_slice(&self.timestamp.to_le_bytes());
+
+        match self.event {
+            JournalEvent::RoleChanged { from, to } => {
+                bytes[8] = KIND_ROLE_CHANGED;
+                bytes[9] = role_to_raw(from) as u8;
+                bytes[10] = role_to_raw(to) as u8;
+            }
+            JournalEvent::ParentChanged {
+                ext_address,
+                rloc16,
+            } => {
+                bytes[8] = KIND_PARENT_CHANGED;
+                bytes[9..17].copy_from_slice(&ext_address);
+                bytes[17..19].copy_from_slice(&rloc16.to_le_bytes());
+            }
+            JournalEvent::Detached { reason } => {
+                bytes[8] = KIND_DETACHED;
+                bytes[9] = reason as u8;
+            }
+        }
+
+        bytes
+    }
+
+    /// Decode an entry encoded via [JournalEntry::to_bytes]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: &[u8; JOURNAL_ENTRY_SIZE] = bytes.try_into().ok()?;
+        let timestamp = u64::from_le_bytes(bytes[..8].try_into().ok()?);
+
+        let event = match bytes[8] {
+            KIND_ROLE_CHANGED => JournalEvent::RoleChanged {
+                from: role_from_raw(bytes[9] as otDeviceRole)?,
+                to: role_from_raw(bytes[10] as otDeviceRole)?,
+            },
+            KIND_PARENT_CHANGED => JournalEvent::ParentChanged {
+                ext_address: bytes[9..17].try_into().ok()?,
+                rloc16: u16::from_le_bytes([bytes[17], bytes[18]]),
+            },
+            KIND_DETACHED => JournalEvent::Detached {
+                reason: match bytes[9] {
+                    0 => DetachReason::Stopped,
+                    1 => DetachReason::ConfigurationChanged,
+                    2 => DetachReason::ParentLost,
+                    3 => DetachReason::PartitionLost,
+                    _ => return None,
+                },
+            },
+            _ => return None,
+        };
+
+        Some(Self { timestamp, event })
+    }
+}
+
+/// Number of events recorded since the journal was cleared, including the ones dropped from it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JournalCounters {
+    pub role_changes: u32,
+    pub parent_changes: u32,
+    pub detaches: u32,
+    /// Children added to the child table (only on an FTD)
+    pub children_added: u32,
+    /// Children removed from the child table (only on an FTD)
+    pub children_removed: u32,
+}
+
+pub(crate) struct Journal {
+    entries: heapless::Deque<JournalEntry, JOURNAL_MAX_ENTRIES>,
+    /// Number of newest entries not yet handed to the flush hook
+    unflushed: usize,
+    counters: JournalCounters,
+    role: otDeviceRole,
+    parent: Option<[u8; 8]>,
+}
+
+impl Journal {
+    pub(crate) const DEFAULT: Self = Self {
+        entries: heapless::Deque::new(),
+        unflushed: 0,
+        counters: JournalCounters {
+            role_changes: 0,
+            parent_changes: 0,
+            detaches: 0,
+            children_added: 0,
+            children_removed: 0,
+        },
+        role: otDeviceRole_OT_DEVICE_ROLE_DISABLED,
+        parent: None,
+    };
+
+    /// Record `event`, the entries to hand to the flush hook before the oldest one is dropped go
+    /// to `flushed`, if there is a hook
+    fn push(&mut self, event: JournalEvent, now: u64, flushed: Option<&mut JournalEntries>) {
+        if self.entries.is_full() {
+            if let Some(flushed) = flushed.filter(|_| self.unflushed == self.entries.len()) {
+                self.take_unflushed(flushed);
+            }
+            self.entries.pop_front();
+        }
+
+        // can't fail, room was made above
+        self.entries
+            .push_back(JournalEntry {
+                timestamp: now,
+                event,
+            })
+            .ok();
+        self.unflushed = (self.unflushed + 1).min(self.entries.len());
+    }
+
+    /// Copy the entries not handed to the flush hook yet to `flushed`
+    fn take_unflushed(&mut self, flushed: &mut JournalEntries) {
+        let skip = self.entries.len() - self.unflushed;
+        for entry in self.entries.iter().skip(skip) {
+            // at most a journal full of entries is flushed at once
+            flushed.push(*entry).ok();
+        }
+        self.unflushed = 0;
+    }
+}
+
+type JournalEntries = heapless::Vec<JournalEntry, JOURNAL_MAX_ENTRIES>;
+
+/// Hand `entries` to the flush hook outside of the critical section, it might write to flash
+pub(crate) fn bump_flush_hook_generation(cs: critical_section::CriticalSection) {
+    let generation = JOURNAL_FLUSH_HOOK_GENERATION.borrow(cs);
+    generation.set(generation.get().wrapping_add(1));
+}
+
+fn call_flush_hook(entries: &JournalEntries) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let Some((hook, generation)) = critical_section::with(|cs| {
+        JOURNAL_FLUSH_HOOK
+            .borrow_ref_mut(cs)
+            .take()
+            .map(|hook| (hook, JOURNAL_FLUSH_HOOK_GENERATION.borrow(cs).get()))
+    }) else {
+        return;
+    };
+    for entry in entries {
+        hook(entry);
+    }
+
+    critical_section::with(|cs| {
+        // unless the hook was set or cleared meanwhile
+        if JOURNAL_FLUSH_HOOK_GENERATION.borrow(cs).get() == generation {
+            *JOURNAL_FLUSH_HOOK.borrow_ref_mut(cs) = Some(hook);
+        }
+    });
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set a hook persisting journal entries, e.g. appending their [JournalEntry::to_bytes] to
+    /// flash
+    ///
+    /// The hook gets the entries not handed to it yet on [OpenThread::journal_flush], and before
+    /// the journal drops an entry it didn't get, so flash is written in batches. It's called
+    /// outside of the critical section, a slow flash write doesn't hold off interrupts.
+    pub fn set_journal_flush_hook(
+        &mut self,
+        hook: Option<&'a mut (dyn FnMut(&JournalEntry) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut flush_hook = JOURNAL_FLUSH_HOOK.borrow_ref_mut(cs);
+            *flush_hook = unsafe { core::mem::transmute(hook) };
+            bump_flush_hook_generation(cs);
+        });
+    }
+
+    /// Hand the entries recorded since the last flush to the hook set via
+    /// [OpenThread::set_journal_flush_hook]
+    pub fn journal_flush(&mut self) {
+        let mut flushed = JournalEntries::new();
+        critical_section::with(|cs| {
+            if JOURNAL_FLUSH_HOOK.borrow_ref(cs).is_some() {
+                JOURNAL.borrow_ref_mut(cs).take_unflushed(&mut flushed);
+            }
+        });
+        call_flush_hook(&flushed);
+    }
+
+    /// Get the journal entries in RAM, oldest first
+    pub fn journal_entries(&self) -> heapless::Vec<JournalEntry, JOURNAL_MAX_ENTRIES> {
+        critical_section::with(|cs| JOURNAL.borrow_ref(cs).entries.iter().copied().collect())
+    }
+
+    /// Get the number of events recorded, including the ones dropped from the journal
+    pub fn journal_counters(&self) -> JournalCounters {
+        critical_section::with(|cs| JOURNAL.borrow_ref(cs).counters)
+    }
+
+    /// Drop all journal entries and reset the counters
+    pub fn journal_clear(&mut self) {
+        critical_section::with(|cs| {
+            let mut journal = JOURNAL.borrow_ref_mut(cs);
+            journal.entries.clear();
+            journal.unflushed = 0;
+            journal.counters = JournalCounters::default();
+        });
+    }
+}
+
+/// Record the events behind a state change, called from OpenThread's state changed callback
+pub(crate) fn record_changes(instance: *mut otInstance, flags: ChangedFlags) {
+    let role = unsafe { otThreadGetDeviceRole(instance) };
+    let parent = if role == otDeviceRole_OT_DEVICE_ROLE_CHILD {
+        let mut parent: otRouterInfo = unsafe { core::mem::zeroed() };
+        (unsafe { otThreadGetParentInfo(instance, &mut parent) } == otError_OT_ERROR_NONE)
+            .then_some(parent)
+    } else {
+        None
+    };
+    let now = current_millis();
+
+    let mut flushed = JournalEntries::new();
+    critical_section::with(|cs| {
+        let mut journal = JOURNAL.borrow_ref_mut(cs);
+        let mut flushing = JOURNAL_FLUSH_HOOK
+            .borrow_ref(cs)
+            .is_some()
+            .then_some(&mut flushed);
+
+        if flags.contains(ChangedFlags::ThreadChildAdded) {
+            journal.counters.children_added = journal.counters.children_added.wrapping_add(1);
+        }
+        if flags.contains(ChangedFlags::ThreadChildRemoved) {
+            journal.counters.children_removed = journal.counters.children_removed.wrapping_add(1);
+        }
+
+        let previous_role = journal.role;
+        if role != previous_role {
+            if let (Some(from), Some(to)) = (role_from_raw(previous_role), role_from_raw(role)) {
+                journal.push(
+                    JournalEvent::RoleChanged { from, to },
+                    now,
+                    flushing.as_deref_mut(),
+                );
+                journal.counters.role_changes = journal.counters.role_changes.wrapping_add(1);
+            }
+
+            if let Some(reason) = detach_reason(previous_role, role, flags) {
+                journal.push(
+                    JournalEvent::Detached { reason },
+                    now,
+                    flushing.as_deref_mut(),
+                );
+                journal.counters.detaches = journal.counters.detaches.wrapping_add(1);
+            }
+            journal.role = role;
+        }
+
+        // the last parent is kept while detached or a router, so becoming its child again
+        // isn't a change
+        if let Some(parent) = parent.filter(|parent| journal.parent != Some(parent.mExtAddress.m8))
+        {
+            journal.push(
+                JournalEvent::ParentChanged {
+                    ext_address: parent.mExtAddress.m8,
+                    rloc16: parent.mRloc16,
+                },
+                now,
+                flushing,
+            );
+            journal.counters.parent_changes = journal.counters.parent_changes.wrapping_add(1);
+            journal.parent = Some(parent.mExtAddress.m8);
+        }
+    });
+
+    call_flush_hook(&flushed);
+}
+
+#[allow(non_upper_case_globals)]
+fn detach_reason(
+    previous_role: otDeviceRole,
+    role: otDeviceRole,
+    flags: ChangedFlags,
+) -> Option<DetachReason> {
+    let attached = |role| {
+        matches!(
+            role,
+            otDeviceRole_OT_DEVICE_ROLE_CHILD
+                | otDeviceRole_OT_DEVICE_ROLE_ROUTER
+                | otDeviceRole_OT_DEVICE_ROLE_LEADER
+        )
+    };
+    if !attached(previous_role) {
+        return None;
+    }
+
+    let configuration = ChangedFlags::ThreadNetworkKeyChanged
+        | ChangedFlags::ThreadNetworkChannelChanged
+        | ChangedFlags::ThreadPanIdChanged
+        | ChangedFlags::ActiveDatasetChanged;
+
+    match role {
+        otDeviceRole_OT_DEVICE_ROLE_DISABLED => Some(DetachReason::Stopped),
+        otDeviceRole_OT_DEVICE_ROLE_DETACHED if flags.intersects(configuration) => {
+            Some(DetachReason::ConfigurationChanged)
+        }
+        otDeviceRole_OT_DEVICE_ROLE_DETACHED
+            if previous_role == otDeviceRole_OT_DEVICE_ROLE_CHILD =>
+        {
+            Some(DetachReason::ParentLost)
+        }
+        otDeviceRole_OT_DEVICE_ROLE_DETACHED => Some(DetachReason::PartitionLost),
+        _ => None,
+    }
+}
+
+#[allow(non_upper_case_globals)]
+fn role_from_raw(role: otDeviceRole) -> Option<ThreadDeviceRole> {
+    match role {
+        otDeviceRole_OT_DEVICE_ROLE_DISABLED => Some(ThreadDeviceRole::Disabled),
+        otDeviceRole_OT_DEVICE_ROLE_DETACHED => Some(ThreadDeviceRole::Detached),
+        otDeviceRole_OT_DEVICE_ROLE_CHILD => Some(ThreadDeviceRole::Child),
+        otDeviceRole_OT_DEVICE_ROLE_ROUTER => Some(ThreadDeviceRole::Router),
+        otDeviceRole_OT_DEVICE_ROLE_LEADER => Some(ThreadDeviceRole::Leader),
+        _ => None,
+    }
+}
+
+fn role_to_raw(role: ThreadDeviceRole) -> otDeviceRole {
+    match role {
+        ThreadDeviceRole::Disabled => otDeviceRole_OT_DEVICE_ROLE_DISABLED,
+        ThreadDeviceRole::Detached => otDeviceRole_OT_DEVICE_ROLE_DETACHED,
+        ThreadDeviceRole::Child => otDeviceRole_OT_DEVICE_ROLE_CHILD,
+        ThreadDeviceRole::Router => otDeviceRole_OT_DEVICE_ROLE_ROUTER,
+        ThreadDeviceRole::Leader => otDeviceRole_OT_DEVICE_ROLE_LEADER,
+    }
+}
@@ -0,0 +1,122 @@
+use esp_openthread_sys::{
+    bindings::{
+        otCryptoContext, otCryptoKey, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_INVALID_STATE, otNetworkKey, otPlatCryptoHkdfDeinit,
+        otPlatCryptoHkdfExpand, otPlatCryptoHkdfExtract, otPlatCryptoHkdfInit,
+        otPlatCryptoRandomGet, otThreadGetNetworkKey,
+    },
+    c_types::c_void,
+};
+
+use crate::{checked, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// Longest output of HKDF-SHA256, 255 hash lengths (RFC 5869)
+pub const HKDF_OUTPUT_MAX_SIZE: usize = 255 * 32;
+
+/// Salt of [OpenThread::derive_application_key], keeps the derived keys apart from the ones
+/// OpenThread derives from the network key itself
+const APPLICATION_KEY_SALT: &[u8] = b"esp-openthread application key";
+
+/// Room for the HKDF context of OpenThread's crypto backend, an HMAC-SHA256 hash with mbedTLS
+const HKDF_CONTEXT_SIZE: usize = 64;
+
+impl<'a> OpenThread<'a> {
+    /// HKDF-SHA256 (RFC 5869) of `input_key` with `salt` and `info` into `output`, using
+    /// OpenThread's crypto backend
+    ///
+    /// `output` takes up to [HKDF_OUTPUT_MAX_SIZE] bytes.
+    pub fn hkdf_sha256(
+        &self,
+        salt: &[u8],
+        input_key: &[u8],
+        info: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), Error> {
+        let input_key_length = u16::try_from(input_key.len()).map_err(|_| INVALID_ARGS)?;
+        let input_key = otCryptoKey {
+            mKey: input_key.as_ptr(),
+            mKeyLength: input_key_length,
+            mKeyRef: 0,
+        };
+        hkdf(salt, &input_key, info, output)
+    }
+
+    /// Derive an application session key from the network key into `output`, e.g. for
+    /// encrypting application payloads end-to-end between devices of the same network
+    ///
+    /// `info` separates keys for different purposes, e.g. a protocol name and version. The
+    /// network key itself is never exposed and the derived keys differ from the ones Thread
+    /// uses, but they change along with the network key. Fails if no network key is set or it
+    /// can't be exported, e.g. because it's kept in the key storage.
+    pub fn derive_application_key(&self, info: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        let mut network_key = otNetworkKey { m8: [0u8; 16] };
+        unsafe { otThreadGetNetworkKey(self.instance, &mut network_key) };
+
+        let result = if network_key.m8 == [0u8; 16] {
+            Err(INVALID_STATE)
+        } else {
+            let input_key = otCryptoKey {
+                mKey: network_key.m8.as_ptr(),
+                mKeyLength: network_key.m8.len() as u16,
+                mKeyRef: 0,
+            };
+            hkdf(APPLICATION_KEY_SALT, &input_key, info, output)
+        };
+
+        // don't leave the key on the stack
+        unsafe { core::ptr::write_volatile(&mut network_key, otNetworkKey { m8: [0u8; 16] }) };
+        result
+    }
+
+    /// Fill `buffer` with random bytes from OpenThread's cryptographically secure generator, e.g.
+    /// for nonces
+    pub fn crypto_random_fill(&self, buffer: &mut [u8]) -> Result<(), Error> {
+        for chunk in buffer.chunks_mut(u16::MAX as usize) {
+            checked!(unsafe { otPlatCryptoRandomGet(chunk.as_mut_ptr(), chunk.len() as u16) })?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn hkdf(
+    salt: &[u8],
+    input_key: &otCryptoKey,
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Error> {
+    if output.len() > HKDF_OUTPUT_MAX_SIZE {
+        return Err(INVALID_ARGS);
+    }
+    let salt_length = u16::try_from(salt.len()).map_err(|_| INVALID_ARGS)?;
+    let info_length = u16::try_from(info.len()).map_err(|_| INVALID_ARGS)?;
+
+    let mut storage = [0u64; HKDF_CONTEXT_SIZE / 8];
+    let mut context = otCryptoContext {
+        mContext: storage.as_mut_ptr() as *mut c_void,
+        mContextSize: HKDF_CONTEXT_SIZE as u16,
+    };
+
+    checked!(unsafe { otPlatCryptoHkdfInit(&mut context) })?;
+    let result = checked!(unsafe {
+        otPlatCryptoHkdfExtract(&mut context, salt.as_ptr(), salt_length, input_key)
+    })
+    .and_then(|_| {
+        checked!(unsafe {
+            otPlatCryptoHkdfExpand(
+                &mut context,
+                info.as_ptr(),
+                info_length,
+                output.as_mut_ptr(),
+                output.len() as u16,
+            )
+        })
+    });
+    unsafe { otPlatCryptoHkdfDeinit(&mut context) };
+
+    // the context holds the pseudorandom key
+    unsafe { core::ptr::write_volatile(&mut storage, [0u64; HKDF_CONTEXT_SIZE / 8]) };
+    result
+}
@@ -0,0 +1,143 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole_OT_DEVICE_ROLE_CHILD, otDeviceRole_OT_DEVICE_ROLE_LEADER,
+    otDeviceRole_OT_DEVICE_ROLE_ROUTER, otError_OT_ERROR_INVALID_STATE, otInstance, otNetworkKey,
+    otThreadGetDeviceRole, otThreadGetKeySequenceCounter, otThreadGetNetworkKey,
+    otThreadSetKeySequenceCounter,
+};
+
+use crate::{ChangedFlags, Error, OpenThread, OperationalDataset};
+
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// The network key a rotation is waiting for
+pub(crate) static KEY_ROTATION: Mutex<RefCell<Option<[u8; 16]>>> = Mutex::new(RefCell::new(None));
+
+pub(crate) static KEY_ROTATION_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(KeyRotationEvent) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Outcome of [OpenThread::rotate_network_key]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyRotationEvent {
+    /// The leader rejected the new key or didn't respond, the network keeps the current one
+    Failed(Error),
+    /// This device uses the new key now, `key_sequence` is the key sequence counter in use
+    Completed { key_sequence: u32 },
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the callback reporting the outcome of [OpenThread::rotate_network_key]
+    pub fn set_key_rotation_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(KeyRotationEvent) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut key_rotation_callback = KEY_ROTATION_CALLBACK.borrow_ref_mut(cs);
+            *key_rotation_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Move the whole network to `new_key` in `delay` milliseconds
+    ///
+    /// The key goes to the leader in a Pending Dataset with fresh timestamps (see
+    /// [OpenThread::migrate_to]). Once the delay expired every device switches to it and resets
+    /// its key sequence counter to 0, as Thread requires. The delay has to give sleepy children
+    /// time to learn about the change, the Thread specification suggests at least 5 minutes.
+    ///
+    /// If `new_key` is the current key only the key sequence counter is advanced, which rotates
+    /// the MAC and MLE keys derived from it. Neighbors follow after the key switch guard time.
+    ///
+    /// The callback set via [OpenThread::set_key_rotation_callback] is called once this device
+    /// uses the new key, or if the leader rejects it.
+    pub fn rotate_network_key(&mut self, new_key: [u8; 16], delay: u32) -> Result<(), Error> {
+        if new_key == self.network_key() {
+            let key_sequence =
+                unsafe { otThreadGetKeySequenceCounter(self.instance) }.wrapping_add(1);
+            unsafe { otThreadSetKeySequenceCounter(self.instance, key_sequence) };
+
+            report(KeyRotationEvent::Completed { key_sequence });
+            return Ok(());
+        }
+
+        #[allow(non_upper_case_globals)]
+        match unsafe { otThreadGetDeviceRole(self.instance) } {
+            otDeviceRole_OT_DEVICE_ROLE_CHILD
+            | otDeviceRole_OT_DEVICE_ROLE_ROUTER
+            | otDeviceRole_OT_DEVICE_ROLE_LEADER => (),
+            _ => return Err(INVALID_STATE),
+        }
+
+        critical_section::with(|cs| *KEY_ROTATION.borrow_ref_mut(cs) = Some(new_key));
+
+        let res = self.migrate_to(
+            OperationalDataset {
+                network_key: Some(new_key),
+                ..OperationalDataset::default()
+            },
+            delay,
+        );
+        if res.is_err() {
+            critical_section::with(|cs| KEY_ROTATION.borrow_ref_mut(cs).take());
+        }
+        res
+    }
+
+    fn network_key(&self) -> [u8; 16] {
+        network_key(self.instance)
+    }
+}
+
+fn network_key(instance: *mut otInstance) -> [u8; 16] {
+    let mut key = otNetworkKey { m8: [0u8; 16] };
+    unsafe { otThreadGetNetworkKey(instance, &mut key) };
+    key.m8
+}
+
+fn report(event: KeyRotationEvent) {
+    log::debug!("key rotation {:?}", event);
+
+    critical_section::with(|cs| {
+        let mut callback = KEY_ROTATION_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(event);
+        }
+    });
+}
+
+/// Complete a pending rotation once OpenThread switched to the new key
+pub(crate) fn on_changes(instance: *mut otInstance, flags: ChangedFlags) {
+    if !flags.contains(ChangedFlags::ThreadNetworkKeyChanged) {
+        return;
+    }
+
+    let current = network_key(instance);
+    let completed = critical_section::with(|cs| {
+        let mut key_rotation = KEY_ROTATION.borrow_ref_mut(cs);
+        if *key_rotation == Some(current) {
+            key_rotation.take();
+            true
+        } else {
+            false
+        }
+    });
+
+    if completed {
+        let key_sequence = unsafe { otThreadGetKeySequenceCounter(instance) };
+        report(KeyRotationEvent::Completed { key_sequence });
+    }
+}
+
+/// Fail a pending rotation if the leader rejected the Pending Dataset
+pub(crate) fn on_migration_result(result: Result<(), Error>) {
+    let Err(error) = result else {
+        return;
+    };
+
+    let failed = critical_section::with(|cs| KEY_ROTATION.borrow_ref_mut(cs).take().is_some());
+    if failed {
+        report(KeyRotationEvent::Failed(error));
+    }
+}
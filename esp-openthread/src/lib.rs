@@ -1,10 +1,92 @@
 #![no_std]
 #![feature(c_variadic)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "async")]
+mod asynch;
+mod attach;
+mod auto_refresh;
+#[cfg(feature = "channel-stats")]
+mod channel_stats;
+mod chip;
+#[cfg(feature = "coap-secure")]
+mod coap_secure;
+mod commissioning_code;
+mod dataset;
+mod dataset_migration;
+#[cfg(feature = "device-info")]
+mod device_info;
+#[cfg(feature = "diag")]
+mod diag;
+#[cfg(feature = "dns-client")]
+mod dns_client;
+#[cfg(feature = "wifi-coex")]
+mod dual_stack;
+#[cfg(feature = "ecdsa")]
+mod ecdsa;
+#[cfg(feature = "embedded-dataset")]
+mod embedded_dataset;
 mod entropy;
+mod fast_poll;
+mod frame_counter;
+#[cfg(feature = "global")]
+mod global;
+#[cfg(feature = "test-harness")]
+mod harness;
+#[cfg(feature = "instrumentation")]
+mod health;
+mod ip6;
+#[cfg(feature = "joiner")]
+mod joiner;
+#[cfg(feature = "event-journal")]
+mod journal;
+#[cfg(feature = "key-derivation")]
+mod key_derivation;
+mod key_rotation;
+mod link;
+#[cfg(feature = "link-raw")]
+mod link_raw;
+mod logging;
+mod memory;
+mod message;
+#[cfg(feature = "message-trace")]
+mod message_trace;
+#[cfg(feature = "embedded-nal-async")]
+mod nal;
+#[cfg(feature = "embassy-net")]
+mod net_driver;
+mod netdata;
+#[cfg(feature = "link-raw")]
+mod pan;
+mod parent_search;
+#[cfg(feature = "ping")]
+mod ping;
 mod platform;
+mod printf;
+#[cfg(feature = "provisioning")]
+mod provisioning;
+mod proximity;
 mod radio;
+#[cfg(feature = "link-raw")]
+mod ranging;
+mod rloc;
+#[cfg(feature = "settings-encryption")]
+mod settings;
+#[cfg(feature = "srp-client")]
+mod srp_client;
+#[cfg(feature = "tcp")]
+mod tcp;
+#[cfg(feature = "testutil")]
+mod testutil;
 mod timer;
+#[cfg(feature = "traffic-meter")]
+mod traffic_meter;
+mod txt;
+#[cfg(feature = "wake-up-end-device")]
+mod wake_up;
+mod watchdog;
 
 use bitflags::bitflags;
 use core::{
@@ -14,23 +96,163 @@ use core::{
     mem::transmute,
     pin::Pin,
     ptr::addr_of_mut,
+    task::{Context, Poll, Waker},
 };
 use critical_section::Mutex;
+use esp_hal::interrupt::Priority;
 use esp_hal::systimer::{Alarm, Target};
 use esp_ieee802154::{rssi_to_lqi, Ieee802154};
+use fugit::MillisDurationU64;
 
 // for now just re-export all
 pub use esp_openthread_sys as sys;
+#[cfg(feature = "diag")]
+use esp_openthread_sys::bindings::otPlatDiagRadioReceiveDone;
 use esp_openthread_sys::bindings::otPlatRadioReceiveDone;
 use no_std_net::Ipv6Addr;
 use sys::{
     bindings::{
-        __BindgenBitfieldUnit, otChangedFlags, otDatasetGetActive, otDatasetSetActive, otError_OT_ERROR_NONE, otExtendedPanId, otInstance, otInstanceInitSingle, otIp6AddUnicastAddress, otIp6Address, otIp6AddressFromString, otIp6Address__bindgen_ty_1, otIp6GetUnicastAddresses, otIp6SetEnabled, otMeshLocalPrefix, otMessage, otMessageAppend, otMessageFree, otMessageGetLength, otMessageInfo, otMessageRead, otNetifAddress, otNetifIdentifier_OT_NETIF_THREAD, otNetworkKey, otNetworkName, otOperationalDataset, otOperationalDatasetComponents, otPskc, otRadioFrame, otRadioFrame__bindgen_ty_1, otRadioFrame__bindgen_ty_1__bindgen_ty_2, otSecurityPolicy, otSetStateChangedCallback, otSockAddr, otTaskletsArePending, otTaskletsProcess, otThreadGetDeviceRole, otThreadSetEnabled, otTimestamp, otUdpBind, otUdpClose, otUdpNewMessage, otUdpOpen, otUdpSend, otUdpSocket
+        __BindgenBitfieldUnit, otChangedFlags, otDatasetGetActive, otDatasetSetActive, otError, otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE, otError_OT_ERROR_NO_BUFS, otError_OT_ERROR_NO_ROUTE, otExtendedPanId, otInstance, otInstanceInitSingle, otIp6AddUnicastAddress, otIp6Address, otIp6AddressFromString, otIp6Address__bindgen_ty_1, otIp6GetUnicastAddresses, otIp6SetEnabled, otLinkGetChannel, otLinkGetPanId, otLinkSetChannel, otLinkSetPanId, otMeshLocalPrefix, otMessage, otMessageAppend, otMessageFree, otMessageGetLength, otMessageGetOffset, otMessageInfo, otMessageRead, otNetifAddress, otNetworkKey, otNetworkName, otOperationalDataset, otOperationalDatasetComponents, otPskc, otRadioFrame, otRadioFrame__bindgen_ty_1, otRadioFrame__bindgen_ty_1__bindgen_ty_2, otSecurityPolicy, otSetStateChangedCallback, otSockAddr, otTaskletsArePending, otTaskletsProcess, otThreadGetDeviceRole, otThreadGetExtendedPanId, otThreadGetMeshLocalPrefix, otThreadGetNetworkName, otThreadSetEnabled, otThreadSetExtendedPanId, otThreadSetNetworkName, otTimestamp, otUdpBind, otUdpClose, otUdpNewMessage, otUdpOpen, otUdpSend, otUdpSocket
     },
     c_types::c_void,
 };
 
-use crate::timer::current_millis;
+use crate::{
+    chip::{Chip, ChipOps},
+    timer::current_millis,
+};
+
+#[cfg(feature = "async")]
+pub use asynch::{wait_for_work, ASYNC_HOUSEKEEPING_INTERVAL};
+pub use attach::AttachEvent;
+pub use auto_refresh::{MulticastGroup, AUTO_REFRESH_MAX_GROUPS};
+#[cfg(feature = "channel-stats")]
+pub use channel_stats::{ChannelStats, CHANNEL_STATS_SAMPLE_INTERVAL};
+#[cfg(feature = "coap-secure")]
+pub use coap_secure::{
+    CoapCode, CoapMethod, CoapSecureReply, CoapSecureRequest, CoapSecureResponse,
+    COAP_SECURE_DEFAULT_PORT, COAP_SECURE_PSK_IDENTITY_MAX_SIZE, COAP_SECURE_PSK_MAX_SIZE,
+    COAP_SECURE_RESPONSE_MAX_SIZE, COAP_URI_PATH_MAX_SIZE,
+};
+pub use commissioning_code::{joiner_id_from_eui64, verhoeff_checksum, verhoeff_validate};
+pub use dataset::{ExtendedPanId, MeshLocalPrefix, NetworkName};
+pub use dataset_migration::{DatasetComponents, CHANNEL_CHANGE_DELAY};
+#[cfg(feature = "device-info")]
+pub use device_info::DeviceInfoFormat;
+#[cfg(feature = "diag")]
+pub use diag::{DiagGpio, GpioMode, DIAG_CMD_LINE_MAX_SIZE};
+#[cfg(feature = "dns-client")]
+pub use dns_client::{DnsNat64Mode, DnsQueryConfig, DnsRecursion, DNS_CLIENT_MAX_ADDRESSES};
+#[cfg(feature = "wifi-coex")]
+pub use dual_stack::{CoexConfig, NetStack};
+#[cfg(feature = "ecdsa")]
+pub use ecdsa::{EcdsaKeyPair, EcdsaPublicKey, EcdsaSignature, Sha256Hash};
+#[cfg(feature = "embedded-dataset")]
+pub use embedded_dataset::{DatasetTransform, EmbeddedDataset};
+pub use fast_poll::FAST_POLL_PERIOD;
+pub use frame_counter::FrameCounters;
+#[cfg(feature = "global")]
+pub use global::{take_global_openthread, with_openthread};
+#[cfg(feature = "test-harness")]
+pub use harness::{InjectedFrame, TransmittedFrame};
+#[cfg(feature = "instrumentation")]
+pub use health::HealthStats;
+pub use ip6::{
+    ipv6_addr_from_ot_ip6_address, ipv6_addr_scope, ipv6_addr_to_string,
+    ot_ip6_address_from_ipv6_addr, parse_ipv6_addr, Ipv6AddressInfo, Ipv6Prefix, NetifIdentifier,
+};
+#[cfg(feature = "joiner")]
+pub use joiner::{
+    joiner_qr_payload, JoinerEvent, JoinerQrPayload, JoinerState, JoinerVendorInfo,
+    JOINER_ADVERTISEMENT_DATA_MAX_LENGTH, JOINER_QR_PAYLOAD_MAX_SIZE,
+};
+#[cfg(feature = "event-journal")]
+pub use journal::{
+    DetachReason, JournalCounters, JournalEntry, JournalEvent, JOURNAL_ENTRY_SIZE,
+    JOURNAL_MAX_ENTRIES,
+};
+#[cfg(feature = "key-derivation")]
+pub use key_derivation::HKDF_OUTPUT_MAX_SIZE;
+pub use key_rotation::KeyRotationEvent;
+#[cfg(feature = "retry-histogram")]
+pub use link::{RetryHistogram, RETRY_HISTOGRAM_MAX_ENTRIES};
+#[cfg(feature = "link-raw")]
+pub use link_raw::{
+    FrameKeyId, FrameSecurity, RawFrame, RxWindowSchedule, THREAD_MAC_SECURITY_LEVEL,
+};
+#[cfg(feature = "log-ring-buffer")]
+pub use logging::LOG_RING_BUFFER_SIZE;
+pub use logging::{LogLevel, LogSink, LogWriter, LOG_LINE_MAX_SIZE};
+pub use memory::{static_ram_usage, StaticRamUsage, RX_PSDU_SIZE, RX_QUEUE_FRAMES};
+pub use message::MessageReader;
+#[cfg(feature = "message-trace")]
+pub use message_trace::{MessageDirection, MessagePriority, MessageTrace};
+#[cfg(feature = "embedded-nal-async")]
+pub use nal::{NalConnectedUdp, NalUnconnectedUdp};
+#[cfg(feature = "embassy-net")]
+pub use net_driver::{
+    NetDriver, NetRxToken, NetTxToken, NET_DRIVER_MTU, NET_DRIVER_RX_QUEUE_SIZE,
+    NET_DRIVER_TX_QUEUE_SIZE,
+};
+pub use netdata::NetworkDataVersions;
+#[cfg(feature = "link-raw")]
+pub use pan::{
+    PanAddress, PanCoordinatorConfig, PanDevice, PanEvent, PAN_BEACON_PAYLOAD_MAX_SIZE,
+    PAN_BROADCAST_ADDRESS, PAN_COORDINATOR_SHORT_ADDRESS, PAN_DATA_PAYLOAD_MAX_SIZE,
+    PAN_MAX_DEVICES,
+};
+pub use parent_search::{
+    PARENT_SEARCH_DEFAULT_CHECK_INTERVAL, PARENT_SEARCH_DEFAULT_RSSI_THRESHOLD,
+};
+#[cfg(feature = "ping")]
+pub use ping::{
+    PingReply, PingStats, RttStats, PING_RTT_HISTOGRAM_BUCKETS, PING_RTT_WINDOW_MAX_SIZE,
+};
+#[cfg(feature = "provisioning")]
+pub use provisioning::{
+    ProvisioningError, ProvisioningOp, PROVISIONING_HEADER_SIZE, PROVISIONING_OP_SET_ACTIVE,
+    PROVISIONING_OP_SET_PENDING,
+};
+pub use proximity::{PeerProximity, PROXIMITY_MAX_PEERS};
+pub use radio::{
+    RadioCalibration, RadioCapabilities, RadioClockAccuracy, RadioFrontEnd,
+    RadioTemperatureCompensation, RadioThroughput, RADIO_CAPABILITIES, RADIO_CHANNEL_COUNT,
+};
+#[cfg(feature = "link-raw")]
+pub use ranging::{two_way_time_of_flight, TimestampedTxDone, RADIO_TIMESTAMP_TICKS_PER_SECOND};
+pub use rloc::{
+    child_id_from_rloc16, is_router_rloc16, parent_rloc16, rloc16_from_address,
+    rloc16_from_router_id, rloc_address, router_id_from_rloc16, service_aloc16, INVALID_RLOC16,
+    LEADER_ALOC16, SERVICE_ALOC16_MAX, SERVICE_ALOC16_MIN,
+};
+#[cfg(feature = "settings-encryption")]
+pub use settings::{
+    set_settings_storage, SettingsKey, SettingsStorage, SETTINGS_RECORD_MAX_SIZE,
+    SETTINGS_RECORD_OVERHEAD, SETTINGS_VALUE_MAX_SIZE,
+};
+#[cfg(feature = "srp-client")]
+pub use srp_client::{
+    SrpClientEvent, SrpClientItemState, SrpClientService, SrpClientServices,
+    SRP_CLIENT_MAX_HOST_ADDRESSES, SRP_CLIENT_MAX_SERVICES, SRP_CLIENT_MAX_SUBTYPES,
+    SRP_CLIENT_NAME_MAX_SIZE, SRP_CLIENT_SUBTYPES_MAX_SIZE, SRP_CLIENT_TXT_DATA_MAX_SIZE,
+};
+#[cfg(feature = "tcp")]
+pub use tcp::{TcpListener, TcpSocket};
+#[cfg(feature = "testutil")]
+pub use testutil::{
+    BenchmarkStats, UdpEcho, UdpGenerator, UdpSink, BENCHMARK_HEADER_SIZE, BENCHMARK_PORT,
+    ECHO_PORT,
+};
+pub use timer::{set_alarm_interrupt_handler, TimerAlarm};
+#[cfg(feature = "traffic-meter")]
+pub use traffic_meter::{
+    TrafficRates, TRAFFIC_METER_DEFAULT_WINDOW, TRAFFIC_METER_MAX_WINDOW,
+    TRAFFIC_METER_SAMPLE_INTERVAL,
+};
+pub use txt::{TxtData, TxtEntries, TxtEntry, TXT_ENTRY_MAX_SIZE};
+#[cfg(feature = "wake-up-end-device")]
+pub use wake_up::WakeUpListenParameters;
+pub use watchdog::Watchdog;
 
 static RADIO: Mutex<RefCell<Option<&'static mut Ieee802154>>> = Mutex::new(RefCell::new(None));
 
@@ -39,7 +261,8 @@ static NETWORK_SETTINGS: Mutex<RefCell<Option<NetworkSettings>>> = Mutex::new(Re
 static CHANGE_CALLBACK: Mutex<RefCell<Option<&'static mut (dyn FnMut(ChangedFlags) + Send)>>> =
     Mutex::new(RefCell::new(None));
 
-static mut RCV_FRAME_PSDU: [u8; 127] = [0u8; 127];
+#[cfg_attr(feature = "lp-ram-buffers", link_section = ".rtc_fast.bss")]
+static mut RCV_FRAME_PSDU: [u8; RX_PSDU_SIZE] = [0u8; RX_PSDU_SIZE];
 static mut RCV_FRAME: otRadioFrame = otRadioFrame {
     mPsdu: unsafe { addr_of_mut!(RCV_FRAME_PSDU) as *mut u8 },
     mLength: 0,
@@ -58,6 +281,14 @@ static mut RCV_FRAME: otRadioFrame = otRadioFrame {
     },
 };
 
+const MULTICAST_SCOPE_REALM_LOCAL: u8 = 3;
+const MULTICAST_SCOPE_SITE_LOCAL: u8 = 5;
+/// Multicast address flag marking a unicast-prefix-based address
+const MULTICAST_FLAG_PREFIX: u8 = 0x2;
+
+/// Static RAM taken by the receive frame buffer
+const RCV_FRAME_SIZE: usize = RX_PSDU_SIZE + core::mem::size_of::<otRadioFrame>();
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! checked {
@@ -75,6 +306,20 @@ pub enum Error {
     InternalError(u32),
 }
 
+#[cfg(any(feature = "embedded-io", feature = "embedded-nal-async"))]
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+/// UDP ports OpenThread binds on the Thread interface
+const RESERVED_UDP_PORTS: &[(u16, &str)] = &[
+    (19788, "MLE"),
+    (61631, "TMF"),
+    (1000, "MeshCoP joiner / joiner router"),
+];
+
 bitflags! {
     /// Specific state/configuration that has changed
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -139,11 +384,67 @@ bitflags! {
         const ActiveDatasetChanged = 268435456;
         /// Pending Operational Dataset changed
         const PendingDatasetChanged = 536870912;
+    }
+}
 
-
+impl ChangedFlags {
+    /// Bits OpenThread set which aren't known here, e.g. flags added by a newer OpenThread
+    pub fn unknown_bits(&self) -> u32 {
+        self.bits() & !Self::all().bits()
     }
 }
 
+// the flags have to match OpenThread's, e.g. after updating the bindings
+const _: () = {
+    use sys::bindings::*;
+
+    assert!(ChangedFlags::Ipv6AddressAdded.bits() == OT_CHANGED_IP6_ADDRESS_ADDED);
+    assert!(ChangedFlags::Ipv6AddressRemoved.bits() == OT_CHANGED_IP6_ADDRESS_REMOVED);
+    assert!(ChangedFlags::ThreadRoleChanged.bits() == OT_CHANGED_THREAD_ROLE);
+    assert!(ChangedFlags::ThreadLlAddressChanged.bits() == OT_CHANGED_THREAD_LL_ADDR);
+    assert!(ChangedFlags::ThreadMeshLocalAddressChanged.bits() == OT_CHANGED_THREAD_ML_ADDR);
+    assert!(ChangedFlags::ThreadRlocAdded.bits() == OT_CHANGED_THREAD_RLOC_ADDED);
+    assert!(ChangedFlags::ThreadRlocRemoved.bits() == OT_CHANGED_THREAD_RLOC_REMOVED);
+    assert!(ChangedFlags::ThreadPartitionIdChanged.bits() == OT_CHANGED_THREAD_PARTITION_ID);
+    assert!(
+        ChangedFlags::ThreadKeySequenceChanged.bits() == OT_CHANGED_THREAD_KEY_SEQUENCE_COUNTER
+    );
+    assert!(ChangedFlags::ThreadNetworkDataChanged.bits() == OT_CHANGED_THREAD_NETDATA);
+    assert!(ChangedFlags::ThreadChildAdded.bits() == OT_CHANGED_THREAD_CHILD_ADDED);
+    assert!(ChangedFlags::ThreadChildRemoved.bits() == OT_CHANGED_THREAD_CHILD_REMOVED);
+    assert!(ChangedFlags::Ipv6MulticastSubscribed.bits() == OT_CHANGED_IP6_MULTICAST_SUBSCRIBED);
+    assert!(
+        ChangedFlags::Ipv6MulticastUnsubscribed.bits() == OT_CHANGED_IP6_MULTICAST_UNSUBSCRIBED
+    );
+    assert!(ChangedFlags::ThreadNetworkChannelChanged.bits() == OT_CHANGED_THREAD_CHANNEL);
+    assert!(ChangedFlags::ThreadPanIdChanged.bits() == OT_CHANGED_THREAD_PANID);
+    assert!(ChangedFlags::ThreadNetworkNameChanged.bits() == OT_CHANGED_THREAD_NETWORK_NAME);
+    assert!(ChangedFlags::ThreadExtendedPanIdChanged.bits() == OT_CHANGED_THREAD_EXT_PANID);
+    assert!(ChangedFlags::ThreadNetworkKeyChanged.bits() == OT_CHANGED_NETWORK_KEY);
+    assert!(ChangedFlags::ThreadPskcChanged.bits() == OT_CHANGED_PSKC);
+    assert!(ChangedFlags::ThreadSecurityPolicyChanged.bits() == OT_CHANGED_SECURITY_POLICY);
+    assert!(
+        ChangedFlags::ChannelManagerNewChannelChanged.bits()
+            == OT_CHANGED_CHANNEL_MANAGER_NEW_CHANNEL
+    );
+    assert!(ChangedFlags::SupportedChannelMaskChanged.bits() == OT_CHANGED_SUPPORTED_CHANNEL_MASK);
+    assert!(ChangedFlags::CommissionerStateChanged.bits() == OT_CHANGED_COMMISSIONER_STATE);
+    assert!(
+        ChangedFlags::ThreadNetworkInterfaceStateChanged.bits() == OT_CHANGED_THREAD_NETIF_STATE
+    );
+    assert!(
+        ChangedFlags::ThreadBackboneRouterStateChanged.bits()
+            == OT_CHANGED_THREAD_BACKBONE_ROUTER_STATE
+    );
+    assert!(
+        ChangedFlags::ThreadBackboneRouterLocalChanged.bits()
+            == OT_CHANGED_THREAD_BACKBONE_ROUTER_LOCAL
+    );
+    assert!(ChangedFlags::JoinerStateChanged.bits() == OT_CHANGED_JOINER_STATE);
+    assert!(ChangedFlags::ActiveDatasetChanged.bits() == OT_CHANGED_ACTIVE_DATASET);
+    assert!(ChangedFlags::PendingDatasetChanged.bits() == OT_CHANGED_PENDING_DATASET);
+};
+
 /// IPv6 network interface unicast address
 #[derive(Debug, Clone, Copy)]
 pub struct NetworkInterfaceUnicastAddress {
@@ -155,8 +456,18 @@ pub struct NetworkInterfaceUnicastAddress {
     pub origin: u8,
 }
 
+impl Default for NetworkInterfaceUnicastAddress {
+    fn default() -> Self {
+        Self {
+            address: no_std_net::Ipv6Addr::UNSPECIFIED,
+            prefix: 0,
+            origin: 0,
+        }
+    }
+}
+
 /// Thread Dataset timestamp
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ThreadTimestamp {
     pub seconds: u64,
     pub ticks: u16,
@@ -164,7 +475,7 @@ pub struct ThreadTimestamp {
 }
 
 /// Security Policy
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct SecurityPolicy {
     /// The value for thrKeyRotation in units of hours.
     pub rotation_time: u16,
@@ -178,7 +489,7 @@ pub struct SecurityPolicy {
     pub native_commissioning_enabled: bool,
     /// Network Key Provisioning is enabled.
     pub network_key_provisioning_enabled: bool,
-    /// Non-CCM Routers enabled.
+    /// Non-CCM Routers enabled, only meaningful with Commercial Commissioning enabled.
     pub non_ccm_routers_enabled: bool,
     /// Obtaining the Network Key for out-of-band commissioning is enabled.
     pub obtain_network_key_enabled: bool,
@@ -186,12 +497,107 @@ pub struct SecurityPolicy {
     pub routers_enabled: bool,
     /// ToBLE link is enabled.
     pub toble_link_enabled: bool,
-    /// Version-threshold for Routing.
+    /// Version-threshold for Routing, Thread 1.2+ devices need at least Thread version
+    /// `SecurityPolicy::VERSION_THRESHOLD_OFFSET + version_threshold_for_routing` to become a
+    /// router, see [SecurityPolicy::routing_allowed].
     pub version_threshold_for_routing: u8,
 }
 
+impl SecurityPolicy {
+    /// Shortest key rotation time (in hours) OpenThread accepts
+    pub const MIN_ROTATION_TIME: u16 = 1;
+    /// Largest `version_threshold_for_routing`, the field is three bits wide
+    pub const VERSION_THRESHOLD_FOR_ROUTING_MAX: u8 = 7;
+    /// Thread version a `version_threshold_for_routing` of 0 stands for, 3 is Thread 1.2
+    pub const VERSION_THRESHOLD_OFFSET: u16 = 3;
+
+    /// Returns an error if the policy can't be put into a dataset as is
+    ///
+    /// A too short rotation time would be rejected by OpenThread and a too large version
+    /// threshold would silently be truncated.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.rotation_time < Self::MIN_ROTATION_TIME
+            || self.version_threshold_for_routing > Self::VERSION_THRESHOLD_FOR_ROUTING_MAX
+        {
+            return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+        }
+
+        Ok(())
+    }
+
+    /// Lowest Thread version (as returned by `otThreadGetVersion`, 3 is Thread 1.2) a Thread
+    /// 1.2+ device needs to become a router
+    pub fn min_routing_version(&self) -> u16 {
+        Self::VERSION_THRESHOLD_OFFSET + self.version_threshold_for_routing as u16
+    }
+
+    /// Set the version threshold for routing from the lowest Thread version allowed to route
+    ///
+    /// Returns an error for versions before Thread 1.2, their routers are allowed via
+    /// `routers_enabled` instead, and for versions beyond what the threshold can express.
+    pub fn set_min_routing_version(&mut self, version: u16) -> Result<(), Error> {
+        let threshold = version
+            .checked_sub(Self::VERSION_THRESHOLD_OFFSET)
+            .filter(|threshold| *threshold <= Self::VERSION_THRESHOLD_FOR_ROUTING_MAX as u16)
+            .ok_or(Error::InternalError(otError_OT_ERROR_INVALID_ARGS))?;
+        self.version_threshold_for_routing = threshold as u8;
+        Ok(())
+    }
+
+    /// Returns `true` if a router-capable device running Thread `version` may become a router
+    ///
+    /// Thread 1.1 devices are allowed if `routers_enabled` is set, later ones if they reach
+    /// [SecurityPolicy::min_routing_version].
+    pub fn routing_allowed(&self, version: u16) -> bool {
+        if version < Self::VERSION_THRESHOLD_OFFSET {
+            self.routers_enabled
+        } else {
+            version >= self.min_routing_version()
+        }
+    }
+}
+
+impl From<otSecurityPolicy> for SecurityPolicy {
+    fn from(value: otSecurityPolicy) -> Self {
+        Self {
+            rotation_time: value.mRotationTime,
+            autonomous_enrollment_enabled: value.mAutonomousEnrollmentEnabled(),
+            commercial_commissioning_enabled: value.mCommercialCommissioningEnabled(),
+            external_commissioning_enabled: value.mExternalCommissioningEnabled(),
+            native_commissioning_enabled: value.mNativeCommissioningEnabled(),
+            network_key_provisioning_enabled: value.mNetworkKeyProvisioningEnabled(),
+            non_ccm_routers_enabled: value.mNonCcmRoutersEnabled(),
+            obtain_network_key_enabled: value.mObtainNetworkKeyEnabled(),
+            routers_enabled: value.mRoutersEnabled(),
+            toble_link_enabled: value.mTobleLinkEnabled(),
+            version_threshold_for_routing: value.mVersionThresholdForRouting(),
+        }
+    }
+}
+
+impl From<SecurityPolicy> for otSecurityPolicy {
+    fn from(value: SecurityPolicy) -> Self {
+        otSecurityPolicy {
+            mRotationTime: value.rotation_time,
+            _bitfield_align_1: [0u8; 0],
+            _bitfield_1: otSecurityPolicy::new_bitfield_1(
+                value.obtain_network_key_enabled,
+                value.native_commissioning_enabled,
+                value.routers_enabled,
+                value.external_commissioning_enabled,
+                value.commercial_commissioning_enabled,
+                value.autonomous_enrollment_enabled,
+                value.network_key_provisioning_enabled,
+                value.toble_link_enabled,
+                value.non_ccm_routers_enabled,
+                value.version_threshold_for_routing,
+            ),
+        }
+    }
+}
+
 /// Active or Pending Operational Dataset
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct OperationalDataset {
     /// Active Timestamp
     pub active_timestamp: Option<ThreadTimestamp>,
@@ -200,11 +606,11 @@ pub struct OperationalDataset {
     /// Network Key
     pub network_key: Option<[u8; 16]>,
     /// Network name
-    pub network_name: Option<heapless::String<16>>,
+    pub network_name: Option<NetworkName>,
     /// Extended PAN ID
-    pub extended_pan_id: Option<[u8; 8]>,
+    pub extended_pan_id: Option<ExtendedPanId>,
     /// Mesh Local Prefix
-    pub mesh_local_prefix: Option<[u8; 8]>,
+    pub mesh_local_prefix: Option<MeshLocalPrefix>,
     /// Delay Timer
     pub delay: Option<u32>,
     /// PAN ID
@@ -219,7 +625,7 @@ pub struct OperationalDataset {
     pub channel_mask: Option<u32>,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 struct NetworkSettings {
     promiscuous: bool,
     ext_address: u64,
@@ -227,7 +633,7 @@ struct NetworkSettings {
     pan_id: u16,
     channel: u8,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThreadDeviceRole {
     Leader,
     Child,
@@ -249,6 +655,24 @@ impl ThreadDeviceRole {
     }
 }
 
+/// Interrupt priorities used by OpenThread
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptPriorities {
+    /// Priority of the systimer alarm driving OpenThread's timers
+    pub timer: Priority,
+    /// Priority of the 802.15.4 radio interrupt, `None` keeps the priority set by `esp-ieee802154`
+    pub radio: Option<Priority>,
+}
+
+impl Default for InterruptPriorities {
+    fn default() -> Self {
+        Self {
+            timer: Priority::Priority1,
+            radio: None,
+        }
+    }
+}
+
 /// Instance of OpenThread
 #[non_exhaustive]
 pub struct OpenThread<'a> {
@@ -258,11 +682,32 @@ pub struct OpenThread<'a> {
 }
 
 impl<'a> OpenThread<'a> {
-    pub fn new(radio: &'a mut Ieee802154, timer: Alarm<Target, 0>, rng: esp_hal::Rng) -> Self {
-        timer::install_isr(timer);
+    /// Initialize OpenThread with its timers on systimer alarm `N`, see [TimerAlarm]
+    pub fn new<const N: u8>(
+        radio: &'a mut Ieee802154,
+        timer: Alarm<Target, N>,
+        rng: esp_hal::Rng,
+    ) -> Self
+    where
+        Alarm<Target, N>: TimerAlarm,
+    {
+        Self::new_with_priorities(radio, timer, rng, InterruptPriorities::default())
+    }
+
+    /// Like [OpenThread::new] but with custom interrupt priorities
+    pub fn new_with_priorities<const N: u8>(
+        radio: &'a mut Ieee802154,
+        timer: Alarm<Target, N>,
+        rng: esp_hal::Rng,
+        priorities: InterruptPriorities,
+    ) -> Self
+    where
+        Alarm<Target, N>: TimerAlarm,
+    {
+        Chip::init_timer(timer, priorities.timer);
         entropy::init_rng(rng);
 
-        radio.set_tx_done_callback_fn(radio::trigger_tx_done);
+        Chip::init_radio(radio, priorities.radio);
 
         critical_section::with(|cs| {
             RADIO
@@ -271,10 +716,14 @@ impl<'a> OpenThread<'a> {
         });
 
         let instance = unsafe { otInstanceInitSingle() };
-        log::debug!("otInstanceInitSingle done, instance = {:p}", instance);
+        log::debug!(
+            "otInstanceInitSingle done on {}, instance = {:p}",
+            <Chip as ChipOps>::NAME,
+            instance
+        );
 
         let res = unsafe {
-            otSetStateChangedCallback(instance, Some(change_callback), core::ptr::null_mut())
+            otSetStateChangedCallback(instance, Some(change_callback), instance as *mut _)
         };
         log::debug!("otSetStateChangedCallback {res}");
 
@@ -285,167 +734,18 @@ impl<'a> OpenThread<'a> {
     }
 
     /// Sets the Active Operational Dataset
+    ///
+    /// Returns an error if the security policy doesn't [validate](SecurityPolicy::validate).
     pub fn set_active_dataset(&mut self, dataset: OperationalDataset) -> Result<(), Error> {
-        let mut raw_dataset = otOperationalDataset {
-            mActiveTimestamp: otTimestamp {
-                mSeconds: 0,
-                mTicks: 0,
-                mAuthoritative: false,
-            },
-            mPendingTimestamp: otTimestamp {
-                mSeconds: 0,
-                mTicks: 0,
-                mAuthoritative: false,
-            },
-            mNetworkKey: otNetworkKey { m8: [0u8; 16] },
-            mNetworkName: otNetworkName { m8: [0i8; 17] },
-            mExtendedPanId: otExtendedPanId { m8: [0u8; 8] },
-            mMeshLocalPrefix: otMeshLocalPrefix { m8: [0u8; 8] },
-            mDelay: 0,
-            mPanId: 0,
-            mChannel: 0,
-            mPskc: otPskc { m8: [0u8; 16] },
-            mSecurityPolicy: otSecurityPolicy {
-                mRotationTime: 0,
-                _bitfield_align_1: [0u8; 0],
-                _bitfield_1: otSecurityPolicy::new_bitfield_1(
-                    false, false, false, false, false, false, false, false, false, 0,
-                ),
-            },
-            mChannelMask: 0,
-            mComponents: otOperationalDatasetComponents {
-                _bitfield_align_1: [0u8; 0],
-                _bitfield_1: otOperationalDatasetComponents::new_bitfield_1(
-                    true, false, true, true, true, false, false, true, true, false, false, false,
-                ),
-            },
-        };
-
-        let mut active_timestamp_present = false;
-        let mut pending_timestamp_present = false;
-        let mut network_key_present = false;
-        let mut network_name_present = false;
-        let mut extended_pan_present = false;
-        let mut mesh_local_prefix_present = false;
-        let mut delay_present = false;
-        let mut pan_id_present = false;
-        let mut channel_present = false;
-        let mut pskc_present = false;
-        let mut security_policy_present = false;
-        let mut channel_mask_present = false;
-
-        if let Some(active_timestamp) = dataset.active_timestamp {
-            raw_dataset.mActiveTimestamp = otTimestamp {
-                mSeconds: active_timestamp.seconds,
-                mTicks: active_timestamp.ticks,
-                mAuthoritative: active_timestamp.authoritative,
-            };
-            active_timestamp_present = true;
-        }
-
-        if let Some(pending_timestamp) = dataset.pending_timestamp {
-            raw_dataset.mActiveTimestamp = otTimestamp {
-                mSeconds: pending_timestamp.seconds,
-                mTicks: pending_timestamp.ticks,
-                mAuthoritative: pending_timestamp.authoritative,
-            };
-            pending_timestamp_present = true;
-        }
-
-        if let Some(network_key) = dataset.network_key {
-            raw_dataset.mNetworkKey = otNetworkKey { m8: network_key };
-            network_key_present = true;
-        }
-
-        if let Some(network_name) = dataset.network_name {
-            let mut raw = [0i8; 17];
-            raw[..network_name.len()]
-                .copy_from_slice(unsafe { core::mem::transmute(network_name.as_bytes()) });
-            raw_dataset.mNetworkName = otNetworkName { m8: raw };
-            network_name_present = true;
-        }
-
-        if let Some(extended_pan_id) = dataset.extended_pan_id {
-            raw_dataset.mExtendedPanId = otExtendedPanId {
-                m8: extended_pan_id,
-            };
-            extended_pan_present = true;
-        }
-
-        if let Some(mesh_local_prefix) = dataset.mesh_local_prefix {
-            raw_dataset.mMeshLocalPrefix = otMeshLocalPrefix {
-                m8: mesh_local_prefix,
-            };
-            mesh_local_prefix_present = true;
-        }
-
-        if let Some(delay) = dataset.delay {
-            raw_dataset.mDelay = delay;
-            delay_present = true;
-        }
-
-        if let Some(pan_id) = dataset.pan_id {
-            raw_dataset.mPanId = pan_id;
-            pan_id_present = true;
-        }
-
-        if let Some(channel) = dataset.channel {
-            raw_dataset.mChannel = channel;
-            channel_present = true;
-        }
-
-        if let Some(pskc) = dataset.pskc {
-            raw_dataset.mPskc = otPskc { m8: pskc };
-            pskc_present = true;
-        }
-
-        if let Some(security_policy) = dataset.security_policy {
-            raw_dataset.mSecurityPolicy = otSecurityPolicy {
-                mRotationTime: security_policy.rotation_time,
-                _bitfield_align_1: [0u8; 0],
-                _bitfield_1: otSecurityPolicy::new_bitfield_1(
-                    security_policy.obtain_network_key_enabled,
-                    security_policy.native_commissioning_enabled,
-                    security_policy.routers_enabled,
-                    security_policy.external_commissioning_enabled,
-                    security_policy.commercial_commissioning_enabled,
-                    security_policy.autonomous_enrollment_enabled,
-                    security_policy.network_key_provisioning_enabled,
-                    security_policy.toble_link_enabled,
-                    security_policy.non_ccm_routers_enabled,
-                    security_policy.version_threshold_for_routing,
-                ),
-            };
-            security_policy_present = true;
-        }
-
-        if let Some(channel_mask) = dataset.channel_mask {
-            raw_dataset.mChannelMask = channel_mask;
-            channel_mask_present = true;
-        }
-
-        raw_dataset.mComponents = otOperationalDatasetComponents {
-            _bitfield_align_1: [0u8; 0],
-            _bitfield_1: otOperationalDatasetComponents::new_bitfield_1(
-                active_timestamp_present,
-                pending_timestamp_present,
-                network_key_present,
-                network_name_present,
-                extended_pan_present,
-                mesh_local_prefix_present,
-                delay_present,
-                pan_id_present,
-                channel_present,
-                pskc_present,
-                security_policy_present,
-                channel_mask_present,
-            ),
-        };
+        validate_security_policy(&dataset)?;
 
+        let raw_dataset = raw_dataset_from_dataset(dataset);
         checked!(unsafe { otDatasetSetActive(self.instance, &raw_dataset) })
     }
 
     /// Set the change callback
+    ///
+    /// Flags this crate doesn't know yet are passed on as well, see [ChangedFlags::unknown_bits].
     pub fn set_change_callback(
         &mut self,
         callback: Option<&'a mut (dyn FnMut(ChangedFlags) + Send)>,
@@ -468,46 +768,129 @@ impl<'a> OpenThread<'a> {
         checked!(unsafe { otThreadSetEnabled(self.instance, enabled) })
     }
 
+    /// Set the Thread Network Name
+    ///
+    /// Only succeeds while Thread is disabled, see [OpenThread::thread_set_enabled].
+    pub fn thread_set_network_name(&mut self, network_name: NetworkName) -> Result<(), Error> {
+        let raw: otNetworkName = network_name.into();
+        checked!(unsafe { otThreadSetNetworkName(self.instance, raw.m8.as_ptr()) })
+    }
+
+    /// Get the Thread Network Name
+    pub fn thread_get_network_name(&self) -> Option<NetworkName> {
+        let raw = unsafe { &*(otThreadGetNetworkName(self.instance) as *const otNetworkName) };
+        NetworkName::try_from(raw).ok()
+    }
+
+    /// Set the IEEE 802.15.4 Extended PAN ID
+    ///
+    /// Only succeeds while Thread is disabled, see [OpenThread::thread_set_enabled].
+    pub fn thread_set_extended_pan_id(
+        &mut self,
+        extended_pan_id: ExtendedPanId,
+    ) -> Result<(), Error> {
+        let raw: otExtendedPanId = extended_pan_id.into();
+        checked!(unsafe { otThreadSetExtendedPanId(self.instance, &raw) })
+    }
+
+    /// Get the IEEE 802.15.4 Extended PAN ID
+    pub fn thread_get_extended_pan_id(&self) -> ExtendedPanId {
+        unsafe { *otThreadGetExtendedPanId(self.instance) }.into()
+    }
+
+    /// Set the IEEE 802.15.4 PAN ID
+    ///
+    /// Only succeeds while Thread is disabled, see [OpenThread::thread_set_enabled].
+    pub fn link_set_pan_id(&mut self, pan_id: u16) -> Result<(), Error> {
+        checked!(unsafe { otLinkSetPanId(self.instance, pan_id) })
+    }
+
+    /// Get the IEEE 802.15.4 PAN ID
+    pub fn link_get_pan_id(&self) -> u16 {
+        unsafe { otLinkGetPanId(self.instance) }
+    }
+
+    /// Set the IEEE 802.15.4 channel
+    ///
+    /// Only succeeds while Thread is disabled, see [OpenThread::thread_set_enabled].
+    pub fn link_set_channel(&mut self, channel: u8) -> Result<(), Error> {
+        checked!(unsafe { otLinkSetChannel(self.instance, channel) })
+    }
+
+    /// Get the IEEE 802.15.4 channel
+    pub fn link_get_channel(&self) -> u8 {
+        unsafe { otLinkGetChannel(self.instance) }
+    }
+
     /// Gets the list of IPv6 addresses assigned to the Thread interface.
     pub fn ipv6_get_unicast_addresses<const N: usize>(
         &self,
     ) -> heapless::Vec<NetworkInterfaceUnicastAddress, N> {
         let mut result = heapless::Vec::new();
-        let mut addr = unsafe { otIp6GetUnicastAddresses(self.instance) };
-
-        loop {
-            let a = unsafe { &*addr };
 
-            let octets = unsafe { a.mAddress.mFields.m16 };
-
-            if result
-                .push(NetworkInterfaceUnicastAddress {
-                    address: no_std_net::Ipv6Addr::new(
-                        octets[0].to_be(),
-                        octets[1].to_be(),
-                        octets[2].to_be(),
-                        octets[3].to_be(),
-                        octets[4].to_be(),
-                        octets[5].to_be(),
-                        octets[6].to_be(),
-                        octets[7].to_be(),
-                    ),
-                    prefix: a.mPrefixLength,
-                    origin: a.mAddressOrigin,
-                })
-                .is_err()
-            {
+        for address in self.unicast_addresses() {
+            if result.push(address).is_err() {
                 break;
             }
+        }
 
-            if a.mNext.is_null() {
-                break;
+        result
+    }
+
+    /// Gets the list of IPv6 addresses assigned to the Thread interface.
+    #[cfg(feature = "alloc")]
+    pub fn ipv6_get_unicast_addresses_vec(
+        &self,
+    ) -> alloc::vec::Vec<NetworkInterfaceUnicastAddress> {
+        self.unicast_addresses().collect()
+    }
+
+    /// Copy the IPv6 addresses assigned to the Thread interface into `addresses`, returns the
+    /// number of addresses copied
+    ///
+    /// Addresses beyond the length of `addresses` are left out, e.g. start from
+    /// `[NetworkInterfaceUnicastAddress::default(); 8]`.
+    pub fn ipv6_get_unicast_addresses_into(
+        &self,
+        addresses: &mut [NetworkInterfaceUnicastAddress],
+    ) -> usize {
+        let mut count = 0;
+        for (slot, address) in addresses.iter_mut().zip(self.unicast_addresses()) {
+            *slot = address;
+            count += 1;
+        }
+
+        count
+    }
+
+    fn unicast_addresses(&self) -> impl Iterator<Item = NetworkInterfaceUnicastAddress> + '_ {
+        let mut addr = unsafe { otIp6GetUnicastAddresses(self.instance) };
+
+        core::iter::from_fn(move || {
+            if addr.is_null() {
+                return None;
             }
 
+            let a = unsafe { &*addr };
             addr = a.mNext;
-        }
 
-        result
+            let octets = unsafe { a.mAddress.mFields.m16 };
+
+            Some(NetworkInterfaceUnicastAddress {
+                address: no_std_net::Ipv6Addr::new(
+                    octets[0].to_be(),
+                    octets[1].to_be(),
+                    octets[2].to_be(),
+                    octets[3].to_be(),
+                    octets[4].to_be(),
+                    octets[5].to_be(),
+                    octets[6].to_be(),
+                    octets[7].to_be(),
+                ),
+                prefix: a.mPrefixLength,
+                origin: a.mAddressOrigin,
+            })
+        })
     }
 
     /// Creates a new UDP socket
@@ -517,34 +900,33 @@ impl<'a> OpenThread<'a> {
     where
         'a: 's,
     {
-        let ot_socket = otUdpSocket {
-            mSockName: otSockAddr {
-                mAddress: otIp6Address {
-                    mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
-                },
-                mPort: 0,
-            },
-            mPeerName: otSockAddr {
-                mAddress: otIp6Address {
-                    mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
-                },
-                mPort: 0,
-            },
-            mHandler: Some(udp_receive_handler),
-            mContext: core::ptr::null_mut(),
-            mHandle: core::ptr::null_mut(),
-            mNext: core::ptr::null_mut(),
-        };
-
         Ok(UdpSocket {
-            ot_socket,
+            ot_socket: new_ot_udp_socket(),
             ot: self,
-            receive_len: 0,
-            receive_from: [0u8; 16],
-            receive_port: 0,
-            max: BUFFER_SIZE,
+            receive: UdpReceiveState::new(),
             _pinned: PhantomPinned::default(),
             receive_buffer: [0u8; BUFFER_SIZE],
+            #[cfg(feature = "alloc")]
+            heap_receive_buffer: alloc::vec::Vec::new(),
+        })
+    }
+
+    /// Creates a new UDP socket buffering received data on the heap
+    #[cfg(feature = "alloc")]
+    pub fn get_udp_socket_with_capacity<'s>(
+        &'s self,
+        capacity: usize,
+    ) -> Result<HeapUdpSocket<'s, 'a>, Error>
+    where
+        'a: 's,
+    {
+        Ok(UdpSocket {
+            ot_socket: new_ot_udp_socket(),
+            ot: self,
+            receive: UdpReceiveState::new(),
+            _pinned: PhantomPinned::default(),
+            receive_buffer: [],
+            heap_receive_buffer: alloc::vec![0u8; capacity],
         })
     }
 
@@ -552,40 +934,108 @@ impl<'a> OpenThread<'a> {
     ///
     /// Make sure to periodically call this function.
     pub fn run_tasklets(&self) {
-        unsafe {
-            if otTaskletsArePending(self.instance) {
-                otTaskletsProcess(self.instance);
+        self.run_tasklets_bounded(1);
+    }
+
+    /// Like [OpenThread::run_tasklets] but runs up to `max` passes over the tasklet queue
+    ///
+    /// OpenThread runs the queued tasklets as a whole, a pass runs the ones queued before it,
+    /// tasklets posted meanwhile wait for the next pass. Returns `true` if tasklets are still
+    /// pending afterwards, e.g. during attach, so a superloop can serve the application before
+    /// calling it again.
+    pub fn run_tasklets_bounded(&self, max: usize) -> bool {
+        #[cfg(feature = "instrumentation")]
+        let start = health::enter();
+        let watchdog_start = watchdog::enter();
+
+        let mut pending = unsafe { otTaskletsArePending(self.instance) };
+        for _ in 0..max {
+            if !pending {
+                break;
             }
+            unsafe { otTaskletsProcess(self.instance) };
+            pending = unsafe { otTaskletsArePending(self.instance) };
         }
+        attach::process(self.instance);
+
+        watchdog::leave(watchdog_start, "run_tasklets");
+        #[cfg(feature = "instrumentation")]
+        health::leave(start, &health::MAX_TASKLETS_TIME);
+
+        pending
     }
 
     /// Run due timers, get and forward received messages
     ///
     /// Make sure to periodically call this function.
     pub fn process(&self) {
+        self.process_budget(usize::MAX, None);
+    }
+
+    /// Like [OpenThread::process] but bounded
+    ///
+    /// Hands at most `max_frames` received frames to OpenThread and stops early once `max_time`
+    /// has elapsed. Returns `true` if the budget ran out with received frames still waiting.
+    pub fn process_budget(&self, max_frames: usize, max_time: Option<MillisDurationU64>) -> bool {
+        #[cfg(feature = "instrumentation")]
+        let start = health::enter();
+        let watchdog_start = watchdog::enter();
+
+        let budget_exhausted = self.process_frames(max_frames, max_time);
+
+        watchdog::leave(watchdog_start, "process");
+        #[cfg(feature = "instrumentation")]
+        health::leave(start, &health::MAX_PROCESS_TIME);
+
+        budget_exhausted
+    }
+
+    fn process_frames(&self, max_frames: usize, max_time: Option<MillisDurationU64>) -> bool {
+        let deadline = max_time.map(|max_time| current_millis() + max_time.to_millis());
+
         crate::timer::run_if_due();
+        parent_search::process(self.instance);
+        #[cfg(feature = "link-raw")]
+        {
+            link_raw::process(self.instance);
+            pan::process(self.instance);
+        }
+        attach::process(self.instance);
+        auto_refresh::process(self.instance);
+        fast_poll::process(self.instance);
+        #[cfg(feature = "embassy-net")]
+        net_driver::process(self.instance);
+        #[cfg(feature = "channel-stats")]
+        channel_stats::process(self.instance);
+        #[cfg(feature = "traffic-meter")]
+        traffic_meter::process(self.instance);
+
+        #[cfg(feature = "test-harness")]
+        if harness::take_tx_done_pending() {
+            radio::trigger_tx_done();
+        }
 
-        while let Some(raw) = with_radio(|radio| radio.get_raw_received()).unwrap() {
-            let rssi = raw.data[raw.data[0] as usize - 1] as i8;
-
-            unsafe {
-                let len = raw.data[0];
-                log::debug!("RCV {:02x?}", &raw.data[1..][..len as usize]);
-
-                RCV_FRAME_PSDU[..len as usize].copy_from_slice(&raw.data[1..][..len as usize]);
-                RCV_FRAME.mLength = len as u16;
-                RCV_FRAME.mRadioType = 1; // ????
-                RCV_FRAME.mChannel = raw.channel;
-                RCV_FRAME.mInfo.mRxInfo.mRssi = rssi;
-                RCV_FRAME.mInfo.mRxInfo.mLqi = rssi_to_lqi(rssi);
-                RCV_FRAME.mInfo.mRxInfo.mTimestamp = current_millis() * 1000;
-                otPlatRadioReceiveDone(
-                    self.instance,
-                    addr_of_mut!(RCV_FRAME),
-                    otError_OT_ERROR_NONE,
-                );
+        for _ in 0..max_frames {
+            if deadline.is_some_and(|deadline| current_millis() >= deadline) {
+                return radio::received_frame_pending();
             }
+
+            let Some(frame) = radio::take_received_frame() else {
+                return false;
+            };
+            radio::update_timestamps(|timestamps| timestamps.rx = frame.ticks);
+            radio::count_received(frame.psdu().len());
+
+            receive_frame(
+                self.instance,
+                frame.psdu(),
+                frame.channel,
+                radio::calibrated_rssi(frame.channel, frame.rssi),
+                frame.timestamp,
+            );
         }
+
+        radio::received_frame_pending()
     }
 
     /// Returns the currently active Dataset.
@@ -663,69 +1113,342 @@ impl<'a> OpenThread<'a> {
 
 impl<'a> Drop for OpenThread<'a> {
     fn drop(&mut self) {
+        // the instance outlives this handle, don't leave it in promiscuous mode with Thread paused
+        #[cfg(feature = "link-raw")]
+        if self.link_raw_is_sniffing() {
+            if let Err(error) = self.link_raw_stop_sniffer() {
+                log::warn!("failed to stop the sniffer: {:?}", error);
+            }
+        }
+
         critical_section::with(|cs| {
             RADIO.borrow_ref_mut(cs).take();
+            radio::RADIO_FRONT_END.borrow_ref_mut(cs).take();
+            radio::RADIO_THROUGHPUT
+                .borrow(cs)
+                .set(radio::ThroughputCounters::DEFAULT);
+            #[cfg(feature = "frame-bursting")]
+            radio::RECEIVE_CONFIGURATION.borrow(cs).set(None);
+            radio::RADIO_TIMESTAMPS
+                .borrow(cs)
+                .set(radio::RadioTimestamps::DEFAULT);
             NETWORK_SETTINGS.borrow_ref_mut(cs).take();
             CHANGE_CALLBACK.borrow_ref_mut(cs).take();
+            dataset_migration::MIGRATION_CALLBACK
+                .borrow_ref_mut(cs)
+                .take();
+            ip6::ADDRESS_CALLBACK.borrow_ref_mut(cs).take();
+            ip6::RECEIVE_CALLBACK.borrow_ref_mut(cs).take();
+            attach::ATTACH_CALLBACK.borrow_ref_mut(cs).take();
+            attach::ATTACH_TRACKER
+                .borrow(cs)
+                .set(attach::AttachTracker::DEFAULT);
+            *auto_refresh::AUTO_REFRESH.borrow_ref_mut(cs) = auto_refresh::AutoRefresh::DEFAULT;
+            auto_refresh::AUTO_REFRESH_CALLBACK
+                .borrow_ref_mut(cs)
+                .take();
+            fast_poll::FAST_POLL
+                .borrow(cs)
+                .set(fast_poll::FastPoll::DEFAULT);
+            parent_search::PARENT_SEARCH
+                .borrow(cs)
+                .set(parent_search::ParentSearch::DEFAULT);
+            *proximity::PROXIMITY.borrow_ref_mut(cs) = proximity::Proximity::DEFAULT;
+            *watchdog::WATCHDOG.borrow_ref_mut(cs) = watchdog::WatchdogState::DEFAULT;
+            #[cfg(feature = "channel-stats")]
+            {
+                *channel_stats::CHANNEL_STATS.borrow_ref_mut(cs) =
+                    channel_stats::ChannelTelemetry::DEFAULT;
+            }
+            #[cfg(feature = "coap-secure")]
+            {
+                *coap_secure::COAP_SECURE.borrow_ref_mut(cs) = coap_secure::CoapSecure::DEFAULT;
+                coap_secure::COAP_SECURE_REQUEST_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+                coap_secure::COAP_SECURE_REPLY_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+                coap_secure::COAP_SECURE_CONNECTION_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+            }
+            #[cfg(feature = "diag")]
+            {
+                diag::DIAG_MODE.borrow(cs).set(false);
+                diag::DIAG_GPIO.borrow_ref_mut(cs).take();
+                diag::RAW_POWER_SETTING
+                    .borrow(cs)
+                    .set(diag::RawPowerSetting::DEFAULT);
+            }
+            #[cfg(feature = "dns-client")]
+            {
+                *dns_client::DNS_RESOLVE.borrow_ref_mut(cs) = dns_client::DnsResolve::DEFAULT;
+            }
+            #[cfg(feature = "joiner")]
+            {
+                joiner::JOINER_CALLBACK.borrow_ref_mut(cs).take();
+                joiner::DATASET_PENDING.store(false, core::sync::atomic::Ordering::Relaxed);
+            }
+            #[cfg(feature = "event-journal")]
+            {
+                *journal::JOURNAL.borrow_ref_mut(cs) = journal::Journal::DEFAULT;
+                journal::JOURNAL_FLUSH_HOOK.borrow_ref_mut(cs).take();
+                journal::bump_flush_hook_generation(cs);
+            }
+            key_rotation::KEY_ROTATION.borrow_ref_mut(cs).take();
+            key_rotation::KEY_ROTATION_CALLBACK
+                .borrow_ref_mut(cs)
+                .take();
+            #[cfg(feature = "link-raw")]
+            {
+                link_raw::LINK_RAW_RECEIVE_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+                link_raw::RX_WINDOWS
+                    .borrow(cs)
+                    .set(link_raw::RxWindows::DEFAULT);
+                link_raw::SNIFFER.borrow(cs).set(None);
+                pan::PAN.borrow_ref_mut(cs).take();
+                pan::PAN_CALLBACK.borrow_ref_mut(cs).take();
+                ranging::TIMESTAMPED_TX_CALLBACK.borrow_ref_mut(cs).take();
+            }
+            *logging::LOG_SINK.borrow_ref_mut(cs) = logging::LogSink::Log;
+            #[cfg(feature = "message-trace")]
+            message_trace::MESSAGE_TRACE_HOOK.borrow_ref_mut(cs).take();
+            #[cfg(feature = "embassy-net")]
+            {
+                *net_driver::NET_DRIVER.borrow_ref_mut(cs) = net_driver::NetQueues::DEFAULT;
+                net_driver::LINK_UP.store(false, core::sync::atomic::Ordering::Release);
+            }
+            #[cfg(feature = "ping")]
+            {
+                ping::PING_REPLY_CALLBACK.borrow_ref_mut(cs).take();
+                *ping::PING.borrow_ref_mut(cs) = ping::Ping::DEFAULT;
+            }
+            #[cfg(feature = "srp-client")]
+            {
+                srp_client::SRP_CLIENT_AUTO_START_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+                srp_client::SRP_CLIENT_CALLBACK.borrow_ref_mut(cs).take();
+                *srp_client::SRP_CLIENT.borrow_ref_mut(cs) = srp_client::SrpClient::DEFAULT;
+            }
+            #[cfg(feature = "test-harness")]
+            harness::TRANSMIT_CAPTURE.borrow_ref_mut(cs).take();
+            #[cfg(feature = "traffic-meter")]
+            {
+                *traffic_meter::TRAFFIC_METER.borrow_ref_mut(cs) =
+                    traffic_meter::TrafficMeter::DEFAULT;
+            }
         });
+
+        #[cfg(feature = "message-trace")]
+        message_trace::remove_message_trace_receiver(self.instance);
+
+        #[cfg(feature = "ping")]
+        ping::reset_ping_handler();
     }
 }
 
-/// Create a new OperationalDataset struct from a raw otOperationalDataset struct.
-fn dataset_from_raw_dataset(raw_dataset: otOperationalDataset) -> OperationalDataset {
-    let mut dataset = OperationalDataset::default();
+/// Create a raw otOperationalDataset struct from an OperationalDataset struct.
+pub(crate) fn raw_dataset_from_dataset(dataset: OperationalDataset) -> otOperationalDataset {
+    let mut raw_dataset = otOperationalDataset {
+        mActiveTimestamp: otTimestamp {
+            mSeconds: 0,
+            mTicks: 0,
+            mAuthoritative: false,
+        },
+        mPendingTimestamp: otTimestamp {
+            mSeconds: 0,
+            mTicks: 0,
+            mAuthoritative: false,
+        },
+        mNetworkKey: otNetworkKey { m8: [0u8; 16] },
+        mNetworkName: otNetworkName { m8: [0i8; 17] },
+        mExtendedPanId: otExtendedPanId { m8: [0u8; 8] },
+        mMeshLocalPrefix: otMeshLocalPrefix { m8: [0u8; 8] },
+        mDelay: 0,
+        mPanId: 0,
+        mChannel: 0,
+        mPskc: otPskc { m8: [0u8; 16] },
+        mSecurityPolicy: otSecurityPolicy {
+            mRotationTime: 0,
+            _bitfield_align_1: [0u8; 0],
+            _bitfield_1: otSecurityPolicy::new_bitfield_1(
+                false, false, false, false, false, false, false, false, false, 0,
+            ),
+        },
+        mChannelMask: 0,
+        mComponents: otOperationalDatasetComponents {
+            _bitfield_align_1: [0u8; 0],
+            _bitfield_1: otOperationalDatasetComponents::new_bitfield_1(
+                true, false, true, true, true, false, false, true, true, false, false, false,
+            ),
+        },
+    };
 
-    dataset.active_timestamp = Some(ThreadTimestamp {
-        seconds: raw_dataset.mActiveTimestamp.mSeconds,
-        ticks: raw_dataset.mActiveTimestamp.mTicks,
-        authoritative: raw_dataset.mActiveTimestamp.mAuthoritative,
-    });
+    let mut active_timestamp_present = false;
+    let mut pending_timestamp_present = false;
+    let mut network_key_present = false;
+    let mut network_name_present = false;
+    let mut extended_pan_present = false;
+    let mut mesh_local_prefix_present = false;
+    let mut delay_present = false;
+    let mut pan_id_present = false;
+    let mut channel_present = false;
+    let mut pskc_present = false;
+    let mut security_policy_present = false;
+    let mut channel_mask_present = false;
+
+    if let Some(active_timestamp) = dataset.active_timestamp {
+        raw_dataset.mActiveTimestamp = otTimestamp {
+            mSeconds: active_timestamp.seconds,
+            mTicks: active_timestamp.ticks,
+            mAuthoritative: active_timestamp.authoritative,
+        };
+        active_timestamp_present = true;
+    }
 
-    dataset.channel = Some(raw_dataset.mChannel);
-    dataset.channel_mask = Some(raw_dataset.mChannelMask);
-    dataset.delay = Some(raw_dataset.mDelay);
-    dataset.extended_pan_id = Some(raw_dataset.mExtendedPanId.m8);
-    dataset.mesh_local_prefix = Some(raw_dataset.mMeshLocalPrefix.m8);
-    dataset.network_key = Some(raw_dataset.mNetworkKey.m8);
-    let name_as_u8: [u8; 17];
-    let mut name_as_vec = heapless::Vec::<u8, 16>::new();
-    unsafe {
-        name_as_u8 = core::mem::transmute(raw_dataset.mNetworkName.m8);
-    };
-    // 15 = remove the null char
-    if let Ok(()) = name_as_vec.extend_from_slice(&name_as_u8[..15]) {}
+    if let Some(pending_timestamp) = dataset.pending_timestamp {
+        raw_dataset.mPendingTimestamp = otTimestamp {
+            mSeconds: pending_timestamp.seconds,
+            mTicks: pending_timestamp.ticks,
+            mAuthoritative: pending_timestamp.authoritative,
+        };
+        pending_timestamp_present = true;
+    }
 
-    if let Ok(network_name) = heapless::String::from_utf8(name_as_vec) {
-        dataset.network_name = Some(network_name);
+    if let Some(network_key) = dataset.network_key {
+        raw_dataset.mNetworkKey = otNetworkKey { m8: network_key };
+        network_key_present = true;
     }
-    dataset.pan_id = Some(raw_dataset.mPanId);
-    dataset.pending_timestamp = Some(ThreadTimestamp {
-        seconds: raw_dataset.mPendingTimestamp.mSeconds,
-        ticks: raw_dataset.mPendingTimestamp.mTicks,
-        authoritative: raw_dataset.mPendingTimestamp.mAuthoritative,
-    });
 
-    dataset.pskc = Some(raw_dataset.mPskc.m8);
-    dataset.security_policy = Some(SecurityPolicy {
-        rotation_time: raw_dataset.mSecurityPolicy.mRotationTime,
-        autonomous_enrollment_enabled: raw_dataset.mSecurityPolicy.mAutonomousEnrollmentEnabled(),
-        commercial_commissioning_enabled: raw_dataset
-            .mSecurityPolicy
-            .mCommercialCommissioningEnabled(),
-        external_commissioning_enabled: raw_dataset.mSecurityPolicy.mExternalCommissioningEnabled(),
-        native_commissioning_enabled: raw_dataset.mSecurityPolicy.mNativeCommissioningEnabled(),
-        network_key_provisioning_enabled: raw_dataset
-            .mSecurityPolicy
-            .mNetworkKeyProvisioningEnabled(),
-        non_ccm_routers_enabled: raw_dataset.mSecurityPolicy.mNonCcmRoutersEnabled(),
-        obtain_network_key_enabled: raw_dataset.mSecurityPolicy.mObtainNetworkKeyEnabled(),
-        routers_enabled: raw_dataset.mSecurityPolicy.mRoutersEnabled(),
-        toble_link_enabled: raw_dataset.mSecurityPolicy.mTobleLinkEnabled(),
-        version_threshold_for_routing: raw_dataset.mSecurityPolicy.mVersionThresholdForRouting(),
-    });
+    if let Some(network_name) = dataset.network_name {
+        raw_dataset.mNetworkName = network_name.into();
+        network_name_present = true;
+    }
+
+    if let Some(extended_pan_id) = dataset.extended_pan_id {
+        raw_dataset.mExtendedPanId = extended_pan_id.into();
+        extended_pan_present = true;
+    }
+
+    if let Some(mesh_local_prefix) = dataset.mesh_local_prefix {
+        raw_dataset.mMeshLocalPrefix = mesh_local_prefix.into();
+        mesh_local_prefix_present = true;
+    }
+
+    if let Some(delay) = dataset.delay {
+        raw_dataset.mDelay = delay;
+        delay_present = true;
+    }
+
+    if let Some(pan_id) = dataset.pan_id {
+        raw_dataset.mPanId = pan_id;
+        pan_id_present = true;
+    }
+
+    if let Some(channel) = dataset.channel {
+        raw_dataset.mChannel = channel;
+        channel_present = true;
+    }
+
+    if let Some(pskc) = dataset.pskc {
+        raw_dataset.mPskc = otPskc { m8: pskc };
+        pskc_present = true;
+    }
+
+    if let Some(security_policy) = dataset.security_policy {
+        raw_dataset.mSecurityPolicy = security_policy.into();
+        security_policy_present = true;
+    }
+
+    if let Some(channel_mask) = dataset.channel_mask {
+        raw_dataset.mChannelMask = channel_mask;
+        channel_mask_present = true;
+    }
 
+    raw_dataset.mComponents = otOperationalDatasetComponents {
+        _bitfield_align_1: [0u8; 0],
+        _bitfield_1: otOperationalDatasetComponents::new_bitfield_1(
+            active_timestamp_present,
+            pending_timestamp_present,
+            network_key_present,
+            network_name_present,
+            extended_pan_present,
+            mesh_local_prefix_present,
+            delay_present,
+            pan_id_present,
+            channel_present,
+            pskc_present,
+            security_policy_present,
+            channel_mask_present,
+        ),
+    };
+
+    raw_dataset
+}
+
+/// Create a new OperationalDataset struct from a raw otOperationalDataset struct.
+/// Validate the security policy of `dataset` if it has one
+///
+/// A dataset read from OpenThread only has one if present there, see [dataset_from_raw_dataset],
+/// so datasets without a policy round-trip unchanged.
+pub(crate) fn validate_security_policy(dataset: &OperationalDataset) -> Result<(), Error> {
     dataset
+        .security_policy
+        .as_ref()
+        .map_or(Ok(()), SecurityPolicy::validate)
+}
+
+pub(crate) fn dataset_from_raw_dataset(raw_dataset: otOperationalDataset) -> OperationalDataset {
+    // OpenThread leaves absent components zeroed, only the present ones are taken
+    let components = raw_dataset.mComponents;
+
+    OperationalDataset {
+        active_timestamp: components
+            .mIsActiveTimestampPresent()
+            .then_some(ThreadTimestamp {
+                seconds: raw_dataset.mActiveTimestamp.mSeconds,
+                ticks: raw_dataset.mActiveTimestamp.mTicks,
+                authoritative: raw_dataset.mActiveTimestamp.mAuthoritative,
+            }),
+        pending_timestamp: components
+            .mIsPendingTimestampPresent()
+            .then_some(ThreadTimestamp {
+                seconds: raw_dataset.mPendingTimestamp.mSeconds,
+                ticks: raw_dataset.mPendingTimestamp.mTicks,
+                authoritative: raw_dataset.mPendingTimestamp.mAuthoritative,
+            }),
+        network_key: components
+            .mIsNetworkKeyPresent()
+            .then_some(raw_dataset.mNetworkKey.m8),
+        network_name: components
+            .mIsNetworkNamePresent()
+            .then(|| NetworkName::try_from(&raw_dataset.mNetworkName).ok())
+            .flatten(),
+        extended_pan_id: components
+            .mIsExtendedPanIdPresent()
+            .then(|| raw_dataset.mExtendedPanId.into()),
+        mesh_local_prefix: components
+            .mIsMeshLocalPrefixPresent()
+            .then(|| raw_dataset.mMeshLocalPrefix.into()),
+        delay: components.mIsDelayPresent().then_some(raw_dataset.mDelay),
+        pan_id: components.mIsPanIdPresent().then_some(raw_dataset.mPanId),
+        channel: components
+            .mIsChannelPresent()
+            .then_some(raw_dataset.mChannel),
+        pskc: components.mIsPskcPresent().then_some(raw_dataset.mPskc.m8),
+        security_policy: components
+            .mIsSecurityPolicyPresent()
+            .then(|| raw_dataset.mSecurityPolicy.into()),
+        channel_mask: components
+            .mIsChannelMaskPresent()
+            .then_some(raw_dataset.mChannelMask),
+    }
 }
+
 /// Create a otNetIfAddress from a Ipv6Addr
 fn ot_netif_address_from_ipv6_addr(address: Ipv6Addr) -> otNetifAddress {
     let seg_big_endian = address.segments().map(|s| s.to_be());
@@ -751,16 +1474,110 @@ unsafe extern "C" fn change_callback(
     _context: *mut esp_openthread_sys::c_types::c_void,
 ) {
     log::debug!("change_callback otChangedFlags={:32b}", flags);
+
+    // newer OpenThread versions add flags, keep them rather than failing
+    let changed = ChangedFlags::from_bits_retain(flags);
+    if changed.unknown_bits() != 0 {
+        log::debug!("unknown changed flags {:#x}", changed.unknown_bits());
+    }
+
+    // the context is the instance, see `OpenThread::new_with_priorities`
+    #[cfg(feature = "event-journal")]
+    journal::record_changes(_context as *mut otInstance, changed);
+    auto_refresh::note_changes(changed);
+    key_rotation::on_changes(_context as *mut otInstance, changed);
+    fast_poll::on_changes(_context as *mut otInstance, changed);
+    #[cfg(feature = "joiner")]
+    joiner::on_changes(_context as *mut otInstance, changed);
+    #[cfg(feature = "embassy-net")]
+    net_driver::on_changes(_context as *mut otInstance, changed);
+
     critical_section::with(|cs| {
         let mut change_callback = CHANGE_CALLBACK.borrow_ref_mut(cs);
         let callback = change_callback.as_mut();
 
         if let Some(callback) = callback {
-            callback(ChangedFlags::from_bits(flags).unwrap());
+            callback(changed);
         }
     });
 }
 
+fn new_ot_udp_socket() -> otUdpSocket {
+    otUdpSocket {
+        mSockName: otSockAddr {
+            mAddress: otIp6Address {
+                mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
+            },
+            mPort: 0,
+        },
+        mPeerName: otSockAddr {
+            mAddress: otIp6Address {
+                mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
+            },
+            mPort: 0,
+        },
+        mHandler: Some(udp_receive_handler),
+        mContext: core::ptr::null_mut(),
+        mHandle: core::ptr::null_mut(),
+        mNext: core::ptr::null_mut(),
+    }
+}
+
+fn new_message_info() -> otMessageInfo {
+    otMessageInfo {
+        mSockAddr: otIp6Address {
+            mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
+        },
+        mPeerAddr: otIp6Address {
+            mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
+        },
+        mSockPort: 0,
+        mPeerPort: 0,
+        mLinkInfo: core::ptr::null(),
+        mHopLimit: 0,
+        _bitfield_align_1: [0u8; 0],
+        _bitfield_1: __BindgenBitfieldUnit::new([0u8; 1]),
+        __bindgen_padding_0: 0,
+    }
+}
+
+/// Hand a received frame to OpenThread
+pub(crate) fn receive_frame(
+    instance: *mut otInstance,
+    psdu: &[u8],
+    channel: u8,
+    rssi: i8,
+    timestamp: u64,
+) {
+    log::debug!("RCV {:02x?}", psdu);
+
+    if psdu.len() > RX_PSDU_SIZE {
+        log::warn!(
+            "dropping frame of {} bytes, larger than RX_PSDU_SIZE",
+            psdu.len()
+        );
+        return;
+    }
+
+    proximity::record_frame(instance, psdu, rssi);
+
+    unsafe {
+        RCV_FRAME_PSDU[..psdu.len()].copy_from_slice(psdu);
+        RCV_FRAME.mLength = psdu.len() as u16;
+        RCV_FRAME.mRadioType = 1; // ????
+        RCV_FRAME.mChannel = channel;
+        RCV_FRAME.mInfo.mRxInfo.mRssi = rssi;
+        RCV_FRAME.mInfo.mRxInfo.mLqi = rssi_to_lqi(rssi);
+        RCV_FRAME.mInfo.mRxInfo.mTimestamp = timestamp;
+        #[cfg(feature = "diag")]
+        if diag::is_diag_mode() {
+            otPlatDiagRadioReceiveDone(instance, addr_of_mut!(RCV_FRAME), otError_OT_ERROR_NONE);
+            return;
+        }
+        otPlatRadioReceiveDone(instance, addr_of_mut!(RCV_FRAME), otError_OT_ERROR_NONE);
+    }
+}
+
 fn with_radio<F, T>(f: F) -> Option<T>
 where
     F: FnOnce(&mut Ieee802154) -> T,
@@ -810,32 +1627,151 @@ fn set_settings(settings: NetworkSettings) {
 pub struct UdpSocket<'s, 'n: 's, const BUFFER_SIZE: usize> {
     ot_socket: otUdpSocket,
     ot: &'s OpenThread<'n>,
-    receive_len: usize,
-    receive_from: [u8; 16],
-    receive_port: u16,
-    max: usize,
+    receive: UdpReceiveState<'s>,
     _pinned: PhantomPinned,
-    // must be last because the callback doesn't know about the actual const generic parameter
     receive_buffer: [u8; BUFFER_SIZE],
+    #[cfg(feature = "alloc")]
+    heap_receive_buffer: alloc::vec::Vec<u8>,
+}
+
+/// A UdpSocket buffering received data on the heap, see [OpenThread::get_udp_socket_with_capacity]
+#[cfg(feature = "alloc")]
+pub type HeapUdpSocket<'s, 'n> = UdpSocket<'s, 'n, 0>;
+
+/// Counters of a single [UdpSocket], see [UdpSocket::stats]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UdpSocketStats {
+    pub datagrams_sent: u32,
+    /// UDP payload bytes
+    pub bytes_sent: u32,
+    pub datagrams_received: u32,
+    /// UDP payload bytes
+    pub bytes_received: u32,
+    /// Buffered datagrams overwritten by a newer one before they were received
+    pub dropped_pending: u32,
+    /// Datagrams cut off because they didn't fit into the receive buffer
+    pub truncated: u32,
+    /// Sends failing because OpenThread ran out of message buffers
+    pub send_failures_no_bufs: u32,
+    /// Sends failing because there is no route to the destination
+    pub send_failures_no_route: u32,
+    /// Sends failing for any other reason
+    pub send_failures_other: u32,
+}
+
+impl UdpSocketStats {
+    fn count_send_failure(&mut self, error: otError) {
+        #[allow(non_upper_case_globals)]
+        let counter = match error {
+            otError_OT_ERROR_NO_BUFS => &mut self.send_failures_no_bufs,
+            otError_OT_ERROR_NO_ROUTE => &mut self.send_failures_no_route,
+            _ => &mut self.send_failures_other,
+        };
+        *counter = counter.wrapping_add(1);
+    }
+}
+
+/// Receive state of a [UdpSocket]
+///
+/// This is what OpenThread gets as the socket context, so the receive handler doesn't need to
+/// know the socket's buffer size. It also holds the socket's statistics.
+struct UdpReceiveState<'s> {
+    stats: UdpSocketStats,
+    pending: bool,
+    len: usize,
+    from: [u8; 16],
+    port: u16,
+    handler: Option<&'s mut (dyn FnMut(&mut MessageReader, Ipv6Addr, u16) + Send)>,
+    waker: Option<Waker>,
+    // points into the pinned socket once it is opened
+    buffer: *mut [u8],
+}
+
+impl<'s> UdpReceiveState<'s> {
+    fn new() -> Self {
+        Self {
+            stats: UdpSocketStats::default(),
+            pending: false,
+            len: 0,
+            from: [0u8; 16],
+            port: 0,
+            handler: None,
+            waker: None,
+            buffer: core::ptr::slice_from_raw_parts_mut(core::ptr::null_mut(), 0),
+        }
+    }
+
+    /// Copy out the pending datagram, if any
+    fn take(&mut self, data: &mut [u8]) -> Option<(usize, Ipv6Addr, u16)> {
+        self.take_split(data, &mut [])
+            .map(|(len, _, from, port)| (len, from, port))
+    }
+
+    /// Copy out the pending datagram, if any, its start into `header` and the rest into `payload`
+    fn take_split(
+        &mut self,
+        header: &mut [u8],
+        payload: &mut [u8],
+    ) -> Option<(usize, usize, Ipv6Addr, u16)> {
+        if !self.pending {
+            return None;
+        }
+
+        self.pending = false;
+        let data = unsafe { &(*self.buffer)[..self.len] };
+        let header_len = usize::min(data.len(), header.len());
+        header[..header_len].copy_from_slice(&data[..header_len]);
+        let payload_len = usize::min(data.len() - header_len, payload.len());
+        payload[..payload_len].copy_from_slice(&data[header_len..][..payload_len]);
+        Some((
+            header_len,
+            payload_len,
+            Ipv6Addr::from(self.from),
+            self.port,
+        ))
+    }
 }
 
 impl<'s, 'n: 's, const BUFFER_SIZE: usize> UdpSocket<'s, 'n, BUFFER_SIZE> {
     /// Open and bind a UDP/IPv6 socket
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` for ports OpenThread uses itself, e.g. MLE's 19788,
+    /// binding them would take its traffic away.
     pub fn bind(self: &mut Pin<&mut Self>, port: u16) -> Result<(), Error> {
+        self.bind_to(port, None, NetifIdentifier::Thread)
+    }
+
+    /// Open and bind a UDP/IPv6 socket to a local address and network interface
+    ///
+    /// `address` restricts the socket to datagrams sent to it (e.g. an OMR address), `None`
+    /// accepts all addresses of the interface.
+    pub fn bind_to(
+        self: &mut Pin<&mut Self>,
+        port: u16,
+        address: Option<Ipv6Addr>,
+        netif: NetifIdentifier,
+    ) -> Result<(), Error> {
+        if netif != NetifIdentifier::Backbone {
+            if let Some(&(_, used_by)) = RESERVED_UDP_PORTS
+                .iter()
+                .find(|(reserved, _)| *reserved == port)
+            {
+                log::warn!("UDP port {port} is used by {used_by}");
+                return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+            }
+        }
+
         let mut sock_addr = otSockAddr {
-            mAddress: otIp6Address {
-                mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
-            },
-            mPort: 0,
+            mAddress: ot_ip6_address_from_ipv6_addr(address.unwrap_or(Ipv6Addr::UNSPECIFIED)),
+            mPort: port,
         };
-        sock_addr.mPort = port;
 
         unsafe {
             checked!(otUdpOpen(
                 self.ot.instance,
                 &self.ot_socket as *const _ as *mut otUdpSocket,
                 Some(udp_receive_handler),
-                self.as_mut().get_unchecked_mut() as *mut _ as *mut crate::sys::c_types::c_void,
+                self.receive_context(),
             ))?;
         }
 
@@ -844,7 +1780,7 @@ impl<'s, 'n: 's, const BUFFER_SIZE: usize> UdpSocket<'s, 'n, BUFFER_SIZE> {
                 self.ot.instance,
                 &self.ot_socket as *const _ as *mut otUdpSocket,
                 &mut sock_addr,
-                otNetifIdentifier_OT_NETIF_THREAD,
+                netif.into(),
             ))?;
         }
 
@@ -866,30 +1802,111 @@ impl<'s, 'n: 's, const BUFFER_SIZE: usize> UdpSocket<'s, 'n, BUFFER_SIZE> {
                 self.ot.instance,
                 &self.ot_socket as *const _ as *mut otUdpSocket,
                 Some(udp_receive_handler),
-                self.as_mut().get_unchecked_mut() as *mut _ as *mut crate::sys::c_types::c_void,
+                self.receive_context(),
             ))?;
         }
         Ok(())
     }
 
+    /// Point the receive state at the buffer and get the context for OpenThread
+    fn receive_context(self: &mut Pin<&mut Self>) -> *mut c_void {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+
+        #[cfg(feature = "alloc")]
+        if BUFFER_SIZE == 0 {
+            socket.receive.buffer = socket.heap_receive_buffer.as_mut_slice();
+            return &mut socket.receive as *mut _ as *mut c_void;
+        }
+
+        socket.receive.buffer = &mut socket.receive_buffer[..];
+        &mut socket.receive as *mut _ as *mut c_void
+    }
+
+    /// Set a handler which is called for every received message
+    ///
+    /// While a handler is set, received data is not buffered and [UdpSocket::receive]
+    /// won't return anything. The handler can read the message in chunks of any size.
+    pub fn set_receive_handler(
+        self: &mut Pin<&mut Self>,
+        handler: Option<&'s mut (dyn FnMut(&mut MessageReader, Ipv6Addr, u16) + Send)>,
+    ) {
+        critical_section::with(|_| {
+            unsafe { self.as_mut().get_unchecked_mut() }.receive.handler = handler;
+        });
+    }
+
     /// Get latest data received on this socket
+    ///
+    /// Returns `None` if no datagram is pending. Data not fitting into `data` is discarded.
     pub fn receive(
         self: &mut Pin<&mut Self>,
         data: &mut [u8],
-    ) -> Result<(usize, Ipv6Addr, u16), Error> {
+    ) -> Result<Option<(usize, Ipv6Addr, u16)>, Error> {
+        critical_section::with(|_| {
+            let receive = &mut unsafe { self.as_mut().get_unchecked_mut() }.receive;
+            Ok(receive.take(data))
+        })
+    }
+
+    /// Like [UdpSocket::receive] but splits the datagram
+    ///
+    /// The first `header.len()` bytes of the UDP payload go into `header`, the rest into
+    /// `payload`, so a protocol parser gets its fixed size header apart from the payload. Returns
+    /// the number of bytes in each, data fitting into neither is discarded.
+    ///
+    /// This is a convenience wrapper: the datagram is buffered in the socket like for
+    /// [UdpSocket::receive] and only the copy out of that buffer is split. To read straight from
+    /// the message, e.g. to inspect the header before deciding where the payload goes, use
+    /// [MessageReader::read_split] in the handler set via [UdpSocket::set_receive_handler].
+    pub fn receive_split(
+        self: &mut Pin<&mut Self>,
+        header: &mut [u8],
+        payload: &mut [u8],
+    ) -> Result<Option<(usize, usize, Ipv6Addr, u16)>, Error> {
+        critical_section::with(|_| {
+            let receive = &mut unsafe { self.as_mut().get_unchecked_mut() }.receive;
+            Ok(receive.take_split(header, payload))
+        })
+    }
+
+    /// Poll for data received on this socket
+    ///
+    /// Like [UdpSocket::receive] but registers the context's waker to be woken once a datagram
+    /// arrives, for use in futures.
+    pub fn poll_receive(
+        self: &mut Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &mut [u8],
+    ) -> Poll<Result<(usize, Ipv6Addr, u16), Error>> {
         critical_section::with(|_| {
-            let len = self.receive_len as usize;
-            if len == 0 {
-                Ok((0, Ipv6Addr::UNSPECIFIED, 0))
-            } else {
-                unsafe { self.as_mut().get_unchecked_mut() }.receive_len = 0;
-                data[..len].copy_from_slice(&self.receive_buffer[..len]);
-                let ip = Ipv6Addr::from(self.receive_from);
-                Ok((len, ip, self.receive_port))
+            let receive = &mut unsafe { self.as_mut().get_unchecked_mut() }.receive;
+            match receive.take(data) {
+                Some(received) => Poll::Ready(Ok(received)),
+                None => {
+                    receive.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
             }
         })
     }
 
+    /// Port the socket is bound to, 0 if it isn't
+    pub fn local_port(&self) -> u16 {
+        self.ot_socket.mSockName.mPort
+    }
+
+    /// Get the counters of this socket, they start at zero when the socket is created
+    pub fn stats(&self) -> UdpSocketStats {
+        critical_section::with(|_| self.receive.stats)
+    }
+
+    /// Reset the counters of this socket to zero
+    pub fn reset_stats(self: &mut Pin<&mut Self>) {
+        critical_section::with(|_| {
+            unsafe { self.as_mut().get_unchecked_mut() }.receive.stats = UdpSocketStats::default();
+        });
+    }
+
     /// Send data to the given peer
     pub fn send(
         self: &mut Pin<&mut Self>,
@@ -897,35 +1914,103 @@ impl<'s, 'n: 's, const BUFFER_SIZE: usize> UdpSocket<'s, 'n, BUFFER_SIZE> {
         port: u16,
         data: &[u8],
     ) -> Result<(), Error> {
-        let mut message_info = otMessageInfo {
-            mSockAddr: otIp6Address {
-                mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
-            },
-            mPeerAddr: otIp6Address {
-                mFields: otIp6Address__bindgen_ty_1 { m32: [0, 0, 0, 0] },
-            },
-            mSockPort: 0,
-            mPeerPort: 0,
-            mLinkInfo: core::ptr::null(),
-            mHopLimit: 0,
-            _bitfield_align_1: [0u8; 0],
-            _bitfield_1: __BindgenBitfieldUnit::new([0u8; 1]),
-            __bindgen_padding_0: 0,
-        };
+        self.send_vectored(dst, port, &[data])
+    }
+
+    /// Send data gathered from multiple buffers to the given peer
+    ///
+    /// Each segment is appended to the message in order, so e.g. a header and a
+    /// payload don't need to be copied into one contiguous buffer first.
+    pub fn send_vectored(
+        self: &mut Pin<&mut Self>,
+        dst: Ipv6Addr,
+        port: u16,
+        segments: &[&[u8]],
+    ) -> Result<(), Error> {
+        let mut message_info = new_message_info();
         message_info.mPeerAddr.mFields.m8 = dst.octets();
         message_info.mPeerPort = port;
 
+        self.send_message(&mut message_info, segments)
+    }
+
+    /// Send data to a realm-local or site-local multicast group
+    ///
+    /// `hop_limit` limits how many hops the datagram is forwarded, `multicast_loop` controls
+    /// whether it's also delivered to this device if it's subscribed to the group.
+    /// Unicast-prefix-based groups (e.g. `ff33:40:<mesh-local prefix>::1`) must embed the
+    /// mesh-local prefix of the current network.
+    pub fn send_multicast(
+        self: &mut Pin<&mut Self>,
+        group: Ipv6Addr,
+        port: u16,
+        data: &[u8],
+        hop_limit: u8,
+        multicast_loop: bool,
+    ) -> Result<(), Error> {
+        self.validate_multicast_group(&group)?;
+
+        let mut message_info = new_message_info();
+        message_info.mPeerAddr.mFields.m8 = group.octets();
+        message_info.mPeerPort = port;
+        message_info.mHopLimit = hop_limit;
+        message_info.set_mAllowZeroHopLimit(hop_limit == 0);
+        message_info.set_mMulticastLoop(multicast_loop);
+
+        self.send_message(&mut message_info, &[data])
+    }
+
+    fn validate_multicast_group(&self, group: &Ipv6Addr) -> Result<(), Error> {
+        let octets = group.octets();
+        if octets[0] != 0xff {
+            return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+        }
+
+        let flags = octets[1] >> 4;
+        let scope = octets[1] & 0x0f;
+        if scope != MULTICAST_SCOPE_REALM_LOCAL && scope != MULTICAST_SCOPE_SITE_LOCAL {
+            return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+        }
+
+        // unicast-prefix-based (RFC 3306), the embedded /64 prefix has to be ours
+        if flags & MULTICAST_FLAG_PREFIX != 0 {
+            let mesh_local_prefix = unsafe { otThreadGetMeshLocalPrefix(self.ot.instance) };
+            if mesh_local_prefix.is_null()
+                || octets[3] != 64
+                || octets[4..12] != unsafe { (*mesh_local_prefix).m8 }
+            {
+                return Err(Error::InternalError(otError_OT_ERROR_INVALID_ARGS));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_message(
+        self: &mut Pin<&mut Self>,
+        message_info: &mut otMessageInfo,
+        segments: &[&[u8]],
+    ) -> Result<(), Error> {
         let message = unsafe { otUdpNewMessage(self.ot.instance, core::ptr::null()) };
         if message.is_null() {
-            return Err(Error::InternalError(0));
+            self.count_send_failure(otError_OT_ERROR_NO_BUFS);
+            return Err(Error::InternalError(otError_OT_ERROR_NO_BUFS));
         }
 
-        unsafe {
-            checked!(otMessageAppend(
-                message,
-                data.as_ptr() as *const c_void,
-                data.len() as u16
-            ))?;
+        for segment in segments {
+            let res = unsafe {
+                checked!(otMessageAppend(
+                    message,
+                    segment.as_ptr() as *const c_void,
+                    segment.len() as u16
+                ))
+            };
+
+            if let Err(Error::InternalError(err)) = res {
+                unsafe { otMessageFree(message) };
+                self.count_send_failure(err);
+                return res;
+            }
         }
 
         unsafe {
@@ -933,18 +2018,46 @@ impl<'s, 'n: 's, const BUFFER_SIZE: usize> UdpSocket<'s, 'n, BUFFER_SIZE> {
                 self.ot.instance,
                 &self.ot_socket as *const _ as *mut otUdpSocket,
                 message,
-                &mut message_info,
+                message_info,
             );
 
             if err != otError_OT_ERROR_NONE && !message.is_null() {
                 otMessageFree(message);
+                self.count_send_failure(err);
                 return Err(Error::InternalError(err));
             }
         }
 
+        let length: usize = segments.iter().map(|segment| segment.len()).sum();
+        critical_section::with(|_| {
+            let stats = &mut unsafe { self.as_mut().get_unchecked_mut() }.receive.stats;
+            stats.datagrams_sent = stats.datagrams_sent.wrapping_add(1);
+            stats.bytes_sent = stats.bytes_sent.wrapping_add(length as u32);
+        });
+
+        // messages are created without settings, i.e. with normal priority
+        #[cfg(feature = "message-trace")]
+        message_trace::trace_message(&MessageTrace {
+            direction: MessageDirection::Transmit,
+            peer_address: Ipv6Addr::from(unsafe { message_info.mPeerAddr.mFields.m8 }),
+            peer_port: message_info.mPeerPort,
+            sock_port: self.ot_socket.mSockName.mPort,
+            length,
+            priority: Some(MessagePriority::Normal),
+        });
+
         Ok(())
     }
 
+    fn count_send_failure(self: &mut Pin<&mut Self>, error: otError) {
+        critical_section::with(|_| {
+            unsafe { self.as_mut().get_unchecked_mut() }
+                .receive
+                .stats
+                .count_send_failure(error);
+        });
+    }
+
     /// Close a UDP/IPv6 socket
     pub fn close(self: &mut Pin<&mut Self>) -> Result<(), Error> {
         unsafe {
@@ -980,18 +2093,51 @@ unsafe extern "C" fn udp_receive_handler(
     message: *mut otMessage,
     message_info: *const otMessageInfo,
 ) {
-    let socket = context as *mut UdpSocket<1024>;
-    let len = u16::min((*socket).max as u16, otMessageGetLength(message));
+    let receive = context as *mut UdpReceiveState;
+    let payload_len = otMessageGetLength(message) - otMessageGetOffset(message);
 
     critical_section::with(|_| {
+        let stats = &mut (*receive).stats;
+        stats.datagrams_received = stats.datagrams_received.wrapping_add(1);
+        stats.bytes_received = stats.bytes_received.wrapping_add(payload_len as u32);
+    });
+
+    if let Some(handler) = (*receive).handler.as_mut() {
+        let mut reader = MessageReader::new(message);
+        handler(
+            &mut reader,
+            Ipv6Addr::from((*message_info).mPeerAddr.mFields.m8),
+            (*message_info).mPeerPort,
+        );
+        return;
+    }
+
+    let max = usize::min((*receive).buffer.len(), u16::MAX as usize) as u16;
+    let len = u16::min(max, payload_len);
+
+    critical_section::with(|_| {
+        let stats = &mut (*receive).stats;
+        if (*receive).pending {
+            stats.dropped_pending = stats.dropped_pending.wrapping_add(1);
+        }
+        if len < payload_len {
+            stats.truncated = stats.truncated.wrapping_add(1);
+        }
+
+        // the payload, past the IPv6 and UDP headers
         otMessageRead(
             message,
-            0,
-            &mut (*socket).receive_buffer as *mut _ as *mut crate::sys::c_types::c_void,
+            otMessageGetOffset(message),
+            (*receive).buffer as *mut u8 as *mut crate::sys::c_types::c_void,
             len,
         );
-        (*socket).receive_port = (*message_info).mPeerPort;
-        (*socket).receive_from = (*message_info).mPeerAddr.mFields.m8;
-        (*socket).receive_len = len as usize;
+        (*receive).port = (*message_info).mPeerPort;
+        (*receive).from = (*message_info).mPeerAddr.mFields.m8;
+        (*receive).len = len as usize;
+        (*receive).pending = true;
+
+        if let Some(waker) = (*receive).waker.take() {
+            waker.wake();
+        }
     });
 }
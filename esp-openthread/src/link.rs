@@ -0,0 +1,60 @@
+use esp_openthread_sys::bindings::{
+    otLinkGetMaxFrameRetriesDirect, otLinkSetMaxFrameRetriesDirect,
+};
+#[cfg(feature = "retry-histogram")]
+use esp_openthread_sys::bindings::{
+    otLinkGetTxDirectRetrySuccessHistogram, otLinkResetTxRetrySuccessHistogram,
+};
+
+use crate::OpenThread;
+
+/// Maximum number of entries of a [RetryHistogram]
+#[cfg(feature = "retry-histogram")]
+pub const RETRY_HISTOGRAM_MAX_ENTRIES: usize = 16;
+
+/// Number of frames sent successfully per number of retries
+///
+/// The n-th entry counts the frames which needed n retries.
+#[cfg(feature = "retry-histogram")]
+pub type RetryHistogram = heapless::Vec<u32, RETRY_HISTOGRAM_MAX_ENTRIES>;
+
+impl<'a> OpenThread<'a> {
+    /// Set the maximum number of frame retries during direct transmission
+    pub fn link_set_max_frame_retries_direct(&mut self, retries: u8) {
+        unsafe { otLinkSetMaxFrameRetriesDirect(self.instance, retries) }
+    }
+
+    /// Get the maximum number of frame retries during direct transmission
+    pub fn link_get_max_frame_retries_direct(&self) -> u8 {
+        unsafe { otLinkGetMaxFrameRetriesDirect(self.instance) }
+    }
+
+    /// Get the retry histogram of direct transmissions
+    #[cfg(feature = "retry-histogram")]
+    pub fn link_get_tx_direct_retry_success_histogram(&self) -> RetryHistogram {
+        let mut entries = 0u8;
+        let histogram =
+            unsafe { otLinkGetTxDirectRetrySuccessHistogram(self.instance, &mut entries) };
+        histogram_from_raw(histogram, entries)
+    }
+
+    /// Clear the retry histograms of direct and indirect transmissions
+    #[cfg(feature = "retry-histogram")]
+    pub fn link_reset_tx_retry_success_histogram(&mut self) {
+        unsafe { otLinkResetTxRetrySuccessHistogram(self.instance) }
+    }
+}
+
+#[cfg(feature = "retry-histogram")]
+fn histogram_from_raw(histogram: *const u32, entries: u8) -> RetryHistogram {
+    if histogram.is_null() {
+        return RetryHistogram::new();
+    }
+
+    let histogram = unsafe { core::slice::from_raw_parts(histogram, entries as usize) };
+    histogram
+        .iter()
+        .take(RETRY_HISTOGRAM_MAX_ENTRIES)
+        .copied()
+        .collect()
+}
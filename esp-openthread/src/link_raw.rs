@@ -0,0 +1,498 @@
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole_OT_DEVICE_ROLE_DISABLED, otError, otError_OT_ERROR_INVALID_ARGS,
+    otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NONE, otInstance, otIp6IsEnabled,
+    otIp6SetEnabled, otLinkGetChannel, otLinkRawGetTransmitBuffer, otLinkRawIsEnabled,
+    otLinkRawReceive, otLinkRawSetReceiveDone, otLinkRawSleep, otLinkRawTransmit,
+    otLinkRawTransmitDone, otLinkSetChannel, otLinkSetPromiscuous, otRadioFrame,
+    otThreadGetDeviceRole, otThreadSetEnabled,
+};
+
+use crate::{checked, radio::RADIO_TIMESTAMPS, timer::current_millis, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// Size of the FCS the radio appends to transmitted frames
+pub(crate) const FCS_SIZE: usize = 2;
+
+pub(crate) const FCF_SECURITY_ENABLED: u16 = 1 << 3;
+pub(crate) const FCF_PAN_ID_COMPRESSION: u16 = 1 << 6;
+const FCF_SEQUENCE_SUPPRESSION: u16 = 1 << 8;
+pub(crate) const FCF_DST_ADDR_SHIFT: u16 = 10;
+pub(crate) const FCF_FRAME_VERSION_SHIFT: u16 = 12;
+pub(crate) const FCF_SRC_ADDR_SHIFT: u16 = 14;
+
+const FRAME_VERSION_2015: u16 = 2;
+
+pub(crate) const ADDR_MODE_NONE: u16 = 0;
+pub(crate) const ADDR_MODE_SHORT: u16 = 2;
+pub(crate) const ADDR_MODE_EXTENDED: u16 = 3;
+
+const SECURITY_LEVEL_MASK: u8 = 0b111;
+const SECURITY_KEY_ID_MODE_SHIFT: u8 = 3;
+const SECURITY_FRAME_COUNTER_SUPPRESSION: u8 = 1 << 5;
+
+/// Security level Thread secures its MAC frames with, ENC-MIC-32
+pub const THREAD_MAC_SECURITY_LEVEL: u8 = 5;
+
+pub(crate) static LINK_RAW_RECEIVE_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(&RawFrame) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static RX_WINDOWS: Mutex<Cell<RxWindows>> = Mutex::new(Cell::new(RxWindows::DEFAULT));
+
+/// What to restore once the sniffer stops, `None` while not sniffing
+pub(crate) static SNIFFER: Mutex<Cell<Option<SnifferResume>>> = Mutex::new(Cell::new(None));
+
+/// An IEEE 802.15.4 frame received while the raw link-layer is enabled
+#[derive(Debug, Clone, Copy)]
+pub struct RawFrame<'a> {
+    /// PSDU including the FCS
+    pub psdu: &'a [u8],
+    pub channel: u8,
+    pub rssi: i8,
+    pub lqi: u8,
+    /// Receive timestamp (in microseconds)
+    pub timestamp: u64,
+    /// Systimer count when the radio interrupt took the frame, see
+    /// [crate::RADIO_TIMESTAMP_TICKS_PER_SECOND]
+    pub ticks: u64,
+}
+
+impl<'a> RawFrame<'a> {
+    /// Returns `true` if the Security Enabled bit of the frame control field is set
+    pub fn is_secured(&self) -> bool {
+        self.psdu.len() >= 2
+            && u16::from_le_bytes([self.psdu[0], self.psdu[1]]) & FCF_SECURITY_ENABLED != 0
+    }
+
+    /// The Auxiliary Security Header of a secured frame, e.g. to check that all mesh traffic
+    /// is encrypted while sniffing
+    ///
+    /// `None` if the frame isn't secured or its header is cut short, [RawFrame::is_secured]
+    /// tells them apart. The frame isn't decrypted, the header is sent in the clear.
+    pub fn security(&self) -> Option<FrameSecurity> {
+        parse_security(self.psdu)
+    }
+}
+
+/// Key identifier of a secured frame, its key ID mode and fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKeyId {
+    /// Key ID mode 0, the key follows from the originator and recipient
+    Implicit,
+    /// Key ID mode 1, the key index, with Thread's MAC key the low 7 bits of the Key Sequence
+    /// plus one
+    Index(u8),
+    /// Key ID mode 2, a 4 byte key source and the key index, Thread puts the Key Sequence in the
+    /// key source
+    Source4([u8; 4], u8),
+    /// Key ID mode 3, an 8 byte key source and the key index
+    Source8([u8; 8], u8),
+}
+
+/// The Auxiliary Security Header of a received frame, see [RawFrame::security]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSecurity {
+    /// Security level 0 to 7, Thread uses [THREAD_MAC_SECURITY_LEVEL]
+    pub level: u8,
+    pub key_id: FrameKeyId,
+    /// `None` if suppressed, only possible with the 2015 frame format
+    pub frame_counter: Option<u32>,
+}
+
+impl FrameSecurity {
+    /// Returns `true` if the payload is encrypted, i.e. for security levels 4 to 7
+    pub fn is_encrypted(&self) -> bool {
+        self.level & 0b100 != 0
+    }
+
+    /// Returns `true` if the frame carries a MIC, i.e. for all security levels but 0 and 4
+    pub fn is_authenticated(&self) -> bool {
+        self.level & 0b011 != 0
+    }
+}
+
+/// Periodic receive windows, the radio sleeps in between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RxWindowSchedule {
+    /// Time from the start of one window to the start of the next one (in milliseconds)
+    pub period: u32,
+    /// Time the radio receives in every period (in milliseconds)
+    pub window: u32,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct RxWindows {
+    schedule: Option<RxWindowSchedule>,
+    receiving: bool,
+    /// milliseconds
+    next_event: u64,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct SnifferResume {
+    thread_enabled: bool,
+    ipv6_enabled: bool,
+}
+
+impl RxWindows {
+    pub(crate) const DEFAULT: Self = Self {
+        schedule: None,
+        receiving: false,
+        next_event: 0,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Enable the raw link-layer to receive IEEE 802.15.4 frames without Thread
+    ///
+    /// Only succeeds while IPv6 is disabled, see [OpenThread::ipv6_set_enabled]. The callback
+    /// gets every received frame, the radio starts out sleeping. Passing `None` disables the
+    /// raw link-layer, stops the receive windows and the PAN coordinator.
+    pub fn link_raw_set_receive_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(&RawFrame) + Send)>,
+    ) -> Result<(), Error> {
+        let enabled = callback.is_some();
+        checked!(unsafe {
+            otLinkRawSetReceiveDone(
+                self.instance,
+                if enabled {
+                    Some(link_raw_receive_done)
+                } else {
+                    None
+                },
+            )
+        })?;
+
+        critical_section::with(|cs| {
+            let mut receive_callback = LINK_RAW_RECEIVE_CALLBACK.borrow_ref_mut(cs);
+            *receive_callback = unsafe { core::mem::transmute(callback) };
+
+            if !enabled {
+                RX_WINDOWS.borrow(cs).set(RxWindows::DEFAULT);
+                crate::pan::PAN.borrow_ref_mut(cs).take();
+                crate::pan::PAN_CALLBACK.borrow_ref_mut(cs).take();
+                crate::ranging::TIMESTAMPED_TX_CALLBACK
+                    .borrow_ref_mut(cs)
+                    .take();
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Returns `true` if the raw link-layer is enabled
+    pub fn link_raw_is_enabled(&self) -> bool {
+        unsafe { otLinkRawIsEnabled(self.instance) }
+    }
+
+    /// Turn on the receiver, on the channel set via [OpenThread::link_set_channel]
+    pub fn link_raw_receive(&mut self) -> Result<(), Error> {
+        checked!(unsafe { otLinkRawReceive(self.instance) })
+    }
+
+    /// Turn off the receiver
+    pub fn link_raw_sleep(&mut self) -> Result<(), Error> {
+        checked!(unsafe { otLinkRawSleep(self.instance) })
+    }
+
+    /// Receive in periodic windows (e.g. 10 ms every 500 ms) and sleep in between
+    ///
+    /// The first window starts right away. Windows are opened and closed as part of
+    /// [OpenThread::process], which needs to be called at least when
+    /// [OpenThread::link_raw_next_rx_window_event] is due. `None` stops the windows and leaves
+    /// the radio sleeping.
+    pub fn link_raw_set_rx_windows(
+        &mut self,
+        schedule: Option<RxWindowSchedule>,
+    ) -> Result<(), Error> {
+        if let Some(schedule) = schedule {
+            if schedule.period == 0 || schedule.window > schedule.period {
+                return Err(INVALID_ARGS);
+            }
+        } else {
+            checked!(unsafe { otLinkRawSleep(self.instance) })?;
+        }
+
+        critical_section::with(|cs| {
+            RX_WINDOWS.borrow(cs).set(RxWindows {
+                schedule,
+                receiving: false,
+                next_event: current_millis(),
+            })
+        });
+
+        process(self.instance);
+        Ok(())
+    }
+
+    /// Time (in milliseconds, see `current_millis`) the next receive window opens or closes
+    pub fn link_raw_next_rx_window_event(&self) -> Option<u64> {
+        critical_section::with(|cs| {
+            let rx_windows = RX_WINDOWS.borrow(cs).get();
+            rx_windows.schedule.map(|_| rx_windows.next_event)
+        })
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Pause Thread and receive every frame on `channel`, regardless of its destination
+    ///
+    /// Thread and IPv6 are disabled while sniffing, the instance and its datasets are kept.
+    /// [OpenThread::link_raw_stop_sniffer] resumes them.
+    pub fn link_raw_start_sniffer(
+        &mut self,
+        channel: u8,
+        callback: &'a mut (dyn FnMut(&RawFrame) + Send),
+    ) -> Result<(), Error> {
+        if self.link_raw_is_sniffing() {
+            return Err(INVALID_STATE);
+        }
+
+        let resume = SnifferResume {
+            thread_enabled: unsafe { otThreadGetDeviceRole(self.instance) }
+                != otDeviceRole_OT_DEVICE_ROLE_DISABLED,
+            ipv6_enabled: unsafe { otIp6IsEnabled(self.instance) },
+        };
+
+        let res = self.enter_sniffer(resume, channel, callback);
+        if res.is_err() {
+            self.resume_from_sniffer(resume).ok();
+        }
+        res
+    }
+
+    /// Stop sniffing and resume Thread as it was before [OpenThread::link_raw_start_sniffer]
+    pub fn link_raw_stop_sniffer(&mut self) -> Result<(), Error> {
+        let resume = critical_section::with(|cs| SNIFFER.borrow(cs).take());
+        self.resume_from_sniffer(resume.ok_or(INVALID_STATE)?)
+    }
+
+    /// Returns `true` while sniffing, see [OpenThread::link_raw_start_sniffer]
+    pub fn link_raw_is_sniffing(&self) -> bool {
+        critical_section::with(|cs| SNIFFER.borrow(cs).get().is_some())
+    }
+
+    fn enter_sniffer(
+        &mut self,
+        resume: SnifferResume,
+        channel: u8,
+        callback: &'a mut (dyn FnMut(&RawFrame) + Send),
+    ) -> Result<(), Error> {
+        if resume.thread_enabled {
+            checked!(unsafe { otThreadSetEnabled(self.instance, false) })?;
+        }
+        if resume.ipv6_enabled {
+            checked!(unsafe { otIp6SetEnabled(self.instance, false) })?;
+        }
+
+        self.link_raw_set_receive_callback(Some(callback))?;
+        // with the raw link-layer enabled this only changes its channel, the datasets are kept
+        checked!(unsafe { otLinkSetChannel(self.instance, channel) })?;
+        checked!(unsafe { otLinkSetPromiscuous(self.instance, true) })?;
+        checked!(unsafe { otLinkRawReceive(self.instance) })?;
+
+        critical_section::with(|cs| SNIFFER.borrow(cs).set(Some(resume)));
+        Ok(())
+    }
+
+    fn resume_from_sniffer(&mut self, resume: SnifferResume) -> Result<(), Error> {
+        checked!(unsafe { otLinkSetPromiscuous(self.instance, false) })?;
+        self.link_raw_set_receive_callback(None)?;
+
+        if resume.ipv6_enabled {
+            checked!(unsafe { otIp6SetEnabled(self.instance, true) })?;
+        }
+        if resume.thread_enabled {
+            checked!(unsafe { otThreadSetEnabled(self.instance, true) })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Open or close the receive window if due, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let now = current_millis();
+    let receive = critical_section::with(|cs| {
+        let cell = RX_WINDOWS.borrow(cs);
+        let mut rx_windows = cell.get();
+
+        let schedule = rx_windows.schedule?;
+        if now < rx_windows.next_event {
+            return None;
+        }
+
+        rx_windows.receiving = !rx_windows.receiving;
+        if rx_windows.receiving {
+            rx_windows.next_event += schedule.window as u64;
+        } else {
+            rx_windows.next_event += (schedule.period - schedule.window) as u64;
+        }
+
+        // skip windows missed while process() wasn't called
+        if rx_windows.next_event < now {
+            rx_windows.receiving = false;
+            rx_windows.next_event = now;
+        }
+
+        cell.set(rx_windows);
+        Some(rx_windows.receiving)
+    });
+
+    let res = match receive {
+        Some(true) => unsafe { otLinkRawReceive(instance) },
+        Some(false) => unsafe { otLinkRawSleep(instance) },
+        None => return,
+    };
+    if res != otError_OT_ERROR_NONE {
+        log::warn!("switching the receive window failed {}", res);
+    }
+}
+
+/// Parse the Auxiliary Security Header following the addressing fields of a secured frame
+fn parse_security(psdu: &[u8]) -> Option<FrameSecurity> {
+    let fcf = u16::from_le_bytes([*psdu.first()?, *psdu.get(1)?]);
+    if fcf & FCF_SECURITY_ENABLED == 0 {
+        return None;
+    }
+
+    let version_2015 = (fcf >> FCF_FRAME_VERSION_SHIFT) & 0b11 == FRAME_VERSION_2015;
+    let dst_mode = (fcf >> FCF_DST_ADDR_SHIFT) & 0b11;
+    let src_mode = (fcf >> FCF_SRC_ADDR_SHIFT) & 0b11;
+    let pan_id_compression = fcf & FCF_PAN_ID_COMPRESSION != 0;
+
+    // the PAN ID rules of IEEE 802.15.4-2015 table 7-2, the older ones depend on the addresses
+    let (dst_pan, src_pan) = if version_2015 {
+        let both_extended = dst_mode == ADDR_MODE_EXTENDED && src_mode == ADDR_MODE_EXTENDED;
+        let dst_pan = match (dst_mode, src_mode) {
+            (ADDR_MODE_NONE, ADDR_MODE_NONE) => pan_id_compression,
+            (ADDR_MODE_NONE, _) => false,
+            (_, ADDR_MODE_NONE) => !pan_id_compression,
+            _ => !(both_extended && pan_id_compression),
+        };
+        let src_pan = src_mode != ADDR_MODE_NONE && !pan_id_compression && !both_extended;
+        (dst_pan, src_pan)
+    } else {
+        (
+            dst_mode != ADDR_MODE_NONE,
+            src_mode != ADDR_MODE_NONE && !pan_id_compression,
+        )
+    };
+
+    let sequence = !(version_2015 && fcf & FCF_SEQUENCE_SUPPRESSION != 0);
+    let pos = 2
+        + sequence as usize
+        + if dst_pan { 2 } else { 0 }
+        + address_size(dst_mode)
+        + if src_pan { 2 } else { 0 }
+        + address_size(src_mode);
+
+    let header = psdu.get(pos..)?;
+    let control = *header.first()?;
+    let mut pos = 1;
+    let frame_counter = if control & SECURITY_FRAME_COUNTER_SUPPRESSION != 0 && version_2015 {
+        None
+    } else {
+        pos += 4;
+        Some(u32::from_le_bytes(header.get(1..5)?.try_into().ok()?))
+    };
+
+    let key_id = match (control >> SECURITY_KEY_ID_MODE_SHIFT) & 0b11 {
+        0 => FrameKeyId::Implicit,
+        1 => FrameKeyId::Index(*header.get(pos)?),
+        2 => FrameKeyId::Source4(
+            header.get(pos..pos + 4)?.try_into().ok()?,
+            *header.get(pos + 4)?,
+        ),
+        _ => FrameKeyId::Source8(
+            header.get(pos..pos + 8)?.try_into().ok()?,
+            *header.get(pos + 8)?,
+        ),
+    };
+
+    Some(FrameSecurity {
+        level: control & SECURITY_LEVEL_MASK,
+        key_id,
+        frame_counter,
+    })
+}
+
+fn address_size(mode: u16) -> usize {
+    match mode {
+        ADDR_MODE_SHORT => 2,
+        ADDR_MODE_EXTENDED => 8,
+        _ => 0,
+    }
+}
+
+/// Send `psdu` (without the FCS, which the radio appends) on the current channel with CSMA/CA
+///
+/// With `ack_request` the frame is retried up to three times, `done` is called once it's sent or
+/// failed.
+pub(crate) unsafe fn link_raw_transmit(
+    instance: *mut otInstance,
+    psdu: &[u8],
+    ack_request: bool,
+    done: otLinkRawTransmitDone,
+) -> otError {
+    let tx = otLinkRawGetTransmitBuffer(instance);
+    if tx.is_null() {
+        return otError_OT_ERROR_INVALID_STATE;
+    }
+
+    let tx = &mut *tx;
+    core::slice::from_raw_parts_mut(tx.mPsdu, psdu.len()).copy_from_slice(psdu);
+    tx.mLength = (psdu.len() + FCS_SIZE) as u16;
+    tx.mChannel = otLinkGetChannel(instance);
+
+    let tx_info = &mut tx.mInfo.mTxInfo;
+    tx_info.mMaxCsmaBackoffs = 4;
+    tx_info.mMaxFrameRetries = if ack_request { 3 } else { 0 };
+    tx_info.set_mCsmaCaEnabled(true);
+    tx_info.set_mIsARetx(false);
+    tx_info.set_mIsHeaderUpdated(false);
+    tx_info.set_mIsSecurityProcessed(false);
+
+    otLinkRawTransmit(instance, done)
+}
+
+unsafe extern "C" fn link_raw_receive_done(
+    _instance: *mut otInstance,
+    frame: *mut otRadioFrame,
+    error: otError,
+) {
+    log::debug!("link_raw_receive_done {}", error);
+
+    if error != otError_OT_ERROR_NONE || frame.is_null() {
+        return;
+    }
+
+    let frame = &*frame;
+    let psdu = core::slice::from_raw_parts(frame.mPsdu, frame.mLength as usize);
+    if crate::pan::on_frame(psdu) {
+        return;
+    }
+
+    let rx_info = &frame.mInfo.mRxInfo;
+    let raw_frame = RawFrame {
+        psdu,
+        channel: frame.mChannel,
+        rssi: rx_info.mRssi,
+        lqi: rx_info.mLqi,
+        timestamp: rx_info.mTimestamp,
+        ticks: critical_section::with(|cs| RADIO_TIMESTAMPS.borrow(cs).get().rx),
+    };
+
+    critical_section::with(|cs| {
+        let mut callback = LINK_RAW_RECEIVE_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(&raw_frame);
+        }
+    });
+}
@@ -0,0 +1,223 @@
+use core::{
+    cell::RefCell,
+    ffi::VaListImpl,
+    fmt::Write,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otLogLevel, OT_LOG_LEVEL_CRIT, OT_LOG_LEVEL_DEBG, OT_LOG_LEVEL_INFO, OT_LOG_LEVEL_NOTE,
+        OT_LOG_LEVEL_WARN,
+    },
+    c_types::c_char,
+};
+
+use crate::{printf, OpenThread};
+
+/// Longest log line passed to a sink, longer ones are cut off
+pub const LOG_LINE_MAX_SIZE: usize = 256;
+
+/// Bytes of log lines kept by [LogSink::RingBuffer]
+#[cfg(feature = "log-ring-buffer")]
+pub const LOG_RING_BUFFER_SIZE: usize = 2048;
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Debug as u8);
+
+pub(crate) static LOG_SINK: Mutex<RefCell<LogSink<'static>>> =
+    Mutex::new(RefCell::new(LogSink::Log));
+
+#[cfg(feature = "log-ring-buffer")]
+pub(crate) static LOG_RING_BUFFER: Mutex<RefCell<LogRingBuffer>> =
+    Mutex::new(RefCell::new(LogRingBuffer::DEFAULT));
+
+/// Level of an OpenThread log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    None = 0,
+    Crit = 1,
+    Warn = 2,
+    Note = 3,
+    Info = 4,
+    Debug = 5,
+}
+
+impl LogLevel {
+    fn from_raw(level: otLogLevel) -> Self {
+        match level as u32 {
+            OT_LOG_LEVEL_CRIT => Self::Crit,
+            OT_LOG_LEVEL_WARN => Self::Warn,
+            OT_LOG_LEVEL_NOTE => Self::Note,
+            OT_LOG_LEVEL_INFO => Self::Info,
+            OT_LOG_LEVEL_DEBG => Self::Debug,
+            _ => Self::None,
+        }
+    }
+}
+
+/// A custom destination for OpenThread's log lines, see [LogSink::Custom]
+pub trait LogWriter {
+    /// Write a line, without line break
+    fn write_line(&mut self, level: LogLevel, line: &str);
+}
+
+/// Where OpenThread's log lines go, see [OpenThread::set_log_sink]
+pub enum LogSink<'a> {
+    /// Forward to the `log` crate with target `openthread`, the default
+    Log,
+    /// Write to a serial port, e.g. an esp-hal `Uart`, each line ending with `\r\n`
+    Uart(&'a mut (dyn Write + Send)),
+    /// Write to an RTT up channel, e.g. one set up by `rtt_target::rtt_init!`
+    #[cfg(feature = "log-rtt")]
+    Rtt(rtt_target::UpChannel),
+    /// Keep the latest lines in RAM, see [OpenThread::log_ring_buffer_read]
+    #[cfg(feature = "log-ring-buffer")]
+    RingBuffer,
+    Custom(&'a mut (dyn LogWriter + Send)),
+}
+
+impl<'a> OpenThread<'a> {
+    /// Select where OpenThread's log lines go
+    ///
+    /// Which lines OpenThread emits at all is decided when building it (`OT_LOG_LEVEL`), see
+    /// [OpenThread::set_log_level] to filter further at runtime.
+    pub fn set_log_sink(&mut self, sink: LogSink<'a>) {
+        critical_section::with(|cs| {
+            *LOG_SINK.borrow_ref_mut(cs) = unsafe { core::mem::transmute(sink) };
+        });
+    }
+
+    /// Only pass log lines up to `level` to the sink
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+    }
+
+    pub fn log_level(&self) -> LogLevel {
+        LogLevel::from_raw(LOG_LEVEL.load(Ordering::Relaxed) as otLogLevel)
+    }
+
+    /// Take the oldest log lines kept by [LogSink::RingBuffer] into `buffer`
+    ///
+    /// Returns the number of bytes taken, the lines are separated by `\n`. Only whole lines are
+    /// taken unless the oldest line doesn't fit into `buffer`. E.g. to dump them into UDP
+    /// datagrams or CoAP responses on devices without a serial connection.
+    #[cfg(feature = "log-ring-buffer")]
+    pub fn log_ring_buffer_read(&mut self, buffer: &mut [u8]) -> usize {
+        critical_section::with(|cs| LOG_RING_BUFFER.borrow_ref_mut(cs).read(buffer))
+    }
+
+    /// Get the number of log lines [LogSink::RingBuffer] dropped to make room for newer ones
+    #[cfg(feature = "log-ring-buffer")]
+    pub fn log_ring_buffer_dropped(&self) -> u32 {
+        critical_section::with(|cs| LOG_RING_BUFFER.borrow_ref(cs).dropped)
+    }
+
+    /// Discard the log lines kept by [LogSink::RingBuffer]
+    #[cfg(feature = "log-ring-buffer")]
+    pub fn log_ring_buffer_clear(&mut self) {
+        critical_section::with(|cs| {
+            *LOG_RING_BUFFER.borrow_ref_mut(cs) = LogRingBuffer::DEFAULT;
+        });
+    }
+}
+
+#[cfg(feature = "log-ring-buffer")]
+pub(crate) struct LogRingBuffer {
+    lines: heapless::Deque<u8, LOG_RING_BUFFER_SIZE>,
+    dropped: u32,
+}
+
+#[cfg(feature = "log-ring-buffer")]
+impl LogRingBuffer {
+    pub(crate) const DEFAULT: Self = Self {
+        lines: heapless::Deque::new(),
+        dropped: 0,
+    };
+
+    fn push(&mut self, line: &str) {
+        // keep the line break
+        let line = &line.as_bytes()[..usize::min(line.len(), LOG_RING_BUFFER_SIZE - 1)];
+
+        while self.lines.capacity() - self.lines.len() < line.len() + 1 {
+            while let Some(byte) = self.lines.pop_front() {
+                if byte == b'\n' {
+                    break;
+                }
+            }
+            self.dropped = self.dropped.wrapping_add(1);
+        }
+
+        for byte in line.iter().chain(b"\n") {
+            // there is room, see above
+            let _ = self.lines.push_back(*byte);
+        }
+    }
+
+    fn read(&mut self, buffer: &mut [u8]) -> usize {
+        let available = usize::min(buffer.len(), self.lines.len());
+
+        // up to the last line break that fits, or all that fits if there is none
+        let end = self
+            .lines
+            .iter()
+            .take(available)
+            .enumerate()
+            .rfind(|(_, byte)| **byte == b'\n')
+            .map_or(available, |(index, _)| index + 1);
+
+        for byte in buffer[..end].iter_mut() {
+            // `end` is at most the number of bytes kept
+            *byte = self.lines.pop_front().unwrap_or_default();
+        }
+        end
+    }
+}
+
+/// Format a log line from OpenThread and pass it to the sink, called from `otPlatLog`
+///
+/// # Safety
+///
+/// `format` must be a NUL terminated string and `args` must match its conversions.
+pub(crate) unsafe fn log(level: otLogLevel, format: *const c_char, args: &mut VaListImpl<'_>) {
+    let level = LogLevel::from_raw(level);
+    if level == LogLevel::None || level as u8 > LOG_LEVEL.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let mut buffer = [0u8; LOG_LINE_MAX_SIZE];
+    let len = usize::min(
+        printf::format(&mut buffer, format, args),
+        LOG_LINE_MAX_SIZE - 1,
+    );
+    let line = match core::str::from_utf8(&buffer[..len]) {
+        Ok(line) => line,
+        // e.g. cut off in the middle of a character
+        Err(error) => core::str::from_utf8(&buffer[..error.valid_up_to()]).unwrap_or_default(),
+    };
+    let line = line.trim_end_matches(['\r', '\n']);
+
+    critical_section::with(|cs| match &mut *LOG_SINK.borrow_ref_mut(cs) {
+        LogSink::Log => {
+            let level = match level {
+                LogLevel::None | LogLevel::Crit => log::Level::Error,
+                LogLevel::Warn => log::Level::Warn,
+                LogLevel::Note | LogLevel::Info => log::Level::Info,
+                LogLevel::Debug => log::Level::Debug,
+            };
+            log::log!(target: "openthread", level, "{}", line);
+        }
+        LogSink::Uart(uart) => {
+            // nothing to do about a failing serial port
+            let _ = write!(uart, "{}\r\n", line);
+        }
+        #[cfg(feature = "log-rtt")]
+        LogSink::Rtt(channel) => {
+            channel.write(line.as_bytes());
+            channel.write(b"\n");
+        }
+        #[cfg(feature = "log-ring-buffer")]
+        LogSink::RingBuffer => LOG_RING_BUFFER.borrow_ref_mut(cs).push(line),
+        LogSink::Custom(writer) => writer.write_line(level, line),
+    });
+}
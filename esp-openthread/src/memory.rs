@@ -0,0 +1,75 @@
+use core::mem::size_of_val;
+
+use crate::{ip6, parent_search, radio, CHANGE_CALLBACK, NETWORK_SETTINGS, RADIO};
+
+/// Parse a decimal number given via an environment variable at build time
+const fn env_usize(value: Option<&str>, default: usize) -> usize {
+    let Some(value) = value else {
+        return default;
+    };
+
+    let bytes = value.as_bytes();
+    assert!(!bytes.is_empty(), "expected a decimal number");
+
+    let mut result = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "expected a decimal number");
+        result = result * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+    result
+}
+
+/// Number of received frames buffered between the radio interrupt and [crate::OpenThread::process]
+///
+/// Set via the `ESP_OPENTHREAD_RX_QUEUE_FRAMES` environment variable at build time, defaults to 8.
+pub const RX_QUEUE_FRAMES: usize = env_usize(option_env!("ESP_OPENTHREAD_RX_QUEUE_FRAMES"), 8);
+
+/// Largest PSDU accepted from the radio, longer frames are dropped
+///
+/// Set via the `ESP_OPENTHREAD_RX_PSDU_SIZE` environment variable at build time, defaults to the
+/// IEEE 802.15.4 maximum of 127 bytes.
+pub const RX_PSDU_SIZE: usize = env_usize(option_env!("ESP_OPENTHREAD_RX_PSDU_SIZE"), 127);
+
+const _: () = assert!(
+    RX_QUEUE_FRAMES > 0,
+    "ESP_OPENTHREAD_RX_QUEUE_FRAMES must be at least 1"
+);
+const _: () = assert!(
+    RX_PSDU_SIZE > 0 && RX_PSDU_SIZE <= 127,
+    "ESP_OPENTHREAD_RX_PSDU_SIZE must be between 1 and 127"
+);
+
+/// Static RAM used by this crate (in bytes)
+///
+/// Doesn't include OpenThread's own state nor socket buffers, which live wherever the sockets do.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticRamUsage {
+    /// Transmit and receive frame buffers
+    pub radio_frames: usize,
+    /// Queue of frames between the radio interrupt and [crate::OpenThread::process]
+    pub rx_queue: usize,
+    /// Callbacks and settings
+    pub bookkeeping: usize,
+}
+
+impl StaticRamUsage {
+    pub fn total(&self) -> usize {
+        self.radio_frames + self.rx_queue + self.bookkeeping
+    }
+}
+
+/// Report the static RAM footprint of this crate
+pub fn static_ram_usage() -> StaticRamUsage {
+    StaticRamUsage {
+        radio_frames: radio::FRAME_BUFFERS_SIZE + crate::RCV_FRAME_SIZE,
+        rx_queue: radio::RX_QUEUE_RAM_SIZE,
+        bookkeeping: size_of_val(&RADIO)
+            + size_of_val(&NETWORK_SETTINGS)
+            + size_of_val(&CHANGE_CALLBACK)
+            + size_of_val(&ip6::ADDRESS_CALLBACK)
+            + size_of_val(&ip6::RECEIVE_CALLBACK)
+            + size_of_val(&parent_search::PARENT_SEARCH),
+    }
+}
@@ -0,0 +1,88 @@
+use core::marker::PhantomData;
+
+use esp_openthread_sys::{
+    bindings::{otMessage, otMessageGetLength, otMessageGetOffset, otMessageRead},
+    c_types::c_void,
+};
+
+/// Reads the payload of an OpenThread message in chunks
+///
+/// Only valid while OpenThread hands out the message (i.e. inside a receive handler).
+/// Reading starts at the message's payload offset, the caller's buffer can be much
+/// smaller than the whole message.
+pub struct MessageReader<'m> {
+    message: *const otMessage,
+    offset: u16,
+    end: u16,
+    _phantom: PhantomData<&'m otMessage>,
+}
+
+impl<'m> MessageReader<'m> {
+    pub(crate) unsafe fn new(message: *const otMessage) -> Self {
+        Self {
+            message,
+            offset: otMessageGetOffset(message),
+            end: otMessageGetLength(message),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Total length of the payload in bytes
+    pub fn len(&self) -> usize {
+        (self.end - unsafe { otMessageGetOffset(self.message) }) as usize
+    }
+
+    /// Returns `true` if the payload is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of bytes not yet read
+    pub fn remaining(&self) -> usize {
+        (self.end - self.offset) as usize
+    }
+
+    /// Current read position relative to the start of the payload
+    pub fn position(&self) -> usize {
+        (self.offset - unsafe { otMessageGetOffset(self.message) }) as usize
+    }
+
+    /// Move the read position relative to the start of the payload
+    ///
+    /// The position is clamped to the end of the payload.
+    pub fn seek(&mut self, position: usize) {
+        let start = unsafe { otMessageGetOffset(self.message) } as usize;
+        self.offset = usize::min(start + position, self.end as usize) as u16;
+    }
+
+    /// Read the next chunk into `buf`
+    ///
+    /// Returns the number of bytes read, `0` means the whole payload was consumed.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        let len = usize::min(buf.len(), self.remaining()) as u16;
+        if len == 0 {
+            return 0;
+        }
+
+        let read = unsafe {
+            otMessageRead(
+                self.message,
+                self.offset,
+                buf.as_mut_ptr() as *mut c_void,
+                len,
+            )
+        };
+        self.offset += read;
+        read as usize
+    }
+
+    /// Read the next chunk into `header` and the one following it into `payload`
+    ///
+    /// Returns the number of bytes read into each. The header can also be read on its own with
+    /// [MessageReader::read] first, to pick the payload buffer by what it says.
+    pub fn read_split(&mut self, header: &mut [u8], payload: &mut [u8]) -> (usize, usize) {
+        let header_len = self.read(header);
+        let payload_len = self.read(payload);
+        (header_len, payload_len)
+    }
+}
@@ -0,0 +1,127 @@
+use core::{cell::RefCell, ptr::addr_of_mut};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otInstance, otMessage, otMessageGetLength, otMessageGetOffset, otMessageInfo,
+        otUdpAddReceiver, otUdpReceiver, otUdpRemoveReceiver,
+    },
+    c_types::c_void,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{ip6::ipv6_addr_from_ot_ip6_address, OpenThread};
+
+pub(crate) static MESSAGE_TRACE_HOOK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(&MessageTrace) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Registered with OpenThread while a hook is set, OpenThread links it into its receiver list
+static mut MESSAGE_TRACE_RECEIVER: otUdpReceiver = otUdpReceiver {
+    mNext: core::ptr::null_mut(),
+    mHandler: None,
+    mContext: core::ptr::null_mut(),
+};
+
+/// Whether a traced datagram was sent or received
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageDirection {
+    Transmit,
+    Receive,
+}
+
+/// Priority OpenThread queues a message with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessagePriority {
+    Low,
+    Normal,
+    High,
+}
+
+/// A UDP datagram observed by the message trace hook
+#[derive(Debug, Clone, Copy)]
+pub struct MessageTrace {
+    pub direction: MessageDirection,
+    /// Destination when transmitting, source when receiving
+    pub peer_address: Ipv6Addr,
+    pub peer_port: u16,
+    /// Port of the sending or receiving socket
+    pub sock_port: u16,
+    /// UDP payload length (in bytes)
+    pub length: usize,
+    /// `None` if OpenThread doesn't expose it, which is the case for received datagrams
+    pub priority: Option<MessagePriority>,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set a hook observing UDP datagrams, e.g. for an on-device traffic profiler
+    ///
+    /// Every UDP datagram OpenThread receives is reported, including the stack's own traffic
+    /// (MLE, CoAP), before it's handed to a socket. Transmitted datagrams are reported for the
+    /// sockets of this crate only, OpenThread doesn't offer a hook for its own transmissions.
+    ///
+    /// The hook runs inside [OpenThread::process] and [OpenThread::run_tasklets] respectively
+    /// inside the socket's send call, it needs to be quick.
+    pub fn set_message_trace_hook(
+        &mut self,
+        hook: Option<&'a mut (dyn FnMut(&MessageTrace) + Send)>,
+    ) {
+        let has_hook = hook.is_some();
+        critical_section::with(|cs| {
+            let mut trace_hook = MESSAGE_TRACE_HOOK.borrow_ref_mut(cs);
+            *trace_hook = unsafe { core::mem::transmute(hook) };
+        });
+
+        if !has_hook {
+            remove_message_trace_receiver(self.instance);
+            return;
+        }
+
+        unsafe {
+            let receiver = addr_of_mut!(MESSAGE_TRACE_RECEIVER);
+            if (*receiver).mHandler.is_none() {
+                (*receiver).mHandler = Some(message_trace_receiver);
+                otUdpAddReceiver(self.instance, receiver);
+            }
+        }
+    }
+}
+
+/// Report a datagram to the hook, if one is set
+pub(crate) fn trace_message(trace: &MessageTrace) {
+    critical_section::with(|cs| {
+        let mut hook = MESSAGE_TRACE_HOOK.borrow_ref_mut(cs);
+        if let Some(hook) = hook.as_mut() {
+            hook(trace);
+        }
+    });
+}
+
+pub(crate) fn remove_message_trace_receiver(instance: *mut otInstance) {
+    unsafe {
+        let receiver = addr_of_mut!(MESSAGE_TRACE_RECEIVER);
+        if (*receiver).mHandler.is_some() {
+            otUdpRemoveReceiver(instance, receiver);
+            (*receiver).mHandler = None;
+        }
+    }
+}
+
+unsafe extern "C" fn message_trace_receiver(
+    _context: *mut c_void,
+    message: *const otMessage,
+    message_info: *const otMessageInfo,
+) -> bool {
+    let message_info = &*message_info;
+    trace_message(&MessageTrace {
+        direction: MessageDirection::Receive,
+        peer_address: ipv6_addr_from_ot_ip6_address(&message_info.mPeerAddr),
+        peer_port: message_info.mPeerPort,
+        sock_port: message_info.mSockPort,
+        length: (otMessageGetLength(message) - otMessageGetOffset(message)) as usize,
+        priority: None,
+    });
+
+    // only observe, let OpenThread hand the datagram on
+    false
+}
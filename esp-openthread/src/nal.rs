@@ -0,0 +1,125 @@
+use core::{
+    future::poll_fn,
+    net::{Ipv6Addr as CoreIpv6Addr, SocketAddr, SocketAddrV6},
+    pin::Pin,
+};
+
+use esp_openthread_sys::bindings::otError_OT_ERROR_INVALID_ARGS;
+use no_std_net::Ipv6Addr;
+
+use crate::{new_message_info, Error, UdpSocket};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// A bound [UdpSocket] as `embedded_nal_async::UnconnectedUdp`, e.g. for a CoAP server
+///
+/// Sending doesn't wait, receiving waits for a datagram and needs OpenThread driven
+/// concurrently, e.g. by [crate::OpenThread::run]. The local address reported for received
+/// datagrams is the unspecified address with the bound port, OpenThread doesn't tell which of
+/// its addresses a datagram was sent to.
+pub struct NalUnconnectedUdp<'p, 's, 'n: 's, const BUFFER_SIZE: usize> {
+    socket: Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>>,
+}
+
+/// A bound [UdpSocket] talking to a single peer as `embedded_nal_async::ConnectedUdp`, e.g. for
+/// an MQTT-SN client
+///
+/// Datagrams from other peers are discarded. Like [NalUnconnectedUdp] receiving needs
+/// OpenThread driven concurrently.
+pub struct NalConnectedUdp<'p, 's, 'n: 's, const BUFFER_SIZE: usize> {
+    socket: Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>>,
+    remote: (Ipv6Addr, u16),
+}
+
+impl<'p, 's, 'n: 's, const BUFFER_SIZE: usize> NalUnconnectedUdp<'p, 's, 'n, BUFFER_SIZE> {
+    /// Wrap a socket already bound via [UdpSocket::bind] or [UdpSocket::bind_to]
+    pub fn new(socket: Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>>) -> Self {
+        Self { socket }
+    }
+
+    pub fn into_inner(self) -> Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>> {
+        self.socket
+    }
+}
+
+impl<'p, 's, 'n: 's, const BUFFER_SIZE: usize> NalConnectedUdp<'p, 's, 'n, BUFFER_SIZE> {
+    /// Wrap a socket already bound via [UdpSocket::bind] or [UdpSocket::bind_to], talking to
+    /// `remote`
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` for IPv4 addresses, Thread is IPv6 only.
+    pub fn new(
+        socket: Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>>,
+        remote: SocketAddr,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            socket,
+            remote: ipv6_endpoint(remote)?,
+        })
+    }
+
+    pub fn into_inner(self) -> Pin<&'p mut UdpSocket<'s, 'n, BUFFER_SIZE>> {
+        self.socket
+    }
+}
+
+impl<'p, 's, 'n: 's, const BUFFER_SIZE: usize> embedded_nal_async::UnconnectedUdp
+    for NalUnconnectedUdp<'p, 's, 'n, BUFFER_SIZE>
+{
+    type Error = Error;
+
+    async fn send(
+        &mut self,
+        local: SocketAddr,
+        remote: SocketAddr,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let (local_address, _) = ipv6_endpoint(local)?;
+        let (address, port) = ipv6_endpoint(remote)?;
+
+        let mut message_info = new_message_info();
+        // the unspecified address leaves the choice to OpenThread's source address selection
+        message_info.mSockAddr.mFields.m8 = local_address.octets();
+        message_info.mPeerAddr.mFields.m8 = address.octets();
+        message_info.mPeerPort = port;
+
+        self.socket.send_message(&mut message_info, &[data])
+    }
+
+    async fn receive_into(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<(usize, SocketAddr, SocketAddr), Self::Error> {
+        let (len, address, port) = poll_fn(|cx| self.socket.poll_receive(cx, buffer)).await?;
+
+        let local = SocketAddrV6::new(CoreIpv6Addr::UNSPECIFIED, self.socket.local_port(), 0, 0);
+        let remote = SocketAddrV6::new(CoreIpv6Addr::from(address.octets()), port, 0, 0);
+        Ok((len, local.into(), remote.into()))
+    }
+}
+
+impl<'p, 's, 'n: 's, const BUFFER_SIZE: usize> embedded_nal_async::ConnectedUdp
+    for NalConnectedUdp<'p, 's, 'n, BUFFER_SIZE>
+{
+    type Error = Error;
+
+    async fn send(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let (address, port) = self.remote;
+        self.socket.send(address, port, data)
+    }
+
+    async fn receive_into(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            let (len, address, port) = poll_fn(|cx| self.socket.poll_receive(cx, buffer)).await?;
+            if (address, port) == self.remote {
+                return Ok(len);
+            }
+        }
+    }
+}
+
+fn ipv6_endpoint(address: SocketAddr) -> Result<(Ipv6Addr, u16), Error> {
+    match address {
+        SocketAddr::V6(address) => Ok((Ipv6Addr::from(address.ip().octets()), address.port())),
+        SocketAddr::V4(_) => Err(INVALID_ARGS),
+    }
+}
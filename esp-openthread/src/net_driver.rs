@@ -0,0 +1,282 @@
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Waker},
+};
+
+use critical_section::Mutex;
+use embassy_net_driver::{Capabilities, HardwareAddress, LinkState};
+use esp_openthread_sys::{
+    bindings::{
+        otDeviceRole_OT_DEVICE_ROLE_CHILD, otDeviceRole_OT_DEVICE_ROLE_LEADER,
+        otDeviceRole_OT_DEVICE_ROLE_ROUTER, otError_OT_ERROR_INVALID_STATE, otInstance,
+        otIp6IsEnabled, otIp6NewMessageFromBuffer, otIp6Send, otIp6SetReceiveCallback, otMessage,
+        otMessageFree, otMessageGetLength, otMessageRead, otThreadGetDeviceRole,
+    },
+    c_types::c_void,
+};
+
+use crate::{ChangedFlags, Error, OpenThread};
+
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// IPv6 minimum MTU, the largest datagram passed through the driver
+pub const NET_DRIVER_MTU: usize = 1280;
+
+/// Number of received datagrams buffered until embassy-net picks them up
+pub const NET_DRIVER_RX_QUEUE_SIZE: usize = 2;
+
+/// Number of datagrams buffered until [OpenThread::process] hands them to OpenThread
+pub const NET_DRIVER_TX_QUEUE_SIZE: usize = 2;
+
+type Datagram = heapless::Vec<u8, NET_DRIVER_MTU>;
+
+/// Set while a [NetDriver] exists
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+pub(crate) static LINK_UP: AtomicBool = AtomicBool::new(false);
+
+pub(crate) static NET_DRIVER: Mutex<RefCell<NetQueues>> =
+    Mutex::new(RefCell::new(NetQueues::DEFAULT));
+
+pub(crate) struct NetQueues {
+    rx: heapless::Deque<Datagram, NET_DRIVER_RX_QUEUE_SIZE>,
+    tx: heapless::Deque<Datagram, NET_DRIVER_TX_QUEUE_SIZE>,
+    rx_waker: Option<Waker>,
+    tx_waker: Option<Waker>,
+    link_waker: Option<Waker>,
+}
+
+impl NetQueues {
+    pub(crate) const DEFAULT: Self = Self {
+        rx: heapless::Deque::new(),
+        tx: heapless::Deque::new(),
+        rx_waker: None,
+        tx_waker: None,
+        link_waker: None,
+    };
+}
+
+/// The Thread interface as an `embassy_net_driver::Driver`, to run embassy-net's sockets on top
+/// of Thread
+///
+/// The driver only moves IPv6 datagrams, OpenThread still needs to be driven via
+/// [OpenThread::process] and [OpenThread::run_tasklets] (e.g. by [OpenThread::run] with the
+/// `async` feature). embassy-net doesn't learn Thread's addresses on its own, configure it with
+/// a static IPv6 address assigned by OpenThread, e.g. the mesh-local EID. The link is up while
+/// the device is attached as child, router or leader.
+pub struct NetDriver {
+    _private: (),
+}
+
+pub struct NetRxToken {
+    datagram: Datagram,
+}
+
+pub struct NetTxToken {
+    _private: (),
+}
+
+impl<'a> OpenThread<'a> {
+    /// Create the embassy-net driver for the Thread interface
+    ///
+    /// Takes over the IPv6 receive callback, see [OpenThread::set_ipv6_receive_callback]. Fails
+    /// with `OT_ERROR_INVALID_STATE` while another [NetDriver] exists.
+    pub fn net_driver(&mut self) -> Result<NetDriver, Error> {
+        if TAKEN.swap(true, Ordering::AcqRel) {
+            return Err(INVALID_STATE);
+        }
+
+        unsafe {
+            otIp6SetReceiveCallback(self.instance, Some(receive_callback), core::ptr::null_mut());
+        }
+        update_link_state(self.instance);
+
+        Ok(NetDriver { _private: () })
+    }
+}
+
+impl Drop for NetDriver {
+    fn drop(&mut self) {
+        critical_section::with(|cs| *NET_DRIVER.borrow_ref_mut(cs) = NetQueues::DEFAULT);
+        TAKEN.store(false, Ordering::Release);
+    }
+}
+
+impl embassy_net_driver::Driver for NetDriver {
+    type RxToken<'a> = NetRxToken;
+    type TxToken<'a> = NetTxToken;
+
+    fn receive(&mut self, cx: &mut Context) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
+        critical_section::with(|cs| {
+            let mut queues = NET_DRIVER.borrow_ref_mut(cs);
+            match queues.rx.pop_front() {
+                Some(datagram) => Some((NetRxToken { datagram }, NetTxToken { _private: () })),
+                None => {
+                    register(&mut queues.rx_waker, cx);
+                    None
+                }
+            }
+        })
+    }
+
+    fn transmit(&mut self, cx: &mut Context) -> Option<Self::TxToken<'_>> {
+        critical_section::with(|cs| {
+            let mut queues = NET_DRIVER.borrow_ref_mut(cs);
+            if queues.tx.is_full() {
+                register(&mut queues.tx_waker, cx);
+                None
+            } else {
+                Some(NetTxToken { _private: () })
+            }
+        })
+    }
+
+    fn link_state(&mut self, cx: &mut Context) -> LinkState {
+        critical_section::with(|cs| register(&mut NET_DRIVER.borrow_ref_mut(cs).link_waker, cx));
+
+        if LINK_UP.load(Ordering::Acquire) {
+            LinkState::Up
+        } else {
+            LinkState::Down
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let mut capabilities = Capabilities::default();
+        capabilities.max_transmission_unit = NET_DRIVER_MTU;
+        capabilities
+    }
+
+    fn hardware_address(&self) -> HardwareAddress {
+        // OpenThread does the 6LoWPAN adaptation, embassy-net sees plain IPv6
+        HardwareAddress::Ip
+    }
+}
+
+impl embassy_net_driver::RxToken for NetRxToken {
+    fn consume<R, F>(mut self, f: F) -> R
+    where
+        F: FnOnce(&mut [u8]) -> R,
+    {
+        f(&mut self.datagram)
+    }
+}
+
+impl embassy_net_driver::TxToken for NetTxToken {
+    fn consume<R, F>(self, len: usize, f: F) -> R
+    where
+        F: FnOnce(&mut [u8]) -> R,
+    {
+        let mut datagram = Datagram::new();
+        // embassy-net doesn't exceed the MTU it was given
+        datagram.resize_default(len.min(NET_DRIVER_MTU)).ok();
+        let result = f(&mut datagram);
+
+        let queued = critical_section::with(|cs| {
+            NET_DRIVER.borrow_ref_mut(cs).tx.push_back(datagram).is_ok()
+        });
+        if queued {
+            #[cfg(feature = "async")]
+            crate::asynch::signal();
+        } else {
+            log::warn!("net driver transmit queue full, datagram dropped");
+        }
+
+        result
+    }
+}
+
+/// Hand the datagrams embassy-net queued to OpenThread, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    loop {
+        let datagram = critical_section::with(|cs| NET_DRIVER.borrow_ref_mut(cs).tx.pop_front());
+        let Some(datagram) = datagram else {
+            break;
+        };
+
+        let message = unsafe {
+            otIp6NewMessageFromBuffer(
+                instance,
+                datagram.as_ptr(),
+                datagram.len() as u16,
+                core::ptr::null(),
+            )
+        };
+        if message.is_null() {
+            log::warn!("no message buffer or malformed IPv6 header, datagram dropped");
+        } else {
+            // takes ownership of the message, also on failure
+            let res = unsafe { otIp6Send(instance, message) };
+            if res != 0 {
+                log::debug!("otIp6Send failed {}", res);
+            }
+        }
+
+        critical_section::with(|cs| wake(&mut NET_DRIVER.borrow_ref_mut(cs).tx_waker));
+    }
+}
+
+/// Follow the device role and interface state, called from the state change callback
+pub(crate) fn on_changes(instance: *mut otInstance, flags: ChangedFlags) {
+    if flags.intersects(
+        ChangedFlags::ThreadRoleChanged | ChangedFlags::ThreadNetworkInterfaceStateChanged,
+    ) {
+        update_link_state(instance);
+    }
+}
+
+fn update_link_state(instance: *mut otInstance) {
+    let role = unsafe { otThreadGetDeviceRole(instance) };
+    let up = unsafe { otIp6IsEnabled(instance) }
+        && (role == otDeviceRole_OT_DEVICE_ROLE_CHILD
+            || role == otDeviceRole_OT_DEVICE_ROLE_ROUTER
+            || role == otDeviceRole_OT_DEVICE_ROLE_LEADER);
+
+    if LINK_UP.swap(up, Ordering::AcqRel) != up {
+        critical_section::with(|cs| wake(&mut NET_DRIVER.borrow_ref_mut(cs).link_waker));
+    }
+}
+
+unsafe extern "C" fn receive_callback(message: *mut otMessage, _context: *mut c_void) {
+    // the callback outlives a dropped driver
+    if TAKEN.load(Ordering::Acquire) {
+        queue_received(message);
+    }
+
+    // we own the message
+    otMessageFree(message);
+}
+
+unsafe fn queue_received(message: *mut otMessage) {
+    let len = otMessageGetLength(message) as usize;
+    if len > NET_DRIVER_MTU {
+        log::warn!("received datagram of {len} bytes exceeds the MTU, dropped");
+        return;
+    }
+
+    let mut datagram = Datagram::new();
+    datagram.resize_default(len).ok();
+    otMessageRead(message, 0, datagram.as_mut_ptr() as *mut c_void, len as u16);
+
+    critical_section::with(|cs| {
+        let mut queues = NET_DRIVER.borrow_ref_mut(cs);
+        if queues.rx.push_back(datagram).is_err() {
+            log::debug!("net driver receive queue full, datagram dropped");
+        }
+        wake(&mut queues.rx_waker);
+    });
+}
+
+fn register(slot: &mut Option<Waker>, cx: &Context) {
+    match slot {
+        Some(waker) if waker.will_wake(cx.waker()) => (),
+        _ => *slot = Some(cx.waker().clone()),
+    }
+}
+
+fn wake(slot: &mut Option<Waker>) {
+    if let Some(waker) = slot.take() {
+        waker.wake();
+    }
+}
@@ -0,0 +1,68 @@
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND, otExtAddress,
+    otJoinerDiscerner, otNetDataGetStableVersion, otNetDataGetVersion,
+    otNetDataSteeringDataCheckJoiner, otNetDataSteeringDataCheckJoinerWithDiscerner,
+    OT_JOINER_MAX_DISCERNER_LENGTH,
+};
+
+use crate::{Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// Versions of the Network Data as distributed by the leader
+///
+/// Every change of the Network Data increments the version, changes to stable data also the
+/// stable version. Once all devices report the same versions they share the same configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkDataVersions {
+    pub version: u8,
+    pub stable_version: u8,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the versions of the full and the stable Network Data
+    pub fn netdata_get_versions(&self) -> NetworkDataVersions {
+        NetworkDataVersions {
+            version: unsafe { otNetDataGetVersion(self.instance) },
+            stable_version: unsafe { otNetDataGetStableVersion(self.instance) },
+        }
+    }
+
+    /// Check whether the steering data allows the joiner with the given EUI-64
+    ///
+    /// Fails if the Network Data has no steering data, i.e. no commissioner is active.
+    pub fn netdata_steering_data_contains_joiner(&self, eui64: [u8; 8]) -> Result<bool, Error> {
+        let eui64 = otExtAddress { m8: eui64 };
+        steering_data_result(unsafe { otNetDataSteeringDataCheckJoiner(self.instance, &eui64) })
+    }
+
+    /// Check whether the steering data allows the joiner with the given discerner
+    ///
+    /// `length` is the number of lowest bits of `value` making up the discerner.
+    pub fn netdata_steering_data_contains_discerner(
+        &self,
+        value: u64,
+        length: u8,
+    ) -> Result<bool, Error> {
+        if length == 0 || length > OT_JOINER_MAX_DISCERNER_LENGTH as u8 {
+            return Err(INVALID_ARGS);
+        }
+
+        let discerner = otJoinerDiscerner {
+            mValue: value,
+            mLength: length,
+        };
+        steering_data_result(unsafe {
+            otNetDataSteeringDataCheckJoinerWithDiscerner(self.instance, &discerner)
+        })
+    }
+}
+
+#[allow(non_upper_case_globals)]
+fn steering_data_result(res: u32) -> Result<bool, Error> {
+    match res {
+        otError_OT_ERROR_NONE => Ok(true),
+        otError_OT_ERROR_NOT_FOUND => Ok(false),
+        _ => Err(Error::InternalError(res)),
+    }
+}
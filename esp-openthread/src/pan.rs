@@ -0,0 +1,684 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otError, otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NONE,
+    otError_OT_ERROR_NO_BUFS, otInstance, otLinkGetExtendedAddress, otLinkRawReceive,
+    otLinkRawSetShortAddress, otLinkSetChannel, otRadioFrame,
+};
+
+use crate::{
+    checked,
+    link_raw::{
+        link_raw_transmit, ADDR_MODE_EXTENDED, ADDR_MODE_NONE, ADDR_MODE_SHORT, FCF_DST_ADDR_SHIFT,
+        FCF_FRAME_VERSION_SHIFT, FCF_PAN_ID_COMPRESSION, FCF_SECURITY_ENABLED, FCF_SRC_ADDR_SHIFT,
+        FCS_SIZE,
+    },
+    radio::otPlatRadioSetPanId,
+    Error, OpenThread, RawFrame,
+};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Number of devices which can be associated with the coordinator
+pub const PAN_MAX_DEVICES: usize = 16;
+
+/// Short address the coordinator uses for itself
+pub const PAN_COORDINATOR_SHORT_ADDRESS: u16 = 0x0000;
+
+/// Broadcast short address, frames sent to it aren't acknowledged
+pub const PAN_BROADCAST_ADDRESS: u16 = 0xffff;
+
+/// Longest beacon payload (aMaxBeaconPayloadLength)
+pub const PAN_BEACON_PAYLOAD_MAX_SIZE: usize = 52;
+
+/// Longest payload of a data frame sent via [OpenThread::pan_send]
+pub const PAN_DATA_PAYLOAD_MAX_SIZE: usize = MAX_PSDU_SIZE - FCS_SIZE - 9;
+
+const MAX_PSDU_SIZE: usize = 127;
+const TX_QUEUE_SIZE: usize = 4;
+
+const FRAME_TYPE_BEACON: u16 = 0;
+const FRAME_TYPE_DATA: u16 = 1;
+const FRAME_TYPE_COMMAND: u16 = 3;
+
+const FCF_ACK_REQUEST: u16 = 1 << 5;
+
+const COMMAND_ASSOCIATION_REQUEST: u8 = 0x01;
+const COMMAND_ASSOCIATION_RESPONSE: u8 = 0x02;
+const COMMAND_BEACON_REQUEST: u8 = 0x07;
+
+const ASSOCIATION_SUCCESS: u8 = 0x00;
+const ASSOCIATION_PAN_AT_CAPACITY: u8 = 0x01;
+const ASSOCIATION_ACCESS_DENIED: u8 = 0x02;
+
+/// Beacon order and superframe order 15 (no superframe), final CAP slot 15, PAN coordinator
+const SUPERFRAME_SPEC: u16 = 0x4fff;
+const SUPERFRAME_ASSOCIATION_PERMIT: u16 = 1 << 15;
+
+pub(crate) static PAN_CALLBACK: Mutex<RefCell<Option<&'static mut (dyn FnMut(PanEvent) + Send)>>> =
+    Mutex::new(RefCell::new(None));
+
+/// The coordinator while started, see [OpenThread::pan_coordinator_start]
+pub(crate) static PAN: Mutex<RefCell<Option<PanCoordinator>>> = Mutex::new(RefCell::new(None));
+
+/// Address of a device in a PAN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanAddress {
+    Short(u16),
+    /// IEEE 802.15.4 Extended Address, most significant byte first
+    Extended([u8; 8]),
+}
+
+/// A device associated with the coordinator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanDevice {
+    /// IEEE 802.15.4 Extended Address, most significant byte first
+    pub ext_address: [u8; 8],
+    /// Short address assigned by the coordinator
+    pub short_address: u16,
+}
+
+/// Settings of a PAN started via [OpenThread::pan_coordinator_start]
+#[derive(Debug, Clone, Copy)]
+pub struct PanCoordinatorConfig<'c> {
+    pub pan_id: u16,
+    pub channel: u8,
+    /// Accept association requests of devices not in the device table yet
+    pub permit_association: bool,
+    /// Sent in every beacon, e.g. to tell the PAN of an application apart from others, at most
+    /// [PAN_BEACON_PAYLOAD_MAX_SIZE] bytes
+    pub beacon_payload: &'c [u8],
+}
+
+/// Reported by the coordinator to the callback passed to [OpenThread::pan_coordinator_start]
+#[derive(Debug, Clone, Copy)]
+pub enum PanEvent {
+    /// The device acknowledged its association response and is part of the PAN
+    Associated(PanDevice),
+    /// An association request was turned down
+    AssociationDenied {
+        ext_address: [u8; 8],
+        /// The device table is full, otherwise associations aren't permitted
+        pan_at_capacity: bool,
+    },
+    /// A frame queued via [OpenThread::pan_send] was sent, or failed, e.g. with
+    /// `OT_ERROR_NO_ACK`
+    TransmitDone {
+        destination: u16,
+        result: Result<(), Error>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum TxKind {
+    /// A beacon or a denied association, nothing to report
+    Management,
+    AssociationResponse {
+        device: PanDevice,
+        /// Roll the device back if it doesn't get the response
+        added: bool,
+    },
+    Data {
+        destination: u16,
+    },
+}
+
+struct TxFrame {
+    /// Without the FCS, which the radio appends
+    psdu: heapless::Vec<u8, { MAX_PSDU_SIZE - FCS_SIZE }>,
+    ack_request: bool,
+    kind: TxKind,
+}
+
+pub(crate) struct PanCoordinator {
+    pan_id: u16,
+    ext_address: [u8; 8],
+    permit_association: bool,
+    beacon_payload: heapless::Vec<u8, PAN_BEACON_PAYLOAD_MAX_SIZE>,
+    devices: heapless::Vec<PanDevice, PAN_MAX_DEVICES>,
+    sequence: u8,
+    tx_queue: heapless::Deque<TxFrame, TX_QUEUE_SIZE>,
+    in_flight: Option<TxKind>,
+}
+
+/// The parts of a received frame the coordinator looks at
+struct MacFrame<'p> {
+    frame_type: u16,
+    dst_pan: Option<u16>,
+    src: Option<PanAddress>,
+    /// MAC payload, without the FCS
+    payload: &'p [u8],
+}
+
+impl<'a> OpenThread<'a> {
+    /// Run a basic IEEE 802.15.4 star network as its PAN coordinator, e.g. for proprietary
+    /// sensors, without Thread
+    ///
+    /// Needs the raw link-layer enabled via [OpenThread::link_raw_set_receive_callback], the
+    /// receiver stays on while the coordinator runs. Beacon requests are answered with a beacon
+    /// and association requests with a short address, these frames don't reach the receive
+    /// callback. The association response is sent right away rather than held for a data
+    /// request, so devices have to keep receiving after requesting association. Thread's
+    /// datasets are left untouched.
+    pub fn pan_coordinator_start(
+        &mut self,
+        config: &PanCoordinatorConfig,
+        callback: &'a mut (dyn FnMut(PanEvent) + Send),
+    ) -> Result<(), Error> {
+        if !self.link_raw_is_enabled() {
+            return Err(INVALID_STATE);
+        }
+        if config.pan_id == PAN_BROADCAST_ADDRESS {
+            return Err(INVALID_ARGS);
+        }
+        let beacon_payload =
+            heapless::Vec::from_slice(config.beacon_payload).map_err(|_| INVALID_ARGS)?;
+
+        // `otLinkSetPanId` would clear the Thread datasets, the PAN ID only matters to the radio
+        otPlatRadioSetPanId(self.instance, config.pan_id);
+        checked!(unsafe { otLinkSetChannel(self.instance, config.channel) })?;
+        checked!(unsafe {
+            otLinkRawSetShortAddress(self.instance, PAN_COORDINATOR_SHORT_ADDRESS)
+        })?;
+        checked!(unsafe { otLinkRawReceive(self.instance) })?;
+
+        let ext_address = unsafe { (*otLinkGetExtendedAddress(self.instance)).m8 };
+
+        critical_section::with(|cs| {
+            *PAN.borrow_ref_mut(cs) = Some(PanCoordinator {
+                pan_id: config.pan_id,
+                ext_address,
+                permit_association: config.permit_association,
+                beacon_payload,
+                devices: heapless::Vec::new(),
+                sequence: 0,
+                tx_queue: heapless::Deque::new(),
+                in_flight: None,
+            });
+
+            let mut pan_callback = PAN_CALLBACK.borrow_ref_mut(cs);
+            *pan_callback = unsafe { core::mem::transmute(Some(callback)) };
+        });
+
+        Ok(())
+    }
+
+    /// Stop answering beacon and association requests, frames not sent yet are dropped
+    pub fn pan_coordinator_stop(&mut self) {
+        critical_section::with(|cs| {
+            PAN.borrow_ref_mut(cs).take();
+            PAN_CALLBACK.borrow_ref_mut(cs).take();
+        });
+    }
+
+    /// Returns `true` while the coordinator runs, see [OpenThread::pan_coordinator_start]
+    pub fn pan_coordinator_is_running(&self) -> bool {
+        critical_section::with(|cs| PAN.borrow_ref(cs).is_some())
+    }
+
+    /// Accept or refuse association requests of devices not in the device table yet
+    pub fn pan_coordinator_set_permit_association(&mut self, permit: bool) -> Result<(), Error> {
+        with_pan(|pan| {
+            pan.permit_association = permit;
+            Ok(())
+        })
+    }
+
+    /// Copy the device table into `devices`, returns the number of devices copied
+    pub fn pan_coordinator_devices(&self, devices: &mut [PanDevice]) -> usize {
+        critical_section::with(|cs| {
+            let pan = PAN.borrow_ref(cs);
+            let Some(pan) = pan.as_ref() else {
+                return 0;
+            };
+
+            let count = pan.devices.len().min(devices.len());
+            devices[..count].copy_from_slice(&pan.devices[..count]);
+            count
+        })
+    }
+
+    /// Put a device into the table, e.g. to restore the table from flash after a reboot
+    ///
+    /// Fails with `OT_ERROR_NO_BUFS` if the table is full and with `OT_ERROR_INVALID_ARGS` if
+    /// the short address is reserved or taken by another device.
+    pub fn pan_coordinator_add_device(&mut self, device: PanDevice) -> Result<(), Error> {
+        with_pan(|pan| {
+            if !is_assignable(device.short_address)
+                || pan.devices.iter().any(|other| {
+                    other.short_address == device.short_address
+                        && other.ext_address != device.ext_address
+                })
+            {
+                return Err(INVALID_ARGS);
+            }
+
+            match pan
+                .devices
+                .iter_mut()
+                .find(|other| other.ext_address == device.ext_address)
+            {
+                Some(other) => *other = device,
+                None => pan.devices.push(device).map_err(|_| NO_BUFS)?,
+            }
+            Ok(())
+        })
+    }
+
+    /// Remove a device from the table, it has to associate again to get a short address
+    ///
+    /// Returns `false` if it wasn't in the table.
+    pub fn pan_coordinator_remove_device(&mut self, ext_address: [u8; 8]) -> bool {
+        critical_section::with(|cs| {
+            PAN.borrow_ref_mut(cs)
+                .as_mut()
+                .is_some_and(|pan| pan.remove_device(ext_address))
+        })
+    }
+
+    /// Queue a data frame from the coordinator to the device with the short address
+    /// `destination`, or to all devices via [PAN_BROADCAST_ADDRESS]
+    ///
+    /// Frames are sent as part of [OpenThread::process], the outcome is reported as
+    /// [PanEvent::TransmitDone]. With `ack` the frame is retried until acknowledged, which isn't
+    /// possible for broadcasts. Fails with `OT_ERROR_NO_BUFS` while four frames are queued.
+    pub fn pan_send(&mut self, destination: u16, payload: &[u8], ack: bool) -> Result<(), Error> {
+        if payload.len() > PAN_DATA_PAYLOAD_MAX_SIZE {
+            return Err(INVALID_ARGS);
+        }
+
+        with_pan(|pan| {
+            let mut fcf = FRAME_TYPE_DATA
+                | FCF_PAN_ID_COMPRESSION
+                | (ADDR_MODE_SHORT << FCF_DST_ADDR_SHIFT)
+                | (ADDR_MODE_SHORT << FCF_SRC_ADDR_SHIFT);
+            let ack_request = ack && destination != PAN_BROADCAST_ADDRESS;
+            if ack_request {
+                fcf |= FCF_ACK_REQUEST;
+            }
+
+            let mut psdu = heapless::Vec::new();
+            push(&mut psdu, &fcf.to_le_bytes())?;
+            push(&mut psdu, &[pan.next_sequence()])?;
+            push(&mut psdu, &pan.pan_id.to_le_bytes())?;
+            push(&mut psdu, &destination.to_le_bytes())?;
+            push(&mut psdu, &PAN_COORDINATOR_SHORT_ADDRESS.to_le_bytes())?;
+            push(&mut psdu, payload)?;
+
+            pan.tx_queue
+                .push_back(TxFrame {
+                    psdu,
+                    ack_request,
+                    kind: TxKind::Data { destination },
+                })
+                .map_err(|_| NO_BUFS)
+        })
+    }
+}
+
+impl<'a> RawFrame<'a> {
+    /// Sender and payload if this is an unsecured data frame, e.g. from a device of the PAN
+    /// run via [OpenThread::pan_coordinator_start]
+    pub fn pan_data(&self) -> Option<(PanAddress, &'a [u8])> {
+        let frame = parse_frame(self.psdu)?;
+        if frame.frame_type != FRAME_TYPE_DATA {
+            return None;
+        }
+
+        Some((frame.src?, frame.payload))
+    }
+}
+
+impl PanCoordinator {
+    fn next_sequence(&mut self) -> u8 {
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+        sequence
+    }
+
+    fn remove_device(&mut self, ext_address: [u8; 8]) -> bool {
+        let before = self.devices.len();
+        self.devices
+            .retain(|device| device.ext_address != ext_address);
+        self.devices.len() != before
+    }
+
+    fn queue_beacon(&mut self) -> Result<(), Error> {
+        let fcf = FRAME_TYPE_BEACON | (ADDR_MODE_SHORT << FCF_SRC_ADDR_SHIFT);
+        let mut superframe_spec = SUPERFRAME_SPEC;
+        if self.permit_association && !self.devices.is_full() {
+            superframe_spec |= SUPERFRAME_ASSOCIATION_PERMIT;
+        }
+
+        let mut psdu = heapless::Vec::new();
+        push(&mut psdu, &fcf.to_le_bytes())?;
+        push(&mut psdu, &[self.next_sequence()])?;
+        push(&mut psdu, &self.pan_id.to_le_bytes())?;
+        push(&mut psdu, &PAN_COORDINATOR_SHORT_ADDRESS.to_le_bytes())?;
+        push(&mut psdu, &superframe_spec.to_le_bytes())?;
+        // no GTS, no pending addresses
+        push(&mut psdu, &[0, 0])?;
+        push(&mut psdu, &self.beacon_payload)?;
+
+        self.tx_queue
+            .push_back(TxFrame {
+                psdu,
+                ack_request: false,
+                kind: TxKind::Management,
+            })
+            .map_err(|_| NO_BUFS)
+    }
+
+    /// Assign a short address to `ext_address` and queue the response, returns the event to
+    /// report if the request is denied
+    fn associate(&mut self, ext_address: [u8; 8]) -> Option<PanEvent> {
+        let known = self
+            .devices
+            .iter()
+            .find(|device| device.ext_address == ext_address)
+            .copied();
+
+        let (status, device, added) = if let Some(device) = known {
+            (ASSOCIATION_SUCCESS, device, false)
+        } else if !self.permit_association {
+            (ASSOCIATION_ACCESS_DENIED, unassigned(ext_address), false)
+        } else if let Some(short_address) = self.free_short_address() {
+            let device = PanDevice {
+                ext_address,
+                short_address,
+            };
+            if self.devices.push(device).is_ok() {
+                (ASSOCIATION_SUCCESS, device, true)
+            } else {
+                (ASSOCIATION_PAN_AT_CAPACITY, unassigned(ext_address), false)
+            }
+        } else {
+            (ASSOCIATION_PAN_AT_CAPACITY, unassigned(ext_address), false)
+        };
+
+        if self
+            .queue_association_response(status, device, added)
+            .is_err()
+        {
+            log::warn!("no room to queue the association response");
+            if added {
+                self.remove_device(ext_address);
+            }
+            return None;
+        }
+
+        (status != ASSOCIATION_SUCCESS).then_some(PanEvent::AssociationDenied {
+            ext_address,
+            pan_at_capacity: status == ASSOCIATION_PAN_AT_CAPACITY,
+        })
+    }
+
+    fn queue_association_response(
+        &mut self,
+        status: u8,
+        device: PanDevice,
+        added: bool,
+    ) -> Result<(), Error> {
+        let fcf = FRAME_TYPE_COMMAND
+            | FCF_ACK_REQUEST
+            | FCF_PAN_ID_COMPRESSION
+            | (ADDR_MODE_EXTENDED << FCF_DST_ADDR_SHIFT)
+            | (ADDR_MODE_EXTENDED << FCF_SRC_ADDR_SHIFT);
+
+        let mut psdu = heapless::Vec::new();
+        push(&mut psdu, &fcf.to_le_bytes())?;
+        push(&mut psdu, &[self.next_sequence()])?;
+        push(&mut psdu, &self.pan_id.to_le_bytes())?;
+        push(&mut psdu, &reversed(device.ext_address))?;
+        push(&mut psdu, &reversed(self.ext_address))?;
+        push(&mut psdu, &[COMMAND_ASSOCIATION_RESPONSE])?;
+        push(&mut psdu, &device.short_address.to_le_bytes())?;
+        push(&mut psdu, &[status])?;
+
+        let kind = if status == ASSOCIATION_SUCCESS {
+            TxKind::AssociationResponse { device, added }
+        } else {
+            TxKind::Management
+        };
+
+        self.tx_queue
+            .push_back(TxFrame {
+                psdu,
+                ack_request: true,
+                kind,
+            })
+            .map_err(|_| NO_BUFS)
+    }
+
+    fn free_short_address(&self) -> Option<u16> {
+        (PAN_COORDINATOR_SHORT_ADDRESS + 1..0xfffe).find(|short_address| {
+            !self
+                .devices
+                .iter()
+                .any(|device| device.short_address == *short_address)
+        })
+    }
+}
+
+/// Answer beacon and association requests, returns `true` if `psdu` was such a request
+///
+/// Called for every frame the raw link-layer receives, before the receive callback.
+pub(crate) fn on_frame(psdu: &[u8]) -> bool {
+    let Some(frame) = parse_frame(psdu) else {
+        return false;
+    };
+    if frame.frame_type != FRAME_TYPE_COMMAND {
+        return false;
+    }
+    let Some(&command) = frame.payload.first() else {
+        return false;
+    };
+
+    let (consumed, event) = critical_section::with(|cs| {
+        let mut pan = PAN.borrow_ref_mut(cs);
+        let Some(pan) = pan.as_mut() else {
+            return (false, None);
+        };
+
+        match (command, frame.src) {
+            (COMMAND_BEACON_REQUEST, _) => {
+                if pan.queue_beacon().is_err() {
+                    log::warn!("no room to queue a beacon");
+                }
+                (true, None)
+            }
+            (COMMAND_ASSOCIATION_REQUEST, Some(PanAddress::Extended(ext_address)))
+                if frame.dst_pan == Some(pan.pan_id) =>
+            {
+                (true, pan.associate(ext_address))
+            }
+            _ => (false, None),
+        }
+    });
+
+    if let Some(event) = event {
+        report(event);
+    }
+    consumed
+}
+
+/// Send the next queued frame unless one is in flight, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    if crate::ranging::is_transmitting() {
+        return;
+    }
+
+    let frame = critical_section::with(|cs| {
+        let mut pan = PAN.borrow_ref_mut(cs);
+        let pan = pan.as_mut()?;
+        if pan.in_flight.is_some() {
+            return None;
+        }
+
+        let frame = pan.tx_queue.pop_front()?;
+        pan.in_flight = Some(frame.kind);
+        Some(frame)
+    });
+    let Some(frame) = frame else {
+        return;
+    };
+
+    let res = unsafe {
+        link_raw_transmit(
+            instance,
+            &frame.psdu,
+            frame.ack_request,
+            Some(pan_transmit_done),
+        )
+    };
+
+    if res != otError_OT_ERROR_NONE {
+        log::warn!("pan transmit failed {}", res);
+        let kind = critical_section::with(|cs| {
+            PAN.borrow_ref_mut(cs)
+                .as_mut()
+                .and_then(|pan| pan.in_flight.take())
+        });
+        if let Some(kind) = kind {
+            transmit_done(kind, res);
+        }
+    }
+}
+
+unsafe extern "C" fn pan_transmit_done(
+    _instance: *mut otInstance,
+    _frame: *mut otRadioFrame,
+    _ack_frame: *mut otRadioFrame,
+    error: otError,
+) {
+    log::debug!("pan_transmit_done {}", error);
+
+    let kind = critical_section::with(|cs| {
+        PAN.borrow_ref_mut(cs)
+            .as_mut()
+            .and_then(|pan| pan.in_flight.take())
+    });
+    if let Some(kind) = kind {
+        transmit_done(kind, error);
+    }
+}
+
+fn transmit_done(kind: TxKind, error: otError) {
+    let result = checked!(error);
+
+    let event = match kind {
+        TxKind::Management => None,
+        TxKind::AssociationResponse { device, added } => {
+            if result.is_ok() {
+                Some(PanEvent::Associated(device))
+            } else {
+                if added {
+                    critical_section::with(|cs| {
+                        if let Some(pan) = PAN.borrow_ref_mut(cs).as_mut() {
+                            pan.remove_device(device.ext_address);
+                        }
+                    });
+                }
+                None
+            }
+        }
+        TxKind::Data { destination } => Some(PanEvent::TransmitDone {
+            destination,
+            result,
+        }),
+    };
+
+    if let Some(event) = event {
+        report(event);
+    }
+}
+
+fn report(event: PanEvent) {
+    critical_section::with(|cs| {
+        let mut callback = PAN_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(event);
+        }
+    });
+}
+
+fn with_pan(f: impl FnOnce(&mut PanCoordinator) -> Result<(), Error>) -> Result<(), Error> {
+    critical_section::with(|cs| f(PAN.borrow_ref_mut(cs).as_mut().ok_or(INVALID_STATE)?))
+}
+
+fn parse_frame(psdu: &[u8]) -> Option<MacFrame<'_>> {
+    let body = &psdu[..psdu.len().checked_sub(FCS_SIZE)?];
+    let fcf = u16::from_le_bytes([*body.first()?, *body.get(1)?]);
+    // secured frames and the 2015 frame format aren't supported
+    if fcf & FCF_SECURITY_ENABLED != 0 || (fcf >> FCF_FRAME_VERSION_SHIFT) & 0b11 > 1 {
+        return None;
+    }
+
+    // frame control and sequence number
+    let mut pos = 3;
+    let mut dst_pan = None;
+    let dst_mode = (fcf >> FCF_DST_ADDR_SHIFT) & 0b11;
+    if dst_mode != ADDR_MODE_NONE {
+        dst_pan = Some(read_u16(body, &mut pos)?);
+        read_address(body, &mut pos, dst_mode)?;
+    }
+
+    let mut src = None;
+    let src_mode = (fcf >> FCF_SRC_ADDR_SHIFT) & 0b11;
+    if src_mode != ADDR_MODE_NONE {
+        if fcf & FCF_PAN_ID_COMPRESSION == 0 {
+            read_u16(body, &mut pos)?;
+        }
+        src = Some(read_address(body, &mut pos, src_mode)?);
+    }
+
+    Some(MacFrame {
+        frame_type: fcf & 0b111,
+        dst_pan,
+        src,
+        payload: body.get(pos..)?,
+    })
+}
+
+fn read_u16(body: &[u8], pos: &mut usize) -> Option<u16> {
+    let value = u16::from_le_bytes(body.get(*pos..*pos + 2)?.try_into().ok()?);
+    *pos += 2;
+    Some(value)
+}
+
+fn read_address(body: &[u8], pos: &mut usize, mode: u16) -> Option<PanAddress> {
+    match mode {
+        ADDR_MODE_SHORT => read_u16(body, pos).map(PanAddress::Short),
+        ADDR_MODE_EXTENDED => {
+            let ext_address: [u8; 8] = body.get(*pos..*pos + 8)?.try_into().ok()?;
+            *pos += 8;
+            Some(PanAddress::Extended(reversed(ext_address)))
+        }
+        _ => None,
+    }
+}
+
+fn push<const N: usize>(psdu: &mut heapless::Vec<u8, N>, bytes: &[u8]) -> Result<(), Error> {
+    psdu.extend_from_slice(bytes).map_err(|_| NO_BUFS)
+}
+
+/// Extended addresses are sent least significant byte first
+fn reversed(mut ext_address: [u8; 8]) -> [u8; 8] {
+    ext_address.reverse();
+    ext_address
+}
+
+/// Short address 0xfffe, "no short address assigned"
+fn unassigned(ext_address: [u8; 8]) -> PanDevice {
+    PanDevice {
+        ext_address,
+        short_address: 0xfffe,
+    }
+}
+
+fn is_assignable(short_address: u16) -> bool {
+    short_address != PAN_COORDINATOR_SHORT_ADDRESS && short_address < 0xfffe
+}
@@ -0,0 +1,148 @@
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otDeviceRole_OT_DEVICE_ROLE_CHILD, otError_OT_ERROR_NONE, otInstance, otThreadGetDeviceRole,
+    otThreadGetParentAverageRssi, otThreadGetParentLastRssi, otThreadSearchForBetterParent,
+};
+
+use crate::{checked, timer::current_millis, Error, OpenThread};
+
+/// Default interval between parent checks, same as OpenThread's `OPENTHREAD_CONFIG_PARENT_SEARCH_CHECK_INTERVAL`
+pub const PARENT_SEARCH_DEFAULT_CHECK_INTERVAL: u32 = 9 * 60;
+
+/// Default parent RSSI below which a better parent is searched, same as OpenThread's `OPENTHREAD_CONFIG_PARENT_SEARCH_RSS_THRESHOLD`
+pub const PARENT_SEARCH_DEFAULT_RSSI_THRESHOLD: i8 = -65;
+
+pub(crate) static PARENT_SEARCH: Mutex<Cell<ParentSearch>> =
+    Mutex::new(Cell::new(ParentSearch::DEFAULT));
+
+#[derive(Clone, Copy)]
+pub(crate) struct ParentSearch {
+    enabled: bool,
+    /// seconds
+    check_interval: u32,
+    rssi_threshold: i8,
+    /// milliseconds
+    next_check: u64,
+}
+
+impl ParentSearch {
+    pub(crate) const DEFAULT: Self = Self {
+        enabled: false,
+        check_interval: PARENT_SEARCH_DEFAULT_CHECK_INTERVAL,
+        rssi_threshold: PARENT_SEARCH_DEFAULT_RSSI_THRESHOLD,
+        next_check: 0,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Periodically search for a better parent while attached as a child
+    ///
+    /// Every check interval the average RSSI of the parent is compared against the threshold,
+    /// if it's lower a search for a better parent is started. Checks run as part of
+    /// [OpenThread::process].
+    pub fn thread_set_parent_search_enabled(&mut self, enabled: bool) {
+        update(|parent_search| {
+            parent_search.enabled = enabled;
+            parent_search.next_check = next_check(parent_search.check_interval);
+        });
+    }
+
+    /// Returns `true` if the periodic parent search is enabled
+    pub fn thread_is_parent_search_enabled(&self) -> bool {
+        critical_section::with(|cs| PARENT_SEARCH.borrow(cs).get().enabled)
+    }
+
+    /// Set the interval (in seconds) between parent checks
+    pub fn thread_set_parent_search_check_interval(&mut self, interval: u32) {
+        update(|parent_search| {
+            parent_search.check_interval = interval;
+            parent_search.next_check = next_check(interval);
+        });
+    }
+
+    /// Get the interval (in seconds) between parent checks
+    pub fn thread_get_parent_search_check_interval(&self) -> u32 {
+        critical_section::with(|cs| PARENT_SEARCH.borrow(cs).get().check_interval)
+    }
+
+    /// Set the parent RSSI (in dBm) below which a better parent is searched
+    pub fn thread_set_parent_search_rssi_threshold(&mut self, rssi: i8) {
+        update(|parent_search| parent_search.rssi_threshold = rssi);
+    }
+
+    /// Get the parent RSSI (in dBm) below which a better parent is searched
+    pub fn thread_get_parent_search_rssi_threshold(&self) -> i8 {
+        critical_section::with(|cs| PARENT_SEARCH.borrow(cs).get().rssi_threshold)
+    }
+
+    /// Start searching for a better parent right away, staying attached to the current one
+    ///
+    /// Fails if the device is not attached as a child.
+    pub fn thread_search_for_better_parent(&mut self) -> Result<(), Error> {
+        checked!(unsafe { otThreadSearchForBetterParent(self.instance) })
+    }
+
+    /// Get the average RSSI (in dBm) of the parent
+    pub fn thread_get_parent_average_rssi(&self) -> Result<i8, Error> {
+        let mut rssi = 0;
+        checked!(unsafe { otThreadGetParentAverageRssi(self.instance, &mut rssi) })?;
+        Ok(rssi)
+    }
+
+    /// Get the RSSI (in dBm) of the last frame received from the parent
+    pub fn thread_get_parent_last_rssi(&self) -> Result<i8, Error> {
+        let mut rssi = 0;
+        checked!(unsafe { otThreadGetParentLastRssi(self.instance, &mut rssi) })?;
+        Ok(rssi)
+    }
+}
+
+fn update(f: impl FnOnce(&mut ParentSearch)) {
+    critical_section::with(|cs| {
+        let cell = PARENT_SEARCH.borrow(cs);
+        let mut parent_search = cell.get();
+        f(&mut parent_search);
+        cell.set(parent_search);
+    });
+}
+
+fn next_check(check_interval: u32) -> u64 {
+    current_millis() + check_interval as u64 * 1000
+}
+
+/// Check the parent if the check interval elapsed, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let due = critical_section::with(|cs| {
+        let cell = PARENT_SEARCH.borrow(cs);
+        let mut parent_search = cell.get();
+
+        if !parent_search.enabled || current_millis() < parent_search.next_check {
+            return None;
+        }
+
+        parent_search.next_check = next_check(parent_search.check_interval);
+        cell.set(parent_search);
+        Some(parent_search.rssi_threshold)
+    });
+
+    let Some(rssi_threshold) = due else {
+        return;
+    };
+
+    if unsafe { otThreadGetDeviceRole(instance) } != otDeviceRole_OT_DEVICE_ROLE_CHILD {
+        return;
+    }
+
+    let mut rssi = 0;
+    let res = unsafe { otThreadGetParentAverageRssi(instance, &mut rssi) };
+    if res == otError_OT_ERROR_NONE && rssi < rssi_threshold {
+        log::debug!(
+            "parent rssi {} below {}, searching a better parent",
+            rssi,
+            rssi_threshold
+        );
+        unsafe { otThreadSearchForBetterParent(instance) };
+    }
+}
@@ -0,0 +1,333 @@
+use core::{cell::RefCell, ptr::addr_of_mut};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NO_BUFS, otIcmp6Handler, otIcmp6Header,
+        otIcmp6RegisterHandler, otIcmp6SendEchoRequest, otIp6NewMessage, otMessage,
+        otMessageAppend, otMessageFree, otMessageGetLength, otMessageGetOffset, otMessageInfo,
+        otMessageRead, OT_ICMP6_TYPE_ECHO_REPLY,
+    },
+    c_types::c_void,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{checked, new_message_info, timer::current_millis, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Identifies the echo requests sent by [OpenThread::ping]
+const PING_IDENTIFIER: u16 = 0x6570;
+
+/// The echo request payload starts with the send time (in milliseconds)
+const TIMESTAMP_SIZE: u16 = 4;
+
+/// Largest window [OpenThread::ping_set_rtt_window] accepts
+pub const PING_RTT_WINDOW_MAX_SIZE: usize = 64;
+
+/// Number of buckets of [RttStats::histogram]
+pub const PING_RTT_HISTOGRAM_BUCKETS: usize = 8;
+
+pub(crate) static PING: Mutex<RefCell<Ping>> = Mutex::new(RefCell::new(Ping::DEFAULT));
+
+pub(crate) static PING_REPLY_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(PingReply) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Registered with OpenThread on the first ping, OpenThread links it into its handler list
+static mut PING_HANDLER: otIcmp6Handler = otIcmp6Handler {
+    mReceiveCallback: None,
+    mContext: core::ptr::null_mut(),
+    mNext: core::ptr::null_mut(),
+};
+
+/// An echo reply to [OpenThread::ping]
+#[derive(Debug, Clone, Copy)]
+pub struct PingReply {
+    pub from: Ipv6Addr,
+    pub sequence: u16,
+    /// Payload size (in bytes)
+    pub size: u16,
+    pub hop_limit: u8,
+    /// Round-trip time (in milliseconds)
+    pub rtt: u32,
+}
+
+/// Round-trip time statistics over the last replies, see [OpenThread::ping_set_rtt_window]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RttStats {
+    /// Number of replies the statistics cover
+    pub samples: u16,
+    /// milliseconds
+    pub min: u32,
+    /// milliseconds
+    pub max: u32,
+    /// milliseconds
+    pub avg: u32,
+    /// Mean difference between consecutive round-trip times (in milliseconds)
+    pub jitter: u32,
+    /// Replies per round-trip time range: below 16 ms, 16 - 31 ms, 32 - 63 ms and so on, the
+    /// last bucket counts everything from 1024 ms on
+    pub histogram: [u16; PING_RTT_HISTOGRAM_BUCKETS],
+}
+
+/// Counters of [OpenThread::ping]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingStats {
+    /// Echo requests sent
+    pub sent: u32,
+    /// Echo replies received, replies to requests sent before the last reset included
+    pub received: u32,
+    /// `None` if no window is set or no reply was received yet
+    pub rtt: Option<RttStats>,
+}
+
+pub(crate) struct Ping {
+    sent: u32,
+    received: u32,
+    window: usize,
+    rtts: heapless::Deque<u32, PING_RTT_WINDOW_MAX_SIZE>,
+}
+
+impl Ping {
+    pub(crate) const DEFAULT: Self = Self {
+        sent: 0,
+        received: 0,
+        window: 0,
+        rtts: heapless::Deque::new(),
+    };
+
+    fn add_rtt(&mut self, rtt: u32) {
+        if self.window == 0 {
+            return;
+        }
+
+        while self.rtts.len() >= self.window {
+            self.rtts.pop_front();
+        }
+        self.rtts.push_back(rtt).ok();
+    }
+
+    fn rtt_stats(&self) -> Option<RttStats> {
+        if self.rtts.is_empty() {
+            return None;
+        }
+
+        let mut stats = RttStats {
+            samples: self.rtts.len() as u16,
+            min: u32::MAX,
+            max: 0,
+            avg: 0,
+            jitter: 0,
+            histogram: [0; PING_RTT_HISTOGRAM_BUCKETS],
+        };
+
+        let mut sum = 0u64;
+        let mut deviation = 0u64;
+        let mut previous = None;
+        for &rtt in self.rtts.iter() {
+            stats.min = stats.min.min(rtt);
+            stats.max = stats.max.max(rtt);
+            sum += rtt as u64;
+
+            if let Some(previous) = previous {
+                deviation += rtt.abs_diff(previous) as u64;
+            }
+            previous = Some(rtt);
+
+            stats.histogram[histogram_bucket(rtt)] += 1;
+        }
+
+        stats.avg = (sum / self.rtts.len() as u64) as u32;
+        if self.rtts.len() > 1 {
+            stats.jitter = (deviation / (self.rtts.len() as u64 - 1)) as u32;
+        }
+
+        Some(stats)
+    }
+}
+
+fn histogram_bucket(rtt: u32) -> usize {
+    if rtt < 16 {
+        0
+    } else {
+        usize::min(rtt.ilog2() as usize - 3, PING_RTT_HISTOGRAM_BUCKETS - 1)
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Send an ICMPv6 echo request with a payload of `size` bytes (at least 4)
+    ///
+    /// Replies are reported to the callback set via [OpenThread::set_ping_reply_callback] and
+    /// counted in [OpenThread::ping_get_stats].
+    pub fn ping(&mut self, destination: Ipv6Addr, size: u16) -> Result<(), Error> {
+        if size < TIMESTAMP_SIZE {
+            return Err(INVALID_ARGS);
+        }
+
+        unsafe {
+            let handler = addr_of_mut!(PING_HANDLER);
+            if (*handler).mReceiveCallback.is_none() {
+                (*handler).mReceiveCallback = Some(ping_receive_callback);
+                let res = checked!(otIcmp6RegisterHandler(self.instance, handler));
+                if res.is_err() {
+                    (*handler).mReceiveCallback = None;
+                    return res;
+                }
+            }
+        }
+
+        let message = unsafe { otIp6NewMessage(self.instance, core::ptr::null()) };
+        if message.is_null() {
+            return Err(NO_BUFS);
+        }
+
+        let res = append_payload(message, size).and_then(|_| {
+            let mut message_info = new_message_info();
+            message_info.mPeerAddr.mFields.m8 = destination.octets();
+            checked!(unsafe {
+                otIcmp6SendEchoRequest(self.instance, message, &message_info, PING_IDENTIFIER)
+            })
+        });
+
+        if res.is_err() {
+            unsafe { otMessageFree(message) };
+            return res;
+        }
+
+        critical_section::with(|cs| {
+            let mut ping = PING.borrow_ref_mut(cs);
+            ping.sent = ping.sent.wrapping_add(1);
+        });
+
+        Ok(())
+    }
+
+    /// Set a callback for the echo replies to [OpenThread::ping]
+    pub fn set_ping_reply_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(PingReply) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut reply_callback = PING_REPLY_CALLBACK.borrow_ref_mut(cs);
+            *reply_callback = unsafe { core::mem::transmute(callback) };
+        });
+    }
+
+    /// Keep the round-trip times of the last `window` replies for [RttStats]
+    ///
+    /// `0` (the default) disables the round-trip time statistics. Changing the window discards
+    /// the kept round-trip times.
+    pub fn ping_set_rtt_window(&mut self, window: usize) -> Result<(), Error> {
+        if window > PING_RTT_WINDOW_MAX_SIZE {
+            return Err(INVALID_ARGS);
+        }
+
+        critical_section::with(|cs| {
+            let mut ping = PING.borrow_ref_mut(cs);
+            ping.window = window;
+            ping.rtts.clear();
+        });
+
+        Ok(())
+    }
+
+    /// Get the ping counters and round-trip time statistics
+    pub fn ping_get_stats(&self) -> PingStats {
+        critical_section::with(|cs| {
+            let ping = PING.borrow_ref(cs);
+            PingStats {
+                sent: ping.sent,
+                received: ping.received,
+                rtt: ping.rtt_stats(),
+            }
+        })
+    }
+
+    /// Reset the ping counters and discard the kept round-trip times
+    pub fn ping_reset_stats(&mut self) {
+        critical_section::with(|cs| {
+            let mut ping = PING.borrow_ref_mut(cs);
+            ping.sent = 0;
+            ping.received = 0;
+            ping.rtts.clear();
+        });
+    }
+}
+
+fn append_payload(message: *mut otMessage, size: u16) -> Result<(), Error> {
+    let timestamp = (current_millis() as u32).to_be_bytes();
+    checked!(unsafe {
+        otMessageAppend(message, timestamp.as_ptr() as *const c_void, TIMESTAMP_SIZE)
+    })?;
+
+    let padding = [0u8; 16];
+    let mut remaining = size - TIMESTAMP_SIZE;
+    while remaining > 0 {
+        let len = remaining.min(padding.len() as u16);
+        checked!(unsafe { otMessageAppend(message, padding.as_ptr() as *const c_void, len) })?;
+        remaining -= len;
+    }
+
+    Ok(())
+}
+
+/// Forget the handler registration, the next OpenThread instance needs it registered again
+pub(crate) fn reset_ping_handler() {
+    unsafe {
+        let handler = addr_of_mut!(PING_HANDLER);
+        (*handler).mReceiveCallback = None;
+        (*handler).mNext = core::ptr::null_mut();
+    }
+}
+
+unsafe extern "C" fn ping_receive_callback(
+    _context: *mut c_void,
+    message: *mut otMessage,
+    message_info: *const otMessageInfo,
+    icmp_header: *const otIcmp6Header,
+) {
+    let icmp_header = &*icmp_header;
+    let data = icmp_header.mData.m8;
+    if icmp_header.mType as u32 != OT_ICMP6_TYPE_ECHO_REPLY
+        || u16::from_be_bytes([data[0], data[1]]) != PING_IDENTIFIER
+    {
+        return;
+    }
+
+    let offset = otMessageGetOffset(message);
+    let size = otMessageGetLength(message) - offset;
+    let mut timestamp = [0u8; TIMESTAMP_SIZE as usize];
+    if size < TIMESTAMP_SIZE
+        || otMessageRead(
+            message,
+            offset,
+            timestamp.as_mut_ptr() as *mut c_void,
+            TIMESTAMP_SIZE,
+        ) != TIMESTAMP_SIZE
+    {
+        return;
+    }
+
+    let message_info = &*message_info;
+    let reply = PingReply {
+        from: Ipv6Addr::from(message_info.mPeerAddr.mFields.m8),
+        sequence: u16::from_be_bytes([data[2], data[3]]),
+        size,
+        hop_limit: message_info.mHopLimit,
+        rtt: (current_millis() as u32).wrapping_sub(u32::from_be_bytes(timestamp)),
+    };
+    log::debug!("ping_receive_callback {:?}", reply);
+
+    critical_section::with(|cs| {
+        let mut ping = PING.borrow_ref_mut(cs);
+        ping.received = ping.received.wrapping_add(1);
+        ping.add_rtt(reply.rtt);
+
+        let mut callback = PING_REPLY_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(reply);
+        }
+    });
+}
@@ -1,10 +1,14 @@
 // mostly stubbing out the platform stuff for now
 
+use core::ffi::VaList;
+
 use esp_openthread_sys::{
     bindings::{otError, otLogLevel, otLogRegion},
     c_types,
 };
 
+use crate::{logging, printf};
+
 pub(crate) static mut CURRENT_INSTANCE: usize = 0;
 
 #[no_mangle]
@@ -14,19 +18,29 @@ pub extern "C" fn otPlatReset(_instance: *const u8) -> otError {
 
 #[no_mangle]
 pub unsafe extern "C" fn otPlatLog(
-    _level: otLogLevel,
+    level: otLogLevel,
     _region: otLogRegion,
-    _format: *const c_types::c_char,
-    _args: ...
-) -> otError {
-    todo!()
+    format: *const c_types::c_char,
+    mut args: ...
+) {
+    logging::log(level, format, &mut args);
 }
 
 // other C functions
 
 #[no_mangle]
-pub extern "C" fn vsnprintf() {
-    todo!()
+pub unsafe extern "C" fn vsnprintf(
+    buffer: *mut c_types::c_char,
+    size: usize,
+    format: *const c_types::c_char,
+    mut args: VaList,
+) -> c_types::c_int {
+    let buffer: &mut [u8] = if size == 0 {
+        &mut []
+    } else {
+        core::slice::from_raw_parts_mut(buffer as *mut u8, size)
+    };
+    printf::format(buffer, format, &mut args) as c_types::c_int
 }
 
 #[no_mangle]
@@ -41,8 +55,13 @@ pub extern "C" fn isprint() {
 }
 
 #[no_mangle]
-pub extern "C" fn snprintf() {
-    todo!()
+pub unsafe extern "C" fn snprintf(
+    buffer: *mut c_types::c_char,
+    size: usize,
+    format: *const c_types::c_char,
+    mut args: ...
+) -> c_types::c_int {
+    vsnprintf(buffer, size, format, args.as_va_list())
 }
 
 #[no_mangle]
@@ -0,0 +1,302 @@
+// the subset of C's `printf` formatting OpenThread uses, e.g. for its log lines
+
+use core::ffi::{c_char, c_int, c_long, c_longlong, c_uint, c_ulong, c_ulonglong, VaListImpl};
+
+/// Bytes written to a buffer, cut off at its end but counted in full like `vsnprintf` does
+struct Output<'b> {
+    buffer: &'b mut [u8],
+    /// Bytes to write at most, one is kept for the terminating NUL
+    capacity: usize,
+    len: usize,
+}
+
+impl<'b> Output<'b> {
+    fn new(buffer: &'b mut [u8]) -> Self {
+        let capacity = buffer.len().saturating_sub(1);
+        Self {
+            buffer,
+            capacity,
+            len: 0,
+        }
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        if self.len < self.capacity {
+            let count = usize::min(bytes.len(), self.capacity - self.len);
+            self.buffer[self.len..][..count].copy_from_slice(&bytes[..count]);
+        }
+        self.len += bytes.len();
+    }
+
+    fn pad(&mut self, byte: u8, count: usize) {
+        for _ in 0..count {
+            self.put(&[byte]);
+        }
+    }
+
+    fn finish(self) -> usize {
+        if !self.buffer.is_empty() {
+            self.buffer[usize::min(self.len, self.capacity)] = 0;
+        }
+        self.len
+    }
+}
+
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    zero: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Length {
+    Default,
+    Char,
+    Short,
+    Long,
+    LongLong,
+    Size,
+    Max,
+}
+
+/// Format `format` with `args` into `buffer` like `vsnprintf`
+///
+/// Returns the length of the complete output, which was cut off if it's not less than the
+/// buffer length. The output is NUL terminated unless `buffer` is empty.
+///
+/// # Safety
+///
+/// `format` must be a NUL terminated string and `args` must match its conversions.
+pub(crate) unsafe fn format(
+    buffer: &mut [u8],
+    format: *const c_char,
+    args: &mut VaListImpl<'_>,
+) -> usize {
+    let mut out = Output::new(buffer);
+    let mut format = format as *const u8;
+
+    let next = |format: &mut *const u8| {
+        let byte = **format;
+        if byte != 0 {
+            *format = format.add(1);
+        }
+        byte
+    };
+
+    loop {
+        let byte = next(&mut format);
+        match byte {
+            0 => break,
+            b'%' => (),
+            _ => {
+                out.put(&[byte]);
+                continue;
+            }
+        }
+
+        let mut spec = Spec::default();
+        let mut byte = next(&mut format);
+        loop {
+            match byte {
+                b'-' => spec.left = true,
+                b'0' => spec.zero = true,
+                b'+' => spec.plus = true,
+                b' ' => spec.space = true,
+                b'#' => spec.alternate = true,
+                _ => break,
+            }
+            byte = next(&mut format);
+        }
+
+        if byte == b'*' {
+            let width = args.arg::<c_int>();
+            spec.left |= width < 0;
+            spec.width = width.unsigned_abs() as usize;
+            byte = next(&mut format);
+        } else {
+            while byte.is_ascii_digit() {
+                spec.width = spec.width * 10 + (byte - b'0') as usize;
+                byte = next(&mut format);
+            }
+        }
+
+        if byte == b'.' {
+            byte = next(&mut format);
+            if byte == b'*' {
+                // a negative precision counts as none
+                spec.precision = usize::try_from(args.arg::<c_int>()).ok();
+                byte = next(&mut format);
+            } else {
+                let mut precision = 0;
+                while byte.is_ascii_digit() {
+                    precision = precision * 10 + (byte - b'0') as usize;
+                    byte = next(&mut format);
+                }
+                spec.precision = Some(precision);
+            }
+        }
+
+        let mut length = Length::Default;
+        loop {
+            length = match (byte, length) {
+                (b'h', Length::Short) => Length::Char,
+                (b'h', _) => Length::Short,
+                (b'l', Length::Long) => Length::LongLong,
+                (b'l', _) => Length::Long,
+                (b'z' | b't', _) => Length::Size,
+                (b'j', _) => Length::Max,
+                (b'L', _) => Length::LongLong,
+                _ => break,
+            };
+            byte = next(&mut format);
+        }
+
+        match byte {
+            b'd' | b'i' => {
+                let value = signed_arg(args, length);
+                let sign: &[u8] = if value < 0 {
+                    b"-"
+                } else if spec.plus {
+                    b"+"
+                } else if spec.space {
+                    b" "
+                } else {
+                    b""
+                };
+                number(&mut out, &spec, sign, value.unsigned_abs(), 10, false);
+            }
+            b'u' => number(&mut out, &spec, b"", unsigned_arg(args, length), 10, false),
+            b'x' | b'X' => {
+                let value = unsigned_arg(args, length);
+                let prefix: &[u8] = match (spec.alternate && value != 0, byte) {
+                    (true, b'x') => b"0x",
+                    (true, _) => b"0X",
+                    (false, _) => b"",
+                };
+                number(&mut out, &spec, prefix, value, 16, byte == b'X');
+            }
+            b'o' => {
+                let value = unsigned_arg(args, length);
+                let prefix: &[u8] = if spec.alternate && value != 0 {
+                    b"0"
+                } else {
+                    b""
+                };
+                number(&mut out, &spec, prefix, value, 8, false);
+            }
+            b'p' => {
+                let value = args.arg::<usize>() as u64;
+                number(&mut out, &spec, b"0x", value, 16, false);
+            }
+            b'c' => {
+                let value = args.arg::<c_int>() as u8;
+                padded(&mut out, &spec, &[value]);
+            }
+            b's' => {
+                let value = args.arg::<*const c_char>();
+                let value = if value.is_null() {
+                    b"(null)"
+                } else {
+                    c_str_bytes(value as *const u8, spec.precision)
+                };
+                padded(&mut out, &spec, value);
+            }
+            b'%' => out.put(b"%"),
+            0 => {
+                out.put(b"%");
+                break;
+            }
+            _ => out.put(&[b'%', byte]),
+        }
+    }
+
+    out.finish()
+}
+
+unsafe fn signed_arg(args: &mut VaListImpl<'_>, length: Length) -> i64 {
+    match length {
+        Length::Default => args.arg::<c_int>() as i64,
+        Length::Char => args.arg::<c_int>() as i8 as i64,
+        Length::Short => args.arg::<c_int>() as i16 as i64,
+        Length::Long => args.arg::<c_long>().into(),
+        Length::LongLong | Length::Max => args.arg::<c_longlong>(),
+        Length::Size => args.arg::<isize>() as i64,
+    }
+}
+
+unsafe fn unsigned_arg(args: &mut VaListImpl<'_>, length: Length) -> u64 {
+    match length {
+        Length::Default => args.arg::<c_uint>() as u64,
+        Length::Char => args.arg::<c_uint>() as u8 as u64,
+        Length::Short => args.arg::<c_uint>() as u16 as u64,
+        Length::Long => args.arg::<c_ulong>().into(),
+        Length::LongLong | Length::Max => args.arg::<c_ulonglong>(),
+        Length::Size => args.arg::<usize>() as u64,
+    }
+}
+
+/// The bytes of a NUL terminated string, at most `max` of them
+unsafe fn c_str_bytes<'s>(value: *const u8, max: Option<usize>) -> &'s [u8] {
+    let max = max.unwrap_or(usize::MAX);
+    let mut len = 0;
+    while len < max && *value.add(len) != 0 {
+        len += 1;
+    }
+    core::slice::from_raw_parts(value, len)
+}
+
+fn padded(out: &mut Output, spec: &Spec, value: &[u8]) {
+    let padding = spec.width.saturating_sub(value.len());
+    if !spec.left {
+        out.pad(b' ', padding);
+    }
+    out.put(value);
+    if spec.left {
+        out.pad(b' ', padding);
+    }
+}
+
+fn number(out: &mut Output, spec: &Spec, prefix: &[u8], value: u64, radix: u64, upper: bool) {
+    let digits: &[u8; 16] = if upper {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+
+    // u64::MAX has 22 octal digits
+    let mut raw = [0u8; 22];
+    let mut start = raw.len();
+    let mut remaining = value;
+    while remaining != 0 {
+        start -= 1;
+        raw[start] = digits[(remaining % radix) as usize];
+        remaining /= radix;
+    }
+    let value = &raw[start..];
+
+    // a zero precision prints nothing for 0, otherwise at least one digit
+    let precision = spec.precision.unwrap_or(1);
+    let zeros = precision.saturating_sub(value.len());
+    let len = prefix.len() + zeros + value.len();
+    let padding = spec.width.saturating_sub(len);
+
+    // the `0` flag is ignored with a precision
+    let zero_pad = spec.zero && !spec.left && spec.precision.is_none();
+    if !spec.left && !zero_pad {
+        out.pad(b' ', padding);
+    }
+    out.put(prefix);
+    if zero_pad {
+        out.pad(b'0', padding);
+    }
+    out.pad(b'0', zeros);
+    out.put(value);
+    if spec.left {
+        out.pad(b' ', padding);
+    }
+}
@@ -0,0 +1,128 @@
+use embedded_io::{Read, ReadExactError};
+use esp_openthread_sys::bindings::{
+    otDatasetSetActiveTlvs, otDatasetSetPendingTlvs, otError_OT_ERROR_INVALID_ARGS,
+    otOperationalDatasetTlvs, OT_OPERATIONAL_DATASET_MAX_LENGTH,
+};
+
+use crate::{checked, dataset::validate_tlvs, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+const DATASET_MAX_LENGTH: usize = OT_OPERATIONAL_DATASET_MAX_LENGTH as usize;
+
+/// Operation code of a frame setting the Active Operational Dataset
+pub const PROVISIONING_OP_SET_ACTIVE: u8 = 0x01;
+
+/// Operation code of a frame setting the Pending Operational Dataset
+pub const PROVISIONING_OP_SET_PENDING: u8 = 0x02;
+
+/// Bytes in front of the dataset TLVs, the operation code and the big endian length
+pub const PROVISIONING_HEADER_SIZE: usize = 3;
+
+/// What a provisioning frame asks for, see [OpenThread::provision_from]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvisioningOp {
+    SetActive,
+    SetPending,
+}
+
+impl ProvisioningOp {
+    fn from_raw(op: u8) -> Option<Self> {
+        match op {
+            PROVISIONING_OP_SET_ACTIVE => Some(Self::SetActive),
+            PROVISIONING_OP_SET_PENDING => Some(Self::SetPending),
+            _ => None,
+        }
+    }
+}
+
+/// Why a provisioning frame wasn't applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProvisioningError<E> {
+    /// Reading from the transport failed
+    Io(E),
+    /// The transport ended in the middle of a frame
+    UnexpectedEof,
+    /// The operation code is none of `PROVISIONING_OP_*`, the rest of the frame is unread
+    UnknownOp(u8),
+    /// The frame announced more TLVs than a dataset holds, the TLVs are unread
+    TooLong(u16),
+    /// OpenThread rejected the dataset, e.g. `OT_ERROR_INVALID_ARGS` for malformed TLVs
+    Dataset(Error),
+}
+
+impl<E> From<ReadExactError<E>> for ProvisioningError<E> {
+    fn from(error: ReadExactError<E>) -> Self {
+        match error {
+            ReadExactError::UnexpectedEof => Self::UnexpectedEof,
+            ReadExactError::Other(error) => Self::Io(error),
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Read one provisioning frame from `reader` and apply the dataset it carries, e.g. from an
+    /// NFC tag emulator or a factory UART jig
+    ///
+    /// A frame is the operation code ([PROVISIONING_OP_SET_ACTIVE] or
+    /// [PROVISIONING_OP_SET_PENDING]), the length of the TLVs as big endian `u16` and the dataset
+    /// TLVs as output by `dataset active -x`. Blocks until the whole frame is read. The TLVs have
+    /// to parse and carry a security policy passing [crate::SecurityPolicy::validate], if any. A
+    /// pending dataset also needs a pending timestamp and a delay timer. The TLVs are wiped from
+    /// the stack afterwards.
+    pub fn provision_from<R: Read>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<ProvisioningOp, ProvisioningError<R::Error>> {
+        let mut header = [0u8; PROVISIONING_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+
+        let op =
+            ProvisioningOp::from_raw(header[0]).ok_or(ProvisioningError::UnknownOp(header[0]))?;
+        let len = u16::from_be_bytes([header[1], header[2]]);
+        if len as usize > DATASET_MAX_LENGTH {
+            return Err(ProvisioningError::TooLong(len));
+        }
+
+        let mut tlvs = otOperationalDatasetTlvs {
+            mTlvs: [0u8; DATASET_MAX_LENGTH],
+            mLength: len as u8,
+        };
+        let result = match reader.read_exact(&mut tlvs.mTlvs[..len as usize]) {
+            Ok(()) => apply(self, &tlvs, op).map_err(ProvisioningError::Dataset),
+            Err(error) => Err(error.into()),
+        };
+
+        // don't leave the network key on the stack
+        unsafe {
+            core::ptr::write_volatile(
+                &mut tlvs,
+                otOperationalDatasetTlvs {
+                    mTlvs: [0u8; DATASET_MAX_LENGTH],
+                    mLength: 0,
+                },
+            )
+        };
+        result.map(|_| op)
+    }
+}
+
+fn apply(
+    ot: &mut OpenThread,
+    tlvs: &otOperationalDatasetTlvs,
+    op: ProvisioningOp,
+) -> Result<(), Error> {
+    let components = validate_tlvs(tlvs)?;
+
+    match op {
+        ProvisioningOp::SetActive => {
+            checked!(unsafe { otDatasetSetActiveTlvs(ot.instance, tlvs) })
+        }
+        ProvisioningOp::SetPending => {
+            if !components.mIsPendingTimestampPresent() || !components.mIsDelayPresent() {
+                return Err(INVALID_ARGS);
+            }
+            checked!(unsafe { otDatasetSetPendingTlvs(ot.instance, tlvs) })
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_NONE, otInstance, otRouterInfo, otThreadGetParentInfo,
+};
+
+use crate::{timer::current_millis, OpenThread};
+
+/// Number of peers tracked by [OpenThread::proximity_set_enabled], the least recently heard
+/// peer is replaced once the table is full
+pub const PROXIMITY_MAX_PEERS: usize = 16;
+
+/// Weight of a new RSSI sample in the rolling average, 1/8 like OpenThread's link quality
+/// averaging
+const AVERAGE_WEIGHT_SHIFT: u32 = 3;
+
+// IEEE 802.15.4 frame control fields
+const FRAME_TYPE_MASK: u16 = 0x0007;
+const FRAME_TYPE_ACK: u16 = 0x0002;
+const PAN_ID_COMPRESSION: u16 = 1 << 6;
+const SEQUENCE_NUMBER_SUPPRESSION: u16 = 1 << 8;
+const DST_ADDR_MODE_OFFSET: u32 = 10;
+const FRAME_VERSION_OFFSET: u32 = 12;
+const SRC_ADDR_MODE_OFFSET: u32 = 14;
+const ADDR_MODE_NONE: u16 = 0;
+const ADDR_MODE_SHORT: u16 = 2;
+const ADDR_MODE_EXTENDED: u16 = 3;
+const FRAME_VERSION_2015: u16 = 2;
+
+pub(crate) static PROXIMITY: Mutex<RefCell<Proximity>> =
+    Mutex::new(RefCell::new(Proximity::DEFAULT));
+
+/// Received signal strength of a peer, see [OpenThread::proximity_get]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerProximity {
+    pub ext_address: [u8; 8],
+    /// Rolling average of the RSSI (in dBm), recent frames weigh more
+    pub average_rssi: i8,
+    /// RSSI (in dBm) of the last frame
+    pub last_rssi: i8,
+    /// Frames received from the peer since it's tracked
+    pub frames: u32,
+    /// When the last frame was received (in milliseconds, see `current_millis`)
+    pub last_seen: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Peer {
+    proximity: PeerProximity,
+    /// Average RSSI scaled by `1 << AVERAGE_WEIGHT_SHIFT`
+    scaled_average: i32,
+}
+
+pub(crate) struct Proximity {
+    enabled: bool,
+    peers: heapless::Vec<Peer, PROXIMITY_MAX_PEERS>,
+}
+
+impl Proximity {
+    pub(crate) const DEFAULT: Self = Self {
+        enabled: false,
+        peers: heapless::Vec::new(),
+    };
+
+    fn add_sample(&mut self, ext_address: [u8; 8], rssi: i8, now: u64) {
+        if let Some(peer) = self
+            .peers
+            .iter_mut()
+            .find(|peer| peer.proximity.ext_address == ext_address)
+        {
+            peer.scaled_average += rssi as i32 - (peer.scaled_average >> AVERAGE_WEIGHT_SHIFT);
+            peer.proximity.average_rssi = (peer.scaled_average >> AVERAGE_WEIGHT_SHIFT) as i8;
+            peer.proximity.last_rssi = rssi;
+            peer.proximity.frames = peer.proximity.frames.saturating_add(1);
+            peer.proximity.last_seen = now;
+            return;
+        }
+
+        let peer = Peer {
+            proximity: PeerProximity {
+                ext_address,
+                average_rssi: rssi,
+                last_rssi: rssi,
+                frames: 1,
+                last_seen: now,
+            },
+            scaled_average: (rssi as i32) << AVERAGE_WEIGHT_SHIFT,
+        };
+
+        if let Err(peer) = self.peers.push(peer) {
+            if let Some(oldest) = self
+                .peers
+                .iter_mut()
+                .min_by_key(|peer| peer.proximity.last_seen)
+            {
+                *oldest = peer;
+            }
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Track the RSSI of every peer frames are received from, e.g. for presence detection
+    ///
+    /// Peers are identified by their extended address, frames sent from a short address are
+    /// only attributed if it's the parent's. Disabling forgets all peers.
+    pub fn proximity_set_enabled(&mut self, enabled: bool) {
+        critical_section::with(|cs| {
+            let mut proximity = PROXIMITY.borrow_ref_mut(cs);
+            proximity.enabled = enabled;
+            if !enabled {
+                proximity.peers.clear();
+            }
+        });
+    }
+
+    /// Returns `true` if peer RSSI tracking is enabled
+    pub fn proximity_is_enabled(&self) -> bool {
+        critical_section::with(|cs| PROXIMITY.borrow_ref(cs).enabled)
+    }
+
+    /// Get the received signal strength of the peer with the given extended address
+    pub fn proximity_get(&self, ext_address: [u8; 8]) -> Option<PeerProximity> {
+        critical_section::with(|cs| {
+            PROXIMITY
+                .borrow_ref(cs)
+                .peers
+                .iter()
+                .find(|peer| peer.proximity.ext_address == ext_address)
+                .map(|peer| peer.proximity)
+        })
+    }
+
+    /// Get the received signal strength of all tracked peers
+    pub fn proximity_peers(&self) -> heapless::Vec<PeerProximity, PROXIMITY_MAX_PEERS> {
+        critical_section::with(|cs| {
+            PROXIMITY
+                .borrow_ref(cs)
+                .peers
+                .iter()
+                .map(|peer| peer.proximity)
+                .collect()
+        })
+    }
+
+    /// Forget all tracked peers
+    pub fn proximity_clear(&mut self) {
+        critical_section::with(|cs| PROXIMITY.borrow_ref_mut(cs).peers.clear());
+    }
+}
+
+/// Account a received frame to its sender, called for every frame handed to OpenThread
+pub(crate) fn record_frame(instance: *mut otInstance, psdu: &[u8], rssi: i8) {
+    if !critical_section::with(|cs| PROXIMITY.borrow_ref(cs).enabled) {
+        return;
+    }
+
+    let ext_address = match mac_source_address(psdu) {
+        Some(SourceAddress::Extended(ext_address)) => ext_address,
+        Some(SourceAddress::Short(short_address)) => {
+            let mut parent: otRouterInfo = unsafe { core::mem::zeroed() };
+            if unsafe { otThreadGetParentInfo(instance, &mut parent) } != otError_OT_ERROR_NONE
+                || parent.mRloc16 != short_address
+            {
+                return;
+            }
+            parent.mExtAddress.m8
+        }
+        None => return,
+    };
+
+    let now = current_millis();
+    critical_section::with(|cs| {
+        PROXIMITY
+            .borrow_ref_mut(cs)
+            .add_sample(ext_address, rssi, now)
+    });
+}
+
+enum SourceAddress {
+    Short(u16),
+    Extended([u8; 8]),
+}
+
+/// Parse the source address from the MAC header, see IEEE 802.15.4-2015 section 7.2.1
+fn mac_source_address(psdu: &[u8]) -> Option<SourceAddress> {
+    let fcf = u16::from_le_bytes([*psdu.first()?, *psdu.get(1)?]);
+    if fcf & FRAME_TYPE_MASK == FRAME_TYPE_ACK {
+        return None;
+    }
+
+    let dst_addr_mode = (fcf >> DST_ADDR_MODE_OFFSET) & 0x3;
+    let src_addr_mode = (fcf >> SRC_ADDR_MODE_OFFSET) & 0x3;
+    let frame_version = (fcf >> FRAME_VERSION_OFFSET) & 0x3;
+    let pan_id_compression = fcf & PAN_ID_COMPRESSION != 0;
+
+    if src_addr_mode != ADDR_MODE_SHORT && src_addr_mode != ADDR_MODE_EXTENDED {
+        return None;
+    }
+
+    let mut offset = 2;
+    if frame_version < FRAME_VERSION_2015 || fcf & SEQUENCE_NUMBER_SUPPRESSION == 0 {
+        offset += 1;
+    }
+
+    let (dst_pan_id, src_pan_id) = if frame_version < FRAME_VERSION_2015 {
+        (dst_addr_mode != ADDR_MODE_NONE, !pan_id_compression)
+    } else {
+        match (dst_addr_mode, src_addr_mode) {
+            (ADDR_MODE_NONE, _) => (false, !pan_id_compression),
+            (ADDR_MODE_EXTENDED, ADDR_MODE_EXTENDED) => (!pan_id_compression, false),
+            _ => (true, !pan_id_compression),
+        }
+    };
+
+    if dst_pan_id {
+        offset += 2;
+    }
+    offset += match dst_addr_mode {
+        ADDR_MODE_SHORT => 2,
+        ADDR_MODE_EXTENDED => 8,
+        _ => 0,
+    };
+    if src_pan_id {
+        offset += 2;
+    }
+
+    if src_addr_mode == ADDR_MODE_SHORT {
+        let address = psdu.get(offset..offset + 2)?;
+        Some(SourceAddress::Short(u16::from_le_bytes([
+            address[0], address[1],
+        ])))
+    } else {
+        // sent in reverse byte order
+        let mut ext_address: [u8; 8] = psdu.get(offset..offset + 8)?.try_into().ok()?;
+        ext_address.reverse();
+        Some(SourceAddress::Extended(ext_address))
+    }
+}
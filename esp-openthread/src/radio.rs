@@ -1,14 +1,122 @@
+use bitflags::bitflags;
 use esp_ieee802154::Config;
+#[cfg(feature = "diag")]
+use esp_openthread_sys::bindings::otPlatDiagRadioTransmitDone;
 use esp_openthread_sys::bindings::{
-    __BindgenBitfieldUnit, otError, otError_OT_ERROR_NONE, otInstance, otPlatRadioTxDone,
-    otPlatRadioTxStarted, otRadioFrame, otRadioFrame__bindgen_ty_1,
+    __BindgenBitfieldUnit, otError, otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NONE,
+    otInstance, otPlatRadioTxDone, otPlatRadioTxStarted, otRadioFrame, otRadioFrame__bindgen_ty_1,
     otRadioFrame__bindgen_ty_1__bindgen_ty_1,
 };
 
-use core::ptr::addr_of_mut;
+use core::{
+    cell::{Cell, RefCell},
+    mem::size_of,
+    ptr::addr_of_mut,
+};
+
+use critical_section::Mutex;
+
+use heapless::spsc::{Consumer, Producer, Queue};
+
+use crate::{
+    get_settings,
+    memory::{RX_PSDU_SIZE, RX_QUEUE_FRAMES},
+    platform::CURRENT_INSTANCE,
+    set_settings,
+    timer::{current_millis, current_ticks, TICKS_PER_SECOND},
+    with_radio, NetworkSettings, OpenThread,
+};
+
+/// Number of IEEE 802.15.4 channels in the 2.4 GHz band, 11 to 26
+pub const RADIO_CHANNEL_COUNT: usize = 16;
+
+const RADIO_CHANNEL_MIN: u8 = 11;
+
+static RADIO_CALIBRATION: Mutex<Cell<RadioCalibration>> =
+    Mutex::new(Cell::new(RadioCalibration::DEFAULT));
+
+static RADIO_CLOCK_ACCURACY: Mutex<Cell<RadioClockAccuracy>> =
+    Mutex::new(Cell::new(RadioClockAccuracy::DEFAULT));
+
+static RADIO_TEMPERATURE_COMPENSATION: Mutex<Cell<RadioTemperatureCompensation>> =
+    Mutex::new(Cell::new(RadioTemperatureCompensation::DEFAULT));
+
+/// Die temperature (in °C) fed via [OpenThread::radio_set_temperature]
+static RADIO_TEMPERATURE: Mutex<Cell<Option<i8>>> = Mutex::new(Cell::new(None));
+
+/// Transmit power set via [OpenThread::radio_set_transmit_power], `None` keeps the driver's default
+pub(crate) static TRANSMIT_POWER: Mutex<Cell<Option<i8>>> = Mutex::new(Cell::new(None));
+
+pub(crate) static RADIO_TIMESTAMPS: Mutex<Cell<RadioTimestamps>> =
+    Mutex::new(Cell::new(RadioTimestamps::DEFAULT));
+
+pub(crate) static RADIO_THROUGHPUT: Mutex<Cell<ThroughputCounters>> =
+    Mutex::new(Cell::new(ThroughputCounters::DEFAULT));
+
+/// Receive configuration the radio is in, kept for the next transmission with `frame-bursting`
+#[cfg(feature = "frame-bursting")]
+pub(crate) static RECEIVE_CONFIGURATION: Mutex<Cell<Option<(u8, i8, NetworkSettings)>>> =
+    Mutex::new(Cell::new(None));
+
+pub(crate) static RADIO_FRONT_END: Mutex<RefCell<Option<&'static mut (dyn RadioFrontEnd + Send)>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Capacity of the queue between the radio interrupt and `process()`, it holds one frame less
+const RX_QUEUE_SIZE: usize = RX_QUEUE_FRAMES + 1;
+
+/// Static RAM taken by the receive queue
+pub(crate) const RX_QUEUE_RAM_SIZE: usize = size_of::<Queue<ReceivedFrame, RX_QUEUE_SIZE>>()
+    + 2 * size_of::<Option<Producer<'static, ReceivedFrame, RX_QUEUE_SIZE>>>();
+
+/// Static RAM taken by the transmit and sent frame buffers
+pub(crate) const FRAME_BUFFERS_SIZE: usize = 2 * (127 + size_of::<otRadioFrame>());
+
+/// A frame taken from the radio in the interrupt, waiting to be handed to OpenThread
+pub(crate) struct ReceivedFrame {
+    psdu: [u8; RX_PSDU_SIZE],
+    len: u8,
+    pub channel: u8,
+    /// RSSI as reported by the radio, the calibration is applied when handing the frame over
+    pub rssi: i8,
+    /// Receive timestamp (in microseconds)
+    pub timestamp: u64,
+    /// Systimer count when the radio interrupt took the frame
+    pub ticks: u64,
+}
+
+/// Systimer counts of the latest radio events, captured as close to the radio as the driver
+/// allows
+#[derive(Clone, Copy)]
+pub(crate) struct RadioTimestamps {
+    /// The frame being handed to OpenThread was taken from the radio
+    pub rx: u64,
+    /// The latest frame was handed to the radio for transmission
+    pub tx_started: u64,
+    /// The radio reported the latest frame sent
+    pub tx_done: u64,
+}
+
+impl RadioTimestamps {
+    pub(crate) const DEFAULT: Self = Self {
+        rx: 0,
+        tx_started: 0,
+        tx_done: 0,
+    };
+}
+
+impl ReceivedFrame {
+    pub fn psdu(&self) -> &[u8] {
+        &self.psdu[..self.len as usize]
+    }
+}
 
-use crate::{get_settings, platform::CURRENT_INSTANCE, set_settings, with_radio, NetworkSettings};
+// single producer (the radio interrupt) and single consumer (`process()`), no locking needed
+#[cfg_attr(feature = "lp-ram-buffers", link_section = ".rtc_fast.bss")]
+static mut RX_QUEUE: Queue<ReceivedFrame, RX_QUEUE_SIZE> = Queue::new();
+static mut RX_PRODUCER: Option<Producer<'static, ReceivedFrame, RX_QUEUE_SIZE>> = None;
+static mut RX_CONSUMER: Option<Consumer<'static, ReceivedFrame, RX_QUEUE_SIZE>> = None;
 
+#[cfg_attr(feature = "lp-ram-buffers", link_section = ".rtc_fast.bss")]
 pub static mut PSDU: [u8; 127] = [0u8; 127];
 pub static mut TRANSMIT_BUFFER: otRadioFrame = otRadioFrame {
     mPsdu: unsafe { addr_of_mut!(PSDU) as *mut u8 },
@@ -30,6 +138,7 @@ pub static mut TRANSMIT_BUFFER: otRadioFrame = otRadioFrame {
     },
 };
 
+#[cfg_attr(feature = "lp-ram-buffers", link_section = ".rtc_fast.bss")]
 pub static mut SENT_FRAME_PSDU: [u8; 127] = [0u8; 127];
 static mut SENT_FRAME: otRadioFrame = otRadioFrame {
     mPsdu: unsafe { addr_of_mut!(SENT_FRAME_PSDU) as *mut u8 },
@@ -56,10 +165,413 @@ pub extern "C" fn otPlatRadioGetIeeeEui64(_instance: *const otInstance, _out: *m
     todo!()
 }
 
+bitflags! {
+    /// Radio capabilities claimed towards OpenThread, see `OT_RADIO_CAPS_*`
+    ///
+    /// OpenThread handles everything not claimed in software (e.g. its own ACK timer and CSMA
+    /// backoff).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct RadioCapabilities: u8 {
+        /// The radio waits for ACKs and reports missing ones
+        const AckTimeout = 1;
+        /// The radio performs energy scans
+        const EnergyScan = 2;
+        /// The radio retransmits frames
+        const TransmitRetries = 4;
+        /// The radio performs CSMA/CA backoff
+        const CsmaBackoff = 8;
+        /// The radio transmits directly from sleep
+        const SleepToTx = 16;
+        /// The radio applies MAC security to transmitted frames
+        const TransmitSecurity = 32;
+        /// The radio transmits at a scheduled time
+        const TransmitTiming = 64;
+        /// The radio receives at a scheduled time
+        const ReceiveTiming = 128;
+    }
+}
+
+/// Capabilities claimed by this platform, selected via the `radio-caps-*` features
+///
+/// Transmit security isn't offered, the radio driver can't encrypt frames.
+pub const RADIO_CAPABILITIES: RadioCapabilities = claimed_capabilities();
+
+const fn claimed_capabilities() -> RadioCapabilities {
+    let mut caps = RadioCapabilities::empty();
+    if cfg!(feature = "radio-caps-ack-timeout") {
+        caps = caps.union(RadioCapabilities::AckTimeout);
+    }
+    if cfg!(feature = "radio-caps-csma-backoff") {
+        caps = caps.union(RadioCapabilities::CsmaBackoff);
+    }
+    if cfg!(feature = "radio-caps-sleep-to-tx") {
+        caps = caps.union(RadioCapabilities::SleepToTx);
+    }
+    caps
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the radio capabilities claimed towards OpenThread
+    ///
+    /// OpenThread takes the software path for every capability not contained.
+    pub fn radio_get_capabilities(&self) -> RadioCapabilities {
+        RADIO_CAPABILITIES
+    }
+}
+
+/// Per-channel corrections of the radio, e.g. measured during manufacturing test
+///
+/// Index 0 is channel 11, index 15 channel 26.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadioCalibration {
+    /// Added to the transmit power on the channel (in dB)
+    pub tx_power_offsets: [i8; RADIO_CHANNEL_COUNT],
+    /// Added to the RSSI measured on the channel (in dB), which also corrects the LQI derived
+    /// from it
+    pub rssi_offsets: [i8; RADIO_CHANNEL_COUNT],
+}
+
+impl RadioCalibration {
+    pub const DEFAULT: Self = Self {
+        tx_power_offsets: [0; RADIO_CHANNEL_COUNT],
+        rssi_offsets: [0; RADIO_CHANNEL_COUNT],
+    };
+}
+
+impl Default for RadioCalibration {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Program per-channel transmit power and RSSI corrections
+    ///
+    /// The transmit power corrections apply from the next transmission or receive on, the RSSI
+    /// corrections to every frame received from then on.
+    pub fn radio_set_calibration(&mut self, calibration: RadioCalibration) {
+        critical_section::with(|cs| RADIO_CALIBRATION.borrow(cs).set(calibration));
+    }
+
+    /// Get the per-channel transmit power and RSSI corrections
+    pub fn radio_get_calibration(&self) -> RadioCalibration {
+        critical_section::with(|cs| RADIO_CALIBRATION.borrow(cs).get())
+    }
+
+    /// Set the transmit power (in dBm) before calibration, `None` uses the driver's default
+    pub fn radio_set_transmit_power(&mut self, power: Option<i8>) {
+        critical_section::with(|cs| TRANSMIT_POWER.borrow(cs).set(power));
+    }
+
+    /// Get the transmit power (in dBm) before calibration, `None` if the driver's default is used
+    pub fn radio_get_transmit_power(&self) -> Option<i8> {
+        critical_section::with(|cs| TRANSMIT_POWER.borrow(cs).get())
+    }
+}
+
+/// Correction of the transmit power over the die temperature, e.g. fitted from measurements in a
+/// climatic chamber
+///
+/// The power amplifier loses output power as the die heats up, over -40 to +85 °C this adds up
+/// to several dB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadioTemperatureCompensation {
+    /// Die temperature (in °C) the transmit power and [RadioCalibration] refer to
+    pub reference_temperature: i8,
+    /// Added to the transmit power per °C above the reference temperature (in 0.01 dB),
+    /// subtracted per °C below it
+    pub tx_power_slope: i16,
+}
+
+impl RadioTemperatureCompensation {
+    /// No correction
+    pub const DEFAULT: Self = Self {
+        reference_temperature: 25,
+        tx_power_slope: 0,
+    };
+
+    /// Correction of the transmit power (in dB) at `temperature` (in °C)
+    pub fn tx_power_offset(&self, temperature: i8) -> i8 {
+        let offset = (temperature as i32 - self.reference_temperature as i32)
+            * self.tx_power_slope as i32
+            / 100;
+        offset.clamp(i8::MIN as i32, i8::MAX as i32) as i8
+    }
+}
+
+impl Default for RadioTemperatureCompensation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Program the correction of the transmit power over the die temperature
+    pub fn radio_set_temperature_compensation(
+        &mut self,
+        compensation: RadioTemperatureCompensation,
+    ) {
+        critical_section::with(|cs| RADIO_TEMPERATURE_COMPENSATION.borrow(cs).set(compensation));
+    }
+
+    /// Get the correction of the transmit power over the die temperature
+    pub fn radio_get_temperature_compensation(&self) -> RadioTemperatureCompensation {
+        critical_section::with(|cs| RADIO_TEMPERATURE_COMPENSATION.borrow(cs).get())
+    }
+
+    /// Feed the die temperature (in °C), e.g. read from esp-hal's temperature sensor every few
+    /// seconds, `None` stops the compensation
+    ///
+    /// Like [OpenThread::radio_set_calibration] it applies from the next transmission or
+    /// receive on. The frequency isn't corrected, the driver has no means to trim the crystal.
+    pub fn radio_set_temperature(&mut self, temperature: Option<i8>) {
+        critical_section::with(|cs| RADIO_TEMPERATURE.borrow(cs).set(temperature));
+    }
+
+    /// Get the die temperature (in °C) last fed, `None` if not compensating
+    pub fn radio_get_temperature(&self) -> Option<i8> {
+        critical_section::with(|cs| RADIO_TEMPERATURE.borrow(cs).get())
+    }
+}
+
+/// Accuracy of the clock scheduling CSL receive windows, reported to CSL peers
+///
+/// A CSL parent widens the window in which it transmits to this device by the drift accumulated
+/// since the last synchronization plus the uncertainty, too optimistic values make it miss the
+/// receive windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadioClockAccuracy {
+    /// Worst case drift of the clock (in ± ppm)
+    pub accuracy: u8,
+    /// Fixed scheduling uncertainty (in units of 10 microseconds)
+    pub uncertainty: u8,
+}
+
+impl RadioClockAccuracy {
+    /// A typical 40 MHz crystal with ±10 ppm tolerance and ±10 ppm over temperature, scheduled
+    /// within 100 microseconds
+    pub const DEFAULT: Self = Self {
+        accuracy: 20,
+        uncertainty: 10,
+    };
+
+    /// Derive the accuracy from the specs in the datasheet of the board's crystal, all in ± ppm
+    ///
+    /// `tolerance` is the frequency tolerance at 25 °C, `stability` the variation over the
+    /// operating temperature range and `aging` the drift over the lifetime of the device.
+    /// `uncertainty` is in units of 10 microseconds.
+    pub const fn from_crystal(tolerance: u8, stability: u8, aging: u8, uncertainty: u8) -> Self {
+        Self {
+            accuracy: tolerance.saturating_add(stability).saturating_add(aging),
+            uncertainty,
+        }
+    }
+}
+
+impl Default for RadioClockAccuracy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the accuracy of the clock reported to CSL peers, e.g. measured for the board
+    ///
+    /// Takes effect with the next CSL synchronization.
+    pub fn radio_set_clock_accuracy(&mut self, accuracy: RadioClockAccuracy) {
+        critical_section::with(|cs| RADIO_CLOCK_ACCURACY.borrow(cs).set(accuracy));
+    }
+
+    /// Get the accuracy of the clock reported to CSL peers
+    pub fn radio_get_clock_accuracy(&self) -> RadioClockAccuracy {
+        critical_section::with(|cs| RADIO_CLOCK_ACCURACY.borrow(cs).get())
+    }
+}
+
+/// Frames and bytes that went over the radio, see [OpenThread::radio_get_throughput]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RadioThroughput {
+    /// Frames handed to the radio, retransmissions not included
+    pub frames_sent: u32,
+    /// PSDU bytes of `frames_sent`
+    pub bytes_sent: u64,
+    /// Frames sent again because they weren't acknowledged
+    pub retransmissions: u32,
+    pub frames_received: u32,
+    /// PSDU bytes of `frames_received`
+    pub bytes_received: u64,
+    /// Milliseconds the counters cover
+    pub duration: u64,
+}
+
+impl RadioThroughput {
+    const DEFAULT: Self = Self {
+        frames_sent: 0,
+        bytes_sent: 0,
+        retransmissions: 0,
+        frames_received: 0,
+        bytes_received: 0,
+        duration: 0,
+    };
+
+    /// Bits per second sent, retransmissions not included
+    pub fn tx_goodput(&self) -> u32 {
+        bits_per_second(self.bytes_sent, self.duration)
+    }
+
+    /// Bits per second received
+    pub fn rx_goodput(&self) -> u32 {
+        bits_per_second(self.bytes_received, self.duration)
+    }
+}
+
+fn bits_per_second(bytes: u64, duration: u64) -> u32 {
+    if duration == 0 {
+        return 0;
+    }
+
+    (bytes * 8 * 1000 / duration) as u32
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ThroughputCounters {
+    pub(crate) throughput: RadioThroughput,
+    /// When the counters were reset (in milliseconds)
+    since: u64,
+}
+
+impl ThroughputCounters {
+    pub(crate) const DEFAULT: Self = Self {
+        throughput: RadioThroughput::DEFAULT,
+        since: 0,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the frames and bytes sent and received since the counters were reset, e.g. to measure
+    /// the goodput of an OTA update
+    pub fn radio_get_throughput(&self) -> RadioThroughput {
+        let counters = critical_section::with(|cs| RADIO_THROUGHPUT.borrow(cs).get());
+        RadioThroughput {
+            duration: current_millis() - counters.since,
+            ..counters.throughput
+        }
+    }
+
+    /// Reset the counters of [OpenThread::radio_get_throughput] to zero
+    pub fn radio_reset_throughput(&mut self) {
+        critical_section::with(|cs| {
+            RADIO_THROUGHPUT.borrow(cs).set(ThroughputCounters {
+                throughput: RadioThroughput::DEFAULT,
+                since: current_millis(),
+            })
+        });
+    }
+}
+
+/// Count a frame handed to OpenThread as received
+pub(crate) fn count_received(len: usize) {
+    count_throughput(|throughput| {
+        throughput.frames_received = throughput.frames_received.wrapping_add(1);
+        throughput.bytes_received = throughput.bytes_received.wrapping_add(len as u64);
+    });
+}
+
+fn count_throughput(f: impl FnOnce(&mut RadioThroughput)) {
+    critical_section::with(|cs| {
+        let cell = RADIO_THROUGHPUT.borrow(cs);
+        let mut counters = cell.get();
+        f(&mut counters.throughput);
+        cell.set(counters);
+    });
+}
+
+/// Hooks sequencing external radio hardware with the radio, e.g. a PA/LNA front-end module or an
+/// antenna switch driven by GPIOs
+///
+/// Each hook is called right before the radio changes its state, from OpenThread's processing.
+/// It needs to be quick, the radio waits for it.
+pub trait RadioFrontEnd {
+    /// The radio is about to transmit on `channel`, e.g. enable the PA
+    fn transmit(&mut self, channel: u8);
+
+    /// The radio is about to receive on `channel`, e.g. enable the LNA
+    fn receive(&mut self, channel: u8);
+
+    /// The radio goes to sleep, e.g. power down the front-end module
+    fn sleep(&mut self) {}
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the hooks for external radio hardware, see [RadioFrontEnd]
+    pub fn set_radio_front_end(&mut self, front_end: Option<&'a mut (dyn RadioFrontEnd + Send)>) {
+        critical_section::with(|cs| {
+            let mut radio_front_end = RADIO_FRONT_END.borrow_ref_mut(cs);
+            *radio_front_end = unsafe { core::mem::transmute(front_end) };
+        });
+    }
+}
+
+fn with_front_end(f: impl FnOnce(&mut dyn RadioFrontEnd)) {
+    critical_section::with(|cs| {
+        let mut front_end = RADIO_FRONT_END.borrow_ref_mut(cs);
+        if let Some(front_end) = front_end.as_mut() {
+            f(&mut **front_end);
+        }
+    });
+}
+
+pub(crate) fn channel_index(channel: u8) -> Option<usize> {
+    let index = channel.checked_sub(RADIO_CHANNEL_MIN)? as usize;
+    (index < RADIO_CHANNEL_COUNT).then_some(index)
+}
+
+/// Transmit power for the channel with its correction applied
+pub(crate) fn calibrated_tx_power(channel: u8) -> i8 {
+    #[cfg(feature = "diag")]
+    if let Some(power) = crate::diag::raw_tx_power() {
+        return power;
+    }
+
+    let (calibration, compensation, temperature) = critical_section::with(|cs| {
+        (
+            RADIO_CALIBRATION.borrow(cs).get(),
+            RADIO_TEMPERATURE_COMPENSATION.borrow(cs).get(),
+            RADIO_TEMPERATURE.borrow(cs).get(),
+        )
+    });
+    let power = match temperature {
+        Some(temperature) => {
+            transmit_power().saturating_add(compensation.tx_power_offset(temperature))
+        }
+        None => transmit_power(),
+    };
+    match channel_index(channel) {
+        Some(index) => power.saturating_add(calibration.tx_power_offsets[index]),
+        None => power,
+    }
+}
+
+/// Transmit power before calibration
+pub(crate) fn transmit_power() -> i8 {
+    critical_section::with(|cs| TRANSMIT_POWER.borrow(cs).get())
+        .unwrap_or(Config::default().txpower)
+}
+
+/// RSSI measured on the channel with its correction applied
+pub(crate) fn calibrated_rssi(channel: u8, rssi: i8) -> i8 {
+    match channel_index(channel) {
+        Some(index) => rssi.saturating_add(critical_section::with(|cs| {
+            RADIO_CALIBRATION.borrow(cs).get().rssi_offsets[index]
+        })),
+        None => rssi,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn otPlatRadioGetCaps(instance: *const otInstance) -> u8 {
-    log::info!("otPlatRadioGetCaps {:p}", instance);
-    0 // Radio supports no capability. See OT_RADIO_CAPS_*
+    log::info!("otPlatRadioGetCaps {:p} {:?}", instance, RADIO_CAPABILITIES);
+    RADIO_CAPABILITIES.bits()
 }
 
 #[no_mangle]
@@ -77,6 +589,33 @@ pub extern "C" fn otPlatRadioEnable(instance: *const otInstance) -> otError {
 #[no_mangle]
 pub extern "C" fn otPlatRadioSleep(instance: *const otInstance) -> otError {
     log::info!("otPlatRadioSleep {:p}", instance);
+
+    #[cfg(feature = "frame-bursting")]
+    critical_section::with(|cs| RECEIVE_CONFIGURATION.borrow(cs).set(None));
+
+    with_front_end(|front_end| front_end.sleep());
+
+    // stop listening once the radio is idle so a raw receive window really closes,
+    // `otPlatRadioReceive` turns it on again
+    #[cfg(feature = "link-raw")]
+    {
+        let settings = get_settings();
+        with_radio(|radio| {
+            radio.set_config(Config {
+                channel: settings.channel,
+                txpower: calibrated_tx_power(settings.channel),
+                promiscuous: settings.promiscuous,
+                pan_id: Some(settings.pan_id),
+                short_addr: Some(settings.short_address),
+                ext_addr: Some(settings.ext_address),
+                rx_when_idle: false,
+                auto_ack_rx: true,
+                auto_ack_tx: true,
+                ..Config::default()
+            });
+        });
+    }
+
     otError_OT_ERROR_NONE
 }
 
@@ -96,6 +635,7 @@ pub extern "C" fn otPlatRadioSetPromiscuous(_instance: *const otInstance, enable
     with_radio(|radio| {
         radio.set_config(Config {
             channel: settings.channel,
+            txpower: calibrated_tx_power(settings.channel),
             promiscuous: settings.promiscuous,
             pan_id: Some(settings.pan_id),
             short_addr: Some(settings.short_address),
@@ -120,6 +660,38 @@ pub extern "C" fn otPlatRadioGetReceiveSensitivity(_instance: *const otInstance)
     33
 }
 
+/// The transmit power used on the current channel, with calibration and temperature compensation
+#[no_mangle]
+pub extern "C" fn otPlatRadioGetTransmitPower(
+    _instance: *mut otInstance,
+    power: *mut i8,
+) -> otError {
+    if power.is_null() {
+        return otError_OT_ERROR_INVALID_ARGS;
+    }
+
+    unsafe { *power = calibrated_tx_power(get_settings().channel) };
+    otError_OT_ERROR_NONE
+}
+
+/// Like [OpenThread::radio_set_transmit_power], the corrections still apply
+#[no_mangle]
+pub extern "C" fn otPlatRadioSetTransmitPower(_instance: *mut otInstance, power: i8) -> otError {
+    log::info!("otPlatRadioSetTransmitPower {power}");
+    critical_section::with(|cs| TRANSMIT_POWER.borrow(cs).set(Some(power)));
+    otError_OT_ERROR_NONE
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatRadioGetCslAccuracy(_instance: *const otInstance) -> u8 {
+    critical_section::with(|cs| RADIO_CLOCK_ACCURACY.borrow(cs).get().accuracy)
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatRadioGetCslUncertainty(_instance: *const otInstance) -> u8 {
+    critical_section::with(|cs| RADIO_CLOCK_ACCURACY.borrow(cs).get().uncertainty)
+}
+
 #[no_mangle]
 pub extern "C" fn otPlatRadioEnergyScan(
     _instance: *const otInstance,
@@ -152,6 +724,7 @@ pub extern "C" fn otPlatRadioSetExtendedAddress(instance: *const otInstance, add
     with_radio(|radio| {
         radio.set_config(Config {
             channel: settings.channel,
+            txpower: calibrated_tx_power(settings.channel),
             promiscuous: settings.promiscuous,
             pan_id: Some(settings.pan_id),
             short_addr: Some(settings.short_address),
@@ -176,6 +749,7 @@ pub extern "C" fn otPlatRadioSetShortAddress(instance: *const otInstance, addres
     with_radio(|radio| {
         radio.set_config(Config {
             channel: settings.channel,
+            txpower: calibrated_tx_power(settings.channel),
             promiscuous: settings.promiscuous,
             pan_id: Some(settings.pan_id),
             short_addr: Some(settings.short_address),
@@ -200,6 +774,7 @@ pub extern "C" fn otPlatRadioSetPanId(_instance: *const otInstance, pan_id: u16)
     with_radio(|radio| {
         radio.set_config(Config {
             channel: settings.channel,
+            txpower: calibrated_tx_power(settings.channel),
             promiscuous: settings.promiscuous,
             pan_id: Some(settings.pan_id),
             short_addr: Some(settings.short_address),
@@ -217,6 +792,9 @@ pub extern "C" fn otPlatRadioTransmit(
     instance: *const otInstance,
     frame: *const otRadioFrame,
 ) -> otError {
+    #[cfg(feature = "instrumentation")]
+    crate::health::sample_stack();
+
     let frame = unsafe { &*frame };
     let data = unsafe { core::slice::from_raw_parts(frame.mPsdu, frame.mLength as usize) };
 
@@ -229,20 +807,64 @@ pub extern "C" fn otPlatRadioTransmit(
     let settings = get_settings();
     log::info!("Settings {:x?}", settings);
 
-    with_radio(|radio| {
-        radio.set_config(Config {
-            channel: frame.mChannel,
-            promiscuous: settings.promiscuous,
-            pan_id: Some(settings.pan_id),
-            short_addr: Some(settings.short_address),
-            ext_addr: Some(settings.ext_address),
-            auto_ack_rx: true,
-            auto_ack_tx: true,
-            ..Config::default()
+    #[cfg(feature = "test-harness")]
+    let send = crate::harness::capture_transmit(frame);
+    #[cfg(not(feature = "test-harness"))]
+    let send = true;
+
+    if frame.mInfo.mTxInfo.mIsARetx() {
+        count_throughput(|throughput| {
+            throughput.retransmissions = throughput.retransmissions.wrapping_add(1)
+        });
+    } else {
+        count_throughput(|throughput| {
+            throughput.frames_sent = throughput.frames_sent.wrapping_add(1);
+            throughput.bytes_sent = throughput.bytes_sent.wrapping_add(data.len() as u64);
         });
+    }
 
-        radio.transmit_raw(data).ok();
-    });
+    if send {
+        with_front_end(|front_end| front_end.transmit(frame.mChannel));
+
+        // back to back with the last receive there's nothing to reconfigure, the radio goes
+        // straight to transmitting
+        #[cfg(feature = "frame-bursting")]
+        let burst = critical_section::with(|cs| {
+            let receive_configuration = RECEIVE_CONFIGURATION.borrow(cs);
+            let burst = receive_configuration.get()
+                == Some((
+                    frame.mChannel,
+                    calibrated_tx_power(frame.mChannel),
+                    settings,
+                ));
+            if !burst {
+                receive_configuration.set(None);
+            }
+            burst
+        });
+        #[cfg(not(feature = "frame-bursting"))]
+        let burst = false;
+
+        with_radio(|radio| {
+            if !burst {
+                radio.set_config(Config {
+                    channel: frame.mChannel,
+                    txpower: calibrated_tx_power(frame.mChannel),
+                    promiscuous: settings.promiscuous,
+                    pan_id: Some(settings.pan_id),
+                    short_addr: Some(settings.short_address),
+                    ext_addr: Some(settings.ext_address),
+                    auto_ack_rx: true,
+                    auto_ack_tx: true,
+                    ..Config::default()
+                });
+            }
+
+            let started = current_ticks();
+            radio.transmit_raw(data).ok();
+            update_timestamps(|timestamps| timestamps.tx_started = started);
+        });
+    }
 
     unsafe {
         SENT_FRAME_PSDU[..frame.mLength as usize].copy_from_slice(core::slice::from_raw_parts(
@@ -263,6 +885,8 @@ pub extern "C" fn otPlatRadioTransmit(
 #[no_mangle]
 pub extern "C" fn otPlatRadioReceive(_instance: *mut otInstance, channel: u8) -> otError {
     log::info!("otPlatRadioReceive channel = {channel}");
+    #[cfg(feature = "instrumentation")]
+    crate::health::sample_stack();
 
     let settings = get_settings();
     log::info!("Settings {:x?}", settings);
@@ -272,17 +896,38 @@ pub extern "C" fn otPlatRadioReceive(_instance: *mut otInstance, channel: u8) ->
         ..settings
     });
 
+    with_front_end(|front_end| front_end.receive(channel));
+
+    // unchanged since the last receive, e.g. between the frames of a burst
+    #[cfg(feature = "frame-bursting")]
+    let configured = critical_section::with(|cs| {
+        let configuration = Some((
+            channel,
+            calibrated_tx_power(channel),
+            NetworkSettings {
+                channel,
+                ..settings
+            },
+        ));
+        RECEIVE_CONFIGURATION.borrow(cs).replace(configuration) == configuration
+    });
+    #[cfg(not(feature = "frame-bursting"))]
+    let configured = false;
+
     with_radio(|radio| {
-        radio.set_config(Config {
-            channel: channel,
-            promiscuous: settings.promiscuous,
-            pan_id: Some(settings.pan_id),
-            short_addr: Some(settings.short_address),
-            ext_addr: Some(settings.ext_address),
-            auto_ack_rx: true,
-            auto_ack_tx: true,
-            ..Config::default()
-        });
+        if !configured {
+            radio.set_config(Config {
+                channel: channel,
+                txpower: calibrated_tx_power(channel),
+                promiscuous: settings.promiscuous,
+                pan_id: Some(settings.pan_id),
+                short_addr: Some(settings.short_address),
+                ext_addr: Some(settings.ext_address),
+                auto_ack_rx: true,
+                auto_ack_tx: true,
+                ..Config::default()
+            });
+        }
         radio.start_receive();
     });
 
@@ -290,8 +935,23 @@ pub extern "C" fn otPlatRadioReceive(_instance: *mut otInstance, channel: u8) ->
 }
 
 pub(crate) fn trigger_tx_done() {
+    let done = current_ticks();
+    update_timestamps(|timestamps| timestamps.tx_done = done);
+
     log::warn!("trigger_tx_done");
 
+    #[cfg(feature = "diag")]
+    if crate::diag::is_diag_mode() {
+        unsafe {
+            otPlatDiagRadioTransmitDone(
+                CURRENT_INSTANCE as *mut otInstance,
+                addr_of_mut!(SENT_FRAME) as *mut otRadioFrame,
+                otError_OT_ERROR_NONE,
+            );
+        }
+        return;
+    }
+
     unsafe {
         otPlatRadioTxDone(
             CURRENT_INSTANCE as *mut otInstance,
@@ -301,3 +961,79 @@ pub(crate) fn trigger_tx_done() {
         );
     }
 }
+
+/// Set up an empty receive queue, must happen before the radio's receive callback is installed
+pub(crate) fn init_rx_queue() {
+    critical_section::with(|_| unsafe {
+        *addr_of_mut!(RX_PRODUCER) = None;
+        *addr_of_mut!(RX_CONSUMER) = None;
+
+        let queue = &mut *addr_of_mut!(RX_QUEUE);
+        *queue = Queue::new();
+        let (producer, consumer) = queue.split();
+        *addr_of_mut!(RX_PRODUCER) = Some(producer);
+        *addr_of_mut!(RX_CONSUMER) = Some(consumer);
+    });
+}
+
+/// Called by the radio driver from its interrupt whenever frames were received
+///
+/// Only moves the frames from the driver to the receive queue, in a single critical section.
+/// RSSI calibration and throughput counting happen when `process()` takes them.
+pub(crate) fn rx_available() {
+    let Some(producer) = (unsafe { (*addr_of_mut!(RX_PRODUCER)).as_mut() }) else {
+        return;
+    };
+
+    with_radio(|radio| {
+        while let Some(raw) = radio.get_raw_received() {
+            let len = raw.data[0];
+            if len < 2 {
+                log::warn!("dropping frame of {} bytes, shorter than the FCS", len);
+                continue;
+            }
+            if len as usize > RX_PSDU_SIZE {
+                log::warn!("dropping frame of {} bytes, larger than RX_PSDU_SIZE", len);
+                continue;
+            }
+
+            let ticks = current_ticks();
+            let mut frame = ReceivedFrame {
+                psdu: [0u8; RX_PSDU_SIZE],
+                len,
+                channel: raw.channel,
+                rssi: raw.data[len as usize - 1] as i8,
+                timestamp: ticks / (TICKS_PER_SECOND / 1_000_000),
+                ticks,
+            };
+            frame.psdu[..len as usize].copy_from_slice(&raw.data[1..][..len as usize]);
+
+            if producer.enqueue(frame).is_err() {
+                log::warn!("receive queue full, dropping frame");
+            }
+        }
+    });
+
+    // one wake-up for all frames taken
+    #[cfg(feature = "async")]
+    crate::asynch::signal();
+}
+
+pub(crate) fn update_timestamps(f: impl FnOnce(&mut RadioTimestamps)) {
+    critical_section::with(|cs| {
+        let cell = RADIO_TIMESTAMPS.borrow(cs);
+        let mut timestamps = cell.get();
+        f(&mut timestamps);
+        cell.set(timestamps);
+    });
+}
+
+/// Take the next frame received by the radio interrupt
+pub(crate) fn take_received_frame() -> Option<ReceivedFrame> {
+    unsafe { (*addr_of_mut!(RX_CONSUMER)).as_mut() }?.dequeue()
+}
+
+/// Returns `true` if the radio interrupt queued frames which weren't taken yet
+pub(crate) fn received_frame_pending() -> bool {
+    unsafe { (*addr_of_mut!(RX_CONSUMER)).as_ref() }.is_some_and(|consumer| consumer.ready())
+}
@@ -0,0 +1,120 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otError, otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NONE,
+    otInstance, otRadioFrame,
+};
+
+use crate::{
+    checked,
+    link_raw::{link_raw_transmit, FCS_SIZE},
+    radio::RADIO_TIMESTAMPS,
+    timer::TICKS_PER_SECOND,
+    Error, OpenThread,
+};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+
+/// Resolution of the radio timestamps, [crate::RawFrame::ticks] and [TimestampedTxDone]
+pub const RADIO_TIMESTAMP_TICKS_PER_SECOND: u64 = TICKS_PER_SECOND;
+
+/// Set while a frame sent via [OpenThread::link_raw_transmit_timestamped] is in flight
+pub(crate) static TIMESTAMPED_TX_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(TimestampedTxDone) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Outcome of a frame sent via [OpenThread::link_raw_transmit_timestamped]
+///
+/// The timestamps are systimer counts, see [RADIO_TIMESTAMP_TICKS_PER_SECOND].
+#[derive(Debug, Clone, Copy)]
+pub struct TimestampedTxDone {
+    pub result: Result<(), Error>,
+    /// The frame was handed to the radio, after CSMA/CA
+    pub started: u64,
+    /// The radio interrupt reported the frame sent
+    pub done: u64,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Send a frame on the raw link-layer and report when it left the radio, e.g. for two-way
+    /// ranging experiments
+    ///
+    /// `psdu` is the frame without the FCS, sent on the channel set via
+    /// [OpenThread::link_set_channel]. With `ack` it's retried up to three times, the timestamps
+    /// are those of the last attempt. `callback` gets the outcome once, possibly from the
+    /// radio interrupt, so it has to be quick. Fails with `OT_ERROR_INVALID_STATE` while the raw link-layer is
+    /// disabled, the PAN coordinator runs or another frame is in flight.
+    ///
+    /// `esp-ieee802154` doesn't expose the MAC's hardware timestamps, the crate captures the
+    /// systimer in the radio interrupt instead. Interrupt latency adds jitter in the order of
+    /// microseconds, so average over many exchanges.
+    pub fn link_raw_transmit_timestamped(
+        &mut self,
+        psdu: &[u8],
+        ack: bool,
+        callback: &'a mut (dyn FnMut(TimestampedTxDone) + Send),
+    ) -> Result<(), Error> {
+        if psdu.is_empty() || psdu.len() + FCS_SIZE > 127 {
+            return Err(INVALID_ARGS);
+        }
+        if !self.link_raw_is_enabled() || self.pan_coordinator_is_running() {
+            return Err(INVALID_STATE);
+        }
+
+        critical_section::with(|cs| {
+            let mut tx_callback = TIMESTAMPED_TX_CALLBACK.borrow_ref_mut(cs);
+            if tx_callback.is_some() {
+                return Err(INVALID_STATE);
+            }
+            *tx_callback = unsafe { core::mem::transmute(Some(callback)) };
+            Ok(())
+        })?;
+
+        let res = unsafe { link_raw_transmit(self.instance, psdu, ack, Some(timestamped_tx_done)) };
+        if res != otError_OT_ERROR_NONE {
+            critical_section::with(|cs| TIMESTAMPED_TX_CALLBACK.borrow_ref_mut(cs).take());
+        }
+        checked!(res)
+    }
+}
+
+/// Time of flight (in nanoseconds) from a two-way exchange, as seen by the initiator
+///
+/// `round_trip` is the time from sending the poll to receiving the response, `reply` the time
+/// the responder took from receiving the poll to sending the response, both in ticks (see
+/// [RADIO_TIMESTAMP_TICKS_PER_SECOND]) and measured with [TimestampedTxDone::done] and
+/// [crate::RawFrame::ticks]. The responder has to report `reply` in its response. Negative
+/// results are jitter, not an error.
+pub fn two_way_time_of_flight(round_trip: u64, reply: u64) -> i64 {
+    let ticks = round_trip as i64 - reply as i64;
+    ticks * 1_000_000_000 / (2 * RADIO_TIMESTAMP_TICKS_PER_SECOND as i64)
+}
+
+unsafe extern "C" fn timestamped_tx_done(
+    _instance: *mut otInstance,
+    _frame: *mut otRadioFrame,
+    _ack_frame: *mut otRadioFrame,
+    error: otError,
+) {
+    log::debug!("timestamped_tx_done {}", error);
+
+    let timestamps = critical_section::with(|cs| RADIO_TIMESTAMPS.borrow(cs).get());
+    let done = TimestampedTxDone {
+        result: checked!(error),
+        started: timestamps.tx_started,
+        done: timestamps.tx_done,
+    };
+
+    let callback = critical_section::with(|cs| TIMESTAMPED_TX_CALLBACK.borrow_ref_mut(cs).take());
+    if let Some(callback) = callback {
+        callback(done);
+    }
+}
+
+/// Returns `true` while a frame sent via [OpenThread::link_raw_transmit_timestamped] is in
+/// flight, the PAN coordinator holds its frames back meanwhile
+pub(crate) fn is_transmitting() -> bool {
+    critical_section::with(|cs| TIMESTAMPED_TX_CALLBACK.borrow_ref(cs).is_some())
+}
@@ -0,0 +1,118 @@
+use esp_openthread_sys::bindings::{
+    otThreadGetLeaderRloc, otThreadGetMeshLocalPrefix, otThreadGetRloc, otThreadGetRloc16,
+    otThreadGetServiceAloc,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{
+    checked, ipv6_addr_from_ot_ip6_address, ot_ip6_address_from_ipv6_addr, Error, MeshLocalPrefix,
+    OpenThread,
+};
+
+/// RLOC16 value meaning "no valid RLOC16"
+pub const INVALID_RLOC16: u16 = 0xfffe;
+
+/// ALOC16 of the leader, whichever device currently is
+pub const LEADER_ALOC16: u16 = 0xfc00;
+
+/// ALOC16 of the service with ID 0, the service IDs 0 to 15 map to consecutive ALOC16s
+pub const SERVICE_ALOC16_MIN: u16 = 0xfc10;
+
+/// ALOC16 of the service with ID 15
+pub const SERVICE_ALOC16_MAX: u16 = 0xfc1f;
+
+const ROUTER_ID_OFFSET: u16 = 10;
+const CHILD_ID_MASK: u16 = 0x01ff;
+
+/// Extract the router ID from an RLOC16
+pub fn router_id_from_rloc16(rloc16: u16) -> u8 {
+    (rloc16 >> ROUTER_ID_OFFSET) as u8
+}
+
+/// Extract the child ID from an RLOC16 (`0` for routers)
+pub fn child_id_from_rloc16(rloc16: u16) -> u16 {
+    rloc16 & CHILD_ID_MASK
+}
+
+/// Build the RLOC16 of the router with the given router ID
+pub fn rloc16_from_router_id(router_id: u8) -> u16 {
+    (router_id as u16) << ROUTER_ID_OFFSET
+}
+
+/// Returns `true` if the RLOC16 belongs to a router (child ID is zero)
+pub fn is_router_rloc16(rloc16: u16) -> bool {
+    child_id_from_rloc16(rloc16) == 0
+}
+
+/// Returns the RLOC16 of the parent router for a given RLOC16
+pub fn parent_rloc16(rloc16: u16) -> u16 {
+    rloc16_from_router_id(router_id_from_rloc16(rloc16))
+}
+
+/// Build the RLOC address `<mesh-local-prefix>:0:ff:fe00:<rloc16>`
+pub fn rloc_address(mesh_local_prefix: &MeshLocalPrefix, rloc16: u16) -> Ipv6Addr {
+    let mut octets = [0u8; 16];
+    octets[..8].copy_from_slice(mesh_local_prefix.as_bytes());
+    octets[11] = 0xff;
+    octets[12] = 0xfe;
+    octets[14..].copy_from_slice(&rloc16.to_be_bytes());
+    Ipv6Addr::from(octets)
+}
+
+/// Get the ALOC16 of a Network Data service, `None` if the service ID is out of range
+pub fn service_aloc16(service_id: u8) -> Option<u16> {
+    let aloc16 = SERVICE_ALOC16_MIN + service_id as u16;
+    (aloc16 <= SERVICE_ALOC16_MAX).then_some(aloc16)
+}
+
+/// Returns the RLOC16 if the address is an RLOC or ALOC with the given mesh-local prefix
+pub fn rloc16_from_address(mesh_local_prefix: &MeshLocalPrefix, address: &Ipv6Addr) -> Option<u16> {
+    let octets = address.octets();
+    if &octets[..8] == mesh_local_prefix.as_bytes() && octets[8..14] == [0, 0, 0, 0xff, 0xfe, 0] {
+        Some(u16::from_be_bytes([octets[14], octets[15]]))
+    } else {
+        None
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Get the RLOC16 of this device
+    pub fn get_rloc16(&self) -> u16 {
+        unsafe { otThreadGetRloc16(self.instance) }
+    }
+
+    /// Build the RLOC address of another node from its RLOC16 and our mesh-local prefix
+    pub fn get_rloc_address_of(&self, rloc16: u16) -> Ipv6Addr {
+        let prefix = MeshLocalPrefix::from(unsafe { *otThreadGetMeshLocalPrefix(self.instance) });
+        rloc_address(&prefix, rloc16)
+    }
+
+    /// Get the RLOC address of this device
+    pub fn get_rloc_address(&self) -> Ipv6Addr {
+        ipv6_addr_from_ot_ip6_address(unsafe { &*otThreadGetRloc(self.instance) })
+    }
+
+    /// Get the RLOC address of the current leader
+    ///
+    /// Fails while detached.
+    pub fn get_leader_rloc_address(&self) -> Result<Ipv6Addr, Error> {
+        let mut address = ot_ip6_address_from_ipv6_addr(Ipv6Addr::UNSPECIFIED);
+        checked!(unsafe { otThreadGetLeaderRloc(self.instance, &mut address) })?;
+        Ok(ipv6_addr_from_ot_ip6_address(&address))
+    }
+
+    /// Get the leader ALOC, which keeps reaching the leader when another device takes over
+    pub fn get_leader_aloc_address(&self) -> Ipv6Addr {
+        self.get_rloc_address_of(LEADER_ALOC16)
+    }
+
+    /// Get the ALOC of the Network Data service with the given service ID, which reaches the
+    /// closest server of the service
+    ///
+    /// Fails while detached.
+    pub fn get_service_aloc_address(&self, service_id: u8) -> Result<Ipv6Addr, Error> {
+        let mut address = ot_ip6_address_from_ipv6_addr(Ipv6Addr::UNSPECIFIED);
+        checked!(unsafe { otThreadGetServiceAloc(self.instance, service_id, &mut address) })?;
+        Ok(ipv6_addr_from_ot_ip6_address(&address))
+    }
+}
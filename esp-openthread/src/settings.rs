@@ -0,0 +1,415 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otCryptoContext, otCryptoKey, otError, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND, otError_OT_ERROR_NOT_IMPLEMENTED,
+        otError_OT_ERROR_NO_BUFS, otInstance, otPlatCryptoAesEncrypt, otPlatCryptoAesFree,
+        otPlatCryptoAesInit, otPlatCryptoAesSetKey, otPlatCryptoHmacSha256Deinit,
+        otPlatCryptoHmacSha256Finish, otPlatCryptoHmacSha256Init, otPlatCryptoHmacSha256Start,
+        otPlatCryptoHmacSha256Update, otPlatCryptoRandomGet, OT_CRYPTO_SHA256_HASH_SIZE,
+    },
+    c_types::{c_int, c_void},
+};
+
+use crate::{checked, key_derivation::hkdf, Error};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+const NOT_IMPLEMENTED: Error = Error::InternalError(otError_OT_ERROR_NOT_IMPLEMENTED);
+
+/// Longest settings value this crate encrypts, OpenThread's largest is an Operational Dataset
+pub const SETTINGS_VALUE_MAX_SIZE: usize = 256;
+
+/// Bytes a record in the [SettingsStorage] adds to the value, the nonce and the tag
+pub const SETTINGS_RECORD_OVERHEAD: usize = NONCE_SIZE + TAG_SIZE;
+
+/// Longest record this crate writes to the [SettingsStorage]
+pub const SETTINGS_RECORD_MAX_SIZE: usize = SETTINGS_VALUE_MAX_SIZE + SETTINGS_RECORD_OVERHEAD;
+
+/// Random part of the AES-CTR counter block, the rest counts the blocks of a record
+const NONCE_SIZE: usize = 12;
+
+/// Truncated HMAC-SHA256
+const TAG_SIZE: usize = 16;
+
+const AES_BLOCK_SIZE: usize = 16;
+
+/// Room for the AES context of OpenThread's crypto backend, an `mbedtls_aes_context`
+const AES_CONTEXT_SIZE: usize = 280;
+
+/// Room for the HMAC-SHA256 context of OpenThread's crypto backend, an mbedTLS message digest
+const HMAC_CONTEXT_SIZE: usize = 64;
+
+/// Salt of [SettingsKey::derive], keeps the settings keys apart from other keys derived from
+/// the same secret
+const SETTINGS_KEY_SALT: &[u8] = b"esp-openthread settings";
+
+static SETTINGS: Mutex<RefCell<Option<EncryptedSettings>>> = Mutex::new(RefCell::new(None));
+
+/// Persistent storage for OpenThread's settings, e.g. a key-value store in flash
+///
+/// Only ever sees encrypted records of up to [SETTINGS_RECORD_MAX_SIZE] bytes, see
+/// [set_settings_storage]. A key can hold several records, their order doesn't matter.
+pub trait SettingsStorage {
+    /// Copy the record at `index` of `key` into `record`, returns the length of the stored
+    /// record or `None` if there is no such record
+    fn get(&mut self, key: u16, index: usize, record: &mut [u8]) -> Option<usize>;
+
+    /// Replace all records of `key` by `record`
+    fn set(&mut self, key: u16, record: &[u8]) -> Result<(), Error>;
+
+    /// Add `record` to the records of `key`
+    fn add(&mut self, key: u16, record: &[u8]) -> Result<(), Error>;
+
+    /// Remove the record at `index` of `key`, or all of them for `None`
+    ///
+    /// Returns `false` if there was nothing to remove.
+    fn delete(&mut self, key: u16, index: Option<usize>) -> bool;
+
+    /// Remove all records, e.g. on a factory reset
+    fn wipe(&mut self);
+}
+
+/// Keys encrypting and authenticating the settings records, see [set_settings_storage]
+pub struct SettingsKey {
+    aes: [u8; 16],
+    mac: [u8; 32],
+}
+
+impl SettingsKey {
+    /// Derive the keys from a device-unique `secret` with HKDF-SHA256
+    ///
+    /// The secret should never leave the device, e.g. the HMAC of a fixed message computed by
+    /// the HMAC peripheral with a key burnt into eFuse for `HMAC_UP` only:
+    ///
+    /// ```ignore
+    /// use esp_hal::hmac::{Hmac, HmacPurpose, KeyId};
+    /// use esp_openthread::SettingsKey;
+    ///
+    /// let mut hmac = Hmac::new(peripherals.HMAC);
+    /// let mut secret = [0u8; 32];
+    /// hmac.init();
+    /// nb::block!(hmac.configure(HmacPurpose::ToUser, KeyId::Key0)).unwrap();
+    /// nb::block!(hmac.update(b"esp-openthread settings")).unwrap();
+    /// nb::block!(hmac.finalize(&mut secret)).unwrap();
+    ///
+    /// let key = SettingsKey::derive(&secret).unwrap();
+    /// ```
+    pub fn derive(secret: &[u8]) -> Result<Self, Error> {
+        let input_key = otCryptoKey {
+            mKey: secret.as_ptr(),
+            mKeyLength: u16::try_from(secret.len()).map_err(|_| INVALID_ARGS)?,
+            mKeyRef: 0,
+        };
+
+        let mut output = [0u8; 48];
+        let result = hkdf(SETTINGS_KEY_SALT, &input_key, b"records", &mut output).map(|_| {
+            let mut key = Self {
+                aes: [0u8; 16],
+                mac: [0u8; 32],
+            };
+            key.aes.copy_from_slice(&output[..16]);
+            key.mac.copy_from_slice(&output[16..]);
+            key
+        });
+
+        // don't leave the keys on the stack
+        unsafe { core::ptr::write_volatile(&mut output, [0u8; 48]) };
+        result
+    }
+
+    /// XOR `data` with the AES-128-CTR key stream for `nonce`, encrypts and decrypts alike
+    fn apply_key_stream(&self, nonce: &[u8], data: &mut [u8]) -> Result<(), Error> {
+        let key = otCryptoKey {
+            mKey: self.aes.as_ptr(),
+            mKeyLength: self.aes.len() as u16,
+            mKeyRef: 0,
+        };
+
+        let mut storage = [0u32; AES_CONTEXT_SIZE / 4];
+        let mut context = otCryptoContext {
+            mContext: storage.as_mut_ptr() as *mut c_void,
+            mContextSize: AES_CONTEXT_SIZE as u16,
+        };
+
+        checked!(unsafe { otPlatCryptoAesInit(&mut context) })?;
+        let result =
+            checked!(unsafe { otPlatCryptoAesSetKey(&mut context, &key) }).and_then(|_| {
+                let mut counter = [0u8; AES_BLOCK_SIZE];
+                counter[..NONCE_SIZE].copy_from_slice(nonce);
+                let mut key_stream = [0u8; AES_BLOCK_SIZE];
+
+                for (block, chunk) in data.chunks_mut(AES_BLOCK_SIZE).enumerate() {
+                    counter[NONCE_SIZE..].copy_from_slice(&(block as u32).to_be_bytes());
+                    checked!(unsafe {
+                        otPlatCryptoAesEncrypt(
+                            &mut context,
+                            counter.as_ptr(),
+                            key_stream.as_mut_ptr(),
+                        )
+                    })?;
+                    for (byte, key) in chunk.iter_mut().zip(key_stream) {
+                        *byte ^= key;
+                    }
+                }
+                Ok(())
+            });
+        unsafe { otPlatCryptoAesFree(&mut context) };
+
+        // the context holds the expanded key
+        unsafe { core::ptr::write_volatile(&mut storage, [0u32; AES_CONTEXT_SIZE / 4]) };
+        result
+    }
+
+    /// HMAC-SHA256 over the settings key, the nonce and the ciphertext, truncated to [TAG_SIZE]
+    fn tag(&self, key: u16, nonce: &[u8], ciphertext: &[u8]) -> Result<[u8; TAG_SIZE], Error> {
+        let mac_key = otCryptoKey {
+            mKey: self.mac.as_ptr(),
+            mKeyLength: self.mac.len() as u16,
+            mKeyRef: 0,
+        };
+
+        let mut storage = [0u64; HMAC_CONTEXT_SIZE / 8];
+        let mut context = otCryptoContext {
+            mContext: storage.as_mut_ptr() as *mut c_void,
+            mContextSize: HMAC_CONTEXT_SIZE as u16,
+        };
+        let mut hash = [0u8; OT_CRYPTO_SHA256_HASH_SIZE as usize];
+
+        checked!(unsafe { otPlatCryptoHmacSha256Init(&mut context) })?;
+        let result = checked!(unsafe { otPlatCryptoHmacSha256Start(&mut context, &mac_key) })
+            .and_then(|_| {
+                for part in [&key.to_be_bytes()[..], nonce, ciphertext] {
+                    checked!(unsafe {
+                        otPlatCryptoHmacSha256Update(
+                            &mut context,
+                            part.as_ptr() as *const c_void,
+                            part.len() as u16,
+                        )
+                    })?;
+                }
+                checked!(unsafe {
+                    otPlatCryptoHmacSha256Finish(&mut context, hash.as_mut_ptr(), hash.len() as _)
+                })
+            });
+        unsafe { otPlatCryptoHmacSha256Deinit(&mut context) };
+
+        result.map(|_| {
+            let mut tag = [0u8; TAG_SIZE];
+            tag.copy_from_slice(&hash[..TAG_SIZE]);
+            tag
+        })
+    }
+}
+
+impl Drop for SettingsKey {
+    fn drop(&mut self) {
+        unsafe {
+            core::ptr::write_volatile(&mut self.aes, [0u8; 16]);
+            core::ptr::write_volatile(&mut self.mac, [0u8; 32]);
+        }
+    }
+}
+
+struct EncryptedSettings {
+    storage: &'static mut (dyn SettingsStorage + Send),
+    key: SettingsKey,
+}
+
+impl EncryptedSettings {
+    /// Encrypt `value` of the settings `key` into `record`, returns the length of the record
+    fn seal(&self, key: u16, value: &[u8], record: &mut [u8]) -> Result<usize, Error> {
+        if value.len() > SETTINGS_VALUE_MAX_SIZE {
+            return Err(NO_BUFS);
+        }
+
+        let (nonce, rest) = record.split_at_mut(NONCE_SIZE);
+        let (ciphertext, rest) = rest.split_at_mut(value.len());
+
+        checked!(unsafe { otPlatCryptoRandomGet(nonce.as_mut_ptr(), NONCE_SIZE as u16) })?;
+        ciphertext.copy_from_slice(value);
+        self.key.apply_key_stream(nonce, ciphertext)?;
+        rest[..TAG_SIZE].copy_from_slice(&self.key.tag(key, nonce, ciphertext)?);
+
+        Ok(value.len() + SETTINGS_RECORD_OVERHEAD)
+    }
+
+    /// Authenticate and decrypt `record` of the settings `key` in place, returns the value or
+    /// `None` if the record doesn't authenticate
+    fn open<'r>(&self, key: u16, record: &'r mut [u8]) -> Option<&'r [u8]> {
+        let value_length = record.len().checked_sub(SETTINGS_RECORD_OVERHEAD)?;
+        let (nonce, rest) = record.split_at_mut(NONCE_SIZE);
+        let (ciphertext, tag) = rest.split_at_mut(value_length);
+
+        // compare all bytes, how many match must not show in the timing
+        let expected = self.key.tag(key, nonce, ciphertext).ok()?;
+        let difference = tag
+            .iter()
+            .zip(expected)
+            .fold(0, |difference, (byte, expected)| {
+                difference | (byte ^ expected)
+            });
+        if difference != 0 {
+            return None;
+        }
+
+        self.key.apply_key_stream(nonce, ciphertext).ok()?;
+        Some(ciphertext)
+    }
+}
+
+/// Keep OpenThread's settings (datasets, network key, child table) encrypted in `storage`
+///
+/// Replaces OpenThread's settings in RAM, so it has to be called before
+/// [OpenThread::new](crate::OpenThread::new), which reads them back. Each record is encrypted
+/// with AES-128-CTR under a random nonce and carries an HMAC-SHA256 tag over its settings key,
+/// nonce and ciphertext. A flash dump then reveals neither the network key nor the datasets,
+/// records that don't authenticate (e.g. written with another key) are treated as missing.
+/// Without a storage OpenThread can't keep any settings.
+pub fn set_settings_storage(storage: &'static mut (dyn SettingsStorage + Send), key: SettingsKey) {
+    critical_section::with(|cs| {
+        SETTINGS
+            .borrow_ref_mut(cs)
+            .replace(EncryptedSettings { storage, key });
+    });
+}
+
+/// Run `f` on the settings outside of the critical section, flash writes take a while
+fn with_settings<R>(f: impl FnOnce(&mut EncryptedSettings) -> R) -> Option<R> {
+    let mut settings = critical_section::with(|cs| SETTINGS.borrow_ref_mut(cs).take())?;
+    let result = f(&mut settings);
+    critical_section::with(|cs| SETTINGS.borrow_ref_mut(cs).replace(settings));
+    Some(result)
+}
+
+/// Encrypt a value and pass the record to `write`, i.e. [SettingsStorage::set] or
+/// [SettingsStorage::add]
+unsafe fn write_record(
+    key: u16,
+    value: *const u8,
+    value_length: u16,
+    write: impl FnOnce(&mut (dyn SettingsStorage + Send), &[u8]) -> Result<(), Error>,
+) -> otError {
+    let value = if value_length == 0 {
+        &[][..]
+    } else {
+        core::slice::from_raw_parts(value, value_length as usize)
+    };
+
+    let mut record = [0u8; SETTINGS_RECORD_MAX_SIZE];
+    let result = with_settings(|settings| {
+        let length = settings.seal(key, value, &mut record)?;
+        write(&mut *settings.storage, &record[..length])
+    })
+    .unwrap_or(Err(NOT_IMPLEMENTED));
+
+    match result {
+        Ok(()) => otError_OT_ERROR_NONE,
+        Err(Error::InternalError(error)) => error,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatSettingsInit(
+    _instance: *mut otInstance,
+    _sensitive_keys: *const u16,
+    _sensitive_keys_length: u16,
+) {
+    // every record is encrypted, not only the sensitive ones
+    if critical_section::with(|cs| SETTINGS.borrow_ref(cs).is_none()) {
+        log::error!("No settings storage, call set_settings_storage before OpenThread::new");
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatSettingsDeinit(_instance: *mut otInstance) {}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatSettingsGet(
+    _instance: *mut otInstance,
+    key: u16,
+    index: c_int,
+    value: *mut u8,
+    value_length: *mut u16,
+) -> otError {
+    let Ok(index) = usize::try_from(index) else {
+        return otError_OT_ERROR_NOT_FOUND;
+    };
+
+    let mut record = [0u8; SETTINGS_RECORD_MAX_SIZE];
+    let found = with_settings(|settings| {
+        let length = settings.storage.get(key, index, &mut record)?;
+        let Some(plaintext) = record
+            .get_mut(..length)
+            .and_then(|record| settings.open(key, record))
+        else {
+            log::warn!("Ignoring settings record {key}/{index}, it doesn't authenticate");
+            return None;
+        };
+
+        // a null value only asks for the length
+        if !value_length.is_null() {
+            if !value.is_null() {
+                let max = usize::from(*value_length);
+                core::ptr::copy_nonoverlapping(plaintext.as_ptr(), value, plaintext.len().min(max));
+            }
+            *value_length = plaintext.len() as u16;
+        }
+        Some(())
+    })
+    .flatten();
+
+    // the record holds the decrypted value
+    core::ptr::write_volatile(&mut record, [0u8; SETTINGS_RECORD_MAX_SIZE]);
+
+    match found {
+        Some(()) => otError_OT_ERROR_NONE,
+        None => otError_OT_ERROR_NOT_FOUND,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatSettingsSet(
+    _instance: *mut otInstance,
+    key: u16,
+    value: *const u8,
+    value_length: u16,
+) -> otError {
+    write_record(key, value, value_length, |storage, record| {
+        storage.set(key, record)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn otPlatSettingsAdd(
+    _instance: *mut otInstance,
+    key: u16,
+    value: *const u8,
+    value_length: u16,
+) -> otError {
+    write_record(key, value, value_length, |storage, record| {
+        storage.add(key, record)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatSettingsDelete(
+    _instance: *mut otInstance,
+    key: u16,
+    index: c_int,
+) -> otError {
+    // -1 removes all records of the key
+    let index = usize::try_from(index).ok();
+
+    match with_settings(|settings| settings.storage.delete(key, index)) {
+        Some(true) => otError_OT_ERROR_NONE,
+        _ => otError_OT_ERROR_NOT_FOUND,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn otPlatSettingsWipe(_instance: *mut otInstance) {
+    with_settings(|settings| settings.storage.wipe());
+}
@@ -0,0 +1,677 @@
+use core::{cell::RefCell, ffi::CStr, marker::PhantomData};
+
+use critical_section::Mutex;
+use esp_openthread_sys::{
+    bindings::{
+        otDnsTxtEntry, otError, otError_OT_ERROR_ALREADY, otError_OT_ERROR_INVALID_ARGS,
+        otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NONE, otError_OT_ERROR_NOT_FOUND,
+        otError_OT_ERROR_NO_BUFS, otIp6Address, otIp6Address__bindgen_ty_1, otSockAddr,
+        otSrpClientAddService, otSrpClientAutoStartCallback, otSrpClientClearHostAndServices,
+        otSrpClientClearService, otSrpClientDisableAutoStartMode, otSrpClientEnableAutoHostAddress,
+        otSrpClientEnableAutoStartMode, otSrpClientGetHostInfo, otSrpClientGetKeyLeaseInterval,
+        otSrpClientGetLeaseInterval, otSrpClientGetServerAddress, otSrpClientHostInfo,
+        otSrpClientIsAutoStartModeEnabled, otSrpClientIsRunning, otSrpClientItemState,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_ADDING,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REFRESHING,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REGISTERED,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVED,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVING,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_ADD,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REFRESH,
+        otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REMOVE, otSrpClientRemoveHostAndServices,
+        otSrpClientRemoveService, otSrpClientService, otSrpClientSetCallback,
+        otSrpClientSetHostAddresses, otSrpClientSetHostName, otSrpClientSetKeyLeaseInterval,
+        otSrpClientSetLeaseInterval, otSrpClientStart, otSrpClientStop,
+    },
+    c_types::{c_char, c_void},
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{
+    checked,
+    ip6::{ipv6_addr_from_ot_ip6_address, ot_ip6_address_from_ipv6_addr},
+    Error, OpenThread,
+};
+
+const ALREADY: Error = Error::InternalError(otError_OT_ERROR_ALREADY);
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+const NOT_FOUND: Error = Error::InternalError(otError_OT_ERROR_NOT_FOUND);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Maximum number of services registered via [OpenThread::srp_client_add_service] at a time
+pub const SRP_CLIENT_MAX_SERVICES: usize = 4;
+
+/// Maximum number of addresses set via [OpenThread::srp_client_set_host_addresses]
+pub const SRP_CLIENT_MAX_HOST_ADDRESSES: usize = 4;
+
+/// Room for a host name, service name or instance name, the terminating NUL included
+pub const SRP_CLIENT_NAME_MAX_SIZE: usize = 64;
+
+/// Maximum number of subtypes of a service
+pub const SRP_CLIENT_MAX_SUBTYPES: usize = 4;
+
+/// Room for all subtype labels of a service, each with its terminating NUL
+pub const SRP_CLIENT_SUBTYPES_MAX_SIZE: usize = 64;
+
+/// Maximum size of the encoded TXT data of a service
+pub const SRP_CLIENT_TXT_DATA_MAX_SIZE: usize = 128;
+
+pub(crate) static SRP_CLIENT_AUTO_START_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(Option<(Ipv6Addr, u16)>) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+pub(crate) static SRP_CLIENT_CALLBACK: Mutex<
+    RefCell<Option<&'static mut (dyn FnMut(SrpClientEvent) + Send)>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Host name, addresses and services handed to OpenThread, which keeps pointers into them
+pub(crate) static SRP_CLIENT: Mutex<RefCell<SrpClient>> =
+    Mutex::new(RefCell::new(SrpClient::DEFAULT));
+
+/// Registration state of the host or a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrpClientItemState {
+    /// To be registered
+    ToAdd,
+    /// Being registered
+    Adding,
+    /// To be registered again to renew the lease
+    ToRefresh,
+    /// Being registered again to renew the lease
+    Refreshing,
+    /// To be removed
+    ToRemove,
+    /// Being removed
+    Removing,
+    /// Registered with the server
+    Registered,
+    /// Removed from the server
+    Removed,
+}
+
+impl SrpClientItemState {
+    #[allow(non_upper_case_globals)]
+    fn from_raw(value: otSrpClientItemState) -> Option<Self> {
+        match value {
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_ADD => Some(Self::ToAdd),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_ADDING => Some(Self::Adding),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REFRESH => Some(Self::ToRefresh),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REFRESHING => Some(Self::Refreshing),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_TO_REMOVE => Some(Self::ToRemove),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVING => Some(Self::Removing),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REGISTERED => Some(Self::Registered),
+            otSrpClientItemState_OT_SRP_CLIENT_ITEM_STATE_REMOVED => Some(Self::Removed),
+            _ => None,
+        }
+    }
+}
+
+/// A service to register, see [OpenThread::srp_client_add_service]
+#[derive(Debug, Clone, Copy)]
+pub struct SrpClientService<'a> {
+    /// Service labels, e.g. `_matter._tcp`
+    pub service_name: &'a str,
+    /// Instance label, e.g. `0123456789ABCDEF-0000000000000001`
+    pub instance_name: &'a str,
+    /// Subtype labels, e.g. `_I0123456789ABCDEF`, at most [SRP_CLIENT_MAX_SUBTYPES]
+    pub subtypes: &'a [&'a str],
+    /// Encoded TXT data, e.g. [crate::TxtData::as_bytes]
+    pub txt_data: &'a [u8],
+    pub port: u16,
+    pub priority: u16,
+    pub weight: u16,
+}
+
+/// An update reported by the callback set via [OpenThread::set_srp_client_callback]
+#[derive(Debug, Clone, Copy)]
+pub struct SrpClientEvent<'a> {
+    /// Outcome of the update, e.g. `OT_ERROR_DUPLICATED` if the host name is taken or
+    /// `OT_ERROR_RESPONSE_TIMEOUT` if the server didn't answer
+    pub result: Result<(), Error>,
+    pub host_state: Option<SrpClientItemState>,
+    /// The services not removed yet
+    pub services: SrpClientServices<'a>,
+}
+
+/// The services of an [SrpClientEvent]
+#[derive(Debug, Clone, Copy)]
+pub struct SrpClientServices<'a>(*const otSrpClientService, PhantomData<&'a ()>);
+
+impl<'a> SrpClientServices<'a> {
+    /// Iterate over the service name, instance name and state of each service
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a str, Option<SrpClientItemState>)> {
+        let mut service = self.0;
+        core::iter::from_fn(move || {
+            if service.is_null() {
+                return None;
+            }
+
+            let current = unsafe { &*service };
+            service = current.mNext;
+            Some((
+                c_str(current.mName),
+                c_str(current.mInstanceName),
+                SrpClientItemState::from_raw(current.mState),
+            ))
+        })
+    }
+}
+
+struct ServiceSlot {
+    service: otSrpClientService,
+    name: [u8; SRP_CLIENT_NAME_MAX_SIZE],
+    instance_name: [u8; SRP_CLIENT_NAME_MAX_SIZE],
+    subtypes: [u8; SRP_CLIENT_SUBTYPES_MAX_SIZE],
+    /// NULL terminated
+    subtype_labels: [*const c_char; SRP_CLIENT_MAX_SUBTYPES + 1],
+    txt_entry: otDnsTxtEntry,
+    txt_data: [u8; SRP_CLIENT_TXT_DATA_MAX_SIZE],
+    in_use: bool,
+}
+
+impl ServiceSlot {
+    const DEFAULT: Self = Self {
+        service: otSrpClientService {
+            mName: core::ptr::null(),
+            mInstanceName: core::ptr::null(),
+            mSubTypeLabels: core::ptr::null(),
+            mTxtEntries: core::ptr::null(),
+            mPort: 0,
+            mPriority: 0,
+            mWeight: 0,
+            mNumTxtEntries: 0,
+            mState: 0,
+            mData: 0,
+            mNext: core::ptr::null_mut(),
+        },
+        name: [0; SRP_CLIENT_NAME_MAX_SIZE],
+        instance_name: [0; SRP_CLIENT_NAME_MAX_SIZE],
+        subtypes: [0; SRP_CLIENT_SUBTYPES_MAX_SIZE],
+        subtype_labels: [core::ptr::null(); SRP_CLIENT_MAX_SUBTYPES + 1],
+        txt_entry: otDnsTxtEntry {
+            mKey: core::ptr::null(),
+            mValue: core::ptr::null(),
+            mValueLength: 0,
+        },
+        txt_data: [0; SRP_CLIENT_TXT_DATA_MAX_SIZE],
+        in_use: false,
+    };
+
+    fn matches(&self, service_name: &str, instance_name: &str) -> bool {
+        self.in_use
+            && name_eq(&self.name, service_name)
+            && name_eq(&self.instance_name, instance_name)
+    }
+}
+
+pub(crate) struct SrpClient {
+    host_name: [u8; SRP_CLIENT_NAME_MAX_SIZE],
+    host_addresses: [otIp6Address; SRP_CLIENT_MAX_HOST_ADDRESSES],
+    services: [ServiceSlot; SRP_CLIENT_MAX_SERVICES],
+}
+
+// the raw pointers only point into the `SRP_CLIENT` static itself
+unsafe impl Send for SrpClient {}
+
+impl SrpClient {
+    pub(crate) const DEFAULT: Self = Self {
+        host_name: [0; SRP_CLIENT_NAME_MAX_SIZE],
+        host_addresses: [otIp6Address {
+            mFields: otIp6Address__bindgen_ty_1 { m8: [0; 16] },
+        }; SRP_CLIENT_MAX_HOST_ADDRESSES],
+        services: [ServiceSlot::DEFAULT; SRP_CLIENT_MAX_SERVICES],
+    };
+
+    fn find(&mut self, service_name: &str, instance_name: &str) -> Option<&mut ServiceSlot> {
+        self.services
+            .iter_mut()
+            .find(|slot| slot.matches(service_name, instance_name))
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Start the SRP client with the given server
+    pub fn srp_client_start(&mut self, server: Ipv6Addr, port: u16) -> Result<(), Error> {
+        let server = otSockAddr {
+            mAddress: ot_ip6_address_from_ipv6_addr(server),
+            mPort: port,
+        };
+        checked!(unsafe { otSrpClientStart(self.instance, &server) })
+    }
+
+    /// Stop the SRP client, also disables the auto-start mode
+    ///
+    /// Host and services are kept and registered again once the client is restarted.
+    pub fn srp_client_stop(&mut self) {
+        unsafe { otSrpClientStop(self.instance) }
+    }
+
+    /// Returns `true` if the SRP client is running
+    pub fn srp_client_is_running(&self) -> bool {
+        unsafe { otSrpClientIsRunning(self.instance) }
+    }
+
+    /// Get the address and port of the SRP server in use, `None` if the client isn't running
+    pub fn srp_client_get_server_address(&self) -> Option<(Ipv6Addr, u16)> {
+        server_from_raw(unsafe { otSrpClientGetServerAddress(self.instance) })
+    }
+
+    /// Discover SRP servers in the Network Data and start or stop the client automatically
+    ///
+    /// The callback gets the selected server when the client is started and `None` when it's
+    /// stopped.
+    pub fn srp_client_enable_auto_start(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(Option<(Ipv6Addr, u16)>) + Send)>,
+    ) {
+        let has_callback = callback.is_some();
+        critical_section::with(|cs| {
+            let mut auto_start_callback = SRP_CLIENT_AUTO_START_CALLBACK.borrow_ref_mut(cs);
+            *auto_start_callback = unsafe { core::mem::transmute(callback) };
+        });
+
+        unsafe {
+            otSrpClientEnableAutoStartMode(
+                self.instance,
+                if has_callback {
+                    Some(srp_client_auto_start_callback)
+                } else {
+                    None
+                },
+                core::ptr::null_mut(),
+            )
+        }
+    }
+
+    /// Stop following the SRP servers in the Network Data, a running client keeps running
+    pub fn srp_client_disable_auto_start(&mut self) {
+        unsafe { otSrpClientDisableAutoStartMode(self.instance) }
+    }
+
+    /// Returns `true` if the auto-start mode is enabled
+    pub fn srp_client_is_auto_start_enabled(&self) -> bool {
+        unsafe { otSrpClientIsAutoStartModeEnabled(self.instance) }
+    }
+
+    /// Unregister the host and all its services from the server
+    ///
+    /// With `remove_key_lease` the host name isn't reserved for this device anymore, e.g. before
+    /// registering with a new key. With `send_unreg_to_server` the server is updated even if
+    /// nothing got registered yet.
+    pub fn srp_client_remove_host_and_services(
+        &mut self,
+        remove_key_lease: bool,
+        send_unreg_to_server: bool,
+    ) -> Result<(), Error> {
+        checked!(unsafe {
+            otSrpClientRemoveHostAndServices(self.instance, remove_key_lease, send_unreg_to_server)
+        })
+    }
+
+    /// Forget the host and all services right away, without updating the server
+    pub fn srp_client_clear_host_and_services(&mut self) {
+        unsafe { otSrpClientClearHostAndServices(self.instance) }
+
+        critical_section::with(|cs| {
+            let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+            for slot in srp_client.services.iter_mut() {
+                slot.in_use = false;
+            }
+        });
+    }
+
+    /// Set the host name label, e.g. `esp-0123456789ABCDEF`
+    ///
+    /// Fails with `OT_ERROR_INVALID_STATE` once the host is registered, remove or clear it
+    /// first.
+    pub fn srp_client_set_host_name(&mut self, host_name: &str) -> Result<(), Error> {
+        if host_name.is_empty() {
+            return Err(INVALID_ARGS);
+        }
+        if !matches!(
+            self.srp_client_get_host_state(),
+            Some(SrpClientItemState::ToAdd | SrpClientItemState::Removed)
+        ) {
+            return Err(INVALID_STATE);
+        }
+
+        let host_name = critical_section::with(|cs| {
+            let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+            copy_name(&mut srp_client.host_name, host_name)?;
+            Ok::<_, Error>(srp_client.host_name.as_ptr() as *const c_char)
+        })?;
+        checked!(unsafe { otSrpClientSetHostName(self.instance, host_name) })
+    }
+
+    /// Register all unicast addresses of the Thread interface except link-local and mesh-local
+    /// ones, following them as they change
+    ///
+    /// Falls back to the Mesh-Local EID if there is no other address.
+    pub fn srp_client_enable_auto_host_address(&mut self) -> Result<(), Error> {
+        checked!(unsafe { otSrpClientEnableAutoHostAddress(self.instance) })
+    }
+
+    /// Register the given host addresses, at most [SRP_CLIENT_MAX_HOST_ADDRESSES], instead of
+    /// following the Thread interface
+    ///
+    /// Fails with `OT_ERROR_INVALID_STATE` while the host is being removed.
+    pub fn srp_client_set_host_addresses(&mut self, addresses: &[Ipv6Addr]) -> Result<(), Error> {
+        if addresses.is_empty() {
+            return Err(INVALID_ARGS);
+        }
+        if addresses.len() > SRP_CLIENT_MAX_HOST_ADDRESSES {
+            return Err(NO_BUFS);
+        }
+        if matches!(
+            self.srp_client_get_host_state(),
+            Some(SrpClientItemState::ToRemove | SrpClientItemState::Removing)
+        ) {
+            return Err(INVALID_STATE);
+        }
+
+        let raw_addresses = critical_section::with(|cs| {
+            let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+            for (raw, address) in srp_client.host_addresses.iter_mut().zip(addresses) {
+                *raw = ot_ip6_address_from_ipv6_addr(*address);
+            }
+            srp_client.host_addresses.as_ptr()
+        });
+        checked!(unsafe {
+            otSrpClientSetHostAddresses(self.instance, raw_addresses, addresses.len() as u8)
+        })
+    }
+
+    /// Get the registration state of the host
+    pub fn srp_client_get_host_state(&self) -> Option<SrpClientItemState> {
+        let host_info: &otSrpClientHostInfo = unsafe { &*otSrpClientGetHostInfo(self.instance) };
+        SrpClientItemState::from_raw(host_info.mState)
+    }
+
+    /// Register a service, at most [SRP_CLIENT_MAX_SERVICES] at a time
+    ///
+    /// The service is copied, the registration is reported via the callback set with
+    /// [OpenThread::set_srp_client_callback]. Fails with `OT_ERROR_ALREADY` if a service with
+    /// the same service and instance name is registered.
+    pub fn srp_client_add_service(&mut self, service: &SrpClientService) -> Result<(), Error> {
+        if service.service_name.is_empty()
+            || service.instance_name.is_empty()
+            || service.subtypes.len() > SRP_CLIENT_MAX_SUBTYPES
+            || service.txt_data.len() > SRP_CLIENT_TXT_DATA_MAX_SIZE
+        {
+            return Err(INVALID_ARGS);
+        }
+
+        let raw_service = critical_section::with(|cs| {
+            let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+            if srp_client
+                .find(service.service_name, service.instance_name)
+                .is_some()
+            {
+                return Err(ALREADY);
+            }
+            let slot = srp_client
+                .services
+                .iter_mut()
+                .find(|slot| !slot.in_use)
+                .ok_or(NO_BUFS)?;
+            fill_slot(slot, service)?;
+            slot.in_use = true;
+            Ok(&mut slot.service as *mut otSrpClientService)
+        })?;
+
+        unsafe {
+            otSrpClientSetCallback(
+                self.instance,
+                Some(srp_client_callback),
+                core::ptr::null_mut(),
+            )
+        };
+        let res = unsafe { otSrpClientAddService(self.instance, raw_service) };
+        if res != otError_OT_ERROR_NONE {
+            release_slot(raw_service);
+        }
+        checked!(res)
+    }
+
+    /// Unregister a service from the server
+    ///
+    /// The service is forgotten once the callback set with
+    /// [OpenThread::set_srp_client_callback] reports the update.
+    pub fn srp_client_remove_service(
+        &mut self,
+        service_name: &str,
+        instance_name: &str,
+    ) -> Result<(), Error> {
+        let raw_service = find_service(service_name, instance_name)?;
+        checked!(unsafe { otSrpClientRemoveService(self.instance, raw_service) })
+    }
+
+    /// Forget a service right away, without updating the server
+    pub fn srp_client_clear_service(
+        &mut self,
+        service_name: &str,
+        instance_name: &str,
+    ) -> Result<(), Error> {
+        let raw_service = find_service(service_name, instance_name)?;
+        checked!(unsafe { otSrpClientClearService(self.instance, raw_service) })?;
+        release_slot(raw_service);
+        Ok(())
+    }
+
+    /// Get the registration state of a service, `None` if it isn't added
+    pub fn srp_client_get_service_state(
+        &self,
+        service_name: &str,
+        instance_name: &str,
+    ) -> Option<SrpClientItemState> {
+        critical_section::with(|cs| {
+            let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+            let slot = srp_client.find(service_name, instance_name)?;
+            SrpClientItemState::from_raw(slot.service.mState)
+        })
+    }
+
+    /// Set the lease (in seconds) requested for the host and services, zero for OpenThread's
+    /// default of two hours
+    ///
+    /// Only affects registrations from now on.
+    pub fn srp_client_set_lease_interval(&mut self, interval: u32) {
+        unsafe { otSrpClientSetLeaseInterval(self.instance, interval) }
+    }
+
+    /// Get the lease (in seconds) requested for the host and services
+    pub fn srp_client_get_lease_interval(&self) -> u32 {
+        unsafe { otSrpClientGetLeaseInterval(self.instance) }
+    }
+
+    /// Set the key lease (in seconds), how long the server reserves the names for this device,
+    /// zero for OpenThread's default of 14 days
+    ///
+    /// Only affects registrations from now on.
+    pub fn srp_client_set_key_lease_interval(&mut self, interval: u32) {
+        unsafe { otSrpClientSetKeyLeaseInterval(self.instance, interval) }
+    }
+
+    /// Get the key lease (in seconds)
+    pub fn srp_client_get_key_lease_interval(&self) -> u32 {
+        unsafe { otSrpClientGetKeyLeaseInterval(self.instance) }
+    }
+
+    /// Set the callback reporting the outcome of each update sent to the server
+    pub fn set_srp_client_callback(
+        &mut self,
+        callback: Option<&'a mut (dyn FnMut(SrpClientEvent) + Send)>,
+    ) {
+        critical_section::with(|cs| {
+            let mut srp_client_callback = SRP_CLIENT_CALLBACK.borrow_ref_mut(cs);
+            *srp_client_callback = unsafe { core::mem::transmute(callback) };
+        });
+
+        // always set, removed services are released by it
+        unsafe {
+            otSrpClientSetCallback(
+                self.instance,
+                Some(srp_client_callback),
+                core::ptr::null_mut(),
+            )
+        };
+    }
+}
+
+fn fill_slot(slot: &mut ServiceSlot, service: &SrpClientService) -> Result<(), Error> {
+    copy_name(&mut slot.name, service.service_name)?;
+    copy_name(&mut slot.instance_name, service.instance_name)?;
+
+    let mut offset = 0;
+    slot.subtype_labels = [core::ptr::null(); SRP_CLIENT_MAX_SUBTYPES + 1];
+    for (label, subtype) in slot.subtype_labels.iter_mut().zip(service.subtypes) {
+        copy_name(&mut slot.subtypes[offset..], subtype)?;
+        *label = slot.subtypes[offset..].as_ptr() as *const c_char;
+        offset += subtype.len() + 1;
+    }
+
+    slot.txt_data[..service.txt_data.len()].copy_from_slice(service.txt_data);
+    // without a key the value is taken as encoded TXT data
+    slot.txt_entry = otDnsTxtEntry {
+        mKey: core::ptr::null(),
+        mValue: slot.txt_data.as_ptr(),
+        mValueLength: service.txt_data.len() as u16,
+    };
+
+    slot.service = otSrpClientService {
+        mName: slot.name.as_ptr() as *const c_char,
+        mInstanceName: slot.instance_name.as_ptr() as *const c_char,
+        mSubTypeLabels: slot.subtype_labels.as_ptr(),
+        mTxtEntries: if service.txt_data.is_empty() {
+            core::ptr::null()
+        } else {
+            &slot.txt_entry as *const otDnsTxtEntry
+        },
+        mPort: service.port,
+        mPriority: service.priority,
+        mWeight: service.weight,
+        mNumTxtEntries: if service.txt_data.is_empty() { 0 } else { 1 },
+        ..ServiceSlot::DEFAULT.service
+    };
+
+    Ok(())
+}
+
+fn find_service(service_name: &str, instance_name: &str) -> Result<*mut otSrpClientService, Error> {
+    critical_section::with(|cs| {
+        let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+        let slot = srp_client
+            .find(service_name, instance_name)
+            .ok_or(NOT_FOUND)?;
+        Ok(&mut slot.service as *mut otSrpClientService)
+    })
+}
+
+/// Mark the slot holding `service` as free, OpenThread is done with it
+fn release_slot(service: *const otSrpClientService) {
+    critical_section::with(|cs| {
+        let mut srp_client = SRP_CLIENT.borrow_ref_mut(cs);
+        if let Some(slot) = srp_client
+            .services
+            .iter_mut()
+            .find(|slot| core::ptr::eq(&slot.service, service))
+        {
+            slot.in_use = false;
+        }
+    });
+}
+
+/// Copy `name` NUL terminated into `buffer`
+fn copy_name(buffer: &mut [u8], name: &str) -> Result<(), Error> {
+    if name.is_empty() || name.len() >= buffer.len() || name.as_bytes().contains(&0) {
+        return Err(INVALID_ARGS);
+    }
+    buffer[..name.len()].copy_from_slice(name.as_bytes());
+    buffer[name.len()] = 0;
+    Ok(())
+}
+
+fn name_eq(buffer: &[u8], name: &str) -> bool {
+    buffer.len() > name.len() && &buffer[..name.len()] == name.as_bytes() && buffer[name.len()] == 0
+}
+
+fn c_str<'a>(name: *const c_char) -> &'a str {
+    if name.is_null() {
+        return "";
+    }
+    unsafe { CStr::from_ptr(name) }.to_str().unwrap_or("")
+}
+
+fn server_from_raw(server: *const otSockAddr) -> Option<(Ipv6Addr, u16)> {
+    if server.is_null() {
+        return None;
+    }
+
+    let server = unsafe { &*server };
+    let address = ipv6_addr_from_ot_ip6_address(&server.mAddress);
+    if address.is_unspecified() && server.mPort == 0 {
+        None
+    } else {
+        Some((address, server.mPort))
+    }
+}
+
+/// The auto-start callback to hand to OpenThread, `None` if no callback is set
+pub(crate) fn auto_start_callback() -> otSrpClientAutoStartCallback {
+    let has_callback =
+        critical_section::with(|cs| SRP_CLIENT_AUTO_START_CALLBACK.borrow_ref(cs).is_some());
+    if has_callback {
+        Some(srp_client_auto_start_callback)
+    } else {
+        None
+    }
+}
+
+unsafe extern "C" fn srp_client_auto_start_callback(
+    server: *const otSockAddr,
+    _context: *mut c_void,
+) {
+    log::debug!("srp_client_auto_start_callback {}", !server.is_null());
+
+    let server = server_from_raw(server);
+    critical_section::with(|cs| {
+        let mut callback = SRP_CLIENT_AUTO_START_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(server);
+        }
+    });
+}
+
+unsafe extern "C" fn srp_client_callback(
+    error: otError,
+    host_info: *const otSrpClientHostInfo,
+    services: *const otSrpClientService,
+    removed_services: *const otSrpClientService,
+    _context: *mut c_void,
+) {
+    log::debug!("srp_client_callback {}", error);
+
+    let mut removed = removed_services;
+    while !removed.is_null() {
+        let next = (*removed).mNext;
+        release_slot(removed);
+        removed = next;
+    }
+
+    let event = SrpClientEvent {
+        result: checked!(error),
+        host_state: if host_info.is_null() {
+            None
+        } else {
+            SrpClientItemState::from_raw((*host_info).mState)
+        },
+        services: SrpClientServices(services, PhantomData),
+    };
+    critical_section::with(|cs| {
+        let mut callback = SRP_CLIENT_CALLBACK.borrow_ref_mut(cs);
+        if let Some(callback) = callback.as_mut() {
+            callback(event);
+        }
+    });
+}
@@ -0,0 +1,754 @@
+use core::{
+    marker::PhantomPinned,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use esp_openthread_sys::{
+    bindings::{
+        otError_OT_ERROR_INVALID_STATE, otError_OT_ERROR_NO_BUFS, otLinkedBuffer, otSockAddr,
+        otTcpAbort, otTcpCommitReceive, otTcpConnect, otTcpDisconnectedReason, otTcpEndpoint,
+        otTcpEndpointDeinitialize, otTcpEndpointGetContext, otTcpEndpointInitialize,
+        otTcpEndpointInitializeArgs, otTcpGetPeerAddress, otTcpIncomingConnectionAction,
+        otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT,
+        otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_DEFER, otTcpListen,
+        otTcpListener, otTcpListenerDeinitialize, otTcpListenerGetContext, otTcpListenerInitialize,
+        otTcpListenerInitializeArgs, otTcpReceiveByReference, otTcpSendByReference,
+        otTcpSendEndOfStream, otTcpStopListening, size_t, OT_TCP_CONNECT_NO_FAST_OPEN,
+    },
+    c_types::c_void,
+};
+use no_std_net::Ipv6Addr;
+
+use crate::{
+    checked,
+    ip6::{ipv6_addr_from_ot_ip6_address, ot_ip6_address_from_ipv6_addr},
+    Error, OpenThread,
+};
+
+const INVALID_STATE: Error = Error::InternalError(otError_OT_ERROR_INVALID_STATE);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// A TCP/IPv6 connection, accepted via a [TcpListener] or opened via [TcpSocket::connect]
+///
+/// `BUFFER_SIZE` bytes are used for each of the receive and the send buffer. OpenThread
+/// recommends 2598 bytes for connections spanning up to three hops.
+///
+/// It's pinned since OpenThread keeps pointers into it while it's in use.
+pub struct TcpSocket<'s, 'n: 's, const BUFFER_SIZE: usize> {
+    ot_endpoint: otTcpEndpoint,
+    ot: &'s OpenThread<'n>,
+    state: TcpSocketState,
+    _pinned: PhantomPinned,
+    receive_buffer: [u8; BUFFER_SIZE],
+    send_buffer: [u8; BUFFER_SIZE],
+}
+
+/// State of a [TcpSocket], the endpoint context
+struct TcpSocketState {
+    initialized: bool,
+    connecting: bool,
+    connected: bool,
+    end_of_stream: bool,
+    /// Bytes in the receive buffer
+    available: usize,
+    send_in_flight: bool,
+    send_link: otLinkedBuffer,
+    /// Woken on any change of the connection, see [TcpSocket::poll_receive]
+    waker: Option<Waker>,
+    /// Listener this socket is queued at to accept a connection
+    listener: *mut TcpListenerState<'static>,
+}
+
+/// Listens for incoming TCP connections and hands them to the sockets passed to
+/// [TcpListener::accept]
+///
+/// Up to `BACKLOG` sockets can wait for a connection at the same time. Connection attempts
+/// arriving while no socket is waiting are deferred, the peer will retry.
+///
+/// ```no_run
+/// let mut listener = openthread.get_tcp_listener::<2>().unwrap();
+/// let mut listener = pin!(listener);
+/// listener.listen(80).unwrap();
+///
+/// let mut socket = openthread.get_tcp_socket::<1024>().unwrap();
+/// let mut socket = pin!(socket);
+/// listener.accept(&mut socket).unwrap();
+/// ```
+pub struct TcpListener<'s, 'n: 's, const BACKLOG: usize> {
+    ot_listener: otTcpListener,
+    ot: &'s OpenThread<'n>,
+    state: TcpListenerState<'s>,
+    _pinned: PhantomPinned,
+    accept_queue: [*mut otTcpEndpoint; BACKLOG],
+}
+
+/// State of a [TcpListener], the listener context
+struct TcpListenerState<'s> {
+    initialized: bool,
+    // points into the pinned listener once it is listening
+    queue: *mut [*mut otTcpEndpoint],
+    queued: usize,
+    deferred: u32,
+    handler: Option<&'s mut (dyn FnMut(Ipv6Addr, u16) + Send)>,
+}
+
+impl<'s> TcpListenerState<'s> {
+    fn pop(&mut self) -> Option<*mut otTcpEndpoint> {
+        if self.queued == 0 {
+            return None;
+        }
+
+        let queue = unsafe { &mut *self.queue };
+        let endpoint = queue[0];
+        queue.copy_within(1..self.queued, 0);
+        self.queued -= 1;
+        Some(endpoint)
+    }
+
+    fn remove(&mut self, endpoint: *mut otTcpEndpoint) {
+        let queue = unsafe { &mut *self.queue };
+        if let Some(index) = queue[..self.queued].iter().position(|e| *e == endpoint) {
+            queue.copy_within(index + 1..self.queued, index);
+            self.queued -= 1;
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Creates a new TCP socket
+    pub fn get_tcp_socket<'s, const BUFFER_SIZE: usize>(
+        &'s self,
+    ) -> Result<TcpSocket<'s, 'a, BUFFER_SIZE>, Error>
+    where
+        'a: 's,
+    {
+        Ok(TcpSocket {
+            ot_endpoint: unsafe { core::mem::zeroed() },
+            ot: self,
+            state: TcpSocketState {
+                initialized: false,
+                connecting: false,
+                connected: false,
+                end_of_stream: false,
+                available: 0,
+                send_in_flight: false,
+                send_link: otLinkedBuffer {
+                    mNext: core::ptr::null_mut(),
+                    mData: core::ptr::null(),
+                    mLength: 0,
+                },
+                waker: None,
+                listener: core::ptr::null_mut(),
+            },
+            _pinned: PhantomPinned,
+            receive_buffer: [0u8; BUFFER_SIZE],
+            send_buffer: [0u8; BUFFER_SIZE],
+        })
+    }
+
+    /// Creates a new TCP listener
+    pub fn get_tcp_listener<'s, const BACKLOG: usize>(
+        &'s self,
+    ) -> Result<TcpListener<'s, 'a, BACKLOG>, Error>
+    where
+        'a: 's,
+    {
+        Ok(TcpListener {
+            ot_listener: unsafe { core::mem::zeroed() },
+            ot: self,
+            state: TcpListenerState {
+                initialized: false,
+                queue: core::ptr::slice_from_raw_parts_mut(core::ptr::null_mut(), 0),
+                queued: 0,
+                deferred: 0,
+                handler: None,
+            },
+            _pinned: PhantomPinned,
+            accept_queue: [core::ptr::null_mut(); BACKLOG],
+        })
+    }
+}
+
+impl<'s, 'n: 's, const BACKLOG: usize> TcpListener<'s, 'n, BACKLOG> {
+    /// Listen for incoming connections on the given port
+    pub fn listen(self: &mut Pin<&mut Self>, port: u16) -> Result<(), Error> {
+        let listener = unsafe { self.as_mut().get_unchecked_mut() };
+
+        if !listener.state.initialized {
+            listener.state.queue = &mut listener.accept_queue[..];
+
+            let args = otTcpListenerInitializeArgs {
+                mContext: &mut listener.state as *mut _ as *mut c_void,
+                mAcceptReadyCallback: Some(tcp_accept_ready),
+                mAcceptDoneCallback: Some(tcp_accept_done),
+            };
+            checked!(unsafe {
+                otTcpListenerInitialize(listener.ot.instance, &mut listener.ot_listener, &args)
+            })?;
+            listener.state.initialized = true;
+        }
+
+        let sock_name = otSockAddr {
+            mAddress: ot_ip6_address_from_ipv6_addr(Ipv6Addr::UNSPECIFIED),
+            mPort: port,
+        };
+        checked!(unsafe { otTcpListen(&mut listener.ot_listener, &sock_name) })
+    }
+
+    /// Stop listening, sockets waiting for a connection stay queued
+    pub fn stop(self: &mut Pin<&mut Self>) -> Result<(), Error> {
+        let listener = unsafe { self.as_mut().get_unchecked_mut() };
+        if !listener.state.initialized {
+            return Ok(());
+        }
+
+        checked!(unsafe { otTcpStopListening(&mut listener.ot_listener) })
+    }
+
+    /// Queue a socket to take the next incoming connection
+    ///
+    /// Fails if the socket is connected or already queued, or if `BACKLOG` sockets are queued.
+    /// [TcpSocket::is_connected] tells when a connection got accepted.
+    pub fn accept<const BUFFER_SIZE: usize>(
+        self: &mut Pin<&mut Self>,
+        socket: &mut Pin<&mut TcpSocket<'_, '_, BUFFER_SIZE>>,
+    ) -> Result<(), Error> {
+        socket.initialize()?;
+
+        let listener = unsafe { self.as_mut().get_unchecked_mut() };
+        let socket = unsafe { socket.as_mut().get_unchecked_mut() };
+
+        critical_section::with(|_| {
+            if !listener.state.initialized
+                || socket.state.connecting
+                || socket.state.connected
+                || !socket.state.listener.is_null()
+            {
+                return Err(INVALID_STATE);
+            }
+
+            if listener.state.queued == BACKLOG {
+                return Err(NO_BUFS);
+            }
+
+            listener.accept_queue[listener.state.queued] = &mut socket.ot_endpoint;
+            listener.state.queued += 1;
+            socket.state.listener = (&mut listener.state as *mut TcpListenerState).cast();
+            Ok(())
+        })
+    }
+
+    /// Set a handler which is called with the peer's address and port for every accepted
+    /// connection
+    pub fn set_accept_handler(
+        self: &mut Pin<&mut Self>,
+        handler: Option<&'s mut (dyn FnMut(Ipv6Addr, u16) + Send)>,
+    ) {
+        critical_section::with(|_| {
+            unsafe { self.as_mut().get_unchecked_mut() }.state.handler = handler;
+        });
+    }
+
+    /// Number of sockets waiting for a connection
+    pub fn queued(&self) -> usize {
+        critical_section::with(|_| self.state.queued)
+    }
+
+    /// Number of connection attempts deferred since no socket was waiting
+    pub fn deferred_connections(&self) -> u32 {
+        critical_section::with(|_| self.state.deferred)
+    }
+}
+
+impl<'s, 'n: 's, const BACKLOG: usize> Drop for TcpListener<'s, 'n, BACKLOG> {
+    fn drop(&mut self) {
+        critical_section::with(|_| {
+            for endpoint in &self.accept_queue[..self.state.queued] {
+                let state = unsafe { otTcpEndpointGetContext(*endpoint) } as *mut TcpSocketState;
+                unsafe { (*state).listener = core::ptr::null_mut() };
+            }
+            self.state.queued = 0;
+        });
+
+        if self.state.initialized {
+            unsafe { otTcpListenerDeinitialize(&mut self.ot_listener) };
+        }
+    }
+}
+
+impl<'s, 'n: 's, const BUFFER_SIZE: usize> TcpSocket<'s, 'n, BUFFER_SIZE> {
+    fn initialize(self: &mut Pin<&mut Self>) -> Result<(), Error> {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+        if socket.state.initialized {
+            return Ok(());
+        }
+
+        let args = otTcpEndpointInitializeArgs {
+            mContext: &mut socket.state as *mut _ as *mut c_void,
+            mEstablishedCallback: Some(tcp_established),
+            mSendDoneCallback: Some(tcp_send_done),
+            mForwardProgressCallback: None,
+            mReceiveAvailableCallback: Some(tcp_receive_available),
+            mDisconnectedCallback: Some(tcp_disconnected),
+            mReceiveBuffer: socket.receive_buffer.as_mut_ptr() as *mut c_void,
+            mReceiveBufferSize: BUFFER_SIZE as size_t,
+        };
+        checked!(unsafe {
+            otTcpEndpointInitialize(socket.ot.instance, &mut socket.ot_endpoint, &args)
+        })?;
+        socket.state.initialized = true;
+
+        Ok(())
+    }
+
+    /// Connect to the given peer
+    ///
+    /// The handshake runs as part of [OpenThread::process], [TcpSocket::is_connected] tells
+    /// once it's done.
+    pub fn connect(self: &mut Pin<&mut Self>, peer: Ipv6Addr, port: u16) -> Result<(), Error> {
+        self.initialize()?;
+
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+        critical_section::with(|_| {
+            if socket.state.connecting || socket.state.connected || !socket.state.listener.is_null()
+            {
+                Err(INVALID_STATE)
+            } else {
+                Ok(())
+            }
+        })?;
+
+        let peer = otSockAddr {
+            mAddress: ot_ip6_address_from_ipv6_addr(peer),
+            mPort: port,
+        };
+        checked!(unsafe {
+            otTcpConnect(&mut socket.ot_endpoint, &peer, OT_TCP_CONNECT_NO_FAST_OPEN)
+        })?;
+
+        critical_section::with(|_| {
+            socket.state.connecting = true;
+            socket.state.end_of_stream = false;
+        });
+        Ok(())
+    }
+
+    /// Returns `true` while a connection is established
+    pub fn is_connected(&self) -> bool {
+        critical_section::with(|_| self.state.connected)
+    }
+
+    /// Returns `true` while a connection initiated by [TcpSocket::connect] is being established
+    pub fn is_connecting(&self) -> bool {
+        critical_section::with(|_| self.state.connecting)
+    }
+
+    /// Returns `true` if [TcpSocket::receive] has data or the end of the stream to report
+    pub fn is_readable(&self) -> bool {
+        critical_section::with(|_| self.state.available > 0 || self.state.end_of_stream)
+    }
+
+    /// Returns `true` if no data can arrive anymore without [TcpSocket::receive] reporting the
+    /// end of the stream, e.g. never connected or aborted
+    fn is_idle(&self) -> bool {
+        critical_section::with(|_| {
+            !self.state.connecting
+                && !self.state.connected
+                && !self.state.end_of_stream
+                && self.state.listener.is_null()
+        })
+    }
+
+    /// Returns `true` while sent data isn't acknowledged by the peer yet
+    pub fn is_sending(&self) -> bool {
+        critical_section::with(|_| self.state.send_in_flight)
+    }
+
+    /// Get the address and port of the connected peer
+    pub fn peer_address(&self) -> Option<(Ipv6Addr, u16)> {
+        if !self.is_connected() {
+            return None;
+        }
+
+        let peer = unsafe { &*otTcpGetPeerAddress(&self.ot_endpoint) };
+        Some((ipv6_addr_from_ot_ip6_address(&peer.mAddress), peer.mPort))
+    }
+
+    /// Send data to the peer
+    ///
+    /// Returns the number of bytes taken, `0` while earlier data isn't acknowledged yet.
+    pub fn send(self: &mut Pin<&mut Self>, data: &[u8]) -> Result<usize, Error> {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+
+        let len = critical_section::with(|_| {
+            if !socket.state.connected {
+                return Err(INVALID_STATE);
+            }
+
+            if socket.state.send_in_flight {
+                return Ok(0);
+            }
+
+            let len = usize::min(data.len(), BUFFER_SIZE);
+            socket.send_buffer[..len].copy_from_slice(&data[..len]);
+            socket.state.send_link.mData = socket.send_buffer.as_ptr();
+            socket.state.send_link.mLength = len as size_t;
+            Ok(len)
+        })?;
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        checked!(unsafe {
+            otTcpSendByReference(&mut socket.ot_endpoint, &mut socket.state.send_link, 0)
+        })?;
+        critical_section::with(|_| socket.state.send_in_flight = true);
+
+        Ok(len)
+    }
+
+    /// Get data received from the peer
+    ///
+    /// Returns `None` if no data is pending and `Some(0)` once the peer closed its side.
+    pub fn receive(self: &mut Pin<&mut Self>, data: &mut [u8]) -> Result<Option<usize>, Error> {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+        if !socket.state.initialized {
+            return Err(INVALID_STATE);
+        }
+
+        let mut link: *const otLinkedBuffer = core::ptr::null();
+        checked!(unsafe { otTcpReceiveByReference(&mut socket.ot_endpoint, &mut link) })?;
+
+        let mut len = 0;
+        while !link.is_null() && len < data.len() {
+            let buffer = unsafe { &*link };
+            let chunk = usize::min(buffer.mLength as usize, data.len() - len);
+            if chunk > 0 {
+                data[len..][..chunk]
+                    .copy_from_slice(unsafe { core::slice::from_raw_parts(buffer.mData, chunk) });
+                len += chunk;
+            }
+            link = buffer.mNext;
+        }
+
+        if len > 0 {
+            checked!(unsafe { otTcpCommitReceive(&mut socket.ot_endpoint, len as size_t, 0) })?;
+            critical_section::with(|_| {
+                socket.state.available = socket.state.available.saturating_sub(len)
+            });
+            Ok(Some(len))
+        } else if critical_section::with(|_| socket.state.end_of_stream) {
+            Ok(Some(0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Poll for the connection initiated by [TcpSocket::connect] to be established
+    ///
+    /// Registers the context's waker to be woken once the handshake is done, for use in
+    /// futures. Fails with `OT_ERROR_INVALID_STATE` if the connection attempt failed.
+    pub fn poll_connected(
+        self: &mut Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Error>> {
+        critical_section::with(|_| {
+            let state = &mut unsafe { self.as_mut().get_unchecked_mut() }.state;
+            if state.connected {
+                Poll::Ready(Ok(()))
+            } else if state.connecting {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            } else {
+                Poll::Ready(Err(INVALID_STATE))
+            }
+        })
+    }
+
+    /// Poll for data received from the peer
+    ///
+    /// Like [TcpSocket::receive] but registers the context's waker to be woken once data or the
+    /// end of the stream arrives, for use in futures. The socket has a single waker, shared with
+    /// [TcpSocket::poll_send] and [TcpSocket::poll_connected]. Fails with
+    /// `OT_ERROR_INVALID_STATE` if no data can arrive, e.g. on a socket never connected.
+    pub fn poll_receive(
+        self: &mut Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &mut [u8],
+    ) -> Poll<Result<usize, Error>> {
+        self.register_waker(cx);
+        match self.receive(data) {
+            Ok(Some(len)) => Poll::Ready(Ok(len)),
+            Ok(None) if self.is_idle() => Poll::Ready(Err(INVALID_STATE)),
+            Ok(None) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+
+    /// Poll for the peer to take data
+    ///
+    /// Like [TcpSocket::send] but registers the context's waker to be woken once earlier data
+    /// is acknowledged, for use in futures, e.g. streaming an OTA image.
+    pub fn poll_send(
+        self: &mut Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<Result<usize, Error>> {
+        self.register_waker(cx);
+        match self.send(data) {
+            Ok(0) if !data.is_empty() => Poll::Pending,
+            result => Poll::Ready(result),
+        }
+    }
+
+    fn register_waker(self: &mut Pin<&mut Self>, cx: &mut Context<'_>) {
+        critical_section::with(|_| {
+            unsafe { self.as_mut().get_unchecked_mut() }.state.waker = Some(cx.waker().clone())
+        });
+    }
+
+    /// Tell the peer no more data will be sent, data already sent is still delivered
+    pub fn close(self: &mut Pin<&mut Self>) -> Result<(), Error> {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+        checked!(unsafe { otTcpSendEndOfStream(&mut socket.ot_endpoint) })
+    }
+
+    /// Reset the connection right away
+    pub fn abort(self: &mut Pin<&mut Self>) -> Result<(), Error> {
+        let socket = unsafe { self.as_mut().get_unchecked_mut() };
+        if !socket.state.initialized {
+            return Ok(());
+        }
+
+        checked!(unsafe { otTcpAbort(&mut socket.ot_endpoint) })?;
+        critical_section::with(|_| {
+            socket.state.connecting = false;
+            socket.state.connected = false;
+            socket.state.send_in_flight = false;
+        });
+        Ok(())
+    }
+}
+
+impl<'s, 'n: 's, const BUFFER_SIZE: usize> Drop for TcpSocket<'s, 'n, BUFFER_SIZE> {
+    fn drop(&mut self) {
+        critical_section::with(|_| {
+            if !self.state.listener.is_null() {
+                unsafe { (*self.state.listener).remove(&mut self.ot_endpoint) };
+                self.state.listener = core::ptr::null_mut();
+            }
+        });
+
+        if self.state.initialized {
+            unsafe { otTcpEndpointDeinitialize(&mut self.ot_endpoint) };
+        }
+    }
+}
+
+/// Blocking I/O for e.g. `embedded-tls`, waiting calls keep OpenThread running via
+/// [OpenThread::process] and [OpenThread::run_tasklets]
+#[cfg(feature = "embedded-io")]
+mod io {
+    use core::pin::Pin;
+
+    use super::{TcpSocket, INVALID_STATE};
+    use crate::Error;
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> TcpSocket<'s, 'n, BUFFER_SIZE> {
+        fn run_openthread(&self) {
+            self.ot.process();
+            self.ot.run_tasklets();
+        }
+
+        fn wait_connected(self: &mut Pin<&mut Self>) -> Result<(), Error> {
+            while self.is_connecting() {
+                self.run_openthread();
+            }
+
+            if self.is_connected() {
+                Ok(())
+            } else {
+                Err(INVALID_STATE)
+            }
+        }
+    }
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> embedded_io::ErrorType
+        for Pin<&mut TcpSocket<'s, 'n, BUFFER_SIZE>>
+    {
+        type Error = Error;
+    }
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> embedded_io::Read
+        for Pin<&mut TcpSocket<'s, 'n, BUFFER_SIZE>>
+    {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            if buf.is_empty() {
+                return Ok(0);
+            }
+
+            while self.is_connecting() {
+                self.run_openthread();
+            }
+
+            loop {
+                if let Some(len) = self.receive(buf)? {
+                    return Ok(len);
+                }
+                if self.is_idle() {
+                    return Err(INVALID_STATE);
+                }
+                self.run_openthread();
+            }
+        }
+    }
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> embedded_io::ReadReady
+        for Pin<&mut TcpSocket<'s, 'n, BUFFER_SIZE>>
+    {
+        fn read_ready(&mut self) -> Result<bool, Error> {
+            Ok(self.is_readable())
+        }
+    }
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> embedded_io::Write
+        for Pin<&mut TcpSocket<'s, 'n, BUFFER_SIZE>>
+    {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+            if buf.is_empty() {
+                return Ok(0);
+            }
+
+            self.wait_connected()?;
+            loop {
+                let len = self.send(buf)?;
+                if len > 0 {
+                    return Ok(len);
+                }
+                self.run_openthread();
+            }
+        }
+
+        fn flush(&mut self) -> Result<(), Error> {
+            self.wait_connected()?;
+            while self.is_sending() {
+                if !self.is_connected() {
+                    return Err(INVALID_STATE);
+                }
+                self.run_openthread();
+            }
+            Ok(())
+        }
+    }
+
+    impl<'s, 'n: 's, const BUFFER_SIZE: usize> embedded_io::WriteReady
+        for Pin<&mut TcpSocket<'s, 'n, BUFFER_SIZE>>
+    {
+        fn write_ready(&mut self) -> Result<bool, Error> {
+            Ok(self.is_connected() && !self.is_sending())
+        }
+    }
+}
+
+unsafe fn socket_state(endpoint: *mut otTcpEndpoint) -> *mut TcpSocketState {
+    otTcpEndpointGetContext(endpoint) as *mut TcpSocketState
+}
+
+unsafe fn wake(socket: *mut TcpSocketState) {
+    if let Some(waker) = (*socket).waker.take() {
+        waker.wake();
+    }
+}
+
+unsafe extern "C" fn tcp_accept_ready(
+    listener: *mut otTcpListener,
+    _peer: *const otSockAddr,
+    accept_into: *mut *mut otTcpEndpoint,
+) -> otTcpIncomingConnectionAction {
+    let state = otTcpListenerGetContext(listener) as *mut TcpListenerState;
+
+    critical_section::with(|_| match (*state).pop() {
+        Some(endpoint) => {
+            (*socket_state(endpoint)).listener = core::ptr::null_mut();
+            *accept_into = endpoint;
+            otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_ACCEPT
+        }
+        None => {
+            log::debug!("tcp_accept_ready no socket queued, deferring");
+            (*state).deferred = (*state).deferred.saturating_add(1);
+            otTcpIncomingConnectionAction_OT_TCP_INCOMING_CONNECTION_ACTION_DEFER
+        }
+    })
+}
+
+unsafe extern "C" fn tcp_accept_done(
+    listener: *mut otTcpListener,
+    endpoint: *mut otTcpEndpoint,
+    peer: *const otSockAddr,
+) {
+    let state = otTcpListenerGetContext(listener) as *mut TcpListenerState;
+
+    critical_section::with(|_| {
+        let socket = socket_state(endpoint);
+        (*socket).connected = true;
+        (*socket).end_of_stream = false;
+        wake(socket);
+    });
+
+    if let Some(handler) = (*state).handler.as_mut() {
+        handler(
+            ipv6_addr_from_ot_ip6_address(&(*peer).mAddress),
+            (*peer).mPort,
+        );
+    }
+}
+
+unsafe extern "C" fn tcp_established(endpoint: *mut otTcpEndpoint) {
+    critical_section::with(|_| {
+        let socket = socket_state(endpoint);
+        (*socket).connecting = false;
+        (*socket).connected = true;
+        (*socket).end_of_stream = false;
+        wake(socket);
+    });
+}
+
+unsafe extern "C" fn tcp_send_done(endpoint: *mut otTcpEndpoint, _data: *mut otLinkedBuffer) {
+    critical_section::with(|_| {
+        let socket = socket_state(endpoint);
+        (*socket).send_in_flight = false;
+        wake(socket);
+    });
+}
+
+unsafe extern "C" fn tcp_receive_available(
+    endpoint: *mut otTcpEndpoint,
+    bytes_available: size_t,
+    end_of_stream: bool,
+    _bytes_remaining: size_t,
+) {
+    critical_section::with(|_| {
+        let socket = socket_state(endpoint);
+        (*socket).available = bytes_available as usize;
+        (*socket).end_of_stream = end_of_stream;
+        wake(socket);
+    });
+}
+
+unsafe extern "C" fn tcp_disconnected(
+    endpoint: *mut otTcpEndpoint,
+    reason: otTcpDisconnectedReason,
+) {
+    log::debug!("tcp_disconnected {}", reason);
+
+    critical_section::with(|_| {
+        let socket = socket_state(endpoint);
+        (*socket).connecting = false;
+        (*socket).connected = false;
+        (*socket).end_of_stream = true;
+        (*socket).send_in_flight = false;
+        wake(socket);
+    });
+}
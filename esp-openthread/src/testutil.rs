@@ -0,0 +1,304 @@
+use core::pin::Pin;
+
+use esp_openthread_sys::bindings::otError_OT_ERROR_INVALID_ARGS;
+use no_std_net::Ipv6Addr;
+
+use crate::{timer::current_millis, Error, UdpSocket};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// The UDP echo port (RFC 862), a suggestion for [UdpEcho]
+pub const ECHO_PORT: u16 = 7;
+
+/// The port iperf uses, a suggestion for [UdpGenerator] and [UdpSink]
+pub const BENCHMARK_PORT: u16 = 5001;
+
+/// Every [UdpGenerator] datagram starts with its sequence number and send time (in
+/// milliseconds), both big endian
+pub const BENCHMARK_HEADER_SIZE: usize = 8;
+
+/// Most datagrams [UdpGenerator::poll] sends at once to catch up with its rate
+const MAX_BURST: u32 = 8;
+
+/// Counters of [UdpEcho], [UdpGenerator] and [UdpSink]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchmarkStats {
+    /// Datagrams echoed, sent or received
+    pub packets: u32,
+    /// UDP payload bytes
+    pub bytes: u64,
+    /// Sends that failed, or for [UdpSink] sequence numbers that never arrived
+    pub lost: u32,
+    /// Datagrams [UdpSink] received after a later one
+    pub out_of_order: u32,
+    /// Milliseconds from the first to the last datagram
+    pub duration: u32,
+}
+
+impl BenchmarkStats {
+    /// Datagrams per second over [BenchmarkStats::duration]
+    pub fn packets_per_second(&self) -> u32 {
+        if self.duration == 0 {
+            return 0;
+        }
+
+        (self.packets as u64 * 1000 / self.duration as u64) as u32
+    }
+
+    /// UDP payload bits per second over [BenchmarkStats::duration]
+    pub fn throughput(&self) -> u32 {
+        if self.duration == 0 {
+            return 0;
+        }
+
+        (self.bytes * 8 * 1000 / self.duration as u64) as u32
+    }
+
+    /// Count a datagram at `now` (in milliseconds), `first` is when the first one was seen
+    fn count(&mut self, first: &mut Option<u32>, now: u32, len: usize) {
+        let first = *first.get_or_insert(now);
+        self.packets = self.packets.wrapping_add(1);
+        self.bytes = self.bytes.wrapping_add(len as u64);
+        self.duration = now.wrapping_sub(first);
+    }
+}
+
+/// Sends every datagram received on a socket back to where it came from
+///
+/// Datagrams up to `N` bytes are echoed completely, longer ones cut off.
+///
+/// ```ignore
+/// use core::pin::pin;
+///
+/// use esp_openthread::{UdpEcho, ECHO_PORT};
+///
+/// // `openthread` is the `OpenThread` instance, set up and attached
+/// let mut socket = openthread.get_udp_socket::<512>().unwrap();
+/// let mut socket = pin!(socket);
+/// socket.bind(ECHO_PORT).unwrap();
+/// let mut echo = UdpEcho::<512>::new();
+/// loop {
+///     openthread.process();
+///     openthread.run_tasklets();
+///     echo.poll(&mut socket).unwrap();
+/// }
+/// ```
+pub struct UdpEcho<const N: usize> {
+    buffer: [u8; N],
+    stats: BenchmarkStats,
+    first: Option<u32>,
+}
+
+impl<const N: usize> UdpEcho<N> {
+    pub fn new() -> Self {
+        Self {
+            buffer: [0u8; N],
+            stats: BenchmarkStats::default(),
+            first: None,
+        }
+    }
+
+    /// Echo the datagram pending on `socket`, if any
+    ///
+    /// A failing send is counted as lost, not returned.
+    pub fn poll<const BUFFER_SIZE: usize>(
+        &mut self,
+        socket: &mut Pin<&mut UdpSocket<'_, '_, BUFFER_SIZE>>,
+    ) -> Result<(), Error> {
+        let Some((len, from, port)) = socket.receive(&mut self.buffer)? else {
+            return Ok(());
+        };
+
+        match socket.send(from, port, &self.buffer[..len]) {
+            Ok(()) => self
+                .stats
+                .count(&mut self.first, current_millis() as u32, len),
+            Err(_) => self.stats.lost = self.stats.lost.wrapping_add(1),
+        }
+
+        Ok(())
+    }
+
+    pub fn stats(&self) -> BenchmarkStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = BenchmarkStats::default();
+        self.first = None;
+    }
+}
+
+impl<const N: usize> Default for UdpEcho<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sends datagrams of a fixed size at a fixed rate, e.g. to a [UdpSink] on another board
+///
+/// Each datagram starts with a [BENCHMARK_HEADER_SIZE] bytes header, the rest of its up to `N`
+/// bytes is filled with a pattern.
+pub struct UdpGenerator<const N: usize> {
+    buffer: [u8; N],
+    dst: Ipv6Addr,
+    port: u16,
+    size: usize,
+    /// Datagrams per second, 0 sends one per poll
+    rate: u32,
+    /// Datagrams to send in total, `None` for no limit
+    count: Option<u32>,
+    sequence: u32,
+    start: Option<u32>,
+    stats: BenchmarkStats,
+}
+
+impl<const N: usize> UdpGenerator<N> {
+    /// Send `size` byte datagrams to `dst` at `rate` datagrams per second
+    ///
+    /// A `rate` of 0 sends one datagram per [UdpGenerator::poll]. Stops after `count`
+    /// datagrams if given. Returns an error if `size` doesn't fit the header or exceeds `N`.
+    pub fn new(
+        dst: Ipv6Addr,
+        port: u16,
+        size: usize,
+        rate: u32,
+        count: Option<u32>,
+    ) -> Result<Self, Error> {
+        if !(BENCHMARK_HEADER_SIZE..=N).contains(&size) {
+            return Err(INVALID_ARGS);
+        }
+
+        let mut buffer = [0u8; N];
+        for (index, byte) in buffer.iter_mut().enumerate() {
+            *byte = index as u8;
+        }
+
+        Ok(Self {
+            buffer,
+            dst,
+            port,
+            size,
+            rate,
+            count,
+            sequence: 0,
+            start: None,
+            stats: BenchmarkStats::default(),
+        })
+    }
+
+    /// Send the datagrams due by now on `socket`
+    ///
+    /// Sends at most a few at once, call it at least as often as the rate requires. A failing
+    /// send (e.g. OpenThread running out of message buffers) is counted as lost, not retried.
+    pub fn poll<const BUFFER_SIZE: usize>(
+        &mut self,
+        socket: &mut Pin<&mut UdpSocket<'_, '_, BUFFER_SIZE>>,
+    ) {
+        let now = current_millis() as u32;
+        let start = *self.start.get_or_insert(now);
+
+        let due = if self.rate == 0 {
+            self.sequence + 1
+        } else {
+            (now.wrapping_sub(start) as u64 * self.rate as u64 / 1000) as u32 + 1
+        };
+        let due = match self.count {
+            Some(count) => u32::min(due, count),
+            None => due,
+        };
+
+        let mut burst = 0;
+        while self.sequence < due && burst < MAX_BURST {
+            self.buffer[0..4].copy_from_slice(&self.sequence.to_be_bytes());
+            self.buffer[4..8].copy_from_slice(&now.to_be_bytes());
+
+            match socket.send(self.dst, self.port, &self.buffer[..self.size]) {
+                Ok(()) => self.stats.count(&mut self.start, now, self.size),
+                Err(_) => self.stats.lost = self.stats.lost.wrapping_add(1),
+            }
+
+            self.sequence += 1;
+            burst += 1;
+        }
+    }
+
+    /// Returns `true` once `count` datagrams were sent
+    pub fn is_done(&self) -> bool {
+        self.count.is_some_and(|count| self.sequence >= count)
+    }
+
+    pub fn stats(&self) -> BenchmarkStats {
+        self.stats
+    }
+}
+
+/// Receives [UdpGenerator] datagrams and counts throughput, losses and reordering
+///
+/// Datagrams are read into `N` bytes, the byte counts are only correct if they fit. A
+/// sequence number of 0 starts a new run and resets the counters.
+pub struct UdpSink<const N: usize> {
+    buffer: [u8; N],
+    next_sequence: u32,
+    first: Option<u32>,
+    stats: BenchmarkStats,
+}
+
+impl<const N: usize> UdpSink<N> {
+    pub fn new() -> Self {
+        Self {
+            buffer: [0u8; N],
+            next_sequence: 0,
+            first: None,
+            stats: BenchmarkStats::default(),
+        }
+    }
+
+    /// Count the datagram pending on `socket`, if any
+    pub fn poll<const BUFFER_SIZE: usize>(
+        &mut self,
+        socket: &mut Pin<&mut UdpSocket<'_, '_, BUFFER_SIZE>>,
+    ) -> Result<(), Error> {
+        let Some((len, _, _)) = socket.receive(&mut self.buffer)? else {
+            return Ok(());
+        };
+        if len < BENCHMARK_HEADER_SIZE {
+            return Ok(());
+        }
+
+        let sequence = u32::from_be_bytes(self.buffer[0..4].try_into().unwrap());
+        if sequence == 0 {
+            self.reset_stats();
+        }
+
+        if sequence >= self.next_sequence {
+            self.stats.lost = self.stats.lost.wrapping_add(sequence - self.next_sequence);
+            self.next_sequence = sequence + 1;
+        } else {
+            // counted as lost when the later one arrived
+            self.stats.out_of_order = self.stats.out_of_order.wrapping_add(1);
+            self.stats.lost = self.stats.lost.saturating_sub(1);
+        }
+
+        self.stats
+            .count(&mut self.first, current_millis() as u32, len);
+
+        Ok(())
+    }
+
+    pub fn stats(&self) -> BenchmarkStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.next_sequence = 0;
+        self.first = None;
+        self.stats = BenchmarkStats::default();
+    }
+}
+
+impl<const N: usize> Default for UdpSink<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
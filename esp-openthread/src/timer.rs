@@ -2,29 +2,128 @@ use esp_hal::macros::interrupt;
 use esp_hal::peripherals::Interrupt;
 use esp_hal::systimer::Alarm;
 use esp_hal::systimer::Target;
+use crate::chip::{Chip, ChipOps};
 use crate::platform::CURRENT_INSTANCE;
-use core::cell::RefCell;
+use core::cell::{Cell, RefCell};
+use core::sync::atomic::{AtomicBool, Ordering};
 use critical_section::Mutex;
 use esp_openthread_sys::bindings::otError;
 use esp_openthread_sys::bindings::otError_OT_ERROR_NONE;
 use esp_openthread_sys::bindings::otInstance;
 use esp_openthread_sys::bindings::otPlatAlarmMilliFired;
+#[cfg(feature = "diag")]
+use esp_openthread_sys::bindings::otPlatDiagAlarmFired;
 
-const TICKS_PER_SECOND: u64 = 16_000_000;
+pub(crate) const TICKS_PER_SECOND: u64 = <Chip as ChipOps>::SYSTIMER_TICKS_PER_SECOND;
 
-static TIMER: Mutex<RefCell<Option<Alarm<Target, 0>>>> = Mutex::new(RefCell::new(None));
+/// A systimer alarm OpenThread's timers can run on, i.e. `Alarm<Target, 0>` to `Alarm<Target, 2>`
+///
+/// The alarm passed to [crate::OpenThread::new] picks the interrupt OpenThread handles, the others
+/// are left to the application, e.g. for `embassy-time`, see [set_alarm_interrupt_handler].
+pub trait TimerAlarm: private::Sealed {}
 
-static TIMER_CALLBACK_SHOULD_RUN: Mutex<RefCell<bool>> = Mutex::new(RefCell::new(false));
+mod private {
+    pub trait Sealed {
+        const INDEX: u8;
+        const INTERRUPT: super::Interrupt;
 
-pub fn install_isr(timer: Alarm<Target, 0>) {
+        fn into_any(self) -> super::AnyAlarm;
+    }
+}
+
+macro_rules! timer_alarm {
+    ($index:literal, $variant:ident, $interrupt:ident) => {
+        impl private::Sealed for Alarm<Target, $index> {
+            const INDEX: u8 = $index;
+            const INTERRUPT: Interrupt = Interrupt::$interrupt;
+
+            fn into_any(self) -> AnyAlarm {
+                AnyAlarm::$variant(self)
+            }
+        }
+
+        impl TimerAlarm for Alarm<Target, $index> {}
+    };
+}
+
+timer_alarm!(0, Alarm0, SYSTIMER_TARGET0);
+timer_alarm!(1, Alarm1, SYSTIMER_TARGET1);
+timer_alarm!(2, Alarm2, SYSTIMER_TARGET2);
+
+pub enum AnyAlarm {
+    Alarm0(Alarm<Target, 0>),
+    Alarm1(Alarm<Target, 1>),
+    Alarm2(Alarm<Target, 2>),
+}
+
+impl AnyAlarm {
+    fn clear_interrupt(&self) {
+        match self {
+            Self::Alarm0(alarm) => alarm.clear_interrupt(),
+            Self::Alarm1(alarm) => alarm.clear_interrupt(),
+            Self::Alarm2(alarm) => alarm.clear_interrupt(),
+        }
+    }
+
+    fn set_target(&mut self, timestamp: u64) {
+        match self {
+            Self::Alarm0(alarm) => alarm.set_target(timestamp),
+            Self::Alarm1(alarm) => alarm.set_target(timestamp),
+            Self::Alarm2(alarm) => alarm.set_target(timestamp),
+        }
+    }
+
+    fn enable_interrupt(&mut self, enable: bool) {
+        match self {
+            Self::Alarm0(alarm) => alarm.enable_interrupt(enable),
+            Self::Alarm1(alarm) => alarm.enable_interrupt(enable),
+            Self::Alarm2(alarm) => alarm.enable_interrupt(enable),
+        }
+    }
+}
+
+static TIMER: Mutex<RefCell<Option<AnyAlarm>>> = Mutex::new(RefCell::new(None));
+
+// index of the alarm in `TIMER`, its interrupt is dispatched to OpenThread
+static TIMER_ALARM_INDEX: Mutex<Cell<Option<u8>>> = Mutex::new(Cell::new(None));
+
+// handlers of the alarms not driving OpenThread
+static ALARM_INTERRUPT_HANDLERS: Mutex<Cell<[Option<fn()>; 3]>> = Mutex::new(Cell::new([None; 3]));
+
+// set from the alarm interrupt, taken by `process()`
+static TIMER_CALLBACK_SHOULD_RUN: AtomicBool = AtomicBool::new(false);
+
+pub fn install_isr<const N: u8>(timer: Alarm<Target, N>, priority: esp_hal::interrupt::Priority)
+where
+    Alarm<Target, N>: TimerAlarm,
+{
     timer.clear_interrupt();
-    critical_section::with(|cs| TIMER.borrow_ref_mut(cs).replace(timer));
+    critical_section::with(|cs| {
+        TIMER
+            .borrow_ref_mut(cs)
+            .replace(private::Sealed::into_any(timer));
+        TIMER_ALARM_INDEX
+            .borrow(cs)
+            .set(Some(<Alarm<Target, N> as private::Sealed>::INDEX));
+    });
 
-    esp_hal::interrupt::enable(
-        Interrupt::SYSTIMER_TARGET0,
-        esp_hal::interrupt::Priority::Priority1,
-    )
-    .unwrap();
+    esp_hal::interrupt::enable(<Alarm<Target, N> as private::Sealed>::INTERRUPT, priority).unwrap();
+}
+
+/// Set the handler of the interrupt of systimer alarm `alarm` (0 to 2) if it doesn't drive
+/// OpenThread's timers
+///
+/// OpenThread takes the `SYSTIMER_TARGET0` to `SYSTIMER_TARGET2` interrupts, as the alarm it runs
+/// on is only known at runtime, and dispatches the ones of the other alarms here. An application
+/// using those registers its handler with this rather than with `#[interrupt]`, and enables the
+/// interrupt itself. With the `wifi-coex` feature `SYSTIMER_TARGET0` stays with `esp-wifi`.
+pub fn set_alarm_interrupt_handler(alarm: u8, handler: Option<fn()>) {
+    critical_section::with(|cs| {
+        let handlers = ALARM_INTERRUPT_HANDLERS.borrow(cs);
+        let mut updated = handlers.get();
+        updated[alarm as usize] = handler;
+        handlers.set(updated);
+    });
 }
 
 pub fn set_timer_target(when: u32) {
@@ -47,8 +146,37 @@ pub fn stop() {
     });
 }
 
+// `esp-wifi` handles alarm 0 itself
+#[cfg(not(feature = "wifi-coex"))]
 #[interrupt]
 fn SYSTIMER_TARGET0() {
+    alarm_interrupt(0);
+}
+
+#[interrupt]
+fn SYSTIMER_TARGET1() {
+    alarm_interrupt(1);
+}
+
+#[interrupt]
+fn SYSTIMER_TARGET2() {
+    alarm_interrupt(2);
+}
+
+fn alarm_interrupt(index: u8) {
+    let (ours, handler) = critical_section::with(|cs| {
+        (
+            TIMER_ALARM_INDEX.borrow(cs).get() == Some(index),
+            ALARM_INTERRUPT_HANDLERS.borrow(cs).get()[index as usize],
+        )
+    });
+    if !ours {
+        if let Some(handler) = handler {
+            handler();
+        }
+        return;
+    }
+
     log::warn!("timer interrupt triggered at {}", current_millis());
     // clear the interrupt
     critical_section::with(|cs| {
@@ -62,9 +190,20 @@ pub fn current_millis() -> u64 {
     esp_hal::systimer::SystemTimer::now() / (TICKS_PER_SECOND / 1000)
 }
 
+pub fn current_micros() -> u64 {
+    esp_hal::systimer::SystemTimer::now() / (TICKS_PER_SECOND / 1_000_000)
+}
+
+/// Systimer count, [TICKS_PER_SECOND] per second, for timestamps finer than a microsecond
+pub(crate) fn current_ticks() -> u64 {
+    esp_hal::systimer::SystemTimer::now()
+}
+
 #[no_mangle]
 pub extern "C" fn otPlatAlarmMilliGetNow() -> u32 {
     log::trace!("otPlatAlarmMilliGetNow");
+    #[cfg(feature = "instrumentation")]
+    crate::health::sample_stack();
     crate::timer::current_millis() as u32
 }
 
@@ -90,19 +229,22 @@ pub extern "C" fn otPlatAlarmMilliStop() -> otError {
 }
 
 fn timer_triggered() {
-    critical_section::with(|cs| *TIMER_CALLBACK_SHOULD_RUN.borrow_ref_mut(cs) = true);
+    TIMER_CALLBACK_SHOULD_RUN.store(true, Ordering::Release);
+    #[cfg(feature = "async")]
+    crate::asynch::signal();
 }
 
 pub(crate) fn run_if_due() {
-    let should_run = critical_section::with(|cs| {
-        let res = *TIMER_CALLBACK_SHOULD_RUN.borrow_ref_mut(cs);
-        *TIMER_CALLBACK_SHOULD_RUN.borrow_ref_mut(cs) = false;
-        res
-    });
+    let should_run = TIMER_CALLBACK_SHOULD_RUN.swap(false, Ordering::Acquire);
 
     if should_run {
         unsafe {
             let instance = CURRENT_INSTANCE as *mut otInstance;
+            #[cfg(feature = "diag")]
+            if crate::diag::is_diag_mode() {
+                otPlatDiagAlarmFired(instance);
+                return;
+            }
             otPlatAlarmMilliFired(instance);
         }
     }
@@ -0,0 +1,234 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_openthread_sys::bindings::{
+    otError_OT_ERROR_INVALID_ARGS, otInstance, otIpCounters, otThreadGetIp6Counters,
+};
+
+use crate::{radio::RADIO_THROUGHPUT, timer::current_millis, Error, OpenThread};
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+
+/// How often (in milliseconds) the interface counters are sampled into the traffic meter
+pub const TRAFFIC_METER_SAMPLE_INTERVAL: u64 = 1000;
+
+/// Default window (in seconds) the rates of [OpenThread::thread_get_traffic_rates] cover
+pub const TRAFFIC_METER_DEFAULT_WINDOW: u32 = 10;
+
+/// Longest window (in seconds) the traffic meter keeps samples for
+pub const TRAFFIC_METER_MAX_WINDOW: u32 = 60;
+
+const MAX_SAMPLES: usize =
+    (TRAFFIC_METER_MAX_WINDOW as u64 * 1000 / TRAFFIC_METER_SAMPLE_INTERVAL) as usize + 1;
+
+pub(crate) static TRAFFIC_METER: Mutex<RefCell<TrafficMeter>> =
+    Mutex::new(RefCell::new(TrafficMeter::DEFAULT));
+
+/// Traffic of the Thread interface over the traffic meter's window, see
+/// [OpenThread::thread_get_traffic_rates]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrafficRates {
+    /// PSDU bytes per second handed to the radio, retransmissions not included
+    pub tx_bytes_per_second: u32,
+    /// PSDU bytes per second received by the radio
+    pub rx_bytes_per_second: u32,
+    /// IPv6 packets sent
+    pub tx_packets: u32,
+    /// IPv6 packets received
+    pub rx_packets: u32,
+    /// IPv6 packets that failed to be sent
+    pub tx_failures: u32,
+    /// IPv6 packets that failed to be received
+    pub rx_failures: u32,
+    /// Milliseconds the rates cover, shorter than the window until enough samples are taken
+    pub duration: u64,
+}
+
+/// The raw counters as read, both can be reset behind the meter's back
+#[derive(Clone, Copy)]
+struct Sample {
+    tx_bytes: u64,
+    rx_bytes: u64,
+    ip: otIpCounters,
+}
+
+/// Running totals since the traffic meter started
+#[derive(Clone, Copy)]
+struct Totals {
+    tx_bytes: u64,
+    rx_bytes: u64,
+    tx_packets: u32,
+    rx_packets: u32,
+    tx_failures: u32,
+    rx_failures: u32,
+}
+
+pub(crate) struct TrafficMeter {
+    /// seconds
+    window: u32,
+    previous: Option<Sample>,
+    totals: Totals,
+    /// Totals with the time (in milliseconds) they were taken, oldest first
+    samples: heapless::Deque<(u64, Totals), MAX_SAMPLES>,
+    /// milliseconds
+    last_sample: u64,
+}
+
+impl TrafficMeter {
+    pub(crate) const DEFAULT: Self = Self {
+        window: TRAFFIC_METER_DEFAULT_WINDOW,
+        previous: None,
+        totals: Totals {
+            tx_bytes: 0,
+            rx_bytes: 0,
+            tx_packets: 0,
+            rx_packets: 0,
+            tx_failures: 0,
+            rx_failures: 0,
+        },
+        samples: heapless::Deque::new(),
+        last_sample: 0,
+    };
+
+    /// Drop the samples older than needed to cover the window
+    fn prune(&mut self, now: u64) {
+        let window = self.window as u64 * 1000;
+        while self.samples.len() > 1 {
+            match self.samples.iter().nth(1) {
+                Some((time, _)) if *time + window <= now => {
+                    self.samples.pop_front();
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the window (in seconds) the rates of [OpenThread::thread_get_traffic_rates] cover
+    ///
+    /// Fails with `OT_ERROR_INVALID_ARGS` outside of 1 to [TRAFFIC_METER_MAX_WINDOW]. A longer
+    /// window takes that long to fill, samples already dropped aren't brought back.
+    pub fn thread_set_traffic_meter_window(&mut self, window: u32) -> Result<(), Error> {
+        if window == 0 || window > TRAFFIC_METER_MAX_WINDOW {
+            return Err(INVALID_ARGS);
+        }
+
+        critical_section::with(|cs| {
+            let mut meter = TRAFFIC_METER.borrow_ref_mut(cs);
+            meter.window = window;
+            meter.prune(current_millis());
+        });
+
+        Ok(())
+    }
+
+    /// Get the window (in seconds) the rates of [OpenThread::thread_get_traffic_rates] cover
+    pub fn thread_get_traffic_meter_window(&self) -> u32 {
+        critical_section::with(|cs| TRAFFIC_METER.borrow_ref(cs).window)
+    }
+
+    /// Get the traffic of the Thread interface over the last window, e.g. for a bandwidth
+    /// dashboard
+    ///
+    /// The radio's byte counters and OpenThread's IPv6 counters (`otThreadGetIp6Counters`) are
+    /// sampled every [TRAFFIC_METER_SAMPLE_INTERVAL] from [OpenThread::process]. OpenThread
+    /// counts IPv6 packets only, the byte rates are those of the 802.15.4 frames carrying them,
+    /// MLE and MAC traffic included. Resetting either counters doesn't disturb the meter.
+    pub fn thread_get_traffic_rates(&self) -> TrafficRates {
+        critical_section::with(|cs| {
+            let meter = TRAFFIC_METER.borrow_ref(cs);
+            let (Some((start, oldest)), Some((end, newest))) =
+                (meter.samples.front(), meter.samples.back())
+            else {
+                return TrafficRates::default();
+            };
+
+            let duration = end - start;
+            TrafficRates {
+                tx_bytes_per_second: per_second(newest.tx_bytes - oldest.tx_bytes, duration),
+                rx_bytes_per_second: per_second(newest.rx_bytes - oldest.rx_bytes, duration),
+                tx_packets: newest.tx_packets.wrapping_sub(oldest.tx_packets),
+                rx_packets: newest.rx_packets.wrapping_sub(oldest.rx_packets),
+                tx_failures: newest.tx_failures.wrapping_sub(oldest.tx_failures),
+                rx_failures: newest.rx_failures.wrapping_sub(oldest.rx_failures),
+                duration,
+            }
+        })
+    }
+
+    /// Discard the samples of the traffic meter, the rates start over from zero
+    pub fn thread_reset_traffic_meter(&mut self) {
+        critical_section::with(|cs| {
+            let mut meter = TRAFFIC_METER.borrow_ref_mut(cs);
+            meter.samples.clear();
+            meter.previous = None;
+        });
+    }
+}
+
+/// Sample the interface counters if due, called from [OpenThread::process]
+pub(crate) fn process(instance: *mut otInstance) {
+    let now = current_millis();
+    let due = critical_section::with(|cs| {
+        let meter = TRAFFIC_METER.borrow_ref(cs);
+        meter.previous.is_none() || now - meter.last_sample >= TRAFFIC_METER_SAMPLE_INTERVAL
+    });
+    if !due {
+        return;
+    }
+
+    let ip = unsafe { *otThreadGetIp6Counters(instance) };
+    critical_section::with(|cs| {
+        let throughput = RADIO_THROUGHPUT.borrow(cs).get().throughput;
+        let sample = Sample {
+            tx_bytes: throughput.bytes_sent,
+            rx_bytes: throughput.bytes_received,
+            ip,
+        };
+
+        let mut meter = TRAFFIC_METER.borrow_ref_mut(cs);
+        if let Some(previous) = meter.previous.replace(sample) {
+            let totals = &mut meter.totals;
+            totals.tx_bytes += delta(sample.tx_bytes, previous.tx_bytes);
+            totals.rx_bytes += delta(sample.rx_bytes, previous.rx_bytes);
+            totals.tx_packets = totals
+                .tx_packets
+                .wrapping_add(delta(sample.ip.mTxSuccess, previous.ip.mTxSuccess));
+            totals.rx_packets = totals
+                .rx_packets
+                .wrapping_add(delta(sample.ip.mRxSuccess, previous.ip.mRxSuccess));
+            totals.tx_failures = totals
+                .tx_failures
+                .wrapping_add(delta(sample.ip.mTxFailure, previous.ip.mTxFailure));
+            totals.rx_failures = totals
+                .rx_failures
+                .wrapping_add(delta(sample.ip.mRxFailure, previous.ip.mRxFailure));
+        }
+        meter.last_sample = now;
+
+        let totals = meter.totals;
+        if meter.samples.is_full() {
+            meter.samples.pop_front();
+        }
+        meter.samples.push_back((now, totals)).ok();
+        meter.prune(now);
+    });
+}
+
+/// Count since `previous`, everything counts as new if the counter was reset in between
+fn delta<T: Copy + PartialOrd + core::ops::Sub<Output = T>>(current: T, previous: T) -> T {
+    if current < previous {
+        current
+    } else {
+        current - previous
+    }
+}
+
+fn per_second(count: u64, duration: u64) -> u32 {
+    if duration == 0 {
+        return 0;
+    }
+
+    (count * 1000 / duration) as u32
+}
@@ -0,0 +1,169 @@
+use esp_openthread_sys::bindings::{otError_OT_ERROR_INVALID_ARGS, otError_OT_ERROR_NO_BUFS};
+
+use crate::Error;
+
+const INVALID_ARGS: Error = Error::InternalError(otError_OT_ERROR_INVALID_ARGS);
+const NO_BUFS: Error = Error::InternalError(otError_OT_ERROR_NO_BUFS);
+
+/// Longest `key=value` entry, its length has to fit the length byte
+pub const TXT_ENTRY_MAX_SIZE: usize = u8::MAX as usize;
+
+/// Encoded DNS-SD TXT data (RFC 6763 section 6) of at most `N` bytes
+///
+/// Holds a sequence of length prefixed `key=value` strings, e.g. for a service registered with
+/// the SRP client or advertised via mDNS. An empty TXT data is encoded by OpenThread as a single
+/// empty entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxtData<const N: usize>(heapless::Vec<u8, N>);
+
+impl<const N: usize> TxtData<N> {
+    pub const fn new() -> Self {
+        Self(heapless::Vec::new())
+    }
+
+    /// Append a `key=value` entry
+    ///
+    /// The key has to be printable ASCII without `=`, RFC 6763 recommends at most 9 characters.
+    /// Nothing is appended if the entry doesn't fit.
+    pub fn push(&mut self, key: &str, value: &[u8]) -> Result<(), Error> {
+        self.push_entry(key, Some(value))
+    }
+
+    /// Append a boolean attribute, i.e. a key without `=` and value, which is `true` when present
+    pub fn push_flag(&mut self, key: &str) -> Result<(), Error> {
+        self.push_entry(key, None)
+    }
+
+    /// Append an entry with the value formatted as decimal number, e.g. `port=5683`
+    pub fn push_u32(&mut self, key: &str, value: u32) -> Result<(), Error> {
+        let mut digits = [0u8; 10];
+        let mut start = digits.len();
+        let mut remaining = value;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (remaining % 10) as u8;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        self.push(key, &digits[start..])
+    }
+
+    fn push_entry(&mut self, key: &str, value: Option<&[u8]>) -> Result<(), Error> {
+        if !is_valid_key(key) {
+            return Err(INVALID_ARGS);
+        }
+
+        let len = key.len() + value.map_or(0, |value| 1 + value.len());
+        if len > TXT_ENTRY_MAX_SIZE {
+            return Err(INVALID_ARGS);
+        }
+        if self.0.len() + 1 + len > N {
+            return Err(NO_BUFS);
+        }
+
+        // can't fail anymore, the capacity was checked above
+        self.0.push(len as u8).ok();
+        self.0.extend_from_slice(key.as_bytes()).ok();
+        if let Some(value) = value {
+            self.0.push(b'=').ok();
+            self.0.extend_from_slice(value).ok();
+        }
+
+        Ok(())
+    }
+
+    /// Remove all entries
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// The encoded TXT data, e.g. to pass on as `txt_data`
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Iterate over the entries
+    pub fn entries(&self) -> TxtEntries<'_> {
+        TxtEntries::new(&self.0)
+    }
+
+    /// Get the value of the first entry with the given key, compared case-insensitively
+    ///
+    /// `Some(None)` for a boolean attribute.
+    pub fn get(&self, key: &str) -> Option<Option<&[u8]>> {
+        self.entries().find_key(key)
+    }
+}
+
+impl<const N: usize> Default for TxtData<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An entry of encoded DNS-SD TXT data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxtEntry<'a> {
+    pub key: &'a str,
+    /// `None` for a boolean attribute (no `=`), an empty value is `Some(&[])`
+    pub value: Option<&'a [u8]>,
+}
+
+/// Iterator over the entries of encoded DNS-SD TXT data, e.g. as received via mDNS or DNS-SD
+///
+/// Empty entries and entries with an invalid key are skipped, iteration ends at an entry that
+/// runs past the end of the data.
+#[derive(Debug, Clone)]
+pub struct TxtEntries<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TxtEntries<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Get the value of the first entry with the given key, compared case-insensitively
+    ///
+    /// `Some(None)` for a boolean attribute.
+    pub fn find_key(mut self, key: &str) -> Option<Option<&'a [u8]>> {
+        self.find(|entry| entry.key.eq_ignore_ascii_case(key))
+            .map(|entry| entry.value)
+    }
+}
+
+impl<'a> Iterator for TxtEntries<'a> {
+    type Item = TxtEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (&len, rest) = self.data.split_first()?;
+            if rest.len() < len as usize {
+                self.data = &[];
+                return None;
+            }
+
+            let (entry, rest) = rest.split_at(len as usize);
+            self.data = rest;
+
+            let (key, value) = match entry.iter().position(|&b| b == b'=') {
+                Some(separator) => (&entry[..separator], Some(&entry[separator + 1..])),
+                None => (entry, None),
+            };
+
+            // only printable ASCII, so valid UTF-8
+            if let Ok(key) = core::str::from_utf8(key) {
+                if is_valid_key(key) {
+                    return Some(TxtEntry { key, value });
+                }
+            }
+        }
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.bytes().all(|b| (0x20..=0x7e).contains(&b) && b != b'=')
+}
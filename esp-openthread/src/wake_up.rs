@@ -0,0 +1,60 @@
+use esp_openthread_sys::bindings::{
+    otLinkGetWakeupChannel, otLinkGetWakeupListenParameters, otLinkIsWakeupListenEnabled,
+    otLinkSetWakeUpListenEnabled, otLinkSetWakeupChannel, otLinkSetWakeupListenParameters,
+};
+
+use crate::{checked, Error, OpenThread};
+
+/// How often and how long a Wake-up End Device listens for wake-up frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WakeUpListenParameters {
+    /// Interval between listen windows (in microseconds)
+    pub interval: u32,
+    /// Length of a listen window (in microseconds)
+    pub duration: u32,
+}
+
+impl<'a> OpenThread<'a> {
+    /// Set the channel wake-up frames are sent and listened for on
+    ///
+    /// `0` uses the PAN channel.
+    pub fn link_set_wake_up_channel(&mut self, channel: u8) -> Result<(), Error> {
+        checked!(unsafe { otLinkSetWakeupChannel(self.instance, channel) })
+    }
+
+    /// Get the channel wake-up frames are sent and listened for on
+    pub fn link_get_wake_up_channel(&self) -> u8 {
+        unsafe { otLinkGetWakeupChannel(self.instance) }
+    }
+
+    /// Enable or disable listening for wake-up frames
+    ///
+    /// Only succeeds while Thread is disabled, see [OpenThread::thread_set_enabled].
+    pub fn link_set_wake_up_listen_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+        checked!(unsafe { otLinkSetWakeUpListenEnabled(self.instance, enabled) })
+    }
+
+    /// Returns `true` if listening for wake-up frames is enabled
+    pub fn link_is_wake_up_listen_enabled(&self) -> bool {
+        unsafe { otLinkIsWakeupListenEnabled(self.instance) }
+    }
+
+    /// Set how often and how long to listen for wake-up frames
+    pub fn link_set_wake_up_listen_parameters(
+        &mut self,
+        parameters: WakeUpListenParameters,
+    ) -> Result<(), Error> {
+        checked!(unsafe {
+            otLinkSetWakeupListenParameters(self.instance, parameters.interval, parameters.duration)
+        })
+    }
+
+    /// Get how often and how long to listen for wake-up frames
+    pub fn link_get_wake_up_listen_parameters(&self) -> WakeUpListenParameters {
+        let mut interval = 0;
+        let mut duration = 0;
+        unsafe { otLinkGetWakeupListenParameters(self.instance, &mut interval, &mut duration) };
+
+        WakeUpListenParameters { interval, duration }
+    }
+}
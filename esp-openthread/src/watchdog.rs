@@ -0,0 +1,99 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use fugit::MillisDurationU64;
+
+use crate::{timer::current_millis, OpenThread};
+
+pub(crate) static WATCHDOG: Mutex<RefCell<WatchdogState>> =
+    Mutex::new(RefCell::new(WatchdogState::DEFAULT));
+
+/// A watchdog fed from [OpenThread::process] and [OpenThread::run_tasklets], see
+/// [OpenThread::set_watchdog]
+pub trait Watchdog {
+    fn feed(&mut self);
+}
+
+impl Watchdog for esp_hal::rtc_cntl::Rwdt {
+    fn feed(&mut self) {
+        esp_hal::rtc_cntl::Rwdt::feed(self)
+    }
+}
+
+pub(crate) struct WatchdogState {
+    watchdog: Option<&'static mut (dyn Watchdog + Send)>,
+    /// milliseconds
+    deadline: u64,
+    /// A call took longer than the deadline, the watchdog isn't fed anymore
+    starved: bool,
+}
+
+impl WatchdogState {
+    pub(crate) const DEFAULT: Self = Self {
+        watchdog: None,
+        deadline: 0,
+        starved: false,
+    };
+}
+
+impl<'a> OpenThread<'a> {
+    /// Feed `watchdog` after every [OpenThread::process] and [OpenThread::run_tasklets] call that
+    /// completes within `deadline`
+    ///
+    /// Once a call takes longer, the reason is logged and the watchdog isn't fed anymore, so it
+    /// resets the chip. A stalled stack never returns and isn't feeding it either. Setting the
+    /// watchdog again starts feeding it again. Its timeout has to be longer than the deadline plus
+    /// the time the application spends between the calls.
+    pub fn set_watchdog(
+        &mut self,
+        watchdog: Option<&'a mut (dyn Watchdog + Send)>,
+        deadline: MillisDurationU64,
+    ) {
+        critical_section::with(|cs| {
+            *WATCHDOG.borrow_ref_mut(cs) = WatchdogState {
+                watchdog: unsafe { core::mem::transmute(watchdog) },
+                deadline: deadline.to_millis(),
+                starved: false,
+            };
+        });
+    }
+
+    /// Returns `true` if the watchdog isn't fed anymore since a call missed the deadline
+    pub fn watchdog_is_starved(&self) -> bool {
+        critical_section::with(|cs| WATCHDOG.borrow_ref(cs).starved)
+    }
+}
+
+/// Mark entering OpenThread, returns the start time to pass to [leave]
+pub(crate) fn enter() -> u64 {
+    current_millis()
+}
+
+/// Mark leaving OpenThread, feeds the watchdog if `what` finished within the deadline
+pub(crate) fn leave(start: u64, what: &str) {
+    let elapsed = current_millis() - start;
+
+    critical_section::with(|cs| {
+        let mut state = WATCHDOG.borrow_ref_mut(cs);
+        if state.starved {
+            return;
+        }
+
+        let deadline = state.deadline;
+        let Some(watchdog) = state.watchdog.as_mut() else {
+            return;
+        };
+
+        if elapsed <= deadline {
+            watchdog.feed();
+        } else {
+            log::error!(
+                "{} took {} ms (deadline {} ms), no longer feeding the watchdog",
+                what,
+                elapsed,
+                deadline
+            );
+            state.starved = true;
+        }
+    });
+}
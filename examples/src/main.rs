@@ -9,12 +9,16 @@ use core::pin::pin;
 use critical_section::Mutex;
 use esp_backtrace as _;
 use esp_ieee802154::Ieee802154;
+use esp_openthread::embed_dataset;
+use esp_openthread::DatasetTransform;
+use esp_openthread::EmbeddedDataset;
 use esp_openthread::NetworkInterfaceUnicastAddress;
-use esp_openthread::OperationalDataset;
-use esp_openthread::ThreadTimestamp;
 use esp_println::println;
 use esp_hal::{clock::ClockControl, peripherals::Peripherals, prelude::*, systimer, Rng};
 
+/// The dataset of the network set up in README.md, `dataset active -x` of the OT-CLI
+static DATASET: EmbeddedDataset = embed_dataset!("0e080000000000010000000300000b35060004001fffe002083a90e3a319a904940708fd1fa298dbd1e3290510fe0458f7db96354eaa6041b880ea9c0f030f4f70656e5468726561642d35386431010258d10410888f813c61972446ab616ee3c556a5910c0402a0f7f8");
+
 #[entry]
 fn main() -> ! {
     esp_println::logger::init_logger_from_env();
@@ -41,23 +45,9 @@ fn main() -> ! {
     };
     openthread.set_change_callback(Some(&mut callback));
 
-    let dataset = OperationalDataset {
-        active_timestamp: Some(ThreadTimestamp {
-            seconds: 1,
-            ticks: 0,
-            authoritative: false,
-        }),
-        network_key: Some([
-            0xfe, 0x04, 0x58, 0xf7, 0xdb, 0x96, 0x35, 0x4e, 0xaa, 0x60, 0x41, 0xb8, 0x80, 0xea,
-            0x9c, 0x0f,
-        ]),
-        network_name: Some("OpenThread-58d1".try_into().unwrap()),
-        extended_pan_id: Some([0x3a, 0x90, 0xe3, 0xa3, 0x19, 0xa9, 0x04, 0x94]),
-        pan_id: Some(0x58d1),
-
-        ..OperationalDataset::default()
-    };
-    openthread.set_active_dataset(dataset).unwrap();
+    openthread
+        .load_embedded_dataset(&DATASET, DatasetTransform::None)
+        .unwrap();
 
     openthread.ipv6_set_enabled(true).unwrap();
 
@@ -77,8 +67,7 @@ fn main() -> ! {
         openthread.process();
         openthread.run_tasklets();
 
-        let (len, from, port) = socket.receive(&mut buffer).unwrap();
-        if len > 0 {
+        if let Some((len, from, port)) = socket.receive(&mut buffer).unwrap() {
             println!(
                 "received {:02x?} from {:?} port {}",
                 &buffer[..len],